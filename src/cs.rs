@@ -0,0 +1,83 @@
+//! Sharing a [`Miim`] between interrupt handlers and the main loop.
+//!
+//! A [`CsMiim`] wraps a [`Miim`], and serializes all accesses to it using a
+//! [critical section](critical_section). A shared reference to a [`CsMiim`]
+//! implements [`Miim`] itself, so that a driver can be created in every
+//! context that needs to access the PHY. For example, an interrupt handler
+//! can read the interrupt source register while the main loop polls the link
+//! status:
+//!
+//! ```ignore
+//! static MIIM: CsMiim<Mdio> = CsMiim::new(mdio);
+//!
+//! // In the main loop
+//! let mut phy = LAN8742A::new(&MIIM, address);
+//! let link_up = phy.link_established();
+//!
+//! // In the interrupt handler
+//! let mut phy = LAN8742A::new(&MIIM, address);
+//! let interrupts = phy.read_and_clear_interrupts();
+//! ```
+//!
+//! Only single register accesses are atomic. Operations that consist of
+//! several accesses, such as accessing MMD registers or paged registers, can
+//! be interleaved with accesses from another context, which may change the
+//! selected MMD register or page. Such operations must be performed in a
+//! single critical section using [`CsMiim::lock`], or must only be
+//! performed in one context.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::Miim;
+
+/// A [`Miim`] that can be shared between contexts, such as interrupt
+/// handlers and the main loop.
+#[derive(Debug)]
+pub struct CsMiim<M: Miim> {
+    miim: Mutex<RefCell<M>>,
+}
+
+impl<M: Miim> CsMiim<M> {
+    /// Create a new shared MIIM
+    pub const fn new(miim: M) -> Self {
+        Self {
+            miim: Mutex::new(RefCell::new(miim)),
+        }
+    }
+
+    /// Perform `f` on the wrapped [`Miim`], in a single critical section.
+    ///
+    /// No other context can access the [`Miim`] while `f` runs.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is called from within `f`.
+    pub fn lock<R>(&self, f: impl FnOnce(&mut M) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.miim.borrow_ref_mut(cs)))
+    }
+
+    /// Release the wrapped [`Miim`]
+    pub fn release(self) -> M {
+        self.miim.into_inner().into_inner()
+    }
+}
+
+impl<M: Miim> Miim for &CsMiim<M> {
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        self.lock(|miim| miim.read(phy, reg))
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        self.lock(|miim| miim.write(phy, reg, data))
+    }
+
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        self.lock(|miim| miim.read_c45(phy, mmd, reg))
+    }
+
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        self.lock(|miim| miim.write_c45(phy, mmd, reg, data))
+    }
+}
@@ -0,0 +1,215 @@
+//! Encoding and decoding of raw Clause 22 and Clause 45 MDIO frames.
+//!
+//! A management frame consists of a preamble of [`PREAMBLE_LEN`] ones,
+//! followed by 32 bits containing the start of frame (ST), the operation
+//! code (OP), the PHY or port address, the register or device address, the
+//! turnaround (TA) and 16 data bits.
+//!
+//! The frames are represented as a `u32` containing those 32 bits, of which
+//! the most significant bit is transmitted first. The preamble is not
+//! included.
+
+/// The amount of ones that are transmitted before every frame
+pub const PREAMBLE_LEN: usize = 32;
+
+/// A Clause 22 operation
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clause22Op {
+    /// Read a register
+    Read,
+    /// Write a register
+    Write,
+}
+
+/// A Clause 45 operation
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clause45Op {
+    /// Set the register address
+    Address,
+    /// Write the register at the current address
+    Write,
+    /// Read the register at the current address
+    Read,
+    /// Read the register at the current address, and increment the address
+    ReadIncrement,
+}
+
+/// A management frame
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frame {
+    /// A Clause 22 frame
+    Clause22 {
+        /// The operation
+        op: Clause22Op,
+        /// The PHY address (5 bits)
+        phy: u8,
+        /// The register address (5 bits)
+        reg: u8,
+        /// The data that is written or read
+        data: u16,
+    },
+    /// A Clause 45 frame
+    Clause45 {
+        /// The operation
+        op: Clause45Op,
+        /// The port address (5 bits)
+        port: u8,
+        /// The device address (5 bits)
+        device: u8,
+        /// The register address, or the data that is written or read
+        data: u16,
+    },
+}
+
+/// An error that occurs while decoding a [`Frame`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The start of frame is not valid
+    InvalidStart,
+    /// The operation code is not valid for the type of frame
+    InvalidOp,
+    /// The turnaround is not valid
+    InvalidTurnaround,
+}
+
+const ST_CLAUSE22: u32 = 0b01;
+const ST_CLAUSE45: u32 = 0b00;
+
+/// The turnaround as it is seen on the line: the STA drives `10` for
+/// writes, and releases the line (which is pulled high) after which the PHY
+/// drives 0 for reads.
+const TA: u32 = 0b10;
+
+impl Frame {
+    /// Encode this frame
+    ///
+    /// The addresses are truncated to 5 bits.
+    pub fn encode(&self) -> u32 {
+        let (st, op, addr1, addr2, data) = match *self {
+            Frame::Clause22 { op, phy, reg, data } => {
+                let op = match op {
+                    Clause22Op::Write => 0b01,
+                    Clause22Op::Read => 0b10,
+                };
+                (ST_CLAUSE22, op, phy, reg, data)
+            }
+            Frame::Clause45 {
+                op,
+                port,
+                device,
+                data,
+            } => {
+                let op = match op {
+                    Clause45Op::Address => 0b00,
+                    Clause45Op::Write => 0b01,
+                    Clause45Op::ReadIncrement => 0b10,
+                    Clause45Op::Read => 0b11,
+                };
+                (ST_CLAUSE45, op, port, device, data)
+            }
+        };
+
+        st << 30
+            | op << 28
+            | ((addr1 & 0x1F) as u32) << 23
+            | ((addr2 & 0x1F) as u32) << 18
+            | TA << 16
+            | data as u32
+    }
+
+    /// Decode a frame
+    ///
+    /// Only the second bit of the turnaround is checked, as the first bit is
+    /// not driven during reads.
+    pub fn decode(bits: u32) -> Result<Self, DecodeError> {
+        let st = bits >> 30;
+        let op = (bits >> 28) & 0b11;
+        let addr1 = ((bits >> 23) & 0x1F) as u8;
+        let addr2 = ((bits >> 18) & 0x1F) as u8;
+        let ta = (bits >> 16) & 0b11;
+        let data = bits as u16;
+
+        if ta & 0b01 != TA & 0b01 {
+            return Err(DecodeError::InvalidTurnaround);
+        }
+
+        match st {
+            ST_CLAUSE22 => {
+                let op = match op {
+                    0b01 => Clause22Op::Write,
+                    0b10 => Clause22Op::Read,
+                    _ => return Err(DecodeError::InvalidOp),
+                };
+
+                Ok(Frame::Clause22 {
+                    op,
+                    phy: addr1,
+                    reg: addr2,
+                    data,
+                })
+            }
+            ST_CLAUSE45 => {
+                let op = match op {
+                    0b00 => Clause45Op::Address,
+                    0b01 => Clause45Op::Write,
+                    0b10 => Clause45Op::ReadIncrement,
+                    _ => Clause45Op::Read,
+                };
+
+                Ok(Frame::Clause45 {
+                    op,
+                    port: addr1,
+                    device: addr2,
+                    data,
+                })
+            }
+            _ => Err(DecodeError::InvalidStart),
+        }
+    }
+
+    /// Whether the data of this frame is driven by the PHY, as opposed to the
+    /// station management entity
+    pub fn is_read(&self) -> bool {
+        matches!(
+            self,
+            Frame::Clause22 {
+                op: Clause22Op::Read,
+                ..
+            } | Frame::Clause45 {
+                op: Clause45Op::Read | Clause45Op::ReadIncrement,
+                ..
+            }
+        )
+    }
+
+    /// The bits of this frame, in the order in which they are transmitted.
+    ///
+    /// The preamble is not included.
+    pub fn bits(&self) -> impl Iterator<Item = bool> {
+        let encoded = self.encode();
+        (0..32).rev().map(move |bit| encoded & (1 << bit) != 0)
+    }
+
+    /// Decode a frame from its bits, in the order in which they are
+    /// transmitted.
+    ///
+    /// Only the first 32 bits of `bits` are used. Missing bits are treated as
+    /// zeroes.
+    pub fn from_bits<I>(bits: I) -> Result<Self, DecodeError>
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let mut encoded = 0u32;
+        let mut count = 0;
+        for bit in bits.into_iter().take(32) {
+            encoded = (encoded << 1) | bit as u32;
+            count += 1;
+        }
+
+        Self::decode(encoded.checked_shl(32 - count).unwrap_or(0))
+    }
+}
@@ -0,0 +1,238 @@
+//! A [`Miim`] for FTDI MPSSE USB adapters.
+//!
+//! [`FtdiMiim`] generates MDIO frames with the Multi-Protocol Synchronous
+//! Serial Engine (MPSSE) of an FT232H, FT2232H or FT4232H, so that PHYs can
+//! be accessed from a desktop during board bring-up.
+//!
+//! The adapter is accessed through any transport that implements
+//! [`std::io::Read`] and [`std::io::Write`], such as `ftdi::Device` from the
+//! `ftdi` crate. The transport must already be in MPSSE mode, for example
+//! using `Device::set_bitmode(0, BitMode::Mpsse)`.
+//!
+//! The adapter is wired up as follows:
+//!
+//! * ADBUS0 (TCK) is MDC
+//! * ADBUS1 (TDI) drives MDIO, preferably through a series resistor
+//! * ADBUS2 (TDO) samples MDIO
+//!
+//! MDIO must be pulled up, as it is released by ADBUS1 while the PHY
+//! responds to a read.
+//!
+//! This module is only available with the `ftdi` feature.
+
+use std::{
+    io::{self, Read, Write},
+    vec::Vec,
+};
+
+use crate::{
+    frame::{Clause22Op, Clause45Op, DecodeError, Frame, PREAMBLE_LEN},
+    Miim,
+};
+
+/// The clock divisor that results in an MDC frequency of 2.5 MHz, the
+/// maximum frequency allowed by IEEE 802.3
+pub const DIVISOR_2_5MHZ: u16 = 11;
+
+// MPSSE commands, see FTDI application note AN_108
+const BYTES_OUT_NEG_MSB: u8 = 0x11;
+const BITS_OUT_NEG_MSB: u8 = 0x13;
+const BYTES_IN_POS_MSB: u8 = 0x20;
+const BITS_IN_POS_MSB: u8 = 0x22;
+const SET_BITS_LOW: u8 = 0x80;
+const LOOPBACK_OFF: u8 = 0x85;
+const SET_DIVISOR: u8 = 0x86;
+const SEND_IMMEDIATE: u8 = 0x87;
+const DISABLE_DIV_BY_5: u8 = 0x8A;
+const DISABLE_3_PHASE: u8 = 0x8D;
+const DISABLE_ADAPTIVE: u8 = 0x97;
+
+const MDC: u8 = 1 << 0;
+const MDIO_OUT: u8 = 1 << 1;
+
+/// A [`Miim`] that generates Clause 22 and Clause 45 frames using an FTDI
+/// MPSSE adapter.
+///
+/// [`Miim`] accesses can not fail, so if the transport returns an error, reads
+/// return `0xFFFF` and the error is stored until it is retrieved with
+/// [`FtdiMiim::take_error`]. Reads from a PHY that does not drive the
+/// turnaround also return `0xFFFF`.
+#[derive(Debug)]
+pub struct FtdiMiim<T: Read + Write> {
+    port: T,
+    error: Option<io::Error>,
+}
+
+impl<T: Read + Write> FtdiMiim<T> {
+    /// Configure the MPSSE of the adapter behind `port`.
+    ///
+    /// The MDC frequency is `60 MHz / ((1 + divisor) * 2)`. Use
+    /// [`DIVISOR_2_5MHZ`] for the maximum frequency allowed by IEEE 802.3.
+    pub fn new(mut port: T, divisor: u16) -> io::Result<Self> {
+        let [div_low, div_high] = divisor.to_le_bytes();
+        port.write_all(&[
+            DISABLE_DIV_BY_5,
+            DISABLE_ADAPTIVE,
+            DISABLE_3_PHASE,
+            LOOPBACK_OFF,
+            SET_DIVISOR,
+            div_low,
+            div_high,
+            SET_BITS_LOW,
+            MDIO_OUT,
+            MDC | MDIO_OUT,
+        ])?;
+        port.flush()?;
+
+        Ok(Self { port, error: None })
+    }
+
+    /// Take the last error that occurred while accessing the transport, if
+    /// any
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Release the underlying transport
+    pub fn release(self) -> T {
+        self.port
+    }
+
+    fn push_write_frame(commands: &mut Vec<u8>, frame: Frame) {
+        let len = (PREAMBLE_LEN / 8 + 4 - 1) as u16;
+        commands.push(BYTES_OUT_NEG_MSB);
+        commands.extend_from_slice(&len.to_le_bytes());
+        commands.extend_from_slice(&[0xFF; PREAMBLE_LEN / 8]);
+        commands.extend_from_slice(&frame.encode().to_be_bytes());
+
+        // Return MDIO to idle
+        commands.extend_from_slice(&[SET_BITS_LOW, MDIO_OUT, MDC | MDIO_OUT]);
+    }
+
+    fn push_read_frame(commands: &mut Vec<u8>, frame: Frame) {
+        let [first, second, _, _] = frame.encode().to_be_bytes();
+
+        // ST, OP, PHYAD/PRTAD and REGAD/DEVAD (14 bits) are driven by us
+        let len = (PREAMBLE_LEN / 8 + 1 - 1) as u16;
+        commands.push(BYTES_OUT_NEG_MSB);
+        commands.extend_from_slice(&len.to_le_bytes());
+        commands.extend_from_slice(&[0xFF; PREAMBLE_LEN / 8]);
+        commands.push(first);
+        commands.extend_from_slice(&[BITS_OUT_NEG_MSB, 6 - 1, second & 0xFC]);
+
+        // The turnaround and data are driven by the PHY
+        commands.extend_from_slice(&[SET_BITS_LOW, 0, MDC]);
+        commands.extend_from_slice(&[BITS_IN_POS_MSB, 2 - 1]);
+        commands.extend_from_slice(&[BYTES_IN_POS_MSB, 2 - 1, 0]);
+
+        commands.extend_from_slice(&[SET_BITS_LOW, MDIO_OUT, MDC | MDIO_OUT, SEND_IMMEDIATE]);
+    }
+
+    fn transfer(&mut self, commands: &[u8], response: &mut [u8]) -> io::Result<()> {
+        self.port.write_all(commands)?;
+        self.port.flush()?;
+        self.port.read_exact(response)
+    }
+
+    fn read_frame(&mut self, mut commands: Vec<u8>, frame: Frame) -> Result<Frame, DecodeError> {
+        Self::push_read_frame(&mut commands, frame);
+
+        let mut response = [0u8; 3];
+        if let Err(e) = self.transfer(&commands, &mut response) {
+            self.error = Some(e);
+            return Err(DecodeError::InvalidTurnaround);
+        }
+
+        // The bits that are clocked in are shifted in from the LSB
+        let turnaround = (response[0] & 0b11) as u32;
+        let data = u16::from_be_bytes([response[1], response[2]]) as u32;
+        Frame::decode(frame.encode() & 0xFFFC_0000 | turnaround << 16 | data)
+    }
+
+    fn write_frames(&mut self, frames: &[Frame]) {
+        let mut commands = Vec::new();
+        for frame in frames {
+            Self::push_write_frame(&mut commands, *frame);
+        }
+
+        if let Err(e) = self.transfer(&commands, &mut []) {
+            self.error = Some(e);
+        }
+    }
+}
+
+impl<T: Read + Write> Miim for FtdiMiim<T> {
+    /// Read a register.
+    ///
+    /// If the PHY does not drive the turnaround, or the transport returns an
+    /// error, `0xFFFF` is returned.
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        let frame = Frame::Clause22 {
+            op: Clause22Op::Read,
+            phy,
+            reg,
+            data: 0,
+        };
+
+        match self.read_frame(Vec::new(), frame) {
+            Ok(Frame::Clause22 { data, .. }) => data,
+            _ => 0xFFFF,
+        }
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        self.write_frames(&[Frame::Clause22 {
+            op: Clause22Op::Write,
+            phy,
+            reg,
+            data,
+        }]);
+    }
+
+    /// Read a register using Clause 45 frames.
+    ///
+    /// If the PHY does not drive the turnaround, or the transport returns an
+    /// error, `0xFFFF` is returned.
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        let mut commands = Vec::new();
+        Self::push_write_frame(
+            &mut commands,
+            Frame::Clause45 {
+                op: Clause45Op::Address,
+                port: phy,
+                device: mmd,
+                data: reg,
+            },
+        );
+
+        let frame = Frame::Clause45 {
+            op: Clause45Op::Read,
+            port: phy,
+            device: mmd,
+            data: 0,
+        };
+
+        match self.read_frame(commands, frame) {
+            Ok(Frame::Clause45 { data, .. }) => data,
+            _ => 0xFFFF,
+        }
+    }
+
+    /// Write to a register using Clause 45 frames.
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        self.write_frames(&[
+            Frame::Clause45 {
+                op: Clause45Op::Address,
+                port: phy,
+                device: mmd,
+                data: reg,
+            },
+            Frame::Clause45 {
+                op: Clause45Op::Write,
+                port: phy,
+                device: mmd,
+                data,
+            },
+        ]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Guards that undo a temporary change to the configuration of a PHY when
+//! they are dropped.
+
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{registers::Bcr, Miim, Phy};
+
+#[cfg(feature = "phy")]
+use crate::phy::{Loopback, PhyWithLoopback, PhyWithPages};
+
+/// A guard that keeps a PHY electrically isolated from its MII, created by
+/// [`Phy::isolate_scoped`].
+///
+/// The PHY can be accessed through the guard. Once the guard is dropped, the
+/// ISOLATE bit of the [`Bcr`] is restored to the state it was in before the
+/// guard was created. Other changes to the [`Bcr`] are kept.
+#[derive(Debug)]
+pub struct IsolateGuard<'phy, M: Miim, P: Phy<M>> {
+    phy: &'phy mut P,
+    was_isolated: bool,
+    _miim: PhantomData<M>,
+}
+
+impl<'phy, M: Miim, P: Phy<M>> IsolateGuard<'phy, M, P> {
+    pub(crate) fn new(phy: &'phy mut P) -> Self {
+        let was_isolated = phy.bcr().isolated();
+        phy.modify_bcr(|bcr| {
+            bcr.set_isolated(true);
+        });
+
+        Self {
+            phy,
+            was_isolated,
+            _miim: PhantomData,
+        }
+    }
+
+    /// Whether the PHY was already isolated when the guard was created
+    pub fn was_isolated(&self) -> bool {
+        self.was_isolated
+    }
+}
+
+impl<M: Miim, P: Phy<M>> Deref for IsolateGuard<'_, M, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.phy
+    }
+}
+
+impl<M: Miim, P: Phy<M>> DerefMut for IsolateGuard<'_, M, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.phy
+    }
+}
+
+impl<M: Miim, P: Phy<M>> Drop for IsolateGuard<'_, M, P> {
+    fn drop(&mut self) {
+        let isolate = Bcr::ISOLATE.bits();
+        let value = if self.was_isolated { isolate } else { 0 };
+        self.phy.modify(Bcr::ADDRESS, isolate, value);
+    }
+}
+
+/// A guard that keeps a PHY in a loopback mode, created by
+/// [`PhyWithLoopback::loopback_scoped`].
+///
+/// The PHY can be accessed through the guard. Once the guard is dropped, the
+/// loopback mode that was enabled before the guard was created is restored.
+#[cfg(feature = "phy")]
+#[derive(Debug)]
+pub struct LoopbackGuard<'phy, M: Miim, P: PhyWithLoopback<M>> {
+    phy: &'phy mut P,
+    previous: Option<Loopback>,
+    _miim: PhantomData<M>,
+}
+
+#[cfg(feature = "phy")]
+impl<'phy, M: Miim, P: PhyWithLoopback<M>> LoopbackGuard<'phy, M, P> {
+    pub(crate) fn new(phy: &'phy mut P, loopback: Loopback) -> Self {
+        let previous = phy.loopback();
+        phy.set_loopback(Some(loopback));
+
+        Self {
+            phy,
+            previous,
+            _miim: PhantomData,
+        }
+    }
+
+    /// The loopback mode that will be restored once the guard is dropped
+    pub fn previous(&self) -> Option<Loopback> {
+        self.previous
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithLoopback<M>> Deref for LoopbackGuard<'_, M, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.phy
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithLoopback<M>> DerefMut for LoopbackGuard<'_, M, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.phy
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithLoopback<M>> Drop for LoopbackGuard<'_, M, P> {
+    fn drop(&mut self) {
+        self.phy.set_loopback(self.previous);
+    }
+}
+
+/// A guard that keeps a page of a PHY selected, created by
+/// [`PhyWithPages::page_scoped`].
+///
+/// The registers on the page can be accessed through the guard. Once the
+/// guard is dropped, the page that was selected before the guard was created
+/// is selected again.
+#[cfg(feature = "phy")]
+#[derive(Debug)]
+pub struct PageGuard<'phy, M: Miim, P: PhyWithPages<M>> {
+    phy: &'phy mut P,
+    previous: u16,
+    _miim: PhantomData<M>,
+}
+
+#[cfg(feature = "phy")]
+impl<'phy, M: Miim, P: PhyWithPages<M>> PageGuard<'phy, M, P> {
+    pub(crate) fn new(phy: &'phy mut P, page: u16) -> Self {
+        let previous = phy.page();
+        phy.write(P::PAGE_SELECT, page);
+
+        Self {
+            phy,
+            previous,
+            _miim: PhantomData,
+        }
+    }
+
+    /// The page that will be selected again once the guard is dropped
+    pub fn previous(&self) -> u16 {
+        self.previous
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithPages<M>> Deref for PageGuard<'_, M, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.phy
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithPages<M>> DerefMut for PageGuard<'_, M, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.phy
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M: Miim, P: PhyWithPages<M>> Drop for PageGuard<'_, M, P> {
+    fn drop(&mut self) {
+        self.phy.write(P::PAGE_SELECT, self.previous);
+    }
+}
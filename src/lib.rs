@@ -4,10 +4,30 @@
 //! A crate that provides access to the MIIM interface described
 //! by IEEE standard 802.3
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod miim;
 
 pub use miim::Miim;
 
+mod guard;
+
+pub use guard::IsolateGuard;
+#[cfg(feature = "phy")]
+pub use guard::{LoopbackGuard, PageGuard};
+
+#[cfg(feature = "critical-section")]
+pub mod cs;
+pub mod frame;
+#[cfg(feature = "ftdi")]
+pub mod ftdi;
+pub mod logged;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "trace")]
+pub mod trace;
+
 #[cfg(feature = "mmd")]
 mod mmd;
 #[cfg(feature = "mmd")]
@@ -19,8 +39,15 @@ use registers::*;
 #[cfg(feature = "phy")]
 pub mod phy;
 
+#[cfg(feature = "mv88e6xxx")]
+pub mod mv88e6xxx;
+
 /// All basic link speeds possibly supported by the PHY.
+///
+/// This is the raw speed selection of the [`Bcr`]. [`Speed`] can be used
+/// instead where an illegal speed can not occur.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LinkSpeed {
     /// 1000 Mbps
@@ -47,13 +74,100 @@ impl From<Bcr> for LinkSpeed {
     }
 }
 
-impl From<LinkSpeed> for Bcr {
-    fn from(link_speed: LinkSpeed) -> Self {
+impl TryFrom<LinkSpeed> for Bcr {
+    type Error = IllegalSpeed;
+
+    fn try_from(link_speed: LinkSpeed) -> Result<Self, Self::Error> {
+        Speed::try_from(link_speed).map(Bcr::from)
+    }
+}
+
+/// The speed of a link
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Speed {
+    /// 10 Mbps
+    Mbps10,
+    /// 100 Mbps
+    Mbps100,
+    /// 1000 Mbps
+    Mbps1000,
+}
+
+/// The duplex mode of a link
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duplex {
+    /// Half duplex
+    Half,
+    /// Full duplex
+    Full,
+}
+
+/// An error that occurs when decoding a speed selection that does not
+/// correspond to a valid [`Speed`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IllegalSpeed;
+
+impl TryFrom<Bcr> for Speed {
+    type Error = IllegalSpeed;
+
+    fn try_from(bcr: Bcr) -> Result<Self, Self::Error> {
+        LinkSpeed::from(bcr).try_into()
+    }
+}
+
+impl TryFrom<LinkSpeed> for Speed {
+    type Error = IllegalSpeed;
+
+    fn try_from(link_speed: LinkSpeed) -> Result<Self, Self::Error> {
         match link_speed {
-            LinkSpeed::Mpbs1000 => Bcr::SPEED_SEL_MSB,
-            LinkSpeed::Mbps100 => Bcr::SPEED_SEL_LSB,
-            LinkSpeed::Mpbs10 => Bcr::empty(),
-            LinkSpeed::Illegal => panic!("Cannot convert illegal link speed into Bcr"),
+            LinkSpeed::Mpbs1000 => Ok(Speed::Mbps1000),
+            LinkSpeed::Mbps100 => Ok(Speed::Mbps100),
+            LinkSpeed::Mpbs10 => Ok(Speed::Mbps10),
+            LinkSpeed::Illegal => Err(IllegalSpeed),
+        }
+    }
+}
+
+impl From<Speed> for LinkSpeed {
+    fn from(speed: Speed) -> Self {
+        match speed {
+            Speed::Mbps1000 => LinkSpeed::Mpbs1000,
+            Speed::Mbps100 => LinkSpeed::Mbps100,
+            Speed::Mbps10 => LinkSpeed::Mpbs10,
+        }
+    }
+}
+
+impl From<Speed> for Bcr {
+    fn from(speed: Speed) -> Self {
+        match speed {
+            Speed::Mbps1000 => Bcr::SPEED_SEL_MSB,
+            Speed::Mbps100 => Bcr::SPEED_SEL_LSB,
+            Speed::Mbps10 => Bcr::empty(),
+        }
+    }
+}
+
+impl From<Bcr> for Duplex {
+    fn from(bcr: Bcr) -> Self {
+        if bcr.full_duplex() {
+            Duplex::Full
+        } else {
+            Duplex::Half
+        }
+    }
+}
+
+impl From<Duplex> for Bcr {
+    fn from(duplex: Duplex) -> Self {
+        match duplex {
+            Duplex::Full => Bcr::DUPLEX_MODE,
+            Duplex::Half => Bcr::empty(),
         }
     }
 }
@@ -62,6 +176,7 @@ impl From<LinkSpeed> for Bcr {
 ///
 /// This struct describes what functions the PHY is capable of.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PhyStatus {
     /// The PHY supports 100BASE-T4
@@ -143,6 +258,7 @@ impl From<Bsr> for PhyStatus {
 /// This register is only valid if the field `extended_status` in the
 ///  [`PhyStatus`] describing this struct is `true`
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ExtendedPhyStatus {
     /// The PHY supports 1000BASE-X Full Duplex
@@ -160,9 +276,11 @@ pub struct ExtendedPhyStatus {
 ///
 /// In practice, [`SelectorField::Std802_3`] is used almost exclusively.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum SelectorField {
     /// The message is an IEEE Std 802.3 message
+    #[default]
     Std802_3,
     /// The message is an IEEE Std 802.9 ISLAN-16T message
     Std802_9Islan16t,
@@ -172,31 +290,36 @@ pub enum SelectorField {
     Std1394,
 }
 
-impl Default for SelectorField {
-    fn default() -> Self {
-        Self::Std802_3
-    }
-}
+/// An error that occurs when decoding a selector field value that does not
+/// correspond to a known [`SelectorField`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownSelectorField(pub u8);
 
-impl From<AutoNegCap> for Option<SelectorField> {
-    fn from(ana: AutoNegCap) -> Self {
-        // We use bitwise XOR (`^`) here to ensure that all bits
-        // we use to check for equivalence are set to their correct values
+impl TryFrom<AutoNegCap> for SelectorField {
+    type Error = UnknownSelectorField;
 
+    fn try_from(ana: AutoNegCap) -> Result<Self, Self::Error> {
         let ana = ana & AutoNegCap::SEL_MASK;
 
-        let field = if (ana ^ AutoNegCap::SEL_802_3).is_empty() {
+        let field = if ana == AutoNegCap::SEL_802_3 {
             SelectorField::Std802_3
-        } else if (ana ^ AutoNegCap::SEL_802_5).is_empty() {
+        } else if ana == AutoNegCap::SEL_802_5 {
             SelectorField::Std802_5
-        } else if (ana ^ AutoNegCap::SEL_802_9_ISLAN_16T).is_empty() {
+        } else if ana == AutoNegCap::SEL_802_9_ISLAN_16T {
             SelectorField::Std802_9Islan16t
-        } else if (ana ^ AutoNegCap::SEL_1394).is_empty() {
+        } else if ana == AutoNegCap::SEL_1394 {
             SelectorField::Std1394
         } else {
-            return None;
+            return Err(UnknownSelectorField(ana.bits() as u8));
         };
-        Some(field)
+        Ok(field)
+    }
+}
+
+impl From<AutoNegCap> for Option<SelectorField> {
+    fn from(ana: AutoNegCap) -> Self {
+        SelectorField::try_from(ana).ok()
     }
 }
 
@@ -213,6 +336,7 @@ impl From<SelectorField> for AutoNegCap {
 
 /// The PHY IDENT of this PHY
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PhyIdent(u16, u16);
 
@@ -234,7 +358,7 @@ impl PhyIdent {
 
     /// The OUI of this PhyIdent
     pub fn oui(&self) -> u32 {
-        (self.0 as u32) << 6 & (self.1 as u32) >> 10
+        (self.0 as u32) << 6 | (self.1 as u32) >> 10
     }
 
     /// The model number of this PhyIdent
@@ -248,11 +372,83 @@ impl PhyIdent {
     }
 }
 
-/// The pause mode supported by this PHY
+/// The address of a PHY on a management interface.
+///
+/// PHY addresses are 5 bits wide, so a `PhyAddress` is always less than 32.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PhyAddress(u8);
+
+impl PhyAddress {
+    /// The highest valid PHY address
+    pub const MAX: Self = Self(31);
+
+    /// The address that is used as a broadcast address by PHYs that support
+    /// broadcast writes
+    pub const BROADCAST: Self = Self(0);
+
+    /// Create a new PHY address, or `None` if `address` is not less than 32.
+    pub const fn new(address: u8) -> Option<Self> {
+        if address <= Self::MAX.0 {
+            Some(Self(address))
+        } else {
+            None
+        }
+    }
+
+    /// Create a new PHY address.
+    ///
+    /// This is intended for creating addresses in a `const` context, where
+    /// an invalid `address` results in a compile-time error.
+    ///
+    /// # Panics
+    /// This function panics if `address` is not less than 32.
+    pub const fn from_const(address: u8) -> Self {
+        match Self::new(address) {
+            Some(address) => address,
+            None => panic!("PHY addresses must be less than 32"),
+        }
+    }
+
+    /// The value of this PHY address
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// An iterator over all valid PHY addresses
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..=Self::MAX.0).map(Self)
+    }
+}
+
+/// An error that occurs when converting a `u8` that is not less than 32 into
+/// a [`PhyAddress`]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidPhyAddress(pub u8);
+
+impl TryFrom<u8> for PhyAddress {
+    type Error = InvalidPhyAddress;
+
+    fn try_from(address: u8) -> Result<Self, Self::Error> {
+        Self::new(address).ok_or(InvalidPhyAddress(address))
+    }
+}
+
+impl From<PhyAddress> for u8 {
+    fn from(address: PhyAddress) -> Self {
+        address.0
+    }
+}
+
+/// The pause mode supported by this PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum Pause {
     /// The PHY supports no PAUSE modes
+    #[default]
     NoPause,
     /// The PHY supports asymmetric PAUSE mode toward its link partner
     AsymmetricPartner,
@@ -263,12 +459,6 @@ pub enum Pause {
     SymmetricAndAsymmetricLocal,
 }
 
-impl Default for Pause {
-    fn default() -> Self {
-        Pause::NoPause
-    }
-}
-
 impl From<AutoNegCap> for Pause {
     fn from(ana: AutoNegCap) -> Self {
         match (
@@ -296,6 +486,7 @@ impl From<Pause> for AutoNegCap {
 
 /// An autonegotiation advertisement.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct AutoNegotiationAdvertisement {
     /// The type of message sent
@@ -328,10 +519,37 @@ impl Default for AutoNegotiationAdvertisement {
     }
 }
 
-impl From<AutoNegCap> for AutoNegotiationAdvertisement {
-    fn from(ana: AutoNegCap) -> Self {
+impl AutoNegotiationAdvertisement {
+    /// Encode this advertisement into the value of an autonegotiation
+    /// advertisement register.
+    ///
+    /// [`Self::decode`] of the result returns an advertisement that is equal to
+    /// this one.
+    pub fn encode(&self) -> AutoNegCap {
+        let mut ana = AutoNegCap::empty();
+
+        ana.set(AutoNegCap::_10BASET, self.hd_10base_t);
+        ana.set(AutoNegCap::_10BASETFD, self.fd_10base_t);
+        ana.set(AutoNegCap::_100BASETX, self.hd_100base_tx);
+        ana.set(AutoNegCap::_100BASETXFD, self.fd_100base_tx);
+        ana.set(AutoNegCap::_100BASET4, self.base100_t4);
+
+        if let Some(selector) = self.selector_field {
+            ana.insert(selector.into());
+        }
+
+        ana.insert(self.pause.into());
+        ana
+    }
+
+    /// Decode the value of an autonegotiation advertisement register.
+    ///
+    /// Bits that are not represented in an [`AutoNegotiationAdvertisement`],
+    /// such as [`AutoNegCap::NEXT_PAGE`], are ignored. An unknown selector
+    /// field is decoded as `None`.
+    pub fn decode(ana: AutoNegCap) -> Self {
         AutoNegotiationAdvertisement {
-            selector_field: ana.into(),
+            selector_field: SelectorField::try_from(ana).ok(),
             hd_10base_t: ana.contains(AutoNegCap::_10BASET),
             fd_10base_t: ana.contains(AutoNegCap::_10BASETFD),
             hd_100base_tx: ana.contains(AutoNegCap::_100BASETX),
@@ -342,7 +560,125 @@ impl From<AutoNegCap> for AutoNegotiationAdvertisement {
     }
 }
 
+impl From<AutoNegCap> for AutoNegotiationAdvertisement {
+    fn from(ana: AutoNegCap) -> Self {
+        Self::decode(ana)
+    }
+}
+
+impl From<AutoNegotiationAdvertisement> for AutoNegCap {
+    fn from(ad: AutoNegotiationAdvertisement) -> Self {
+        ad.encode()
+    }
+}
+
+/// A Clause 73 (backplane) autonegotiation base page, as found in MMD
+/// registers 7.16 to 7.18 (advertisement) and 7.19 to 7.21 (link partner
+/// ability).
+///
+/// The first register of the base page has the same layout as the
+/// [`AutoNegCap`] register, except for the technology abilities.
+#[cfg(feature = "mmd")]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackplaneBasePage {
+    /// The type of message sent
+    pub selector_field: Option<SelectorField>,
+    /// The pause mode supported by the PHY
+    pub pause: Pause,
+    /// A remote fault is signalled
+    pub remote_fault: bool,
+    /// Next pages follow the base page
+    pub next_page: bool,
+    /// The technology abilities
+    pub abilities: BackplaneAbility,
+    /// The FEC abilities and requests
+    pub fec: BackplaneFec,
+}
+
+#[cfg(feature = "mmd")]
+impl Default for BackplaneBasePage {
+    fn default() -> Self {
+        Self {
+            selector_field: Some(SelectorField::default()),
+            pause: Default::default(),
+            remote_fault: false,
+            next_page: false,
+            abilities: BackplaneAbility::empty(),
+            fec: BackplaneFec::empty(),
+        }
+    }
+}
+
+#[cfg(feature = "mmd")]
+impl BackplaneBasePage {
+    /// Encode this base page into the values of the three base page
+    /// registers.
+    ///
+    /// The nonce fields are zero.
+    pub fn encode(&self) -> [u16; 3] {
+        let mut first = AutoNegCap::empty();
+        if let Some(selector) = self.selector_field {
+            first.insert(selector.into());
+        }
+        first.insert(self.pause.into());
+        first.set(AutoNegCap::REMOTE_FAULT, self.remote_fault);
+        first.set(AutoNegCap::NEXT_PAGE, self.next_page);
+
+        let (middle, high) = self.abilities.to_registers();
+        [first.bits(), middle, high | self.fec.bits()]
+    }
+
+    /// Decode the values of the three base page registers.
+    ///
+    /// The nonce and acknowledge fields are ignored. An unknown selector
+    /// field is decoded as `None`.
+    pub fn decode(registers: [u16; 3]) -> Self {
+        let [first, middle, high] = registers;
+        let first = AutoNegCap::from_bits_truncate(first);
+
+        Self {
+            selector_field: SelectorField::try_from(first).ok(),
+            pause: first.into(),
+            remote_fault: first.contains(AutoNegCap::REMOTE_FAULT),
+            next_page: first.contains(AutoNegCap::NEXT_PAGE),
+            abilities: BackplaneAbility::from_registers(middle, high),
+            fec: BackplaneFec::from_bits_truncate(high),
+        }
+    }
+}
+
+/// An MMD that has standard control and status registers
+#[cfg(feature = "mmd")]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MmdDevice {
+    /// The PMA/PMD MMD (1)
+    PmaPmd,
+    /// The PCS MMD (3)
+    Pcs,
+    /// The Auto-Negotiation MMD (7)
+    AutoNeg,
+}
+
+#[cfg(feature = "mmd")]
+impl MmdDevice {
+    /// The device address of this MMD
+    pub fn address(&self) -> u8 {
+        match self {
+            MmdDevice::PmaPmd => 1,
+            MmdDevice::Pcs => 3,
+            MmdDevice::AutoNeg => 7,
+        }
+    }
+}
+
 /// An IEEE 802.3 compatible PHY
+///
+/// All methods that access the PHY, including reads, take `&mut self`, as
+/// they require unique access to the underlying [`Miim`]. Methods that take
+/// `&self` only return information that is known without accessing the PHY,
+/// such as its address.
 pub trait Phy<M: Miim> {
     /// The best advertisement this PHY can send out.
     ///
@@ -353,7 +689,21 @@ pub trait Phy<M: Miim> {
     fn get_miim(&mut self) -> &mut M;
 
     /// Get the address of this PHY
-    fn get_phy_addr(&self) -> u8;
+    fn phy_address(&self) -> PhyAddress;
+
+    /// Get the address of this PHY as a raw `u8`
+    fn get_phy_addr(&self) -> u8 {
+        self.phy_address().get()
+    }
+
+    /// Whether this PHY also responds to writes to [`PhyAddress::BROADCAST`],
+    /// in addition to its own address
+    ///
+    /// This is `false` unless the driver knows that the PHY honors broadcast
+    /// writes.
+    fn honors_broadcast(&mut self) -> bool {
+        false
+    }
 
     /// Read a PHY register over MIIM
     fn read(&mut self, address: u8) -> u16 {
@@ -369,6 +719,13 @@ pub trait Phy<M: Miim> {
         miim.write(phy, address, value)
     }
 
+    /// Modify the bits selected by `mask` in a PHY register, leaving all other
+    /// bits untouched
+    fn modify(&mut self, address: u8, mask: u16, value: u16) {
+        let old = self.read(address);
+        self.write(address, (old & !mask) | (value & mask));
+    }
+
     /// Get the raw value of the Base Control Register of this PHY
     fn bcr(&mut self) -> Bcr {
         Bcr::from_bits_truncate(self.read(Bcr::ADDRESS))
@@ -471,33 +828,14 @@ pub trait Phy<M: Miim> {
             return;
         }
 
-        let mut ana = AutoNegCap::empty();
-
-        if ad.hd_10base_t && status.hd_10mbps {
-            ana.insert(AutoNegCap::_10BASET);
-        }
-
-        if ad.fd_10base_t && status.fd_10mbps {
-            ana.insert(AutoNegCap::_10BASETFD);
-        }
-
-        if ad.hd_100base_tx && status.hd_100base_x {
-            ana.insert(AutoNegCap::_100BASETX);
-        }
-
-        if ad.fd_100base_tx && status.fd_100base_x {
-            ana.insert(AutoNegCap::_100BASETXFD);
-        }
-
-        if ad.base100_t4 {
-            ana.insert(AutoNegCap::_100BASET4);
-        }
-
-        if let Some(selector) = ad.selector_field {
-            ana.insert(selector.into());
-        }
-
-        ana.insert(ad.pause.into());
+        let ad = AutoNegotiationAdvertisement {
+            hd_10base_t: ad.hd_10base_t && status.hd_10mbps,
+            fd_10base_t: ad.fd_10base_t && status.fd_10mbps,
+            hd_100base_tx: ad.hd_100base_tx && status.hd_100base_x,
+            fd_100base_tx: ad.fd_100base_tx && status.fd_100base_x,
+            ..ad
+        };
+        let ana = ad.encode();
 
         self.write(AutoNegCap::LOCAL_CAP_ADDRESS, ana.bits());
 
@@ -539,6 +877,19 @@ pub trait Phy<M: Miim> {
         }
     }
 
+    /// Electrically isolate the PHY from its MII until the returned guard is
+    /// dropped.
+    ///
+    /// This can be used to keep a PHY from driving a shared MII while other
+    /// PHYs on it are brought up. The PHY can still be accessed through the
+    /// guard.
+    fn isolate_scoped(&mut self) -> IsolateGuard<'_, M, Self>
+    where
+        Self: Sized,
+    {
+        IsolateGuard::new(self)
+    }
+
     /// Read an MMD register
     #[cfg(feature = "mmd")]
     fn mmd_read(&mut self, mmd_address: u8, reg_address: u16) -> u16
@@ -556,4 +907,268 @@ pub trait Phy<M: Miim> {
     {
         Mmd::write(self, device_address, reg_address, reg_value)
     }
+
+    /// Modify the bits selected by `mask` in an MMD register, leaving all other
+    /// bits untouched
+    #[cfg(feature = "mmd")]
+    fn mmd_modify(&mut self, device_address: u8, reg_address: u16, mask: u16, value: u16)
+    where
+        Self: Sized,
+    {
+        let old = self.mmd_read(device_address, reg_address);
+        self.mmd_write(device_address, reg_address, (old & !mask) | (value & mask))
+    }
+
+    /// Read the Devices In Package registers, to find out which MMDs this PHY
+    /// implements.
+    ///
+    /// All MMDs report the same value, so registers 1.5 and 1.6 are read. If
+    /// the PMA/PMD MMD is not present, the PCS (3) and Auto-Negotiation (7)
+    /// MMDs are tried instead. If none of them report a valid value, an empty
+    /// set is returned.
+    #[cfg(feature = "mmd")]
+    fn mmd_devices_in_package(&mut self) -> DevicesInPackage
+    where
+        Self: Sized,
+    {
+        for device in [1, 3, 7] {
+            let low = self.mmd_read(device, DevicesInPackage::LOW_ADDRESS);
+            let high = self.mmd_read(device, DevicesInPackage::HIGH_ADDRESS);
+            let bits = (high as u32) << 16 | low as u32;
+
+            if bits != 0 && bits != u32::MAX {
+                return DevicesInPackage::from_bits_truncate(bits);
+            }
+        }
+
+        DevicesInPackage::empty()
+    }
+
+    /// Read the device identifier (registers x.2 and x.3) of MMD `device`.
+    ///
+    /// Returns `None` if the identifier reads as all zeroes or all ones,
+    /// which indicates that the MMD is not present.
+    #[cfg(feature = "mmd")]
+    fn mmd_device_ident(&mut self, device: u8) -> Option<PhyIdent>
+    where
+        Self: Sized,
+    {
+        mmd_ident(self, device, 2)
+    }
+
+    /// Read the package identifier (registers x.14 and x.15) of MMD `device`.
+    ///
+    /// Returns `None` if the identifier reads as all zeroes or all ones,
+    /// which indicates that the MMD is not present.
+    #[cfg(feature = "mmd")]
+    fn mmd_package_ident(&mut self, device: u8) -> Option<PhyIdent>
+    where
+        Self: Sized,
+    {
+        mmd_ident(self, device, 14)
+    }
+
+    /// Get the control register of `device`
+    #[cfg(feature = "mmd")]
+    fn mmd_control(&mut self, device: MmdDevice) -> MmdControl
+    where
+        Self: Sized,
+    {
+        MmdControl::from_bits_truncate(self.mmd_read(device.address(), MmdControl::ADDRESS))
+    }
+
+    /// Reset `device` only, without resetting the rest of the PHY.
+    ///
+    /// Verify that the reset is complete by checking that
+    /// [`Self::mmd_is_resetting`] returns `false` before continuing usage.
+    #[cfg(feature = "mmd")]
+    fn mmd_reset(&mut self, device: MmdDevice)
+    where
+        Self: Sized,
+    {
+        self.mmd_modify(
+            device.address(),
+            MmdControl::ADDRESS,
+            MmdControl::RESET.bits(),
+            MmdControl::RESET.bits(),
+        )
+    }
+
+    /// Check if `device` is currently resetting
+    #[cfg(feature = "mmd")]
+    fn mmd_is_resetting(&mut self, device: MmdDevice) -> bool
+    where
+        Self: Sized,
+    {
+        self.mmd_control(device).contains(MmdControl::RESET)
+    }
+
+    /// Put `device` into, or take it out of, low-power mode.
+    ///
+    /// This has no effect for [`MmdDevice::AutoNeg`], which does not have a
+    /// low-power mode.
+    #[cfg(feature = "mmd")]
+    fn mmd_set_low_power(&mut self, device: MmdDevice, low_power: bool)
+    where
+        Self: Sized,
+    {
+        if device == MmdDevice::AutoNeg {
+            return;
+        }
+
+        let value = if low_power {
+            MmdControl::LOW_POWER.bits()
+        } else {
+            0
+        };
+
+        self.mmd_modify(
+            device.address(),
+            MmdControl::ADDRESS,
+            MmdControl::LOW_POWER.bits(),
+            value,
+        )
+    }
+
+    /// Get the status register of the PMA/PMD or PCS MMD.
+    ///
+    /// Returns `None` for [`MmdDevice::AutoNeg`], use
+    /// [`Self::mmd_an_status`] instead.
+    #[cfg(feature = "mmd")]
+    fn mmd_status(&mut self, device: MmdDevice) -> Option<MmdStatus>
+    where
+        Self: Sized,
+    {
+        if device == MmdDevice::AutoNeg {
+            return None;
+        }
+
+        Some(MmdStatus::from_bits_truncate(
+            self.mmd_read(device.address(), MmdStatus::ADDRESS),
+        ))
+    }
+
+    /// Get the status register of the Auto-Negotiation MMD
+    #[cfg(feature = "mmd")]
+    fn mmd_an_status(&mut self) -> MmdAnStatus
+    where
+        Self: Sized,
+    {
+        MmdAnStatus::from_bits_truncate(self.mmd_read(MmdAnStatus::MMD, MmdAnStatus::ADDRESS))
+    }
+
+    /// Get the PMA/PMD status 2 register (1.8), which reports transmit and
+    /// receive faults
+    #[cfg(feature = "mmd")]
+    fn pma_status2(&mut self) -> PmaStatus2
+    where
+        Self: Sized,
+    {
+        PmaStatus2::from_bits_truncate(self.mmd_read(PmaStatus2::MMD, PmaStatus2::ADDRESS))
+    }
+
+    /// Get the PMD receive signal detect register (1.10)
+    #[cfg(feature = "mmd")]
+    fn pmd_signal_detect(&mut self) -> PmdSignalDetect
+    where
+        Self: Sized,
+    {
+        PmdSignalDetect::from_bits_truncate(
+            self.mmd_read(PmdSignalDetect::MMD, PmdSignalDetect::ADDRESS),
+        )
+    }
+
+    /// Get the 10GBASE-R PCS status 1 register (3.32), which reports block
+    /// lock, high BER and the receive link status
+    #[cfg(feature = "mmd")]
+    fn baser_pcs_status1(&mut self) -> BaseRPcsStatus1
+    where
+        Self: Sized,
+    {
+        BaseRPcsStatus1::from_bits_truncate(
+            self.mmd_read(BaseRPcsStatus1::MMD, BaseRPcsStatus1::ADDRESS),
+        )
+    }
+
+    /// Read and clear the 10GBASE-R PCS status 2 register (3.33), which
+    /// contains the latched block lock and high BER bits, the BER counter and
+    /// the errored blocks counter
+    #[cfg(feature = "mmd")]
+    fn read_and_clear_baser_pcs_status2(&mut self) -> BaseRPcsStatus2
+    where
+        Self: Sized,
+    {
+        BaseRPcsStatus2::from_bits_truncate(
+            self.mmd_read(BaseRPcsStatus2::MMD, BaseRPcsStatus2::ADDRESS),
+        )
+    }
+
+    /// Get the Clause 73 (backplane) base page that this PHY advertises
+    #[cfg(feature = "mmd")]
+    fn backplane_advertisement(&mut self) -> BackplaneBasePage
+    where
+        Self: Sized,
+    {
+        backplane_base_page(self, BackplaneAbility::ADVERTISEMENT_ADDRESS)
+    }
+
+    /// Get the Clause 73 (backplane) base page that the link partner
+    /// advertises
+    #[cfg(feature = "mmd")]
+    fn backplane_partner_ability(&mut self) -> BackplaneBasePage
+    where
+        Self: Sized,
+    {
+        backplane_base_page(self, BackplaneAbility::PARTNER_ADDRESS)
+    }
+
+    /// Set the Clause 73 (backplane) base page that this PHY advertises, and
+    /// restart autonegotiation.
+    ///
+    /// The nonce fields, which are managed by the PHY, are left untouched.
+    #[cfg(feature = "mmd")]
+    fn set_backplane_advertisement(&mut self, page: BackplaneBasePage)
+    where
+        Self: Sized,
+    {
+        const MMD: u8 = BackplaneAbility::MMD;
+        const ADDRESS: u16 = BackplaneAbility::ADVERTISEMENT_ADDRESS;
+        // The echoed and transmitted nonce fields
+        const NONCE_MASK: u16 = 0b11111 << 5;
+        const TX_NONCE_MASK: u16 = 0b11111;
+
+        let [first, middle, high] = page.encode();
+        self.mmd_modify(MMD, ADDRESS, !NONCE_MASK, first);
+        self.mmd_modify(MMD, ADDRESS + 1, !TX_NONCE_MASK, middle);
+        self.mmd_write(MMD, ADDRESS + 2, high);
+
+        let restart = MmdControl::AUTONEG_ENABLE | MmdControl::RESTART_AUTONEG;
+        self.mmd_modify(
+            MmdDevice::AutoNeg.address(),
+            MmdControl::ADDRESS,
+            restart.bits(),
+            restart.bits(),
+        );
+    }
+}
+
+#[cfg(feature = "mmd")]
+fn backplane_base_page<M: Miim, P: Phy<M>>(phy: &mut P, address: u16) -> BackplaneBasePage {
+    let mmd = BackplaneAbility::MMD;
+    BackplaneBasePage::decode([
+        phy.mmd_read(mmd, address),
+        phy.mmd_read(mmd, address + 1),
+        phy.mmd_read(mmd, address + 2),
+    ])
+}
+
+#[cfg(feature = "mmd")]
+fn mmd_ident<M: Miim, P: Phy<M>>(phy: &mut P, device: u8, reg_address: u16) -> Option<PhyIdent> {
+    let msb = phy.mmd_read(device, reg_address);
+    let lsb = phy.mmd_read(device, reg_address + 1);
+
+    match (msb, lsb) {
+        (0, 0) | (0xFFFF, 0xFFFF) => None,
+        (msb, lsb) => Some(PhyIdent::new(msb, lsb)),
+    }
 }
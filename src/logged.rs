@@ -0,0 +1,366 @@
+//! Logging high-level operations performed on a [`Phy`].
+//!
+//! A [`LoggedPhy`] wraps any [`Phy`] and reports the high-level operations
+//! that are performed through it, such as resets and changes of the
+//! autonegotiation advertisement, as [`PhyEvent`]s. Unlike a
+//! [`Recorder`](crate::trace::Recorder), it does not report the individual
+//! MIIM transactions, which keeps application-level logs readable.
+
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+    registers::{Ane, Bcr, Bsr, Esr},
+    AutoNegotiationAdvertisement, ExtendedPhyStatus, Miim, Phy, PhyAddress, PhyIdent, PhyStatus,
+};
+
+#[cfg(feature = "mmd")]
+use crate::{
+    registers::{
+        BaseRPcsStatus1, BaseRPcsStatus2, DevicesInPackage, MmdAnStatus, MmdControl, MmdStatus,
+        PmaStatus2, PmdSignalDetect,
+    },
+    BackplaneBasePage, MmdDevice,
+};
+
+#[cfg(feature = "phy")]
+use crate::phy::PhyWithInterrupts;
+
+/// A high-level operation that was performed on a PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhyEvent {
+    /// The PHY was reset
+    Reset,
+    /// The autonegotiation advertisement was set, and autonegotiation was
+    /// restarted
+    SetAdvertisement(AutoNegotiationAdvertisement),
+    /// The interrupts of the PHY were read and cleared
+    InterruptsRead,
+}
+
+/// A [`Phy`] that reports the high-level operations performed on the
+/// wrapped [`Phy`] to a logger.
+///
+/// The logger is called with the address of the PHY and the [`PhyEvent`],
+/// before the operation is performed.
+///
+/// All other [`Phy`] methods are forwarded to the wrapped PHY, so that driver
+/// specific overrides keep taking effect.
+///
+/// Driver specific functionality of the wrapped PHY can be accessed through
+/// [`Deref`] and [`DerefMut`], but is not logged.
+#[derive(Debug)]
+pub struct LoggedPhy<P, L> {
+    phy: P,
+    logger: L,
+}
+
+impl<P, L> LoggedPhy<P, L>
+where
+    L: FnMut(PhyAddress, PhyEvent),
+{
+    /// Wrap `phy`, reporting its operations to `logger`
+    pub fn new(phy: P, logger: L) -> Self {
+        Self { phy, logger }
+    }
+
+    /// Release the wrapped PHY and the logger
+    pub fn release(self) -> (P, L) {
+        (self.phy, self.logger)
+    }
+
+    fn log<M: Miim>(&mut self, event: PhyEvent)
+    where
+        P: Phy<M>,
+    {
+        let address = self.phy.phy_address();
+        (self.logger)(address, event);
+    }
+}
+
+impl<P, L> Deref for LoggedPhy<P, L> {
+    type Target = P;
+
+    fn deref(&self) -> &Self::Target {
+        &self.phy
+    }
+}
+
+impl<P, L> DerefMut for LoggedPhy<P, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.phy
+    }
+}
+
+impl<M, P, L> Phy<M> for LoggedPhy<P, L>
+where
+    M: Miim,
+    P: Phy<M>,
+    L: FnMut(PhyAddress, PhyEvent),
+{
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        self.phy.best_supported_advertisement()
+    }
+
+    fn get_miim(&mut self) -> &mut M {
+        self.phy.get_miim()
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy.phy_address()
+    }
+
+    fn honors_broadcast(&mut self) -> bool {
+        self.phy.honors_broadcast()
+    }
+
+    fn reset(&mut self) {
+        self.log(PhyEvent::Reset);
+        self.phy.reset()
+    }
+
+    fn blocking_reset(&mut self) {
+        self.log(PhyEvent::Reset);
+        self.phy.blocking_reset()
+    }
+
+    fn set_autonegotiation_advertisement(&mut self, ad: AutoNegotiationAdvertisement) {
+        self.log(PhyEvent::SetAdvertisement(ad));
+        self.phy.set_autonegotiation_advertisement(ad)
+    }
+    fn read(&mut self, address: u8) -> u16 {
+        self.phy.read(address)
+    }
+
+    fn write(&mut self, address: u8, value: u16) {
+        self.phy.write(address, value)
+    }
+
+    fn modify(&mut self, address: u8, mask: u16, value: u16) {
+        self.phy.modify(address, mask, value)
+    }
+
+    fn bcr(&mut self) -> Bcr {
+        self.phy.bcr()
+    }
+
+    fn modify_bcr<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Bcr),
+    {
+        self.phy.modify_bcr(f)
+    }
+
+    fn is_resetting(&mut self) -> bool {
+        self.phy.is_resetting()
+    }
+
+    fn bsr(&mut self) -> Bsr {
+        self.phy.bsr()
+    }
+
+    fn phy_link_up(&mut self) -> bool {
+        self.phy.phy_link_up()
+    }
+
+    fn autoneg_completed(&mut self) -> bool {
+        self.phy.autoneg_completed()
+    }
+
+    fn status(&mut self) -> PhyStatus {
+        self.phy.status()
+    }
+
+    fn esr(&mut self) -> Option<Esr> {
+        self.phy.esr()
+    }
+
+    fn extended_status(&mut self) -> Option<ExtendedPhyStatus> {
+        self.phy.extended_status()
+    }
+
+    fn phy_ident(&mut self) -> Option<PhyIdent> {
+        self.phy.phy_ident()
+    }
+
+    fn get_autonegotiation_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        self.phy.get_autonegotiation_caps()
+    }
+
+    fn get_autonegotiation_partner_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        self.phy.get_autonegotiation_partner_caps()
+    }
+
+    fn ane(&mut self) -> Option<Ane> {
+        self.phy.ane()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_read(&mut self, mmd_address: u8, reg_address: u16) -> u16
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_read(mmd_address, reg_address)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_write(&mut self, device_address: u8, reg_address: u16, reg_value: u16)
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_write(device_address, reg_address, reg_value)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_modify(&mut self, device_address: u8, reg_address: u16, mask: u16, value: u16)
+    where
+        Self: Sized,
+    {
+        self.phy
+            .mmd_modify(device_address, reg_address, mask, value)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_devices_in_package(&mut self) -> DevicesInPackage
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_devices_in_package()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_device_ident(&mut self, device: u8) -> Option<PhyIdent>
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_device_ident(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_package_ident(&mut self, device: u8) -> Option<PhyIdent>
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_package_ident(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_control(&mut self, device: MmdDevice) -> MmdControl
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_control(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_reset(&mut self, device: MmdDevice)
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_reset(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_is_resetting(&mut self, device: MmdDevice) -> bool
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_is_resetting(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_set_low_power(&mut self, device: MmdDevice, low_power: bool)
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_set_low_power(device, low_power)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_status(&mut self, device: MmdDevice) -> Option<MmdStatus>
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_status(device)
+    }
+
+    #[cfg(feature = "mmd")]
+    fn mmd_an_status(&mut self) -> MmdAnStatus
+    where
+        Self: Sized,
+    {
+        self.phy.mmd_an_status()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn pma_status2(&mut self) -> PmaStatus2
+    where
+        Self: Sized,
+    {
+        self.phy.pma_status2()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn pmd_signal_detect(&mut self) -> PmdSignalDetect
+    where
+        Self: Sized,
+    {
+        self.phy.pmd_signal_detect()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn baser_pcs_status1(&mut self) -> BaseRPcsStatus1
+    where
+        Self: Sized,
+    {
+        self.phy.baser_pcs_status1()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn read_and_clear_baser_pcs_status2(&mut self) -> BaseRPcsStatus2
+    where
+        Self: Sized,
+    {
+        self.phy.read_and_clear_baser_pcs_status2()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn backplane_advertisement(&mut self) -> BackplaneBasePage
+    where
+        Self: Sized,
+    {
+        self.phy.backplane_advertisement()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn backplane_partner_ability(&mut self) -> BackplaneBasePage
+    where
+        Self: Sized,
+    {
+        self.phy.backplane_partner_ability()
+    }
+
+    #[cfg(feature = "mmd")]
+    fn set_backplane_advertisement(&mut self, page: BackplaneBasePage)
+    where
+        Self: Sized,
+    {
+        self.phy.set_backplane_advertisement(page)
+    }
+}
+
+#[cfg(feature = "phy")]
+impl<M, P, L> PhyWithInterrupts<M> for LoggedPhy<P, L>
+where
+    M: Miim,
+    P: PhyWithInterrupts<M>,
+    L: FnMut(PhyAddress, PhyEvent),
+{
+    type Interrupts = P::Interrupts;
+
+    fn read_and_clear_interrupts(&mut self) -> Self::Interrupts {
+        self.log(PhyEvent::InterruptsRead);
+        self.phy.read_and_clear_interrupts()
+    }
+}
@@ -1,6 +1,8 @@
 //! This module defines traits and structs used for access to
 //! Media Independent Interface
 
+use crate::PhyAddress;
+
 /// A trait used for implementing access to the Media Indepedent
 /// Interface of an IEEE 802.3 compatible PHY.
 pub trait Miim {
@@ -13,4 +15,88 @@ pub trait Miim {
 
     /// Write to an MII register
     fn write(&mut self, phy: u8, reg: u8, data: u16);
+
+    /// Write to an MII register of all PHYs that honor broadcast writes
+    /// (see [`Phy::honors_broadcast`](crate::Phy::honors_broadcast)), by
+    /// writing to [`PhyAddress::BROADCAST`].
+    fn broadcast_write(&mut self, reg: u8, data: u16) {
+        self.write(PhyAddress::BROADCAST.get(), reg, data)
+    }
+
+    /// Write to an MII register of all PHYs in `phys`
+    ///
+    /// Implementations may override this if the underlying hardware can
+    /// perform these writes more efficiently.
+    fn write_many(&mut self, phys: &[PhyAddress], reg: u8, data: u16) {
+        for phy in phys {
+            self.write(phy.get(), reg, data);
+        }
+    }
+
+    /// Read register `reg` of MMD `mmd` of a Clause 45 PHY
+    ///
+    /// The default implementation accesses the register indirectly, through
+    /// the Clause 22 MMD access registers 13 and 14. Implementations that can
+    /// generate Clause 45 frames should override this, as PHYs that only
+    /// implement Clause 45 do not have these registers.
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        self.write(phy, MMD_CONTROL, mmd as u16 & MMD_DEVAD_MASK);
+        self.write(phy, MMD_DATA, reg);
+        self.write(
+            phy,
+            MMD_CONTROL,
+            MMD_DATA_NO_POSTINC | mmd as u16 & MMD_DEVAD_MASK,
+        );
+        self.read(phy, MMD_DATA)
+    }
+
+    /// Write to register `reg` of MMD `mmd` of a Clause 45 PHY
+    ///
+    /// See [`Miim::read_c45`] for more information.
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        self.write(phy, MMD_CONTROL, mmd as u16 & MMD_DEVAD_MASK);
+        self.write(phy, MMD_DATA, reg);
+        self.write(
+            phy,
+            MMD_CONTROL,
+            MMD_DATA_NO_POSTINC | mmd as u16 & MMD_DEVAD_MASK,
+        );
+        self.write(phy, MMD_DATA, data);
+    }
+}
+
+// The Clause 22 MMD access registers, see `crate::mmd`
+const MMD_CONTROL: u8 = 13;
+const MMD_DATA: u8 = 14;
+const MMD_DEVAD_MASK: u16 = 0b11111;
+const MMD_DATA_NO_POSTINC: u16 = 0b01 << 14;
+
+/// A mutable reference to a [`Miim`] is also a [`Miim`].
+///
+/// This makes it possible to create a PHY around a borrowed [`Miim`], so the
+/// [`Miim`] does not have to be released before it can be used elsewhere.
+impl<M: Miim + ?Sized> Miim for &mut M {
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        (**self).read(phy, reg)
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        (**self).write(phy, reg, data)
+    }
+
+    fn broadcast_write(&mut self, reg: u8, data: u16) {
+        (**self).broadcast_write(reg, data)
+    }
+
+    fn write_many(&mut self, phys: &[PhyAddress], reg: u8, data: u16) {
+        (**self).write_many(phys, reg, data)
+    }
+
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        (**self).read_c45(phy, mmd, reg)
+    }
+
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        (**self).write_c45(phy, mmd, reg, data)
+    }
 }
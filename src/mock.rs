@@ -0,0 +1,193 @@
+//! A mock [`Miim`], for testing drivers without hardware.
+//!
+//! This module is only available with the `mock` feature.
+//!
+//! [`MockMiim`] stores the values of all 32 registers of all 32 PHY
+//! addresses, and returns the last written value when a register is read.
+//! Faults can be injected to test how drivers behave when the bus
+//! misbehaves.
+
+use crate::Miim;
+
+/// The maximum amount of faults that can be pending at the same time
+pub const MAX_FAULTS: usize = 8;
+
+/// A fault that can be injected into a [`MockMiim`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// The transaction does not reach the PHY: writes are not applied, and
+    /// reads return 0
+    Fail,
+    /// The data on the bus is all ones: writes store `0xFFFF`, and reads
+    /// return `0xFFFF`
+    AllOnes,
+    /// The PHY does not see the update: writes are not applied, and reads
+    /// return the value that the register had before the most recent write
+    Stale,
+}
+
+/// The kind of access that a [`Fault`] applies to
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Reads only
+    Read,
+    /// Writes only
+    Write,
+    /// Both reads and writes
+    Any,
+}
+
+impl Access {
+    fn matches(&self, write: bool) -> bool {
+        match self {
+            Access::Read => !write,
+            Access::Write => write,
+            Access::Any => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PendingFault {
+    fault: Fault,
+    access: Access,
+    reg: Option<u8>,
+    remaining: usize,
+}
+
+/// A [`Miim`] that is backed by memory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockMiim {
+    registers: [[u16; 32]; 32],
+    previous: [[u16; 32]; 32],
+    faults: [Option<PendingFault>; MAX_FAULTS],
+    accesses: usize,
+    injected: usize,
+}
+
+impl Default for MockMiim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockMiim {
+    /// Create a new mock with all registers set to 0
+    pub const fn new() -> Self {
+        Self {
+            registers: [[0; 32]; 32],
+            previous: [[0; 32]; 32],
+            faults: [None; MAX_FAULTS],
+            accesses: 0,
+            injected: 0,
+        }
+    }
+
+    /// Get the value of a register, without counting as an access
+    pub fn get(&self, phy: u8, reg: u8) -> u16 {
+        self.registers[(phy & 0x1F) as usize][(reg & 0x1F) as usize]
+    }
+
+    /// Set the value of a register, without counting as an access
+    pub fn set(&mut self, phy: u8, reg: u8, value: u16) {
+        let (phy, reg) = ((phy & 0x1F) as usize, (reg & 0x1F) as usize);
+        self.registers[phy][reg] = value;
+        self.previous[phy][reg] = value;
+    }
+
+    /// The amount of reads and writes that have been performed
+    pub fn accesses(&self) -> usize {
+        self.accesses
+    }
+
+    /// The amount of injected faults that have occured
+    pub fn faults_injected(&self) -> usize {
+        self.injected
+    }
+
+    /// Inject `fault` into the access of kind `access` that occurs after
+    /// `after` more accesses of that kind.
+    ///
+    /// If `reg` is `Some`, only accesses to that register are counted.
+    ///
+    /// Returns `false` if [`MAX_FAULTS`] faults are already pending, in
+    /// which case the fault is not injected.
+    pub fn inject(&mut self, fault: Fault, access: Access, reg: Option<u8>, after: usize) -> bool {
+        if let Some(slot) = self.faults.iter_mut().find(|f| f.is_none()) {
+            *slot = Some(PendingFault {
+                fault,
+                access,
+                reg,
+                remaining: after,
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove all pending faults
+    pub fn clear_faults(&mut self) {
+        self.faults = [None; MAX_FAULTS];
+    }
+
+    fn next_fault(&mut self, reg: u8, write: bool) -> Option<Fault> {
+        self.accesses += 1;
+
+        let mut triggered = None;
+        for slot in self.faults.iter_mut() {
+            let Some(pending) = slot else {
+                continue;
+            };
+
+            if !pending.access.matches(write) || pending.reg.is_some_and(|r| r != reg) {
+                continue;
+            }
+
+            if pending.remaining == 0 {
+                if triggered.is_none() {
+                    triggered = Some(pending.fault);
+                    *slot = None;
+                }
+            } else {
+                pending.remaining -= 1;
+            }
+        }
+
+        if triggered.is_some() {
+            self.injected += 1;
+        }
+
+        triggered
+    }
+}
+
+impl Miim for MockMiim {
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        let (phy, reg) = (phy & 0x1F, reg & 0x1F);
+        let (p, r) = (phy as usize, reg as usize);
+
+        match self.next_fault(reg, false) {
+            None => self.registers[p][r],
+            Some(Fault::Fail) => 0,
+            Some(Fault::AllOnes) => 0xFFFF,
+            Some(Fault::Stale) => self.previous[p][r],
+        }
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        let (phy, reg) = (phy & 0x1F, reg & 0x1F);
+        let (p, r) = (phy as usize, reg as usize);
+
+        let data = match self.next_fault(reg, true) {
+            None => data,
+            Some(Fault::AllOnes) => 0xFFFF,
+            Some(Fault::Fail) | Some(Fault::Stale) => return,
+        };
+
+        self.previous[p][r] = self.registers[p][r];
+        self.registers[p][r] = data;
+    }
+}
@@ -0,0 +1,216 @@
+//! Indirect MIIM access to PHYs behind a Marvell 88E6xxx switch
+//!
+//! The internal PHYs of 88E6xxx switches are not directly reachable on the
+//! MIIM bus. Instead, they are accessed through the SMI PHY Command and Data
+//! registers in the switch's Global 2 register set.
+//!
+//! If the switch is strapped for multi-chip addressing mode, it only responds
+//! to a single PHY address on the MIIM bus, and all of its own registers are
+//! in turn accessed indirectly through the SMI Command and Data registers at
+//! that address.
+//!
+//! [`IndirectMiim`] implements [`Miim`] on top of these mechanisms, so that
+//! the PHY implementations in this crate can be used for PHYs behind an
+//! 88E6xxx switch.
+
+use crate::{Miim, PhyAddress};
+
+use self::registers::{SmiCommand, GLOBAL2, SMI_COMMAND, SMI_DATA, SMI_PHY_COMMAND, SMI_PHY_DATA};
+
+/// The maximum amount of times the busy bit of an SMI command register is
+/// polled before the access is considered to have timed out
+pub const MAX_POLLS: u32 = 1000;
+
+/// An error that occurs while accessing a register through the switch
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmiError {
+    /// The SMI command register read as all ones, which means that the switch
+    /// (or, in single-chip mode, the register) is not present
+    NotPresent,
+    /// The busy bit of the SMI command register did not clear within
+    /// [`MAX_POLLS`] reads
+    Timeout,
+}
+
+/// A [`Miim`] that accesses the PHYs behind a Marvell 88E6xxx switch
+///
+/// [`Miim::read`] returns `0xFFFF` (as if no PHY is present) and
+/// [`Miim::write`] is discarded if the switch does not respond. Use
+/// [`IndirectMiim::try_read`] and [`IndirectMiim::try_write`] to detect
+/// this.
+#[derive(Debug)]
+pub struct IndirectMiim<M: Miim> {
+    miim: M,
+    switch_addr: PhyAddress,
+}
+
+impl<M: Miim> IndirectMiim<M> {
+    /// Create a new `IndirectMiim` for the switch at `switch_addr`.
+    ///
+    /// A `switch_addr` of 0 means that the switch is in single-chip
+    /// addressing mode, and its registers can be accessed directly.
+    pub fn new(miim: M, switch_addr: PhyAddress) -> Self {
+        Self { miim, switch_addr }
+    }
+
+    /// Read the switch register `reg` of switch device `device`
+    ///
+    /// Returns `0xFFFF` if the switch does not respond.
+    pub fn switch_read(&mut self, device: u8, reg: u8) -> u16 {
+        self.try_switch_read(device, reg).unwrap_or(0xFFFF)
+    }
+
+    /// Write the switch register `reg` of switch device `device`
+    ///
+    /// The write is discarded if the switch does not respond.
+    pub fn switch_write(&mut self, device: u8, reg: u8, value: u16) {
+        self.try_switch_write(device, reg, value).ok();
+    }
+
+    /// Read the switch register `reg` of switch device `device`
+    pub fn try_switch_read(&mut self, device: u8, reg: u8) -> Result<u16, SmiError> {
+        if self.switch_addr.get() == 0 {
+            return Ok(self.miim.read(device, reg));
+        }
+
+        self.wait_smi()?;
+        self.miim.write(
+            self.switch_addr.get(),
+            SMI_COMMAND,
+            SmiCommand::read(device, reg).bits(),
+        );
+        self.wait_smi()?;
+        Ok(self.miim.read(self.switch_addr.get(), SMI_DATA))
+    }
+
+    /// Write the switch register `reg` of switch device `device`
+    pub fn try_switch_write(&mut self, device: u8, reg: u8, value: u16) -> Result<(), SmiError> {
+        if self.switch_addr.get() == 0 {
+            self.miim.write(device, reg, value);
+            return Ok(());
+        }
+
+        self.wait_smi()?;
+        self.miim.write(self.switch_addr.get(), SMI_DATA, value);
+        self.miim.write(
+            self.switch_addr.get(),
+            SMI_COMMAND,
+            SmiCommand::write(device, reg).bits(),
+        );
+        self.wait_smi()
+    }
+
+    /// Read register `reg` of the PHY at `phy` behind the switch
+    pub fn try_read(&mut self, phy: u8, reg: u8) -> Result<u16, SmiError> {
+        self.wait_smi_phy()?;
+        self.try_switch_write(GLOBAL2, SMI_PHY_COMMAND, SmiCommand::read(phy, reg).bits())?;
+        self.wait_smi_phy()?;
+        self.try_switch_read(GLOBAL2, SMI_PHY_DATA)
+    }
+
+    /// Write register `reg` of the PHY at `phy` behind the switch
+    pub fn try_write(&mut self, phy: u8, reg: u8, data: u16) -> Result<(), SmiError> {
+        self.wait_smi_phy()?;
+        self.try_switch_write(GLOBAL2, SMI_PHY_DATA, data)?;
+        self.try_switch_write(GLOBAL2, SMI_PHY_COMMAND, SmiCommand::write(phy, reg).bits())?;
+        self.wait_smi_phy()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> M {
+        self.miim
+    }
+
+    fn wait_smi(&mut self) -> Result<(), SmiError> {
+        let switch_addr = self.switch_addr.get();
+        wait_not_busy(|| Ok(self.miim.read(switch_addr, SMI_COMMAND)))
+    }
+
+    fn wait_smi_phy(&mut self) -> Result<(), SmiError> {
+        wait_not_busy(|| self.try_switch_read(GLOBAL2, SMI_PHY_COMMAND))
+    }
+}
+
+fn wait_not_busy<F>(mut read: F) -> Result<(), SmiError>
+where
+    F: FnMut() -> Result<u16, SmiError>,
+{
+    for _ in 0..MAX_POLLS {
+        let command = read()?;
+        if command == 0xFFFF {
+            return Err(SmiError::NotPresent);
+        }
+
+        if !SmiCommand::from_bits_truncate(command).contains(SmiCommand::BUSY) {
+            return Ok(());
+        }
+    }
+
+    Err(SmiError::Timeout)
+}
+
+impl<M: Miim> Miim for IndirectMiim<M> {
+    /// Read a register of a PHY behind the switch
+    ///
+    /// Returns `0xFFFF` if the switch does not respond.
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        self.try_read(phy, reg).unwrap_or(0xFFFF)
+    }
+
+    /// Write a register of a PHY behind the switch
+    ///
+    /// The write is discarded if the switch does not respond.
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        self.try_write(phy, reg, data).ok();
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! 88E6xxx SMI registers
+
+    use bitflags::bitflags;
+
+    /// Register 0 at the switch address, the SMI Command register (multi-chip mode)
+    pub const SMI_COMMAND: u8 = 0x00;
+    /// Register 1 at the switch address, the SMI Data register (multi-chip mode)
+    pub const SMI_DATA: u8 = 0x01;
+
+    /// The switch device containing the Global 2 registers
+    pub const GLOBAL2: u8 = 0x1C;
+    /// Global 2 register 0x18, the SMI PHY Command register
+    pub const SMI_PHY_COMMAND: u8 = 0x18;
+    /// Global 2 register 0x19, the SMI PHY Data register
+    pub const SMI_PHY_DATA: u8 = 0x19;
+
+    bitflags! {
+        /// The layout of both the SMI Command and the SMI PHY Command registers
+        pub struct SmiCommand: u16 {
+            const BUSY = (1 << 15);
+            const CLAUSE_22 = (1 << 12);
+            const OP_MASK = (0b11 << 10);
+            const OP_WRITE = (0b01 << 10);
+            const OP_READ = (0b10 << 10);
+            const DEVICE_MASK = (0x1F << 5);
+            const REG_MASK = (0x1F);
+        }
+    }
+
+    impl SmiCommand {
+        fn command(op: Self, device: u8, reg: u8) -> Self {
+            let address = ((device as u16 & 0x1F) << 5) | (reg as u16 & 0x1F);
+            Self::BUSY | Self::CLAUSE_22 | op | Self::from_bits_truncate(address)
+        }
+
+        /// A Clause 22 read of `reg` on `device`
+        pub fn read(device: u8, reg: u8) -> Self {
+            Self::command(Self::OP_READ, device, reg)
+        }
+
+        /// A Clause 22 write of `reg` on `device`
+        pub fn write(device: u8, reg: u8) -> Self {
+            Self::command(Self::OP_WRITE, device, reg)
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Phy implementation for the Analog Devices ADIN1200 10/100 Ethernet PHY
+//!
+//! The subsystem registers of the ADIN1200 are located in the vendor
+//! specific MMD 0x1E, and are accessed through the Clause 22 MMD access
+//! registers.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    FastLinkDown, InterruptFlags, LedControl1, LedControl2, PhyStatus1, SUBSYSTEM_MMD,
+};
+
+use super::{AdvancedPhySpeed, PhyWithFastLinkDown, PhyWithInterrupts, PhyWithSpeed};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// An MDIO synchronization error occurred
+    MdioSync,
+    /// The autonegotiation status changed
+    AutoNegotiationStatusChange,
+    /// A page was received during autonegotiation
+    PageReceived,
+    /// The idle error counter saturated
+    IdleErrorCounter,
+    /// The MAC interface FIFO overflowed or underflowed
+    FifoOverflowUnderflow,
+    /// The receive status changed
+    RxStatusChange,
+    /// The link status changed
+    LinkStatusChange,
+    /// The link speed changed
+    SpeedChange,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::MdioSync => InterruptFlags::MDIO_SYNC,
+            Interrupt::AutoNegotiationStatusChange => InterruptFlags::AUTONEG_STATUS_CHANGE,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::IdleErrorCounter => InterruptFlags::IDLE_ERROR_COUNTER,
+            Interrupt::FifoOverflowUnderflow => InterruptFlags::FIFO_OVERFLOW_UNDERFLOW,
+            Interrupt::RxStatusChange => InterruptFlags::RX_STATUS_CHANGE,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+        }
+    }
+}
+
+/// An ADIN1200
+#[derive(Debug)]
+pub struct ADIN1200<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> ADIN1200<MIIM> {
+    /// Create a new ADIN1200 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This takes the PHY out of software power-down, which it may be
+    /// strapped to start up in, advertises all supported modes and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_power_down(false);
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Check whether the PHY is in software power-down
+    pub fn powered_down(&mut self) -> bool {
+        self.bcr().power_down()
+    }
+
+    /// Enter or leave software power-down
+    pub fn set_power_down(&mut self, power_down: bool) {
+        self.modify_bcr(|bcr| {
+            bcr.set_power_down(power_down);
+        });
+    }
+
+    /// Read the subsystem register at `address`
+    pub fn subsystem_read(&mut self, address: u16) -> u16 {
+        self.mmd_read(SUBSYSTEM_MMD, address)
+    }
+
+    /// Write the subsystem register at `address`
+    pub fn subsystem_write(&mut self, address: u16, value: u16) {
+        self.mmd_write(SUBSYSTEM_MMD, address, value)
+    }
+
+    /// Get the configuration of the LED_0 pin.
+    ///
+    /// The meaning of the configuration values is described in the datasheet
+    /// (LED_A_CFG).
+    pub fn led_config(&mut self) -> u8 {
+        (self.read(LedControl2::ADDRESS) & LedControl2::LED_A_CONFIG_MASK.bits()) as u8
+    }
+
+    /// Set the configuration of the LED_0 pin.
+    ///
+    /// The meaning of the configuration values is described in the datasheet
+    /// (LED_A_CFG).
+    pub fn set_led_config(&mut self, config: u8) {
+        let mask = LedControl2::LED_A_CONFIG_MASK.bits();
+        self.modify(LedControl2::ADDRESS, mask, config as u16 & mask);
+    }
+
+    /// Enable or disable the LED_0 pin output
+    pub fn set_led_enabled(&mut self, enabled: bool) {
+        let bit = LedControl1::OUTPUT_DISABLE.bits();
+        self.modify(LedControl1::ADDRESS, bit, if enabled { 0 } else { bit });
+    }
+
+    /// Get the conditions that trigger an enhanced (fast) link down
+    pub fn fast_link_down(&mut self) -> FastLinkDown {
+        FastLinkDown::from_bits_truncate(self.subsystem_read(FastLinkDown::ADDRESS))
+    }
+
+    /// Set the conditions that trigger an enhanced (fast) link down.
+    ///
+    /// With enhanced link detection, the PHY drops the link as soon as one of
+    /// the enabled conditions is detected, instead of waiting for the
+    /// standard link fail timer to expire.
+    pub fn set_fast_link_down(&mut self, fld: FastLinkDown) {
+        self.subsystem_write(FastLinkDown::ADDRESS, fld.bits());
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flags = (interrupt.flag() | InterruptFlags::HW_IRQ_ENABLE).bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flags, flags);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Read the PHY Status 1 register
+    pub fn phy_status(&mut self) -> PhyStatus1 {
+        PhyStatus1::from_bits_truncate(self.read(PhyStatus1::ADDRESS))
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for ADIN1200<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for ADIN1200<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for ADIN1200<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithFastLinkDown<MIIM> for ADIN1200<MIIM> {
+    /// The conditions that trigger a fast link down. Fast link down is
+    /// disabled if this is empty.
+    type Config = FastLinkDown;
+
+    fn fast_link_down(&mut self) -> FastLinkDown {
+        self.fast_link_down()
+    }
+
+    fn set_fast_link_down(&mut self, config: FastLinkDown) {
+        self.set_fast_link_down(config)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! ADIN1200 registers
+    //!
+    //! These are shared with the ADIN1300.
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::Interrupt;
+
+    /// The vendor specific MMD containing the subsystem registers
+    pub const SUBSYSTEM_MMD: u8 = 0x1E;
+
+    bitflags! {
+        /// Register 0x18 and register 0x19, the Interrupt Mask and Interrupt
+        /// Status registers
+        pub struct InterruptFlags: u16 {
+            const MDIO_SYNC = (1 << 9);
+            const AUTONEG_STATUS_CHANGE = (1 << 8);
+            const PAGE_RECEIVED = (1 << 6);
+            const IDLE_ERROR_COUNTER = (1 << 5);
+            const FIFO_OVERFLOW_UNDERFLOW = (1 << 4);
+            const RX_STATUS_CHANGE = (1 << 3);
+            const LINK_STATUS_CHANGE = (1 << 2);
+            const SPEED_CHANGE = (1 << 1);
+            /// In the mask register: enable the INT_N pin. In the status
+            /// register: an interrupt is pending.
+            const HW_IRQ_ENABLE = (1 << 0);
+        }
+
+        /// Register 0x1A, the PHY Status 1 register
+        pub struct PhyStatus1: u16 {
+            const PAIR_01_SWAP = (1 << 11);
+            const HCD_TECH_MASK = (0b111 << 7);
+            const LINK = (1 << 6);
+        }
+
+        /// Register 0x1B, the LED Control 1 register
+        pub struct LedControl1: u16 {
+            const EXTENDED_CONFIG_ENABLE = (1 << 10);
+            const OUTPUT_DISABLE = (1 << 1);
+        }
+
+        /// Register 0x1C, the LED Control 2 register
+        pub struct LedControl2: u16 {
+            const LED_A_CONFIG_MASK = (0xF);
+        }
+
+        /// Register 0x8E27 in MMD 0x1E, the Fast Link Down Enable register
+        pub struct FastLinkDown: u16 {
+            const PCS_ERROR_100 = (1 << 7);
+            const PCS_ERROR_1000 = (1 << 6);
+            const SLICER_OUTPUT_STUCK_100 = (1 << 5);
+            const SLICER_OUTPUT_STUCK_1000 = (1 << 4);
+            const SLICER_INPUT_ZERO_100 = (1 << 3);
+            const SLICER_INPUT_ZERO_1000 = (1 << 2);
+            const SLICER_INPUT_INVALID_100 = (1 << 1);
+            const SLICER_INPUT_INVALID_1000 = (1 << 0);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const MASK_ADDRESS: u8 = 0x18;
+        pub const STATUS_ADDRESS: u8 = 0x19;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl PhyStatus1 {
+        pub const ADDRESS: u8 = 0x1A;
+    }
+
+    impl From<PhyStatus1> for Option<AdvancedPhySpeed> {
+        fn from(status: PhyStatus1) -> Self {
+            let speed = match (status & PhyStatus1::HCD_TECH_MASK).bits() >> 7 {
+                0 => AdvancedPhySpeed::HalfDuplexBase10T,
+                1 => AdvancedPhySpeed::FullDuplexBase10T,
+                2 => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                3 => AdvancedPhySpeed::FullDuplexBase100Tx,
+                4 => AdvancedPhySpeed::HalfDuplexBase1000T,
+                5 => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl LedControl1 {
+        pub const ADDRESS: u8 = 0x1B;
+    }
+
+    impl LedControl2 {
+        pub const ADDRESS: u8 = 0x1C;
+    }
+
+    impl FastLinkDown {
+        pub const ADDRESS: u16 = 0x8E27;
+    }
+}
@@ -0,0 +1,321 @@
+//! Phy implementation for the Analog Devices ADIN1300 gigabit Ethernet PHY
+//!
+//! The ADIN1300 shares its register layout with the
+//! [`ADIN1200`](super::adin1200::ADIN1200), and its subsystem registers are
+//! also located in the vendor specific MMD 0x1E.
+
+use crate::{
+    phy::adin1200::{
+        registers::{FastLinkDown, InterruptFlags, PhyStatus1, SUBSYSTEM_MMD},
+        Interrupt,
+    },
+    registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{PhyControl2, PhyControl3, RgmiiConfig};
+
+use super::{
+    AdvancedPhySpeed, PhyWithEee, PhyWithFastLinkDown, PhyWithInterrupts, PhyWithRgmiiDelays,
+    PhyWithSpeed, RgmiiDelays,
+};
+
+/// An internal RGMII clock delay supported by the ADIN1300
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RgmiiDelay {
+    /// 1.6 ns
+    Delay1_6ns,
+    /// 1.8 ns
+    Delay1_8ns,
+    /// 2.0 ns
+    Delay2_0ns,
+    /// 2.2 ns
+    Delay2_2ns,
+    /// 2.4 ns
+    Delay2_4ns,
+}
+
+impl From<RgmiiDelay> for u16 {
+    fn from(delay: RgmiiDelay) -> Self {
+        match delay {
+            RgmiiDelay::Delay1_6ns => 1,
+            RgmiiDelay::Delay1_8ns => 2,
+            RgmiiDelay::Delay2_0ns => 0,
+            RgmiiDelay::Delay2_2ns => 6,
+            RgmiiDelay::Delay2_4ns => 7,
+        }
+    }
+}
+
+/// An ADIN1300
+#[derive(Debug)]
+pub struct ADIN1300<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> ADIN1300<MIIM> {
+    /// Create a new ADIN1300 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This takes the PHY out of software power-down, which it may be
+    /// strapped to start up in, advertises all supported 10/100/1000BASE-T
+    /// modes and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_power_down(false);
+
+        super::advertise_gigabit(self);
+    }
+
+    /// Enter or leave software power-down
+    pub fn set_power_down(&mut self, power_down: bool) {
+        self.modify_bcr(|bcr| {
+            bcr.set_power_down(power_down);
+        });
+    }
+
+    /// Read the subsystem register at `address`
+    pub fn subsystem_read(&mut self, address: u16) -> u16 {
+        self.mmd_read(SUBSYSTEM_MMD, address)
+    }
+
+    /// Write the subsystem register at `address`
+    pub fn subsystem_write(&mut self, address: u16, value: u16) {
+        self.mmd_write(SUBSYSTEM_MMD, address, value)
+    }
+
+    /// Read the RGMII configuration
+    pub fn rgmii_config(&mut self) -> RgmiiConfig {
+        RgmiiConfig::from_bits_truncate(self.subsystem_read(RgmiiConfig::ADDRESS))
+    }
+
+    /// Configure the internal RGMII TX (GTX_CLK) and RX clock delays.
+    ///
+    /// A delay of `None` disables the respective internal delay.
+    pub fn set_rgmii_delays(&mut self, tx_delay: Option<RgmiiDelay>, rx_delay: Option<RgmiiDelay>) {
+        let mut config = self.rgmii_config();
+        config.remove(
+            RgmiiConfig::RX_DELAY_MASK
+                | RgmiiConfig::GTX_DELAY_MASK
+                | RgmiiConfig::RX_DELAY_ENABLE
+                | RgmiiConfig::TX_DELAY_ENABLE,
+        );
+
+        if let Some(delay) = tx_delay {
+            config |= RgmiiConfig::TX_DELAY_ENABLE
+                | RgmiiConfig::from_bits_truncate(u16::from(delay) << 3);
+        }
+
+        if let Some(delay) = rx_delay {
+            config |= RgmiiConfig::RX_DELAY_ENABLE
+                | RgmiiConfig::from_bits_truncate(u16::from(delay) << 6);
+        }
+
+        self.subsystem_write(RgmiiConfig::ADDRESS, config.bits());
+    }
+
+    /// Get the amount of link attempts after which the PHY downshifts to a
+    /// lower speed, or `None` if downshifting is disabled.
+    pub fn downshift(&mut self) -> Option<u8> {
+        let ctrl2 = PhyControl2::from_bits_truncate(self.read(PhyControl2::ADDRESS));
+        if !ctrl2.intersects(PhyControl2::DOWNSHIFT_ENABLE) {
+            return None;
+        }
+
+        let ctrl3 = PhyControl3::from_bits_truncate(self.read(PhyControl3::ADDRESS));
+        Some(((ctrl3 & PhyControl3::DOWNSHIFT_RETRIES_MASK).bits() >> 10) as u8)
+    }
+
+    /// Downshift to a lower speed after `retries` failed link attempts (at
+    /// most 7), or disable downshifting if `retries` is `None`.
+    pub fn set_downshift(&mut self, retries: Option<u8>) {
+        let enable = PhyControl2::DOWNSHIFT_ENABLE.bits();
+
+        if let Some(retries) = retries {
+            let retries = (retries.min(7) as u16) << 10;
+            self.modify(
+                PhyControl3::ADDRESS,
+                PhyControl3::DOWNSHIFT_RETRIES_MASK.bits(),
+                retries,
+            );
+            self.modify(PhyControl2::ADDRESS, enable, enable);
+        } else {
+            self.modify(PhyControl2::ADDRESS, enable, 0);
+        }
+    }
+
+    /// Get the conditions that trigger an enhanced (fast) link down
+    pub fn fast_link_down(&mut self) -> FastLinkDown {
+        FastLinkDown::from_bits_truncate(self.subsystem_read(FastLinkDown::ADDRESS))
+    }
+
+    /// Set the conditions that trigger an enhanced (fast) link down.
+    pub fn set_fast_link_down(&mut self, fld: FastLinkDown) {
+        self.subsystem_write(FastLinkDown::ADDRESS, fld.bits());
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flags = (interrupt.flag() | InterruptFlags::HW_IRQ_ENABLE).bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flags, flags);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Read the PHY Status 1 register
+    pub fn phy_status(&mut self) -> PhyStatus1 {
+        PhyStatus1::from_bits_truncate(self.read(PhyStatus1::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for ADIN1300<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for ADIN1300<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEee<MIIM> for ADIN1300<MIIM> {}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for ADIN1300<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithFastLinkDown<MIIM> for ADIN1300<MIIM> {
+    /// The conditions that trigger a fast link down. Fast link down is
+    /// disabled if this is empty.
+    type Config = FastLinkDown;
+
+    fn fast_link_down(&mut self) -> FastLinkDown {
+        self.fast_link_down()
+    }
+
+    fn set_fast_link_down(&mut self, config: FastLinkDown) {
+        self.set_fast_link_down(config)
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for ADIN1300<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        let config = self.rgmii_config();
+        RgmiiDelays {
+            tx: config.contains(RgmiiConfig::TX_DELAY_ENABLE),
+            rx: config.contains(RgmiiConfig::RX_DELAY_ENABLE),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        let mut config = self.rgmii_config();
+        config.set(RgmiiConfig::TX_DELAY_ENABLE, delays.tx);
+        config.set(RgmiiConfig::RX_DELAY_ENABLE, delays.rx);
+        self.subsystem_write(RgmiiConfig::ADDRESS, config.bits());
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! ADIN1300 specific registers
+    //!
+    //! All other registers are shared with the ADIN1200, see
+    //! [`crate::phy::adin1200::registers`].
+
+    use bitflags::bitflags;
+
+    bitflags! {
+        /// Register 0x16, the PHY Control 2 register
+        pub struct PhyControl2: u16 {
+            const DOWNSHIFT_100_ENABLE = (1 << 11);
+            const DOWNSHIFT_10_ENABLE = (1 << 10);
+            const DOWNSHIFT_ENABLE = Self::DOWNSHIFT_100_ENABLE.bits | Self::DOWNSHIFT_10_ENABLE.bits;
+        }
+
+        /// Register 0x17, the PHY Control 3 register
+        pub struct PhyControl3: u16 {
+            const LINKING_ENABLE = (1 << 13);
+            const DOWNSHIFT_RETRIES_MASK = (0b111 << 10);
+        }
+
+        /// Register 0xFF23 in MMD 0x1E, the RGMII Configuration register
+        pub struct RgmiiConfig: u16 {
+            const RX_DELAY_MASK = (0b111 << 6);
+            const GTX_DELAY_MASK = (0b111 << 3);
+            const RX_DELAY_ENABLE = (1 << 2);
+            const TX_DELAY_ENABLE = (1 << 1);
+            const RGMII_ENABLE = (1 << 0);
+        }
+    }
+
+    impl PhyControl2 {
+        pub const ADDRESS: u8 = 0x16;
+    }
+
+    impl PhyControl3 {
+        pub const ADDRESS: u8 = 0x17;
+    }
+
+    impl RgmiiConfig {
+        pub const ADDRESS: u16 = 0xFF23;
+    }
+}
@@ -0,0 +1,532 @@
+//! Phy implementation for the Aquantia (Marvell) AQR107 and AQR113 family of
+//! multi-gigabit PHYs
+//!
+//! Like the [`M88X3310`](super::m88x3310::M88X3310), these PHYs only
+//! implement Clause 45, so all register accesses of this driver go through
+//! [`Miim::read_c45`] and [`Miim::write_c45`]. The methods of [`Phy`] that
+//! access Clause 22 registers, such as [`Phy::bcr`] and [`Phy::status`], are
+//! not supported.
+//!
+//! The PHY runs firmware that has to be loaded (usually from a flash
+//! attached to the PHY) before it is operational. Use
+//! [`AQR107::firmware_ready`] to wait for it.
+
+use crate::registers::{AutoNegCap, MmdAnStatus, MmdControl, MmdStatus};
+use crate::{AutoNegotiationAdvertisement, Duplex, Miim, MmdDevice, Phy, PhyAddress, PhyIdent};
+
+use super::{c45, AdvancedPhySpeed, MultiGigSpeed, PhyWithInterrupts, PhyWithSpeed};
+
+use self::registers::{
+    AnVendorAlarms, AnVendorAlarmsMask, AnVendorStatus, FirmwareId, GlobalStandardAlarms,
+    GlobalVendorAlarms, ReservedStatus1, ReservedStatus9, SystemInterfaceStatus, ThermalAlarms,
+    ThermalStatus2, AN_MMD, PHYXS_MMD, PMA_MMD, VEND1_MMD,
+};
+
+/// The interface between the PHY and the MAC that the PHY currently uses
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemInterface {
+    /// 10GBASE-KR
+    Kr,
+    /// 1000BASE-KX
+    Kx,
+    /// XFI
+    Xfi,
+    /// USXGMII
+    Usxgmii,
+    /// XAUI
+    Xaui,
+    /// SGMII
+    Sgmii,
+    /// RXAUI
+    Rxaui,
+    /// The system interface is powered off
+    Off,
+    /// Overclocked SGMII, used for 2.5 Gbps
+    OcSgmii,
+}
+
+impl SystemInterface {
+    fn from_type(ty: u16) -> Option<Self> {
+        Some(match ty {
+            0 => Self::Kr,
+            1 => Self::Kx,
+            2 => Self::Xfi,
+            3 => Self::Usxgmii,
+            4 => Self::Xaui,
+            6 => Self::Sgmii,
+            7 => Self::Rxaui,
+            9 => Self::Off,
+            10 => Self::OcSgmii,
+            _ => return None,
+        })
+    }
+}
+
+/// The version of the firmware running on the PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FirmwareVersion {
+    /// The major version
+    pub major: u8,
+    /// The minor version
+    pub minor: u8,
+    /// The build identifier
+    pub build: u8,
+    /// The provisioning identifier
+    pub provisioning: u8,
+}
+
+/// An AQR107, AQR113 or AQR113C
+#[derive(Debug)]
+pub struct AQR107<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> AQR107<MIIM> {
+    /// The PMA/PMD device identifier of the AQR107, with the revision number
+    /// masked out.
+    pub const PHY_IDENT: u32 = 0x03A1B4E0;
+    /// The PMA/PMD device identifier of the AQR113, with the revision number
+    /// masked out.
+    pub const AQR113_PHY_IDENT: u32 = 0x31C31C40;
+    /// The PMA/PMD device identifier of the AQR113C, with the revision number
+    /// masked out.
+    pub const AQR113C_PHY_IDENT: u32 = 0x31C31C10;
+
+    /// Create a new AQR107 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Check whether the PMA/PMD device identifier of this PHY is that of a
+    /// PHY supported by this driver
+    pub fn identify(&mut self) -> bool {
+        self.mmd_device_ident(PMA_MMD).is_some_and(|ident| {
+            let model = ident.raw_u32() & 0xFFFF_FFF0;
+            [
+                Self::PHY_IDENT,
+                Self::AQR113_PHY_IDENT,
+                Self::AQR113C_PHY_IDENT,
+            ]
+            .contains(&model)
+        })
+    }
+
+    /// Check whether the firmware of the PHY has been loaded and is running
+    pub fn firmware_ready(&mut self) -> bool {
+        let status =
+            ReservedStatus9::from_bits_truncate(self.mmd_read(VEND1_MMD, ReservedStatus9::ADDRESS));
+        (status & ReservedStatus9::MODE_MASK).bits() == ReservedStatus9::MODE_RUNNING
+    }
+
+    /// Get the version of the firmware that is running on the PHY.
+    ///
+    /// Returns `None` if the firmware is not running.
+    pub fn firmware_version(&mut self) -> Option<FirmwareVersion> {
+        if !self.firmware_ready() {
+            return None;
+        }
+
+        let id = FirmwareId::from_bits_truncate(self.mmd_read(VEND1_MMD, FirmwareId::ADDRESS));
+        let status1 =
+            ReservedStatus1::from_bits_truncate(self.mmd_read(VEND1_MMD, ReservedStatus1::ADDRESS));
+
+        Some(FirmwareVersion {
+            major: ((id & FirmwareId::MAJOR_MASK).bits() >> 8) as u8,
+            minor: (id & FirmwareId::MINOR_MASK).bits() as u8,
+            build: ((status1 & ReservedStatus1::BUILD_ID_MASK).bits() >> 4) as u8,
+            provisioning: (status1 & ReservedStatus1::PROVISIONING_ID_MASK).bits() as u8,
+        })
+    }
+
+    /// Get the interface that is currently used between the PHY and the MAC.
+    ///
+    /// Returns `None` if the PHY reports an unknown interface.
+    pub fn system_interface(&mut self) -> Option<SystemInterface> {
+        let status = SystemInterfaceStatus::from_bits_truncate(
+            self.mmd_read(PHYXS_MMD, SystemInterfaceStatus::ADDRESS),
+        );
+        SystemInterface::from_type((status & SystemInterfaceStatus::TYPE_MASK).bits() >> 3)
+    }
+
+    /// Get the rate and duplex mode that were negotiated with the link
+    /// partner, including 2.5 and 5 Gbps.
+    ///
+    /// Returns `None` if autonegotiation has not completed.
+    pub fn negotiated_rate(&mut self) -> Option<(MultiGigSpeed, Duplex)> {
+        if !self.autoneg_completed() {
+            return None;
+        }
+
+        let status =
+            AnVendorStatus::from_bits_truncate(self.mmd_read(AN_MMD, AnVendorStatus::ADDRESS));
+        Some((status.rate()?, status.duplex()))
+    }
+
+    /// Get the temperature of the PHY, in units of 1/256 °C.
+    ///
+    /// Returns `None` if the PHY has not measured the temperature yet.
+    pub fn temperature(&mut self) -> Option<i16> {
+        let status2 =
+            ThermalStatus2::from_bits_truncate(self.mmd_read(VEND1_MMD, ThermalStatus2::ADDRESS));
+        if !status2.contains(ThermalStatus2::VALID) {
+            return None;
+        }
+
+        Some(self.mmd_read(VEND1_MMD, ThermalStatus2::TEMPERATURE_ADDRESS) as i16)
+    }
+
+    /// Get the thermal alarms that are currently raised
+    pub fn thermal_alarms(&mut self) -> ThermalAlarms {
+        ThermalAlarms::from_bits_truncate(self.mmd_read(VEND1_MMD, ThermalAlarms::ADDRESS))
+    }
+
+    /// Set the high temperature warning threshold, in units of 1/256 °C
+    pub fn set_high_temperature_warning(&mut self, threshold: i16) {
+        self.mmd_write(
+            VEND1_MMD,
+            ThermalAlarms::HIGH_WARNING_THRESHOLD_ADDRESS,
+            threshold as u16,
+        );
+    }
+
+    /// Read the chip-wide standard alarm summary.
+    ///
+    /// Every bit indicates that an alarm in the corresponding MMD is raised.
+    pub fn global_standard_alarms(&mut self) -> GlobalStandardAlarms {
+        GlobalStandardAlarms::from_bits_truncate(
+            self.mmd_read(VEND1_MMD, GlobalStandardAlarms::STATUS_ADDRESS),
+        )
+    }
+
+    /// Read the chip-wide vendor specific alarm summary.
+    ///
+    /// Every bit indicates that a vendor specific alarm in the corresponding
+    /// MMD is raised.
+    pub fn global_vendor_alarms(&mut self) -> GlobalVendorAlarms {
+        GlobalVendorAlarms::from_bits_truncate(
+            self.mmd_read(VEND1_MMD, GlobalVendorAlarms::STATUS_ADDRESS),
+        )
+    }
+
+    /// Enable or disable the link status change interrupt
+    pub fn set_link_interrupt(&mut self, enabled: bool) {
+        let link = AnVendorAlarmsMask::LINK_STATUS_CHANGE.bits();
+        self.mmd_modify(
+            AN_MMD,
+            AnVendorAlarmsMask::ADDRESS,
+            link,
+            if enabled { link } else { 0 },
+        );
+
+        let std = GlobalStandardAlarms::ALL.bits();
+        self.mmd_modify(
+            VEND1_MMD,
+            GlobalStandardAlarms::MASK_ADDRESS,
+            std,
+            if enabled { std } else { 0 },
+        );
+
+        let vend = (GlobalVendorAlarms::AN | GlobalVendorAlarms::GLOBAL3).bits();
+        self.mmd_modify(
+            VEND1_MMD,
+            GlobalVendorAlarms::MASK_ADDRESS,
+            vend,
+            if enabled { vend } else { 0 },
+        );
+    }
+
+    /// Read and clear the vendor specific autonegotiation alarms, which
+    /// contain the link status change interrupt
+    pub fn read_and_clear_interrupts(&mut self) -> AnVendorAlarms {
+        AnVendorAlarms::from_bits_truncate(self.mmd_read(AN_MMD, AnVendorAlarms::ADDRESS))
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for AQR107<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: false,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+
+    fn mmd_read(&mut self, mmd_address: u8, reg_address: u16) -> u16 {
+        self.miim
+            .read_c45(self.phy_addr.get(), mmd_address, reg_address)
+    }
+
+    fn mmd_write(&mut self, device_address: u8, reg_address: u16, reg_value: u16) {
+        self.miim
+            .write_c45(self.phy_addr.get(), device_address, reg_address, reg_value)
+    }
+
+    fn is_resetting(&mut self) -> bool {
+        MmdControl::from_bits_truncate(self.mmd_read(VEND1_MMD, MmdControl::ADDRESS))
+            .contains(MmdControl::RESET)
+    }
+
+    /// Perform a soft reset of the whole PHY.
+    ///
+    /// The firmware is restarted by this reset, so wait for
+    /// [`AQR107::firmware_ready`] afterwards as well.
+    fn reset(&mut self) {
+        let reset = MmdControl::RESET.bits();
+        self.mmd_modify(VEND1_MMD, MmdControl::ADDRESS, reset, reset);
+    }
+
+    fn phy_link_up(&mut self) -> bool {
+        self.mmd_status(MmdDevice::Pcs)
+            .is_some_and(|status| status.contains(MmdStatus::RECEIVE_LINK_STATUS))
+    }
+
+    fn autoneg_completed(&mut self) -> bool {
+        self.mmd_an_status().contains(MmdAnStatus::AUTONEG_COMPLETE)
+    }
+
+    fn phy_ident(&mut self) -> Option<PhyIdent> {
+        self.mmd_device_ident(PMA_MMD)
+    }
+
+    /// Set the 10BASE-T and 100BASE-TX autonegotiation advertisement, and
+    /// restart autonegotiation
+    fn set_autonegotiation_advertisement(&mut self, ad: AutoNegotiationAdvertisement) {
+        c45::set_autonegotiation_advertisement(self, ad)
+    }
+
+    fn get_autonegotiation_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(c45::autonegotiation_advertisement(
+            self,
+            AutoNegCap::LOCAL_CAP_ADDRESS,
+        ))
+    }
+
+    fn get_autonegotiation_partner_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(c45::autonegotiation_advertisement(
+            self,
+            AutoNegCap::PARTNER_CAP_ADDRESS,
+        ))
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for AQR107<MIIM> {
+    /// Get the negotiated link speed.
+    ///
+    /// Returns `None` for 2.5, 5 and 10 Gbps links, as they can not be
+    /// represented by [`AdvancedPhySpeed`]. Use [`AQR107::negotiated_rate`]
+    /// to get those.
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        let (speed, duplex) = self.negotiated_rate()?;
+        speed.advanced(duplex)
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for AQR107<MIIM> {
+    type Interrupts = AnVendorAlarms;
+
+    fn read_and_clear_interrupts(&mut self) -> AnVendorAlarms {
+        self.read_and_clear_interrupts()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! AQR107 registers
+
+    use bitflags::bitflags;
+
+    use crate::{phy::MultiGigSpeed, Duplex};
+
+    /// The PMA/PMD MMD
+    pub const PMA_MMD: u8 = 1;
+    /// The PHY XS MMD, containing the system interface registers
+    pub const PHYXS_MMD: u8 = 4;
+    /// The Auto-Negotiation MMD
+    pub const AN_MMD: u8 = 7;
+    /// The vendor specific MMD containing the global registers
+    pub const VEND1_MMD: u8 = 30;
+
+    bitflags! {
+        /// Register 0xE812 in MMD 4, the system interface status register
+        pub struct SystemInterfaceStatus: u16 {
+            const TYPE_MASK = (0b11111 << 3);
+        }
+
+        /// Register 0xC800 in MMD 7, the vendor specific autonegotiation
+        /// status register 1
+        pub struct AnVendorStatus: u16 {
+            const RATE_MASK = (0b111 << 1);
+            const FULL_DUPLEX = (1 << 0);
+        }
+
+        /// Register 0xCC01 in MMD 7, the vendor specific autonegotiation
+        /// alarms register 2. Reading this register clears it.
+        pub struct AnVendorAlarms: u16 {
+            const LINK_STATUS_CHANGE = (1 << 0);
+        }
+
+        /// Register 0xD401 in MMD 7, the vendor specific autonegotiation
+        /// interrupt mask register 2
+        pub struct AnVendorAlarmsMask: u16 {
+            const LINK_STATUS_CHANGE = (1 << 0);
+        }
+
+        /// Register 0x0020 in MMD 30, the firmware ID register
+        pub struct FirmwareId: u16 {
+            const MAJOR_MASK = (0xFF << 8);
+            const MINOR_MASK = (0xFF);
+        }
+
+        /// Register 0xC421 through 0xC424 (thresholds) and register 0xC830
+        /// (status) in MMD 30, the thermal alarm registers
+        pub struct ThermalAlarms: u16 {
+            const HIGH_TEMPERATURE_FAILURE = (1 << 14);
+            const LOW_TEMPERATURE_FAILURE = (1 << 13);
+            const HIGH_TEMPERATURE_WARNING = (1 << 12);
+            const LOW_TEMPERATURE_WARNING = (1 << 11);
+        }
+
+        /// Register 0xC821 in MMD 30, the thermal status register 2
+        pub struct ThermalStatus2: u16 {
+            const VALID = (1 << 0);
+        }
+
+        /// Register 0xC885 in MMD 30, the reserved status register 1
+        pub struct ReservedStatus1: u16 {
+            const BUILD_ID_MASK = (0b1111 << 4);
+            const PROVISIONING_ID_MASK = (0b1111);
+        }
+
+        /// Register 0xC88D in MMD 30, the reserved status register 9
+        pub struct ReservedStatus9: u16 {
+            const MODE_MASK = (0xFF);
+        }
+
+        /// Register 0xFC00 (status) and register 0xFC02 (mask) in MMD 30, the
+        /// chip-wide standard interrupt registers
+        pub struct GlobalStandardAlarms: u16 {
+            const PMA1 = (1 << 15);
+            const PMA2 = (1 << 14);
+            const PCS1 = (1 << 13);
+            const PCS2 = (1 << 12);
+            const PCS3 = (1 << 11);
+            const PHY_XS1 = (1 << 10);
+            const PHY_XS2 = (1 << 9);
+            const AN1 = (1 << 8);
+            const AN2 = (1 << 7);
+            const GBE = (1 << 6);
+            const ALL = (1 << 0);
+        }
+
+        /// Register 0xFC01 (status) and register 0xFC03 (mask) in MMD 30, the
+        /// chip-wide vendor specific interrupt registers
+        pub struct GlobalVendorAlarms: u16 {
+            const PMA = (1 << 15);
+            const PCS = (1 << 14);
+            const PHY_XS = (1 << 13);
+            const AN = (1 << 12);
+            const GBE = (1 << 11);
+            const GLOBAL1 = (1 << 2);
+            const GLOBAL2 = (1 << 1);
+            const GLOBAL3 = (1 << 0);
+        }
+    }
+
+    impl SystemInterfaceStatus {
+        pub const ADDRESS: u16 = 0xE812;
+    }
+
+    impl AnVendorStatus {
+        pub const ADDRESS: u16 = 0xC800;
+
+        /// The negotiated rate, or `None` if the rate is unknown
+        pub fn rate(&self) -> Option<MultiGigSpeed> {
+            Some(match (*self & Self::RATE_MASK).bits() >> 1 {
+                0 => MultiGigSpeed::Mbps10,
+                1 => MultiGigSpeed::Mbps100,
+                2 => MultiGigSpeed::Mbps1000,
+                3 => MultiGigSpeed::Mbps10000,
+                4 => MultiGigSpeed::Mbps2500,
+                5 => MultiGigSpeed::Mbps5000,
+                _ => return None,
+            })
+        }
+
+        /// The negotiated duplex mode
+        pub fn duplex(&self) -> Duplex {
+            if self.contains(Self::FULL_DUPLEX) {
+                Duplex::Full
+            } else {
+                Duplex::Half
+            }
+        }
+    }
+
+    impl AnVendorAlarms {
+        pub const ADDRESS: u16 = 0xCC01;
+    }
+
+    impl AnVendorAlarmsMask {
+        pub const ADDRESS: u16 = 0xD401;
+    }
+
+    impl FirmwareId {
+        pub const ADDRESS: u16 = 0x0020;
+    }
+
+    impl ThermalAlarms {
+        pub const HIGH_FAILURE_THRESHOLD_ADDRESS: u16 = 0xC421;
+        pub const LOW_FAILURE_THRESHOLD_ADDRESS: u16 = 0xC422;
+        pub const HIGH_WARNING_THRESHOLD_ADDRESS: u16 = 0xC423;
+        pub const LOW_WARNING_THRESHOLD_ADDRESS: u16 = 0xC424;
+        pub const ADDRESS: u16 = 0xC830;
+    }
+
+    impl ThermalStatus2 {
+        /// The address of the thermal status register 1, which contains the
+        /// temperature in units of 1/256 °C
+        pub const TEMPERATURE_ADDRESS: u16 = 0xC820;
+        pub const ADDRESS: u16 = 0xC821;
+    }
+
+    impl ReservedStatus1 {
+        pub const ADDRESS: u16 = 0xC885;
+    }
+
+    impl ReservedStatus9 {
+        pub const ADDRESS: u16 = 0xC88D;
+        /// The value of [`Self::MODE_MASK`] when the firmware is running
+        pub const MODE_RUNNING: u16 = 0x01;
+    }
+
+    impl GlobalStandardAlarms {
+        pub const STATUS_ADDRESS: u16 = 0xFC00;
+        pub const MASK_ADDRESS: u16 = 0xFC02;
+    }
+
+    impl GlobalVendorAlarms {
+        pub const STATUS_ADDRESS: u16 = 0xFC01;
+        pub const MASK_ADDRESS: u16 = 0xFC03;
+    }
+}
@@ -0,0 +1,465 @@
+//! Phy implementation for the Qualcomm Atheros AR8031 gigabit Ethernet PHY
+//!
+//! Some of the configuration of the AR8031, such as the RGMII clock delays,
+//! is done through its debug registers. These are accessed indirectly by
+//! writing the debug register offset to register 29, and then accessing
+//! register 30.
+
+use crate::{registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    ChipConfiguration, HibernationControl, InterruptFlags, PhySpecificStatus, RxClockDelay,
+    SmartEeeControl3, TxClockDelay, DEBUG_ADDRESS, DEBUG_DATA, SMART_EEE_CONTROL1, SMART_EEE_MMD,
+};
+
+use super::{
+    AdvancedPhySpeed, InterfaceMode, PhyWithEee, PhyWithInterfaceMode, PhyWithInterrupts,
+    PhyWithRgmiiDelays, PhyWithSpeed, RgmiiDelays, UnsupportedInterfaceMode,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+    /// The link speed changed
+    SpeedChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// A page was received during autonegotiation
+    PageReceived,
+    /// The link went down
+    LinkDown,
+    /// The link came up
+    LinkUp,
+    /// The link speed was downgraded
+    SpeedDowngrade,
+    /// The polarity of the receive pair changed
+    PolarityChange,
+    /// A Wake-on-LAN event occurred
+    WakeOnLan,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::AutoNegotiationError => InterruptFlags::AUTONEG_ERROR,
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+            Interrupt::DuplexChange => InterruptFlags::DUPLEX_CHANGE,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::LinkDown => InterruptFlags::LINK_DOWN,
+            Interrupt::LinkUp => InterruptFlags::LINK_UP,
+            Interrupt::SpeedDowngrade => InterruptFlags::SPEED_DOWNGRADE,
+            Interrupt::PolarityChange => InterruptFlags::POLARITY_CHANGE,
+            Interrupt::WakeOnLan => InterruptFlags::WAKE_ON_LAN,
+        }
+    }
+}
+
+/// An AR8031
+#[derive(Debug)]
+pub struct AR8031<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> AR8031<MIIM> {
+    /// Create a new AR8031 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read the debug register at `offset`
+    pub fn debug_read(&mut self, offset: u16) -> u16 {
+        debug_read(self, offset)
+    }
+
+    /// Write the debug register at `offset`
+    pub fn debug_write(&mut self, offset: u16, value: u16) {
+        debug_write(self, offset, value)
+    }
+
+    /// Modify the bits selected by `mask` in the debug register at `offset`,
+    /// leaving all other bits untouched
+    pub fn debug_modify(&mut self, offset: u16, mask: u16, value: u16) {
+        debug_modify(self, offset, mask, value)
+    }
+
+    /// Read the Chip Configuration register
+    pub fn chip_configuration(&mut self) -> ChipConfiguration {
+        ChipConfiguration::from_bits_truncate(self.read(ChipConfiguration::ADDRESS))
+    }
+
+    /// Check whether the RGMII TX and RX clock delays are enabled
+    pub fn delays(&mut self) -> (bool, bool) {
+        delays(self)
+    }
+
+    /// Enable or disable the RGMII TX and RX clock delays
+    pub fn set_delays(&mut self, tx_delay: bool, rx_delay: bool) {
+        set_delays(self, tx_delay, rx_delay)
+    }
+
+    /// Enable or disable hibernation
+    ///
+    /// While hibernating, the PHY powers down when no cable is connected,
+    /// which also stops the RGMII RX clock.
+    pub fn set_hibernation(&mut self, enabled: bool) {
+        let bit = HibernationControl::ENABLE.bits();
+        self.debug_modify(
+            HibernationControl::OFFSET,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Check whether SmartEEE is enabled
+    pub fn smart_eee(&mut self) -> bool {
+        let ctrl = SmartEeeControl3::from_bits_truncate(
+            self.mmd_read(SMART_EEE_MMD, SmartEeeControl3::ADDRESS),
+        );
+        ctrl.contains(SmartEeeControl3::LPI_ENABLE)
+    }
+
+    /// Enable or disable SmartEEE
+    ///
+    /// SmartEEE lets the PHY itself enter and exit LPI, so that EEE can be
+    /// used with a MAC that does not support it.
+    pub fn set_smart_eee(&mut self, enabled: bool) {
+        let bit = SmartEeeControl3::LPI_ENABLE.bits();
+        self.mmd_modify(
+            SMART_EEE_MMD,
+            SmartEeeControl3::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Set the SmartEEE wake time, in microseconds, for 1000BASE-T and
+    /// 100BASE-TX links
+    pub fn set_smart_eee_wake_time(&mut self, tw_1000: u8, tw_100: u8) {
+        self.mmd_write(
+            SMART_EEE_MMD,
+            SMART_EEE_CONTROL1,
+            u16::from_le_bytes([tw_1000, tw_100]),
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, flag);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn phy_specific_status(&mut self) -> PhySpecificStatus {
+        PhySpecificStatus::from_bits_truncate(self.read(PhySpecificStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for AR8031<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for AR8031<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEee<MIIM> for AR8031<MIIM> {}
+
+/// Read the debug register at `offset`
+///
+/// This is shared with the AR8035, which uses the same debug port.
+pub(crate) fn debug_read<M: Miim, P: Phy<M>>(phy: &mut P, offset: u16) -> u16 {
+    phy.write(DEBUG_ADDRESS, offset);
+    phy.read(DEBUG_DATA)
+}
+
+pub(crate) fn debug_write<M: Miim, P: Phy<M>>(phy: &mut P, offset: u16, value: u16) {
+    phy.write(DEBUG_ADDRESS, offset);
+    phy.write(DEBUG_DATA, value);
+}
+
+pub(crate) fn debug_modify<M: Miim, P: Phy<M>>(phy: &mut P, offset: u16, mask: u16, value: u16) {
+    phy.write(DEBUG_ADDRESS, offset);
+    phy.modify(DEBUG_DATA, mask, value);
+}
+
+pub(crate) fn delays<M: Miim, P: Phy<M>>(phy: &mut P) -> (bool, bool) {
+    let tx = TxClockDelay::from_bits_truncate(debug_read(phy, TxClockDelay::OFFSET));
+    let rx = RxClockDelay::from_bits_truncate(debug_read(phy, RxClockDelay::OFFSET));
+    (
+        tx.contains(TxClockDelay::ENABLE),
+        rx.contains(RxClockDelay::ENABLE),
+    )
+}
+
+pub(crate) fn set_delays<M: Miim, P: Phy<M>>(phy: &mut P, tx_delay: bool, rx_delay: bool) {
+    let tx = TxClockDelay::ENABLE.bits();
+    debug_modify(phy, TxClockDelay::OFFSET, tx, if tx_delay { tx } else { 0 });
+
+    let rx = RxClockDelay::ENABLE.bits();
+    debug_modify(phy, RxClockDelay::OFFSET, rx, if rx_delay { rx } else { 0 });
+}
+
+impl<MIIM: Miim> PhyWithInterfaceMode<MIIM> for AR8031<MIIM> {
+    fn interface_mode(&mut self) -> Option<InterfaceMode> {
+        let config = self.chip_configuration();
+
+        if config.is_sgmii() {
+            Some(InterfaceMode::Sgmii)
+        } else if config.is_rgmii() {
+            let (tx_delay, rx_delay) = self.delays();
+            Some(InterfaceMode::Rgmii { tx_delay, rx_delay })
+        } else {
+            // The PHY operates as a media converter, without a MAC interface
+            None
+        }
+    }
+
+    fn set_interface_mode(&mut self, mode: InterfaceMode) -> Result<(), UnsupportedInterfaceMode> {
+        // RGMII or SGMII is selected through the strapping pins
+        let config = self.chip_configuration();
+
+        match mode {
+            InterfaceMode::Rgmii { tx_delay, rx_delay } if config.is_rgmii() => {
+                self.set_delays(tx_delay, rx_delay);
+                Ok(())
+            }
+            InterfaceMode::Sgmii if config.is_sgmii() => Ok(()),
+            mode => Err(UnsupportedInterfaceMode(mode)),
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for AR8031<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for AR8031<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        let (tx, rx) = self.delays();
+        RgmiiDelays { tx, rx }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_delays(delays.tx, delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! AR8031 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::Interrupt;
+
+    /// Register 29, the Debug Port Address Offset register
+    pub const DEBUG_ADDRESS: u8 = 29;
+    /// Register 30, the Debug Port Data register
+    pub const DEBUG_DATA: u8 = 30;
+
+    /// The MMD containing the SmartEEE registers
+    pub const SMART_EEE_MMD: u8 = 3;
+    /// Register 0x805B in MMD 3, the SmartEEE wake time for 1000BASE-T
+    /// (bits 7:0) and 100BASE-TX (bits 15:8)
+    pub const SMART_EEE_CONTROL1: u16 = 0x805B;
+
+    bitflags! {
+        /// Register 18 and register 19, the Interrupt Enable and Interrupt
+        /// Status registers
+        pub struct InterruptFlags: u16 {
+            const AUTONEG_ERROR = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const DUPLEX_CHANGE = (1 << 13);
+            const PAGE_RECEIVED = (1 << 12);
+            const LINK_DOWN = (1 << 11);
+            const LINK_UP = (1 << 10);
+            const SPEED_DOWNGRADE = (1 << 5);
+            const POLARITY_CHANGE = (1 << 1);
+            const WAKE_ON_LAN = (1 << 0);
+        }
+
+        /// Register 17, the PHY Specific Status register
+        pub struct PhySpecificStatus: u16 {
+            const SPEED_MASK = (0b11 << 14);
+            const FULL_DUPLEX = (1 << 13);
+            const RESOLVED = (1 << 11);
+            const LINK = (1 << 10);
+            const MDI_CROSSOVER = (1 << 6);
+        }
+
+        /// Register 31, the Chip Configuration register
+        pub struct ChipConfiguration: u16 {
+            /// Select the fiber (1000BASE-X/100BASE-FX) registers instead of
+            /// the copper registers
+            const FIBER_REGISTERS = (1 << 15);
+            /// The operating mode, which is latched from the strapping pins
+            const MODE_MASK = (0xF);
+        }
+
+        /// Debug register 0x00
+        pub struct RxClockDelay: u16 {
+            const ENABLE = (1 << 15);
+        }
+
+        /// Debug register 0x05
+        pub struct TxClockDelay: u16 {
+            const ENABLE = (1 << 8);
+        }
+
+        /// Debug register 0x0B, the Hibernation Control register
+        pub struct HibernationControl: u16 {
+            const ENABLE = (1 << 15);
+        }
+
+        /// Register 0x805D in MMD 3, the third SmartEEE Control register
+        pub struct SmartEeeControl3: u16 {
+            const LPI_ENABLE = (1 << 8);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const ENABLE_ADDRESS: u8 = 18;
+        pub const STATUS_ADDRESS: u8 = 19;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl PhySpecificStatus {
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl From<PhySpecificStatus> for Option<AdvancedPhySpeed> {
+        fn from(pss: PhySpecificStatus) -> Self {
+            if !pss.contains(PhySpecificStatus::RESOLVED) {
+                return None;
+            }
+
+            let full_duplex = pss.contains(PhySpecificStatus::FULL_DUPLEX);
+            let speed = (pss & PhySpecificStatus::SPEED_MASK).bits() >> 14;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl ChipConfiguration {
+        pub const ADDRESS: u8 = 31;
+
+        /// Check whether the PHY is connected to the MAC through SGMII
+        pub fn is_sgmii(&self) -> bool {
+            (*self & Self::MODE_MASK).bits() == 0b0001
+        }
+
+        /// Check whether the PHY is connected to the MAC through RGMII
+        ///
+        /// This is the case for the copper mode, and for the fiber modes
+        /// that do not operate as a media converter.
+        pub fn is_rgmii(&self) -> bool {
+            matches!(
+                (*self & Self::MODE_MASK).bits(),
+                0b0000 | 0b0010 | 0b0011 | 0b0110 | 0b1011 | 0b1110
+            )
+        }
+    }
+
+    impl RxClockDelay {
+        pub const OFFSET: u16 = 0x00;
+    }
+
+    impl TxClockDelay {
+        pub const OFFSET: u16 = 0x05;
+    }
+
+    impl HibernationControl {
+        pub const OFFSET: u16 = 0x0B;
+    }
+
+    impl SmartEeeControl3 {
+        pub const ADDRESS: u16 = 0x805D;
+    }
+}
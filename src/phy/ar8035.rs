@@ -0,0 +1,284 @@
+//! Phy implementation for the Qualcomm Atheros AR8035 gigabit Ethernet PHY
+//!
+//! The AR8035 shares its debug registers, interrupt registers and PHY
+//! Specific Status register with the [`AR8031`](super::ar8031::AR8031).
+
+use crate::{
+    phy::ar8031::{
+        self,
+        registers::{InterruptFlags, PhySpecificStatus},
+        Interrupt,
+    },
+    registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{Clk25mControl, CLK25M_MMD};
+
+use super::{
+    AdvancedPhySpeed, InterfaceMode, PhyWithClockOutput, PhyWithInterfaceMode, PhyWithInterrupts,
+    PhyWithRgmiiDelays, PhyWithSpeed, RgmiiDelays, UnsupportedInterfaceMode,
+};
+
+/// The clock that is output on the CLK_25M pin
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockOutput {
+    /// 25 MHz from the crystal
+    Crystal25MHz,
+    /// 25 MHz from the DSP
+    Dsp25MHz,
+    /// 50 MHz from the PLL
+    Pll50MHz,
+    /// 50 MHz from the DSP
+    Dsp50MHz,
+    /// 62.5 MHz from the PLL
+    Pll62_5MHz,
+    /// 62.5 MHz from the DSP
+    Dsp62_5MHz,
+    /// 125 MHz from the PLL
+    Pll125MHz,
+    /// 125 MHz from the DSP
+    Dsp125MHz,
+}
+
+/// An AR8035
+#[derive(Debug)]
+pub struct AR8035<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> AR8035<MIIM> {
+    /// Create a new AR8035 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read the debug register at `offset`
+    pub fn debug_read(&mut self, offset: u16) -> u16 {
+        ar8031::debug_read(self, offset)
+    }
+
+    /// Write the debug register at `offset`
+    pub fn debug_write(&mut self, offset: u16, value: u16) {
+        ar8031::debug_write(self, offset, value)
+    }
+
+    /// Modify the bits selected by `mask` in the debug register at `offset`,
+    /// leaving all other bits untouched
+    pub fn debug_modify(&mut self, offset: u16, mask: u16, value: u16) {
+        ar8031::debug_modify(self, offset, mask, value)
+    }
+
+    /// Check whether the RGMII TX and RX clock delays are enabled
+    pub fn delays(&mut self) -> (bool, bool) {
+        ar8031::delays(self)
+    }
+
+    /// Enable or disable the RGMII TX and RX clock delays
+    pub fn set_delays(&mut self, tx_delay: bool, rx_delay: bool) {
+        ar8031::set_delays(self, tx_delay, rx_delay)
+    }
+
+    /// Get the clock that is output on the CLK_25M pin
+    pub fn clock_output(&mut self) -> ClockOutput {
+        Clk25mControl::from_bits_truncate(self.mmd_read(CLK25M_MMD, Clk25mControl::ADDRESS)).into()
+    }
+
+    /// Select the clock that is output on the CLK_25M pin
+    pub fn set_clock_output(&mut self, output: ClockOutput) {
+        self.mmd_modify(
+            CLK25M_MMD,
+            Clk25mControl::ADDRESS,
+            Clk25mControl::SELECT_MASK.bits(),
+            Clk25mControl::from(output).bits(),
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, flag);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn phy_specific_status(&mut self) -> PhySpecificStatus {
+        PhySpecificStatus::from_bits_truncate(self.read(PhySpecificStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for AR8035<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for AR8035<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterfaceMode<MIIM> for AR8035<MIIM> {
+    fn interface_mode(&mut self) -> Option<InterfaceMode> {
+        let (tx_delay, rx_delay) = self.delays();
+        Some(InterfaceMode::Rgmii { tx_delay, rx_delay })
+    }
+
+    fn set_interface_mode(&mut self, mode: InterfaceMode) -> Result<(), UnsupportedInterfaceMode> {
+        match mode {
+            InterfaceMode::Rgmii { tx_delay, rx_delay } => {
+                self.set_delays(tx_delay, rx_delay);
+                Ok(())
+            }
+            mode => Err(UnsupportedInterfaceMode(mode)),
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for AR8035<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithClockOutput<MIIM> for AR8035<MIIM> {
+    type ClockOutput = ClockOutput;
+
+    fn clock_output(&mut self) -> ClockOutput {
+        self.clock_output()
+    }
+
+    fn set_clock_output(&mut self, output: ClockOutput) {
+        self.set_clock_output(output)
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for AR8035<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        let (tx, rx) = self.delays();
+        RgmiiDelays { tx, rx }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_delays(delays.tx, delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! AR8035 specific registers
+    //!
+    //! All other registers are shared with the AR8031, see
+    //! [`crate::phy::ar8031::registers`].
+
+    use bitflags::bitflags;
+
+    use super::ClockOutput;
+
+    /// The MMD containing the CLK_25M control register
+    pub const CLK25M_MMD: u8 = 7;
+
+    bitflags! {
+        /// Register 0x8016 in MMD 7, the CLK_25M Clock Select register
+        pub struct Clk25mControl: u16 {
+            const SELECT_MASK = (0b111 << 2);
+        }
+    }
+
+    impl Clk25mControl {
+        pub const ADDRESS: u16 = 0x8016;
+    }
+
+    impl From<Clk25mControl> for ClockOutput {
+        fn from(ctrl: Clk25mControl) -> Self {
+            match (ctrl & Clk25mControl::SELECT_MASK).bits() >> 2 {
+                0 => ClockOutput::Crystal25MHz,
+                1 => ClockOutput::Dsp25MHz,
+                2 => ClockOutput::Pll50MHz,
+                3 => ClockOutput::Dsp50MHz,
+                4 => ClockOutput::Pll62_5MHz,
+                5 => ClockOutput::Dsp62_5MHz,
+                6 => ClockOutput::Pll125MHz,
+                _ => ClockOutput::Dsp125MHz,
+            }
+        }
+    }
+
+    impl From<ClockOutput> for Clk25mControl {
+        fn from(output: ClockOutput) -> Self {
+            let select = match output {
+                ClockOutput::Crystal25MHz => 0,
+                ClockOutput::Dsp25MHz => 1,
+                ClockOutput::Pll50MHz => 2,
+                ClockOutput::Dsp50MHz => 3,
+                ClockOutput::Pll62_5MHz => 4,
+                ClockOutput::Dsp62_5MHz => 5,
+                ClockOutput::Pll125MHz => 6,
+                ClockOutput::Dsp125MHz => 7,
+            };
+            Clk25mControl::from_bits_truncate(select << 2)
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Tracking the progress of autonegotiation.
+//!
+//! An [`AutonegMonitor`] keeps track of how long autonegotiation has been
+//! running, and reports an [`AutonegOutcome`] once it has completed, failed,
+//! or has been running for longer than a timeout.
+//!
+//! Time is measured in ticks of a monotonic, wrapping `u32` counter that is
+//! provided by the caller, such as a millisecond timer:
+//!
+//! ```
+//! use ieee802_3_miim::phy::{AdvancedPhySpeed, AutonegMonitor, FixedPhy};
+//!
+//! # let mut phy = FixedPhy::new(AdvancedPhySpeed::FullDuplexBase100Tx, true);
+//! # let mut ticks = 0u32;
+//! # let mut now_ms = || { ticks += 1; ticks };
+//! let mut monitor = AutonegMonitor::start(&mut phy, now_ms(), 5_000);
+//!
+//! let outcome = loop {
+//!     if let Some(outcome) = monitor.poll(&mut phy, now_ms()) {
+//!         break outcome;
+//!     }
+//! };
+//! # let _ = outcome;
+//! ```
+
+use crate::{Miim, Phy};
+
+/// The outcome of autonegotiation
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutonegOutcome {
+    /// Autonegotiation completed with a link partner that is autonegotiation
+    /// capable
+    Completed,
+    /// The link was established through parallel detection, because the link
+    /// partner is not autonegotiation capable.
+    ///
+    /// The duplex mode of the link is half duplex, which may not match the
+    /// (forced) duplex mode of the link partner.
+    ParallelDetected,
+    /// Autonegotiation did not complete before the timeout expired
+    TimedOut,
+    /// A parallel detection fault occurred
+    Fault,
+}
+
+/// Tracks the progress of autonegotiation
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutonegMonitor {
+    started: u32,
+    timeout: u32,
+}
+
+impl AutonegMonitor {
+    /// Create a monitor for an autonegotiation process that was started at
+    /// `now`, and times out after `timeout` ticks.
+    pub fn new(now: u32, timeout: u32) -> Self {
+        Self {
+            started: now,
+            timeout,
+        }
+    }
+
+    /// Enable and restart autonegotiation on `phy`, and create a monitor for
+    /// it that times out after `timeout` ticks.
+    pub fn start<M: Miim, P: Phy<M>>(phy: &mut P, now: u32, timeout: u32) -> Self {
+        phy.modify_bcr(|bcr| {
+            bcr.set_autonegotiation(true).restart_autonegotiation();
+        });
+        Self::new(now, timeout)
+    }
+
+    /// Restart tracking at `now`, for example after autonegotiation was
+    /// restarted because of a link down event
+    pub fn restart(&mut self, now: u32) {
+        self.started = now;
+    }
+
+    /// The amount of ticks that autonegotiation has been running for
+    pub fn elapsed(&self, now: u32) -> u32 {
+        now.wrapping_sub(self.started)
+    }
+
+    /// Check whether the timeout has expired
+    pub fn timed_out(&self, now: u32) -> bool {
+        self.elapsed(now) >= self.timeout
+    }
+
+    /// Check the progress of autonegotiation on `phy`.
+    ///
+    /// Returns `None` while autonegotiation is still in progress.
+    ///
+    /// This reads the autonegotiation expansion register, which clears its
+    /// latching bits.
+    pub fn poll<M: Miim, P: Phy<M>>(&mut self, phy: &mut P, now: u32) -> Option<AutonegOutcome> {
+        let ane = phy.ane();
+
+        if ane.is_some_and(|ane| ane.parallel_detection_fault()) {
+            return Some(AutonegOutcome::Fault);
+        }
+
+        let partner_autoneg_capable = match ane {
+            Some(ane) => ane.partner_autoneg_capable(),
+            None => true,
+        };
+        let bsr = phy.bsr();
+
+        if bsr.autoneg_completed() {
+            if partner_autoneg_capable {
+                Some(AutonegOutcome::Completed)
+            } else {
+                Some(AutonegOutcome::ParallelDetected)
+            }
+        } else if self.timed_out(now) {
+            // Some PHYs never report completion if the link was established
+            // through parallel detection
+            if bsr.phy_link_up() && !partner_autoneg_capable {
+                Some(AutonegOutcome::ParallelDetected)
+            } else {
+                Some(AutonegOutcome::TimedOut)
+            }
+        } else {
+            None
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! A bare phy that does not have any compile-time configurations
 //! assigned to it.
 
-use crate::{AutoNegotiationAdvertisement, Miim, Pause, Phy};
+use crate::{
+    AutoNegotiationAdvertisement, Miim, Pause, Phy, PhyAddress, PhyIdent, PhyStatus, SelectorField,
+};
 
 /// A base phy
 #[derive(Debug)]
@@ -9,9 +11,10 @@ pub struct BarePhy<MIIM>
 where
     MIIM: Miim,
 {
-    phy_address: u8,
+    phy_address: PhyAddress,
     miim: MIIM,
     best_supported_advertisement: AutoNegotiationAdvertisement,
+    status: Option<PhyStatus>,
 }
 
 impl<MIIM> BarePhy<MIIM>
@@ -23,11 +26,14 @@ where
     ///
     /// The PHY will calculate it's best supported advertisement on the fly from
     /// details acquired through `miim`.
-    pub fn new(miim: MIIM, phy_address: u8, pause: Pause) -> Self {
+    ///
+    /// The status of the PHY is cached, see [`Self::refresh_capabilities`].
+    pub fn new(miim: MIIM, phy_address: PhyAddress, pause: Pause) -> Self {
         let mut me = Self {
             phy_address,
             miim,
             best_supported_advertisement: Default::default(),
+            status: None,
         };
 
         let mut ana = me.status().best_autoneg_ad();
@@ -37,17 +43,142 @@ where
         me
     }
 
+    /// Create a new bare PHY at the raw address `phy_address`, using `pause`
+    /// as the advertised pause mode.
+    ///
+    /// # Panics
+    /// This function panics if `phy_address` is not less than 32.
+    #[deprecated(note = "use `new` with a `PhyAddress` instead")]
+    pub fn from_raw_address(miim: MIIM, phy_address: u8, pause: Pause) -> Self {
+        Self::new(miim, PhyAddress::from_const(phy_address), pause)
+    }
+
+    /// Create a [`BarePhyBuilder`] for a bare PHY with the given MIIM, at the
+    /// given PHY address.
+    ///
+    /// The builder allows more control over the best supported advertisement
+    /// than [`Self::new`].
+    pub fn builder(miim: MIIM, phy_address: PhyAddress) -> BarePhyBuilder<MIIM> {
+        BarePhyBuilder {
+            phy_address,
+            miim,
+            advertisement: None,
+            pause: None,
+            selector_field: None,
+            read_status: true,
+        }
+    }
+
+    /// Read the status of the PHY again, and update the cached status.
+    ///
+    /// The capabilities of a PHY generally do not change, so [`Phy::status`]
+    /// returns a status that is cached once it is read for the first time.
+    /// This function should be called if they may have changed, for instance
+    /// after a reset.
+    pub fn refresh_capabilities(&mut self) -> PhyStatus {
+        let status = self.bsr().into();
+        self.status = Some(status);
+        status
+    }
+
+    /// Read the PHY identifier of this PHY.
+    ///
+    /// This is useful for finding out which PHY is actually present if a
+    /// conversion into a specific PHY fails.
+    pub fn identify(&mut self) -> Result<PhyIdent, IdentPhyError> {
+        self.phy_ident().ok_or(IdentPhyError::PhyIdentUnavailable)
+    }
+
     /// Release the underlying MIIM
     pub fn release(self) -> MIIM {
         self.miim
     }
 
     /// Change the PHY address
-    pub fn set_phy_addr(&mut self, phy_address: u8) {
+    pub fn set_phy_addr(&mut self, phy_address: PhyAddress) {
         self.phy_address = phy_address;
     }
 }
 
+/// A builder for a [`BarePhy`]
+#[derive(Debug)]
+pub struct BarePhyBuilder<MIIM>
+where
+    MIIM: Miim,
+{
+    phy_address: PhyAddress,
+    miim: MIIM,
+    advertisement: Option<AutoNegotiationAdvertisement>,
+    pause: Option<Pause>,
+    selector_field: Option<SelectorField>,
+    read_status: bool,
+}
+
+impl<MIIM> BarePhyBuilder<MIIM>
+where
+    MIIM: Miim,
+{
+    /// Use `advertisement` as the best supported advertisement, instead of
+    /// calculating it from the status of the PHY.
+    ///
+    /// The advertised pause mode and selector field are still overridden if
+    /// [`Self::pause`] or [`Self::selector_field`] are used.
+    pub fn advertisement(mut self, advertisement: AutoNegotiationAdvertisement) -> Self {
+        self.advertisement = Some(advertisement);
+        self
+    }
+
+    /// Advertise `pause` as the pause mode.
+    pub fn pause(mut self, pause: Pause) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    /// Advertise `selector_field` as the selector field.
+    pub fn selector_field(mut self, selector_field: SelectorField) -> Self {
+        self.selector_field = Some(selector_field);
+        self
+    }
+
+    /// Do not read the status of the PHY while building it.
+    ///
+    /// If no advertisement is configured using [`Self::advertisement`], the
+    /// default [`AutoNegotiationAdvertisement`] is used instead of
+    /// calculating it from the status of the PHY. The status is then read
+    /// and cached the first time it is needed.
+    pub fn skip_status_read(mut self) -> Self {
+        self.read_status = false;
+        self
+    }
+
+    /// Build the [`BarePhy`].
+    pub fn build(self) -> BarePhy<MIIM> {
+        let mut phy = BarePhy {
+            phy_address: self.phy_address,
+            miim: self.miim,
+            best_supported_advertisement: Default::default(),
+            status: None,
+        };
+
+        let mut ana = match self.advertisement {
+            Some(ana) => ana,
+            None if self.read_status => phy.status().best_autoneg_ad(),
+            None => AutoNegotiationAdvertisement::default(),
+        };
+
+        if let Some(pause) = self.pause {
+            ana.pause = pause;
+        }
+
+        if let Some(selector_field) = self.selector_field {
+            ana.selector_field = Some(selector_field);
+        }
+
+        phy.best_supported_advertisement = ana;
+        phy
+    }
+}
+
 impl<MIIM> Phy<MIIM> for BarePhy<MIIM>
 where
     MIIM: Miim,
@@ -60,30 +191,132 @@ where
         &mut self.miim
     }
 
-    fn get_phy_addr(&self) -> u8 {
+    fn phy_address(&self) -> PhyAddress {
         self.phy_address
     }
+
+    fn status(&mut self) -> PhyStatus {
+        match self.status {
+            Some(status) => status,
+            None => self.refresh_capabilities(),
+        }
+    }
 }
 
+/// An error that occurs when converting a [`BarePhy`] into a specific PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum IdentPhyError {
+    /// The PHY does not provide a PHY identifier
     PhyIdentUnavailable,
-    IncorrectPhyIdent,
+    /// The PHY identifier does not match the requested PHY
+    IncorrectPhyIdent(PhyIdent),
+}
+
+impl IdentPhyError {
+    /// The PHY identifier that was read from the PHY, if any
+    pub fn phy_ident(&self) -> Option<PhyIdent> {
+        match self {
+            IdentPhyError::PhyIdentUnavailable => None,
+            IdentPhyError::IncorrectPhyIdent(ident) => Some(*ident),
+        }
+    }
+}
+
+/// A PHY driver that expects a specific PHY identifier
+pub trait IdentifiedPhy<MIIM: Miim> {
+    /// The expected PHY identifier, masked with [`Self::PHY_IDENT_MASK`]
+    const PHY_IDENT: u32;
+
+    /// The bits of the PHY identifier that identify the PHY.
+    ///
+    /// By default, the revision number is ignored. PHYs that can only be
+    /// told apart by their revision number match it as well.
+    const PHY_IDENT_MASK: u32 = 0xFFFF_FFF0;
+
+    /// Check whether `ident` matches [`Self::PHY_IDENT`], ignoring the bits
+    /// that are not in [`Self::PHY_IDENT_MASK`]
+    fn matches_ident(ident: PhyIdent) -> bool {
+        ident.raw_u32() & Self::PHY_IDENT_MASK == Self::PHY_IDENT
+    }
+}
+
+/// Find the lowest address on `miim` at which a PHY responds whose
+/// identifier matches the one expected by the driver `P`.
+///
+/// Address 0 is skipped, because many PHYs also respond to it as the
+/// broadcast address (see [`Phy::honors_broadcast`](crate::Phy::honors_broadcast)),
+/// so a PHY would be found there instead of at its actual address. Use
+/// [`find_phy_including_broadcast`] if the PHY may be strapped to address 0.
+///
+#[cfg_attr(feature = "mock", doc = "```")]
+#[cfg_attr(not(feature = "mock"), doc = "```ignore")]
+/// # use ieee802_3_miim::{mock::MockMiim, phy::{find_phy, KSZ8081R}, PhyAddress};
+/// # let mut miim = MockMiim::default();
+/// # miim.set(3, 2, 0x0022);
+/// # miim.set(3, 3, 0x1561);
+/// let address = find_phy::<KSZ8081R<_>, _>(&mut miim);
+/// # assert_eq!(address, PhyAddress::new(3));
+/// # miim.set(0, 2, 0x0022);
+/// # miim.set(0, 3, 0x1561);
+/// # assert_eq!(find_phy::<KSZ8081R<_>, _>(&mut miim), PhyAddress::new(3));
+/// ```
+pub fn find_phy<P, M>(miim: &mut M) -> Option<PhyAddress>
+where
+    P: IdentifiedPhy<M>,
+    M: Miim,
+{
+    find_phy_in::<P, M>(miim, PhyAddress::all().skip(1))
+}
+
+/// Find the lowest address on `miim` at which a PHY responds whose
+/// identifier matches the one expected by the driver `P`, including address 0.
+///
+/// If a PHY that honors broadcasts is present at a non-zero address, it is
+/// found at address 0. Check [`Phy::honors_broadcast`](crate::Phy::honors_broadcast)
+/// or use [`find_phy`] if that is not desired.
+pub fn find_phy_including_broadcast<P, M>(miim: &mut M) -> Option<PhyAddress>
+where
+    P: IdentifiedPhy<M>,
+    M: Miim,
+{
+    find_phy_in::<P, M>(miim, PhyAddress::all())
+}
+
+fn find_phy_in<P, M>(
+    miim: &mut M,
+    mut addresses: impl Iterator<Item = PhyAddress>,
+) -> Option<PhyAddress>
+where
+    P: IdentifiedPhy<M>,
+    M: Miim,
+{
+    addresses.find(|address| {
+        let ident = PhyIdent::new(miim.read(address.get(), 2), miim.read(address.get(), 3));
+        P::matches_ident(ident)
+    })
 }
 
 macro_rules! into_phy {
-    ($([$feat:literal, $phy:ident, $id:literal],)*) => {
+    ($([$feat:literal, $phy:ident, $id:literal $(, $mask:literal)?],)*) => {
         $(
+            #[cfg(feature = $feat)]
+            impl<MIIM: Miim> IdentifiedPhy<MIIM> for super::$phy<MIIM> {
+                const PHY_IDENT: u32 = $id;
+                $(const PHY_IDENT_MASK: u32 = $mask;)?
+            }
+
             #[cfg(feature = $feat)]
             impl<MIIM: Miim> TryFrom<BarePhy<MIIM>> for super::$phy<MIIM> {
                 type Error = IdentPhyError;
 
                 fn try_from(mut value: BarePhy<MIIM>) -> Result<Self, Self::Error> {
-                    let phy_ident = value.phy_ident().ok_or(IdentPhyError::PhyIdentUnavailable)?.raw_u32();
+                    let phy_ident = value.identify()?;
 
-                    if phy_ident & 0xFFFFFFF0 == $id {
+                    if Self::matches_ident(phy_ident) {
                         Ok(super::$phy::new(value.miim, value.phy_address))
                     } else {
-                        Err(IdentPhyError::IncorrectPhyIdent)
+                        Err(IdentPhyError::IncorrectPhyIdent(phy_ident))
                     }
                 }
             }
@@ -92,7 +325,41 @@ macro_rules! into_phy {
 }
 
 into_phy!(
+    ["ksz8051", KSZ8051, 0x00221550],
     ["ksz8081r", KSZ8081R, 0x00221560],
     ["lan8720a", LAN8720A, 0x0007C0F0],
     ["lan8742a", LAN8742A, 0x0007C130],
+    ["ksz9021", KSZ9021, 0x00221610],
+    ["ksz9131", KSZ9131, 0x00221640],
+    ["lan8710a", LAN8710A, 0x0007C0F0],
+    ["lan8740a", LAN8740A, 0x0007C110],
+    ["lan8741a", LAN8741A, 0x0007C120],
+    ["lan867x", LAN867X, 0x0007C160],
+    ["lan8840", LAN8840, 0x00221650],
+    ["rtl8201f", RTL8201F, 0x001CC810],
+    // The RTL8211E and RTL8211F share their OUI and model number
+    ["rtl8211f", RTL8211F, 0x001CC916, 0xFFFF_FFFF],
+    ["rtl8211e", RTL8211E, 0x001CC915, 0xFFFF_FFFF],
+    ["m88e1510", M88E1510, 0x01410DD0],
+    ["m88e1518", M88E1518, 0x01410DD0],
+    ["vsc8541", VSC8541, 0x00070770],
+    ["vsc8211", VSC8211, 0x000FC4B0],
+    ["vsc8501", VSC8501, 0x00070530],
+    // The AR8031 and AR8035 share their OUI and model number
+    ["ar8031", AR8031, 0x004DD074, 0xFFFF_FFFF],
+    ["ar8035", AR8035, 0x004DD072, 0xFFFF_FFFF],
+    ["adin1200", ADIN1200, 0x0283BC20],
+    ["adin1300", ADIN1300, 0x0283BC30],
+    ["tja1100", TJA1100, 0x0180DC40],
+    ["tja1101", TJA1101, 0x0180DD00],
+    ["tja1102", TJA1102, 0x0180DC80],
+    ["tja1103", TJA1103, 0x001BB010],
+    ["bcm5241", BCM5241, 0x0143BC30],
+    ["bcm54210e", BCM54210E, 0x600D84A0],
+    ["ip101g", IP101G, 0x02430C50],
+    ["yt8511", YT8511, 0x00000100],
+    ["yt8521", YT8521, 0x00000110],
+    ["ics1894", ICS1894, 0x0015F450],
+    ["dp83848", DP83848, 0x20005C90],
+    ["dp83640", DP83640, 0x20005CE0],
 );
@@ -0,0 +1,335 @@
+//! Phy implementation for the Broadcom BCM5241 10/100 Ethernet PHY
+//!
+//! Some of the configuration of the BCM5241 is done through shadow
+//! registers, which replace registers 16 through 31 while shadow register
+//! access is enabled in the Broadcom Test register.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    AuxiliaryErrorStatus, AuxiliaryMode4, AuxiliaryMultiplePhy, AuxiliaryStatus2, BroadcomTest,
+    InterruptRegister,
+};
+
+use super::{AdvancedPhySpeed, PhySpeed, PhyWithInterrupts, PhyWithSpeed};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The link status changed
+    LinkChange,
+    /// The link speed changed
+    SpeedChange,
+    /// The duplex mode changed
+    DuplexChange,
+}
+
+impl Interrupt {
+    /// The enable bit for this interrupt in the [`InterruptRegister`]
+    pub fn enable_flag(&self) -> InterruptRegister {
+        match self {
+            Interrupt::LinkChange => InterruptRegister::LINK_CHANGE_IE,
+            Interrupt::SpeedChange => InterruptRegister::SPEED_CHANGE_IE,
+            Interrupt::DuplexChange => InterruptRegister::DUPLEX_CHANGE_IE,
+        }
+    }
+
+    /// The status bit for this interrupt in the [`InterruptRegister`]
+    pub fn status_flag(&self) -> InterruptRegister {
+        match self {
+            Interrupt::LinkChange => InterruptRegister::LINK_CHANGE,
+            Interrupt::SpeedChange => InterruptRegister::SPEED_CHANGE,
+            Interrupt::DuplexChange => InterruptRegister::DUPLEX_CHANGE,
+        }
+    }
+}
+
+/// A BCM5241
+#[derive(Debug)]
+pub struct BCM5241<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> BCM5241<MIIM> {
+    /// Create a new BCM5241 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.auxiliary_error_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the Auxiliary Error and General Status register
+    pub fn auxiliary_error_status(&mut self) -> AuxiliaryErrorStatus {
+        AuxiliaryErrorStatus::from_bits_truncate(self.read(AuxiliaryErrorStatus::ADDRESS))
+    }
+
+    /// Read the Auxiliary Multiple PHY register
+    pub fn auxiliary_multiple_phy(&mut self) -> AuxiliaryMultiplePhy {
+        AuxiliaryMultiplePhy::from_bits_truncate(self.read(AuxiliaryMultiplePhy::ADDRESS))
+    }
+
+    /// Enable or disable super-isolate mode
+    ///
+    /// In super-isolate mode, the PHY is isolated from the MII, and it does
+    /// not transmit any data or link pulses on the cable.
+    pub fn set_super_isolate(&mut self, enabled: bool) {
+        let bit = AuxiliaryMultiplePhy::SUPER_ISOLATE.bits();
+        self.modify(
+            AuxiliaryMultiplePhy::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Read the shadow register at `address`
+    pub fn shadow_read(&mut self, address: u8) -> u16 {
+        self.with_shadow(|phy| phy.read(address))
+    }
+
+    /// Modify the bits selected by `mask` in the shadow register at
+    /// `address`, leaving all other bits untouched
+    pub fn shadow_modify(&mut self, address: u8, mask: u16, value: u16) {
+        self.with_shadow(|phy| phy.modify(address, mask, value))
+    }
+
+    fn with_shadow<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let sre = BroadcomTest::SHADOW_REGISTER_ENABLE.bits();
+        self.modify(BroadcomTest::ADDRESS, sre, sre);
+        let result = f(self);
+        self.modify(BroadcomTest::ADDRESS, sre, 0);
+        result
+    }
+
+    /// Check whether auto-power-down is enabled
+    pub fn auto_power_down(&mut self) -> bool {
+        AuxiliaryStatus2::from_bits_truncate(self.shadow_read(AuxiliaryStatus2::ADDRESS))
+            .contains(AuxiliaryStatus2::AUTO_POWER_DOWN)
+    }
+
+    /// Enable or disable auto-power-down
+    ///
+    /// While enabled, the PHY powers down when no energy is detected on the
+    /// cable, and periodically wakes up to check for a link partner.
+    pub fn set_auto_power_down(&mut self, enabled: bool) {
+        let bit = AuxiliaryStatus2::AUTO_POWER_DOWN.bits();
+        self.shadow_modify(
+            AuxiliaryStatus2::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Enable or disable standby power-down mode
+    pub fn set_standby(&mut self, enabled: bool) {
+        let bit = AuxiliaryMode4::STANDBY.bits();
+        self.shadow_modify(AuxiliaryMode4::ADDRESS, bit, if enabled { bit } else { 0 });
+    }
+
+    fn interrupt_enables(&mut self) -> InterruptRegister {
+        self.read_and_clear_interrupts() & InterruptRegister::ENABLE_MASK
+    }
+
+    /// Enable an interrupt
+    ///
+    /// Note that this reads the interrupt register, which clears all pending
+    /// interrupts.
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let mut reg_val = self.interrupt_enables();
+        reg_val.insert(interrupt.enable_flag() | InterruptRegister::INTERRUPT_ENABLE);
+        reg_val.remove(InterruptRegister::MASK);
+        self.write(InterruptRegister::ADDRESS, reg_val.bits());
+    }
+
+    /// Disable an interrupt
+    ///
+    /// Note that this reads the interrupt register, which clears all pending
+    /// interrupts.
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let mut reg_val = self.interrupt_enables();
+        reg_val.remove(interrupt.enable_flag());
+        self.write(InterruptRegister::ADDRESS, reg_val.bits());
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptRegister::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptRegister {
+        InterruptRegister::from_bits_truncate(self.read(InterruptRegister::ADDRESS))
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for BCM5241<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for BCM5241<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for BCM5241<MIIM> {
+    type Interrupts = InterruptRegister;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptRegister {
+        self.read_and_clear_interrupts()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! BCM5241 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    use super::Interrupt;
+
+    bitflags! {
+        /// Register 26, the Interrupt register
+        pub struct InterruptRegister: u16 {
+            const INTERRUPT_ENABLE = (1 << 14);
+            const DUPLEX_CHANGE_IE = (1 << 11);
+            const SPEED_CHANGE_IE = (1 << 10);
+            const LINK_CHANGE_IE = (1 << 9);
+            /// Mask all interrupts
+            const MASK = (1 << 8);
+            const DUPLEX_CHANGE = (1 << 3);
+            const SPEED_CHANGE = (1 << 2);
+            const LINK_CHANGE = (1 << 1);
+            const INTERRUPT_STATUS = (1 << 0);
+            const ENABLE_MASK = Self::INTERRUPT_ENABLE.bits
+                | Self::DUPLEX_CHANGE_IE.bits
+                | Self::SPEED_CHANGE_IE.bits
+                | Self::LINK_CHANGE_IE.bits
+                | Self::MASK.bits;
+        }
+
+        /// Register 28, the Auxiliary Error and General Status register
+        pub struct AuxiliaryErrorStatus: u16 {
+            const MDIX_STATUS = (1 << 13);
+            const MDIX_SWAP = (1 << 11);
+            const AUTONEG_INDICATOR = (1 << 3);
+            const FORCE_100 = (1 << 2);
+            const SPEED_100 = (1 << 1);
+            const FULL_DUPLEX = (1 << 0);
+        }
+
+        /// Register 30, the Auxiliary Multiple PHY register
+        pub struct AuxiliaryMultiplePhy: u16 {
+            const HCD_100BASE_TX_FD = (1 << 15);
+            const HCD_100BASE_T4 = (1 << 14);
+            const HCD_100BASE_TX = (1 << 13);
+            const HCD_10BASE_T_FD = (1 << 12);
+            const HCD_10BASE_T = (1 << 11);
+            const RESTART_AUTONEG = (1 << 8);
+            const AUTONEG_COMPLETE = (1 << 7);
+            const SUPER_ISOLATE = (1 << 3);
+        }
+
+        /// Register 31, the Broadcom Test register
+        pub struct BroadcomTest: u16 {
+            const SHADOW_REGISTER_ENABLE = (1 << 7);
+        }
+
+        /// Shadow register 26, the Auxiliary Mode 4 register
+        pub struct AuxiliaryMode4: u16 {
+            const STANDBY = (1 << 3);
+            const LED_MODE_MASK = (0b11);
+        }
+
+        /// Shadow register 27, the Auxiliary Status 2 register
+        pub struct AuxiliaryStatus2: u16 {
+            const AUTO_POWER_DOWN = (1 << 5);
+        }
+    }
+
+    impl InterruptRegister {
+        pub const ADDRESS: u8 = 26;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.status_flag())
+        }
+    }
+
+    impl AuxiliaryErrorStatus {
+        pub const ADDRESS: u8 = 28;
+    }
+
+    impl From<AuxiliaryErrorStatus> for Option<PhySpeed> {
+        fn from(status: AuxiliaryErrorStatus) -> Self {
+            let speed = match (
+                status.contains(AuxiliaryErrorStatus::SPEED_100),
+                status.contains(AuxiliaryErrorStatus::FULL_DUPLEX),
+            ) {
+                (false, false) => PhySpeed::HalfDuplexBase10T,
+                (false, true) => PhySpeed::FullDuplexBase10T,
+                (true, false) => PhySpeed::HalfDuplexBase100Tx,
+                (true, true) => PhySpeed::FullDuplexBase100Tx,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl AuxiliaryMultiplePhy {
+        pub const ADDRESS: u8 = 30;
+    }
+
+    impl BroadcomTest {
+        pub const ADDRESS: u8 = 31;
+    }
+
+    impl AuxiliaryMode4 {
+        pub const ADDRESS: u8 = 26;
+    }
+
+    impl AuxiliaryStatus2 {
+        pub const ADDRESS: u8 = 27;
+    }
+}
@@ -0,0 +1,393 @@
+//! Phy implementation for the Broadcom BCM54210E gigabit Ethernet PHY
+//!
+//! The BCM54210E multiplexes most of its vendor specific registers behind a
+//! few shadow register schemes:
+//!
+//! * register 0x18, the Auxiliary Control register, has 8 shadow registers
+//!   that are selected with its lower 3 bits.
+//! * register 0x1C has 32 shadow registers that are selected with bits 14:10.
+//! * expansion registers are selected through register 0x17, and accessed
+//!   through register 0x15.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    AuxiliaryControl, AuxiliaryStatus, ClockAlignmentControl, InterruptFlags, MiscControl,
+    EXPANSION_DATA, EXPANSION_SELECT, SHADOW_ACCESS, SHADOW_DATA_MASK, SHADOW_SELECT_SHIFT,
+    SHADOW_WRITE_ENABLE,
+};
+
+use super::{AdvancedPhySpeed, PhyWithInterrupts, PhyWithRgmiiDelays, PhyWithSpeed, RgmiiDelays};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// A CRC error was detected
+    CrcError,
+    /// The link status changed
+    LinkStatusChange,
+    /// The link speed changed
+    SpeedChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// The local receiver status changed
+    LocalReceiverStatusChange,
+    /// The remote receiver status changed
+    RemoteReceiverStatusChange,
+    /// A scrambler synchronization error was detected
+    ScramblerSyncError,
+    /// The negotiated highest common denominator is not supported
+    UnsupportedHcd,
+    /// No highest common denominator was found
+    NoHcd,
+    /// A highest common denominator was found, but no link was established
+    NoHcdLink,
+    /// An autonegotiation page was received
+    PageReceived,
+    /// Energy was detected on the cable
+    EnergyDetect,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::CrcError => InterruptFlags::CRC_ERROR,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+            Interrupt::DuplexChange => InterruptFlags::DUPLEX_CHANGE,
+            Interrupt::LocalReceiverStatusChange => InterruptFlags::LOCAL_RX_STATUS_CHANGE,
+            Interrupt::RemoteReceiverStatusChange => InterruptFlags::REMOTE_RX_STATUS_CHANGE,
+            Interrupt::ScramblerSyncError => InterruptFlags::SCRAMBLER_SYNC_ERROR,
+            Interrupt::UnsupportedHcd => InterruptFlags::UNSUPPORTED_HCD,
+            Interrupt::NoHcd => InterruptFlags::NO_HCD,
+            Interrupt::NoHcdLink => InterruptFlags::NO_HCD_LINK,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::EnergyDetect => InterruptFlags::ENERGY_DETECT,
+        }
+    }
+}
+
+/// A BCM54210E
+#[derive(Debug)]
+pub struct BCM54210E<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> BCM54210E<MIIM> {
+    /// Create a new BCM54210E at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, including 1000BASE-T, and
+    /// restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.auxiliary_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the Auxiliary Status Summary register
+    pub fn auxiliary_status(&mut self) -> AuxiliaryStatus {
+        AuxiliaryStatus::from_bits_truncate(self.read(AuxiliaryStatus::ADDRESS))
+    }
+
+    /// Read the Auxiliary Control shadow register selected by `shadow`
+    pub fn aux_control_read(&mut self, shadow: u8) -> u16 {
+        let shadow = (shadow as u16) & AuxiliaryControl::SHADOW_MASK.bits();
+        self.write(
+            AuxiliaryControl::ADDRESS,
+            (shadow << AuxiliaryControl::READ_SELECT_SHIFT) | AuxiliaryControl::SHADOW_MASK.bits(),
+        );
+        self.read(AuxiliaryControl::ADDRESS)
+    }
+
+    /// Write `value` to the Auxiliary Control shadow register selected by
+    /// `shadow`
+    ///
+    /// The lower 3 bits of `value` are replaced by the shadow register
+    /// selector.
+    pub fn aux_control_write(&mut self, shadow: u8, value: u16) {
+        let mask = AuxiliaryControl::SHADOW_MASK.bits();
+        self.write(
+            AuxiliaryControl::ADDRESS,
+            (value & !mask) | (shadow as u16 & mask),
+        );
+    }
+
+    /// Modify the bits selected by `mask` in the Auxiliary Control shadow
+    /// register selected by `shadow`, leaving all other bits untouched
+    pub fn aux_control_modify(&mut self, shadow: u8, mask: u16, value: u16) {
+        let current = self.aux_control_read(shadow);
+        self.aux_control_write(shadow, (current & !mask) | (value & mask));
+    }
+
+    /// Read the register 0x1C shadow register selected by `shadow`
+    pub fn shadow_read(&mut self, shadow: u8) -> u16 {
+        self.write(SHADOW_ACCESS, Self::shadow_select(shadow));
+        self.read(SHADOW_ACCESS) & SHADOW_DATA_MASK
+    }
+
+    /// Write `value` to the register 0x1C shadow register selected by
+    /// `shadow`
+    ///
+    /// Only the lower 10 bits of `value` are written.
+    pub fn shadow_write(&mut self, shadow: u8, value: u16) {
+        self.write(
+            SHADOW_ACCESS,
+            SHADOW_WRITE_ENABLE | Self::shadow_select(shadow) | (value & SHADOW_DATA_MASK),
+        );
+    }
+
+    fn shadow_select(shadow: u8) -> u16 {
+        ((shadow as u16) & 0x1F) << SHADOW_SELECT_SHIFT
+    }
+
+    /// Modify the bits selected by `mask` in the register 0x1C shadow register
+    /// selected by `shadow`, leaving all other bits untouched
+    pub fn shadow_modify(&mut self, shadow: u8, mask: u16, value: u16) {
+        let current = self.shadow_read(shadow);
+        self.shadow_write(shadow, (current & !mask) | (value & mask));
+    }
+
+    /// Read the expansion register at `address`
+    pub fn expansion_read(&mut self, address: u8) -> u16 {
+        self.write(EXPANSION_SELECT, 0x0F00 | address as u16);
+        self.read(EXPANSION_DATA)
+    }
+
+    /// Write `value` to the expansion register at `address`
+    pub fn expansion_write(&mut self, address: u8, value: u16) {
+        self.write(EXPANSION_SELECT, 0x0F00 | address as u16);
+        self.write(EXPANSION_DATA, value);
+    }
+
+    /// Check whether the internal RGMII RX clock delay is enabled
+    pub fn rx_delay(&mut self) -> bool {
+        MiscControl::from_bits_truncate(self.aux_control_read(MiscControl::SHADOW))
+            .contains(MiscControl::RGMII_RX_SKEW_ENABLE)
+    }
+
+    /// Check whether the internal RGMII TX clock delay is enabled
+    pub fn tx_delay(&mut self) -> bool {
+        ClockAlignmentControl::from_bits_truncate(self.shadow_read(ClockAlignmentControl::SHADOW))
+            .contains(ClockAlignmentControl::GTXCLK_DELAY_ENABLE)
+    }
+
+    /// Enable or disable the internal RGMII RX and TX clock delays
+    pub fn set_rgmii_delays(&mut self, rx_delay: bool, tx_delay: bool) {
+        let rx = MiscControl::RGMII_RX_SKEW_ENABLE.bits();
+        self.aux_control_modify(
+            MiscControl::SHADOW,
+            (MiscControl::WRITE_ENABLE | MiscControl::RGMII_RX_SKEW_ENABLE).bits(),
+            MiscControl::WRITE_ENABLE.bits() | if rx_delay { rx } else { 0 },
+        );
+
+        let tx = ClockAlignmentControl::GTXCLK_DELAY_ENABLE.bits();
+        self.shadow_modify(
+            ClockAlignmentControl::SHADOW,
+            tx,
+            if tx_delay { tx } else { 0 },
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        // A set bit in the mask register disables the interrupt
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flag, 0);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flag, flag);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for BCM54210E<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for BCM54210E<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for BCM54210E<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for BCM54210E<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        RgmiiDelays {
+            tx: self.tx_delay(),
+            rx: self.rx_delay(),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_rgmii_delays(delays.rx, delays.tx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! BCM54210E registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::Interrupt;
+
+    /// Register 0x15, the Expansion Register Data register
+    pub const EXPANSION_DATA: u8 = 0x15;
+    /// Register 0x17, the Expansion Register Select register
+    pub const EXPANSION_SELECT: u8 = 0x17;
+
+    /// Register 0x1C, which gives access to its shadow registers
+    pub const SHADOW_ACCESS: u8 = 0x1C;
+    /// Set in [`SHADOW_ACCESS`] to write to the selected shadow register
+    pub const SHADOW_WRITE_ENABLE: u16 = 1 << 15;
+    /// The shift of the shadow register selector in [`SHADOW_ACCESS`]
+    pub const SHADOW_SELECT_SHIFT: u16 = 10;
+    /// The data bits of a shadow register in [`SHADOW_ACCESS`]
+    pub const SHADOW_DATA_MASK: u16 = 0x3FF;
+
+    bitflags! {
+        /// Register 0x18, the Auxiliary Control register
+        pub struct AuxiliaryControl: u16 {
+            const SHADOW_MASK = (0b111);
+        }
+
+        /// Shadow register 0b111 of the Auxiliary Control register, the
+        /// Miscellaneous Control register
+        pub struct MiscControl: u16 {
+            const WRITE_ENABLE = (1 << 15);
+            const FORCE_AUTO_MDIX = (1 << 9);
+            const RGMII_RX_SKEW_ENABLE = (1 << 8);
+            const RGMII_ENABLE = (1 << 7);
+        }
+
+        /// Register 0x19, the Auxiliary Status Summary register
+        pub struct AuxiliaryStatus: u16 {
+            const AUTONEG_COMPLETE = (1 << 15);
+            const HCD_MASK = (0b111 << 8);
+            const LINK_STATUS = (1 << 2);
+        }
+
+        /// Register 0x1A (status) and 0x1B (mask), the interrupt registers
+        pub struct InterruptFlags: u16 {
+            const ENERGY_DETECT = (1 << 14);
+            const PAGE_RECEIVED = (1 << 10);
+            const NO_HCD_LINK = (1 << 9);
+            const NO_HCD = (1 << 8);
+            const UNSUPPORTED_HCD = (1 << 7);
+            const SCRAMBLER_SYNC_ERROR = (1 << 6);
+            const REMOTE_RX_STATUS_CHANGE = (1 << 5);
+            const LOCAL_RX_STATUS_CHANGE = (1 << 4);
+            const DUPLEX_CHANGE = (1 << 3);
+            const SPEED_CHANGE = (1 << 2);
+            const LINK_STATUS_CHANGE = (1 << 1);
+            const CRC_ERROR = (1 << 0);
+        }
+
+        /// Shadow register 0b00011 of register 0x1C, the Clock Alignment
+        /// Control register
+        pub struct ClockAlignmentControl: u16 {
+            const GTXCLK_DELAY_ENABLE = (1 << 9);
+        }
+    }
+
+    impl AuxiliaryControl {
+        pub const ADDRESS: u8 = 0x18;
+        pub const READ_SELECT_SHIFT: u16 = 12;
+    }
+
+    impl MiscControl {
+        pub const SHADOW: u8 = 0b111;
+    }
+
+    impl AuxiliaryStatus {
+        pub const ADDRESS: u8 = 0x19;
+    }
+
+    impl From<AuxiliaryStatus> for Option<AdvancedPhySpeed> {
+        fn from(status: AuxiliaryStatus) -> Self {
+            let speed = match (status & AuxiliaryStatus::HCD_MASK).bits() >> 8 {
+                0b001 => AdvancedPhySpeed::HalfDuplexBase10T,
+                0b010 => AdvancedPhySpeed::FullDuplexBase10T,
+                0b011 => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                0b101 => AdvancedPhySpeed::FullDuplexBase100Tx,
+                0b110 => AdvancedPhySpeed::HalfDuplexBase1000T,
+                0b111 => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl InterruptFlags {
+        pub const STATUS_ADDRESS: u8 = 0x1A;
+        pub const MASK_ADDRESS: u8 = 0x1B;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl ClockAlignmentControl {
+        pub const SHADOW: u8 = 0b00011;
+    }
+}
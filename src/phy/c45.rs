@@ -0,0 +1,184 @@
+//! A bare Clause 45 PHY, and helpers shared by the PHYs that only implement
+//! Clause 45.
+//!
+//! These PHYs do not have the Clause 22 autonegotiation registers, but
+//! provide the same base page in the Auto-Negotiation MMD instead.
+
+use crate::registers::{AutoNegCap, MmdAnStatus, MmdControl, MmdStatus, PmaExtendedAbility};
+use crate::{AutoNegotiationAdvertisement, Miim, MmdDevice, Pause, Phy, PhyAddress, PhyIdent};
+
+use super::IdentPhyError;
+
+/// A bare Clause 45 PHY, that only uses the registers defined by the
+/// standard.
+///
+/// This is the Clause 45 counterpart of [`BarePhy`](super::BarePhy): it can
+/// be used for any PHY that only implements Clause 45 and for which no
+/// specific driver exists. All register accesses go through
+/// [`Miim::read_c45`] and [`Miim::write_c45`]. The methods of [`Phy`] that
+/// access Clause 22 registers, such as [`Phy::bcr`] and [`Phy::status`], are
+/// not supported.
+#[derive(Debug)]
+pub struct C45BarePhy<MIIM>
+where
+    MIIM: Miim,
+{
+    phy_address: PhyAddress,
+    miim: MIIM,
+    best_supported_advertisement: AutoNegotiationAdvertisement,
+}
+
+impl<MIIM> C45BarePhy<MIIM>
+where
+    MIIM: Miim,
+{
+    /// Create a new bare Clause 45 PHY with the given MIIM, at the given PHY
+    /// address, using `pause` as the advertised pause mode.
+    ///
+    /// The best supported advertisement is calculated from the PMA/PMD
+    /// extended ability register (1.11).
+    pub fn new(miim: MIIM, phy_address: PhyAddress, pause: Pause) -> Self {
+        let mut me = Self {
+            phy_address,
+            miim,
+            best_supported_advertisement: Default::default(),
+        };
+
+        let ability = me.pma_extended_ability();
+        me.best_supported_advertisement = AutoNegotiationAdvertisement {
+            hd_10base_t: ability.contains(PmaExtendedAbility::_10BASET),
+            fd_10base_t: ability.contains(PmaExtendedAbility::_10BASET),
+            hd_100base_tx: ability.contains(PmaExtendedAbility::_100BASETX),
+            fd_100base_tx: ability.contains(PmaExtendedAbility::_100BASETX),
+            pause,
+            ..Default::default()
+        };
+        me
+    }
+
+    /// Read the PMA/PMD device identifier (registers 1.2 and 1.3) of this
+    /// PHY.
+    pub fn identify(&mut self) -> Result<PhyIdent, IdentPhyError> {
+        self.phy_ident().ok_or(IdentPhyError::PhyIdentUnavailable)
+    }
+
+    /// Read the PMA/PMD extended ability register (1.11)
+    pub fn pma_extended_ability(&mut self) -> PmaExtendedAbility {
+        PmaExtendedAbility::from_bits_truncate(
+            self.mmd_read(PmaExtendedAbility::MMD, PmaExtendedAbility::ADDRESS),
+        )
+    }
+
+    /// Release the underlying MIIM
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+
+    /// Change the PHY address
+    pub fn set_phy_addr(&mut self, phy_address: PhyAddress) {
+        self.phy_address = phy_address;
+    }
+}
+
+impl<MIIM> Phy<MIIM> for C45BarePhy<MIIM>
+where
+    MIIM: Miim,
+{
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        self.best_supported_advertisement
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_address
+    }
+
+    fn mmd_read(&mut self, mmd_address: u8, reg_address: u16) -> u16 {
+        let phy = self.phy_address.get();
+        self.miim.read_c45(phy, mmd_address, reg_address)
+    }
+
+    fn mmd_write(&mut self, device_address: u8, reg_address: u16, reg_value: u16) {
+        let phy = self.phy_address.get();
+        self.miim
+            .write_c45(phy, device_address, reg_address, reg_value)
+    }
+
+    fn is_resetting(&mut self) -> bool {
+        self.mmd_is_resetting(MmdDevice::PmaPmd)
+    }
+
+    /// Reset the PMA/PMD
+    fn reset(&mut self) {
+        self.mmd_reset(MmdDevice::PmaPmd)
+    }
+
+    /// Check if both the PMA/PMD and the PCS report their link as being up
+    fn phy_link_up(&mut self) -> bool {
+        [MmdDevice::PmaPmd, MmdDevice::Pcs]
+            .into_iter()
+            .all(|device| {
+                self.mmd_status(device)
+                    .is_some_and(|status| status.contains(MmdStatus::RECEIVE_LINK_STATUS))
+            })
+    }
+
+    fn autoneg_completed(&mut self) -> bool {
+        self.mmd_an_status().contains(MmdAnStatus::AUTONEG_COMPLETE)
+    }
+
+    fn phy_ident(&mut self) -> Option<PhyIdent> {
+        self.mmd_device_ident(MmdDevice::PmaPmd.address())
+    }
+
+    fn set_autonegotiation_advertisement(&mut self, ad: AutoNegotiationAdvertisement) {
+        set_autonegotiation_advertisement(self, ad)
+    }
+
+    fn get_autonegotiation_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(autonegotiation_advertisement(
+            self,
+            AutoNegCap::LOCAL_CAP_ADDRESS,
+        ))
+    }
+
+    fn get_autonegotiation_partner_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(autonegotiation_advertisement(
+            self,
+            AutoNegCap::PARTNER_CAP_ADDRESS,
+        ))
+    }
+}
+
+/// The Auto-Negotiation MMD
+const AN_MMD: u8 = 7;
+
+/// Write `ad` to the advertisement register (7.16), and restart
+/// autonegotiation
+pub(crate) fn set_autonegotiation_advertisement<M, P>(phy: &mut P, ad: AutoNegotiationAdvertisement)
+where
+    M: Miim,
+    P: Phy<M>,
+{
+    let address = AutoNegCap::LOCAL_CAP_ADDRESS as u16;
+    phy.mmd_write(AN_MMD, address, ad.encode().bits());
+
+    let bits = (MmdControl::AUTONEG_ENABLE | MmdControl::RESTART_AUTONEG).bits();
+    phy.mmd_modify(AN_MMD, MmdControl::ADDRESS, bits, bits);
+}
+
+/// Read the advertisement (7.16) or the link partner ability (7.19),
+/// depending on `address`
+pub(crate) fn autonegotiation_advertisement<M, P>(
+    phy: &mut P,
+    address: u8,
+) -> AutoNegotiationAdvertisement
+where
+    M: Miim,
+    P: Phy<M>,
+{
+    AutoNegCap::from_bits_truncate(phy.mmd_read(AN_MMD, address as u16)).into()
+}
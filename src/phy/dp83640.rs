@@ -0,0 +1,893 @@
+//! Phy implementation for the Texas Instruments DP83640 precision PTP PHY
+//!
+//! The DP83640 is register compatible with the [`DP83848`](super::dp83848::DP83848)
+//! in its standard and extended registers, and adds an IEEE 1588 clock with
+//! event capture and trigger outputs on its GPIOs.
+//!
+//! The IEEE 1588 registers are located on pages 4 and 5, which are
+//! selected through the PAGESEL register. Page 0 contains the extended
+//! registers, and is selected by default.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    MessageFilter, PtpCtl, PtpEsts, PtpEvnt, PtpRxCfg0, PtpSts, PtpTrig, PtpTsts, PtpTxCfg0,
+    PAGE_PTP, PAGE_PTP_CONFIG, PAGE_SELECT, PTP_EDATA, PTP_RXCFG1, PTP_RXTS, PTP_TDR, PTP_TXCFG1,
+    PTP_TXTS,
+};
+
+use super::{
+    dp83848::{self, registers::PhySts, LinkStatus},
+    AdvancedPhySpeed, LinkCounters, PhySpeed, PhyWithCounters, PhyWithPages, PhyWithSpeed,
+};
+
+/// A point in time of the IEEE 1588 clock
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PtpTimestamp {
+    /// The seconds part of the time
+    pub seconds: u32,
+    /// The nanoseconds part of the time, below 1 000 000 000
+    pub nanoseconds: u32,
+}
+
+/// The configuration of an event capture
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventConfig {
+    /// The GPIO (1 to 12) that is monitored
+    pub gpio: u8,
+    /// Capture rising edges
+    pub rising: bool,
+    /// Capture falling edges
+    pub falling: bool,
+    /// Only capture a single event, instead of all events
+    pub single: bool,
+}
+
+/// A captured event
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Event {
+    /// A mask of the event captures (bit `n` for event `n`) that detected
+    /// this event
+    pub events: u8,
+    /// Whether the event was a rising edge, as opposed to a falling edge
+    pub rising: bool,
+    /// The amount of events that were missed before this event
+    pub missed: u8,
+    /// The time at which the event occurred
+    pub timestamp: PtpTimestamp,
+}
+
+/// The configuration of a trigger
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TriggerConfig {
+    /// The GPIO (1 to 12) that the trigger drives, or 0 if it is only used
+    /// internally
+    pub gpio: u8,
+    /// The time at which the trigger fires
+    pub start: PtpTimestamp,
+    /// Generate a pulse of `pulse_width` nanoseconds, instead of an edge
+    pub pulse: bool,
+    /// Repeat the trigger, generating a periodic signal
+    pub periodic: bool,
+    /// The high time of the output, in nanoseconds
+    pub pulse_width: u32,
+    /// The low time of a periodic output, in nanoseconds
+    ///
+    /// This is only supported by triggers 0 and 1. Other triggers use
+    /// `pulse_width` for the low time as well.
+    pub pulse_width2: u32,
+}
+
+/// The PTP messages that are timestamped
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PtpMessages {
+    /// All event messages
+    AllEvents,
+    /// Only Sync messages
+    Sync,
+    /// Only Delay_Req messages
+    DelayReq,
+    /// Only Pdelay_Req messages
+    PdelayReq,
+    /// Only Pdelay_Resp messages
+    PdelayResp,
+}
+
+/// The configuration of packet timestamping
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampConfig {
+    /// The PTP version of the messages that are timestamped
+    pub ptp_version: u8,
+    /// Timestamp messages sent directly over Ethernet (Layer 2)
+    pub layer2: bool,
+    /// Timestamp messages sent over UDP/IPv4
+    pub ipv4: bool,
+    /// Timestamp messages sent over UDP/IPv6
+    pub ipv6: bool,
+    /// The messages that are timestamped
+    pub messages: PtpMessages,
+}
+
+impl Default for TimestampConfig {
+    fn default() -> Self {
+        Self {
+            ptp_version: 2,
+            layer2: true,
+            ipv4: true,
+            ipv6: true,
+            messages: PtpMessages::AllEvents,
+        }
+    }
+}
+
+/// How transmit timestamps are delivered
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxTimestampMode {
+    /// All timestamps are stored in the transmit timestamp FIFO
+    Fifo,
+    /// The timestamp of Sync messages is inserted into the message as it
+    /// is transmitted (one-step operation). The timestamps of other messages
+    /// are stored in the transmit timestamp FIFO.
+    OneStepSync,
+}
+
+/// The timestamp of a transmitted packet
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TxTimestamp {
+    /// The time at which the packet was transmitted
+    pub timestamp: PtpTimestamp,
+    /// The amount of timestamps that were lost because the FIFO was full,
+    /// saturating at 3
+    pub overflow: u8,
+}
+
+/// The timestamp of a received packet
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RxTimestamp {
+    /// The time at which the packet was received
+    pub timestamp: PtpTimestamp,
+    /// The amount of timestamps that were lost because the FIFO was full,
+    /// saturating at 3
+    pub overflow: u8,
+    /// The sequenceId of the message
+    pub sequence_id: u16,
+    /// The messageType of the message
+    pub message_type: u8,
+    /// A 12 bit hash of the sourcePortIdentity of the message
+    pub source_hash: u16,
+}
+
+/// The state of a trigger
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerState {
+    /// The trigger is not active
+    Inactive,
+    /// The trigger is armed or generating its output
+    Active,
+    /// The trigger was armed with a start time in the past
+    Error,
+}
+
+/// A DP83640
+#[derive(Debug)]
+pub struct DP83640<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+    last_event: PtpTimestamp,
+}
+
+impl<MIIM: Miim> DP83640<MIIM> {
+    /// The amount of event captures
+    pub const EVENTS: u8 = 8;
+    /// The amount of triggers
+    pub const TRIGGERS: u8 = 8;
+
+    /// Create a new DP83640 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self {
+            phy_addr,
+            miim,
+            last_event: PtpTimestamp::default(),
+        }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.phy_sts().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the PHY Status register
+    ///
+    /// Note that the receive error and false carrier indicators in this
+    /// register are latched, and are cleared by reading it.
+    pub fn phy_sts(&mut self) -> PhySts {
+        PhySts::from_bits_truncate(self.read(PhySts::ADDRESS))
+    }
+
+    /// Read and decode the PHY Status register
+    pub fn phy_sts_decoded(&mut self) -> LinkStatus {
+        self.phy_sts().into()
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        self.page_scoped(page).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    fn ptp_command(&mut self, command: PtpCtl) {
+        self.write_paged(PAGE_PTP, PtpCtl::ADDRESS, command.bits());
+    }
+
+    /// Enable or disable the IEEE 1588 clock
+    pub fn set_ptp_enabled(&mut self, enabled: bool) {
+        if enabled {
+            self.ptp_command(PtpCtl::PTP_ENABLE);
+        } else {
+            self.ptp_command(PtpCtl::PTP_DISABLE);
+        }
+    }
+
+    /// Set the time of the IEEE 1588 clock
+    pub fn set_ptp_time(&mut self, time: PtpTimestamp) {
+        let mut phy = self.page_scoped(PAGE_PTP);
+        write_timestamp(&mut *phy, time);
+        phy.write(PtpCtl::ADDRESS, PtpCtl::PTP_LOAD_CLK.bits());
+    }
+
+    /// Get the time of the IEEE 1588 clock
+    pub fn ptp_time(&mut self) -> PtpTimestamp {
+        let mut phy = self.page_scoped(PAGE_PTP);
+        phy.write(PtpCtl::ADDRESS, PtpCtl::PTP_RD_CLK.bits());
+        let ns_lo = phy.read(PTP_TDR) as u32;
+        let ns_hi = phy.read(PTP_TDR) as u32;
+        let sec_lo = phy.read(PTP_TDR) as u32;
+        let sec_hi = phy.read(PTP_TDR) as u32;
+
+        PtpTimestamp {
+            seconds: (sec_hi << 16) | sec_lo,
+            nanoseconds: (ns_hi << 16) | ns_lo,
+        }
+    }
+
+    /// Configure event capture `event` (0 to 7), or disable it if `config`
+    /// is `None`.
+    ///
+    /// This does nothing if `event` does not exist.
+    pub fn configure_event(&mut self, event: u8, config: Option<EventConfig>) {
+        if event >= Self::EVENTS {
+            return;
+        }
+
+        let mut evnt = PtpEvnt::select(event) | PtpEvnt::EVNT_WR;
+        if let Some(config) = config {
+            evnt |= PtpEvnt::gpio(config.gpio);
+            evnt.set(PtpEvnt::EVNT_RISE, config.rising);
+            evnt.set(PtpEvnt::EVNT_FALL, config.falling);
+            evnt.set(PtpEvnt::EVNT_SINGLE, config.single);
+        }
+
+        self.write_paged(PAGE_PTP_CONFIG, PtpEvnt::ADDRESS, evnt.bits());
+    }
+
+    /// Read the oldest event from the event FIFO, if any.
+    pub fn read_event(&mut self) -> Option<Event> {
+        let mut phy = self.page_scoped(PAGE_PTP);
+
+        let ests = PtpEsts::from_bits_truncate(phy.read(PtpEsts::ADDRESS));
+        if !ests.contains(PtpEsts::EVENT_DET) {
+            return None;
+        }
+
+        let events = if ests.contains(PtpEsts::MULT_EVNT) {
+            phy.read(PTP_EDATA) as u8
+        } else {
+            1 << ests.event_num()
+        };
+
+        // Only the words that changed since the previous event are
+        // provided, most significant word first.
+        let mut last_event = phy.last_event;
+        let len = ests.timestamp_len();
+        if len >= 3 {
+            let sec_hi = phy.read(PTP_EDATA) as u32;
+            last_event.seconds = (last_event.seconds & 0xFFFF) | (sec_hi << 16);
+        }
+        if len >= 2 {
+            let sec_lo = phy.read(PTP_EDATA) as u32;
+            last_event.seconds = (last_event.seconds & 0xFFFF_0000) | sec_lo;
+        }
+        if len >= 1 {
+            let ns_hi = phy.read(PTP_EDATA) as u32 & 0x3FFF;
+            last_event.nanoseconds = (last_event.nanoseconds & 0xFFFF) | (ns_hi << 16);
+        }
+        let ns_lo = phy.read(PTP_EDATA) as u32;
+        last_event.nanoseconds = (last_event.nanoseconds & 0xFFFF_0000) | ns_lo;
+        phy.last_event = last_event;
+
+        Some(Event {
+            events,
+            rising: ests.contains(PtpEsts::EVNT_RF),
+            missed: ests.missed(),
+            timestamp: last_event,
+        })
+    }
+
+    /// Read events from the event FIFO into `events`, until either the FIFO
+    /// is empty or `events` is full.
+    ///
+    /// Returns the amount of events that were read.
+    pub fn drain_events(&mut self, events: &mut [Event]) -> usize {
+        drain(events, || self.read_event())
+    }
+
+    /// Configure and arm trigger `trigger` (0 to 7)
+    ///
+    /// This does nothing if `trigger` does not exist.
+    pub fn configure_trigger(&mut self, trigger: u8, config: TriggerConfig) {
+        if trigger >= Self::TRIGGERS {
+            return;
+        }
+
+        let mut trig = PtpTrig::gpio(config.gpio)
+            | PtpTrig::select(trigger)
+            | PtpTrig::TRIG_IF_LATE
+            | PtpTrig::TRIG_WR;
+        trig.set(PtpTrig::TRIG_PULSE, config.pulse);
+        trig.set(PtpTrig::TRIG_PER, config.periodic);
+        self.write_paged(PAGE_PTP_CONFIG, PtpTrig::ADDRESS, trig.bits());
+
+        let mut phy = self.page_scoped(PAGE_PTP);
+        let select = PtpCtl::trigger(trigger);
+        phy.write(PtpCtl::ADDRESS, (select | PtpCtl::TRIG_LOAD).bits());
+
+        write_timestamp(&mut *phy, config.start);
+        phy.write(PTP_TDR, config.pulse_width as u16);
+        phy.write(PTP_TDR, (config.pulse_width >> 16) as u16);
+        if trigger < 2 {
+            phy.write(PTP_TDR, config.pulse_width2 as u16);
+            phy.write(PTP_TDR, (config.pulse_width2 >> 16) as u16);
+        }
+
+        phy.write(PtpCtl::ADDRESS, (select | PtpCtl::TRIG_EN).bits());
+    }
+
+    /// Output a pulse per second on `gpio`, using `trigger`, starting at
+    /// `start_seconds`.
+    ///
+    /// The output is high during the first half of every second.
+    pub fn enable_pps(&mut self, trigger: u8, gpio: u8, start_seconds: u32) {
+        const HALF_SECOND: u32 = 500_000_000;
+
+        self.configure_trigger(
+            trigger,
+            TriggerConfig {
+                gpio,
+                start: PtpTimestamp {
+                    seconds: start_seconds,
+                    nanoseconds: 0,
+                },
+                pulse: false,
+                periodic: true,
+                pulse_width: HALF_SECOND,
+                pulse_width2: HALF_SECOND,
+            },
+        );
+    }
+
+    /// Disable trigger `trigger` (0 to 7)
+    pub fn disable_trigger(&mut self, trigger: u8) {
+        if trigger < Self::TRIGGERS {
+            self.ptp_command(PtpCtl::trigger(trigger) | PtpCtl::TRIG_DIS);
+        }
+    }
+
+    /// Get the state of trigger `trigger` (0 to 7)
+    pub fn trigger_state(&mut self, trigger: u8) -> TriggerState {
+        if trigger >= Self::TRIGGERS {
+            return TriggerState::Inactive;
+        }
+
+        let tsts = PtpTsts::from_bits_truncate(self.read_paged(PAGE_PTP, PtpTsts::ADDRESS));
+        let shift = trigger * 2;
+
+        if tsts.bits() & (PtpTsts::TRIG0_ERROR.bits() << shift) != 0 {
+            TriggerState::Error
+        } else if tsts.bits() & (PtpTsts::TRIG0_ACTIVE.bits() << shift) != 0 {
+            TriggerState::Active
+        } else {
+            TriggerState::Inactive
+        }
+    }
+
+    /// Configure timestamping of transmitted packets, or disable it if
+    /// `config` is `None`.
+    pub fn configure_tx_timestamps(
+        &mut self,
+        config: Option<TimestampConfig>,
+        mode: TxTimestampMode,
+    ) {
+        let (cfg0, filter) = match config {
+            Some(config) => {
+                let mut cfg0 = PtpTxCfg0::version(config.ptp_version) | PtpTxCfg0::TX_TS_EN;
+                cfg0.set(PtpTxCfg0::TX_L2_EN, config.layer2);
+                cfg0.set(PtpTxCfg0::TX_IPV4_EN, config.ipv4);
+                cfg0.set(PtpTxCfg0::TX_IPV6_EN, config.ipv6);
+                if mode == TxTimestampMode::OneStepSync {
+                    cfg0 |= PtpTxCfg0::SYNC_1STEP | PtpTxCfg0::CHK_1STEP;
+                }
+                (cfg0, config.messages.into())
+            }
+            None => (PtpTxCfg0::empty(), MessageFilter::default()),
+        };
+
+        let mut phy = self.page_scoped(PAGE_PTP_CONFIG);
+        phy.write(PTP_TXCFG1, filter.bits());
+        phy.write(PtpTxCfg0::ADDRESS, cfg0.bits());
+    }
+
+    /// Configure timestamping of received packets, or disable it if
+    /// `config` is `None`.
+    pub fn configure_rx_timestamps(&mut self, config: Option<TimestampConfig>) {
+        let (cfg0, filter) = match config {
+            Some(config) => {
+                let mut cfg0 = PtpRxCfg0::version(config.ptp_version) | PtpRxCfg0::RX_TS_EN;
+                cfg0.set(PtpRxCfg0::RX_L2_EN, config.layer2);
+                cfg0.set(PtpRxCfg0::RX_IPV4_EN, config.ipv4);
+                cfg0.set(PtpRxCfg0::RX_IPV6_EN, config.ipv6);
+                (cfg0, config.messages.into())
+            }
+            None => (PtpRxCfg0::empty(), MessageFilter::default()),
+        };
+
+        let mut phy = self.page_scoped(PAGE_PTP_CONFIG);
+        phy.write(PTP_RXCFG1, filter.bits());
+        phy.write(PtpRxCfg0::ADDRESS, cfg0.bits());
+    }
+
+    /// Read the PTP Status register
+    pub fn ptp_status(&mut self) -> PtpSts {
+        PtpSts::from_bits_truncate(self.read_paged(PAGE_PTP, PtpSts::ADDRESS))
+    }
+
+    /// Read the oldest timestamp from the transmit timestamp FIFO, if any.
+    pub fn read_tx_timestamp(&mut self) -> Option<TxTimestamp> {
+        let mut phy = self.page_scoped(PAGE_PTP);
+        let sts = PtpSts::from_bits_truncate(phy.read(PtpSts::ADDRESS));
+        if !sts.contains(PtpSts::TXTS_RDY) {
+            return None;
+        }
+
+        let (timestamp, overflow) = read_packet_timestamp(&mut *phy, PTP_TXTS);
+        Some(TxTimestamp {
+            timestamp,
+            overflow,
+        })
+    }
+
+    /// Read the oldest timestamp from the receive timestamp FIFO, if any.
+    pub fn read_rx_timestamp(&mut self) -> Option<RxTimestamp> {
+        let mut phy = self.page_scoped(PAGE_PTP);
+        let sts = PtpSts::from_bits_truncate(phy.read(PtpSts::ADDRESS));
+        if !sts.contains(PtpSts::RXTS_RDY) {
+            return None;
+        }
+
+        let (timestamp, overflow) = read_packet_timestamp(&mut *phy, PTP_RXTS);
+        let sequence_id = phy.read(PTP_RXTS);
+        let message = phy.read(PTP_RXTS);
+
+        Some(RxTimestamp {
+            timestamp,
+            overflow,
+            sequence_id,
+            message_type: (message >> 12) as u8,
+            source_hash: message & 0xFFF,
+        })
+    }
+
+    /// Read timestamps from the transmit timestamp FIFO into `timestamps`,
+    /// until either the FIFO is empty or `timestamps` is full.
+    ///
+    /// Returns the amount of timestamps that were read.
+    pub fn drain_tx_timestamps(&mut self, timestamps: &mut [TxTimestamp]) -> usize {
+        drain(timestamps, || self.read_tx_timestamp())
+    }
+
+    /// Read timestamps from the receive timestamp FIFO into `timestamps`,
+    /// until either the FIFO is empty or `timestamps` is full.
+    ///
+    /// Returns the amount of timestamps that were read.
+    pub fn drain_rx_timestamps(&mut self, timestamps: &mut [RxTimestamp]) -> usize {
+        drain(timestamps, || self.read_rx_timestamp())
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+/// Write `time` to the PTP Time Data register, which must be on the
+/// currently selected page.
+fn write_timestamp<M: Miim, P: Phy<M>>(phy: &mut P, time: PtpTimestamp) {
+    phy.write(PTP_TDR, time.nanoseconds as u16);
+    phy.write(PTP_TDR, (time.nanoseconds >> 16) as u16);
+    phy.write(PTP_TDR, time.seconds as u16);
+    phy.write(PTP_TDR, (time.seconds >> 16) as u16);
+}
+
+/// Read a packet timestamp and its overflow count from the timestamp FIFO
+/// at `address`, which must be on the currently selected page.
+fn read_packet_timestamp<M: Miim, P: Phy<M>>(phy: &mut P, address: u8) -> (PtpTimestamp, u8) {
+    let ns_lo = phy.read(address) as u32;
+    let ns_hi = phy.read(address);
+    let sec_lo = phy.read(address) as u32;
+    let sec_hi = phy.read(address) as u32;
+
+    let timestamp = PtpTimestamp {
+        seconds: (sec_hi << 16) | sec_lo,
+        nanoseconds: (((ns_hi & 0x3FFF) as u32) << 16) | ns_lo,
+    };
+    (timestamp, (ns_hi >> 14) as u8)
+}
+
+/// Fill `buf` with the values returned by `next`, until it returns `None`.
+fn drain<T, F>(buf: &mut [T], mut next: F) -> usize
+where
+    F: FnMut() -> Option<T>,
+{
+    let mut count = 0;
+    for slot in buf.iter_mut() {
+        if let Some(value) = next() {
+            *slot = value;
+            count += 1;
+        } else {
+            break;
+        }
+    }
+    count
+}
+
+impl<MIIM: Miim> Phy<MIIM> for DP83640<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for DP83640<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for DP83640<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for DP83640<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        dp83848::read_and_clear_counters(self)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! DP83640 IEEE 1588 registers
+    //!
+    //! The standard and extended registers are shared with the DP83848, see
+    //! [`crate::phy::dp83848::registers`].
+
+    use bitflags::bitflags;
+
+    use super::PtpMessages;
+
+    /// Register 0x13, the Page Select register (PAGESEL)
+    pub const PAGE_SELECT: u8 = 0x13;
+    /// The extended registers, which are selected by default
+    pub const PAGE_DEFAULT: u16 = 0;
+    /// The IEEE 1588 base registers
+    pub const PAGE_PTP: u16 = 4;
+    /// The IEEE 1588 configuration registers
+    pub const PAGE_PTP_CONFIG: u16 = 5;
+
+    /// Page 4, register 0x15, the PTP Time Data register (PTP_TDR)
+    pub const PTP_TDR: u8 = 0x15;
+    /// Page 4, register 0x1C, the PTP Transmit Timestamp register (PTP_TXTS)
+    pub const PTP_TXTS: u8 = 0x1C;
+    /// Page 4, register 0x1D, the PTP Receive Timestamp register (PTP_RXTS)
+    pub const PTP_RXTS: u8 = 0x1D;
+    /// Page 4, register 0x1F, the PTP Event Data register (PTP_EDATA)
+    pub const PTP_EDATA: u8 = 0x1F;
+    /// Page 5, register 0x17, the PTP Transmit Configuration register 1
+    /// (PTP_TXCFG1), which contains a [`MessageFilter`]
+    pub const PTP_TXCFG1: u8 = 0x17;
+    /// Page 5, register 0x1A, the PTP Receive Configuration register 1
+    /// (PTP_RXCFG1), which contains a [`MessageFilter`]
+    pub const PTP_RXCFG1: u8 = 0x1A;
+
+    bitflags! {
+        /// Page 4, register 0x14, the PTP Control register (PTP_CTL)
+        pub struct PtpCtl: u16 {
+            const TRIG_SEL_MASK = (0b111 << 10);
+            const TRIG_DIS = (1 << 9);
+            const TRIG_EN = (1 << 8);
+            const TRIG_READ = (1 << 7);
+            const TRIG_LOAD = (1 << 6);
+            const PTP_RD_CLK = (1 << 5);
+            const PTP_LOAD_CLK = (1 << 4);
+            const PTP_STEP_CLK = (1 << 3);
+            const PTP_ENABLE = (1 << 2);
+            const PTP_DISABLE = (1 << 1);
+            const PTP_RESET = (1 << 0);
+        }
+
+        /// Page 4, register 0x16, the PTP Status register (PTP_STS)
+        pub struct PtpSts: u16 {
+            const TXTS_RDY = (1 << 11);
+            const RXTS_RDY = (1 << 10);
+            const TRIG_DONE = (1 << 9);
+            const EVENT_RDY = (1 << 8);
+            const TXTS_IE = (1 << 3);
+            const RXTS_IE = (1 << 2);
+            const TRIG_IE = (1 << 1);
+            const EVENT_IE = (1 << 0);
+        }
+
+        /// Page 4, register 0x17, the PTP Trigger Status register (PTP_TSTS)
+        pub struct PtpTsts: u16 {
+            const TRIG7_ACTIVE = (1 << 15);
+            const TRIG7_ERROR = (1 << 14);
+            const TRIG6_ACTIVE = (1 << 13);
+            const TRIG6_ERROR = (1 << 12);
+            const TRIG5_ACTIVE = (1 << 11);
+            const TRIG5_ERROR = (1 << 10);
+            const TRIG4_ACTIVE = (1 << 9);
+            const TRIG4_ERROR = (1 << 8);
+            const TRIG3_ACTIVE = (1 << 7);
+            const TRIG3_ERROR = (1 << 6);
+            const TRIG2_ACTIVE = (1 << 5);
+            const TRIG2_ERROR = (1 << 4);
+            const TRIG1_ACTIVE = (1 << 3);
+            const TRIG1_ERROR = (1 << 2);
+            const TRIG0_ACTIVE = (1 << 1);
+            const TRIG0_ERROR = (1 << 0);
+        }
+
+        /// Page 4, register 0x1E, the PTP Event Status register (PTP_ESTS)
+        pub struct PtpEsts: u16 {
+            const EVNTS_MISSED_MASK = (0b111 << 8);
+            const EVNT_TS_LEN_MASK = (0b11 << 6);
+            /// The event was a rising edge
+            const EVNT_RF = (1 << 5);
+            const EVNT_NUM_MASK = (0b111 << 2);
+            const MULT_EVNT = (1 << 1);
+            const EVENT_DET = (1 << 0);
+        }
+
+        /// Page 5, register 0x14, the PTP Trigger Configuration register (PTP_TRIG)
+        pub struct PtpTrig: u16 {
+            const TRIG_PULSE = (1 << 15);
+            const TRIG_PER = (1 << 14);
+            const TRIG_IF_LATE = (1 << 13);
+            const TRIG_NOTIFY = (1 << 12);
+            const TRIG_GPIO_MASK = (0xF << 8);
+            const TRIG_TOGGLE = (1 << 7);
+            const TRIG_CSEL_MASK = (0b111 << 1);
+            const TRIG_WR = (1 << 0);
+        }
+
+        /// Page 5, register 0x15, the PTP Event Configuration register (PTP_EVNT)
+        pub struct PtpEvnt: u16 {
+            const EVNT_RISE = (1 << 14);
+            const EVNT_FALL = (1 << 13);
+            const EVNT_SINGLE = (1 << 12);
+            const EVNT_GPIO_MASK = (0xF << 8);
+            const EVNT_SEL_MASK = (0b111 << 1);
+            const EVNT_WR = (1 << 0);
+        }
+
+        /// Page 5, register 0x16, the PTP Transmit Configuration register 0 (PTP_TXCFG0)
+        pub struct PtpTxCfg0: u16 {
+            const SYNC_1STEP = (1 << 15);
+            const DR_INSERT = (1 << 13);
+            const NTP_TS_EN = (1 << 12);
+            const IGNORE_2STEP = (1 << 11);
+            const CRC_1STEP = (1 << 10);
+            const CHK_1STEP = (1 << 9);
+            const IP1588_EN = (1 << 8);
+            const TX_L2_EN = (1 << 7);
+            const TX_IPV6_EN = (1 << 6);
+            const TX_IPV4_EN = (1 << 5);
+            const TX_PTP_VER_MASK = (0xF << 1);
+            const TX_TS_EN = (1 << 0);
+        }
+
+        /// Page 5, register 0x19, the PTP Receive Configuration register 0 (PTP_RXCFG0)
+        pub struct PtpRxCfg0: u16 {
+            const DOMAIN_EN = (1 << 15);
+            const ALT_MAST_DIS = (1 << 14);
+            const USER_IP_SEL = (1 << 13);
+            const USER_IP_EN = (1 << 12);
+            const RX_SLAVE = (1 << 11);
+            const IP1588_EN_MASK = (0b111 << 8);
+            const RX_L2_EN = (1 << 7);
+            const RX_IPV6_EN = (1 << 6);
+            const RX_IPV4_EN = (1 << 5);
+            const RX_PTP_VER_MASK = (0xF << 1);
+            const RX_TS_EN = (1 << 0);
+        }
+
+        /// The contents of PTP_TXCFG1 and PTP_RXCFG1, which select the
+        /// messages that are timestamped by matching the first byte of the
+        /// PTP header, which contains the messageType.
+        pub struct MessageFilter: u16 {
+            const BYTE0_MASK = (0xFF << 8);
+            const BYTE0_DATA = (0xFF);
+        }
+    }
+
+    impl PtpSts {
+        pub const ADDRESS: u8 = 0x16;
+    }
+
+    impl PtpTxCfg0 {
+        pub const ADDRESS: u8 = 0x16;
+
+        /// Only timestamp messages with PTP version `version`
+        pub fn version(version: u8) -> Self {
+            Self::from_bits_truncate((version as u16) << 1) & Self::TX_PTP_VER_MASK
+        }
+    }
+
+    impl PtpRxCfg0 {
+        pub const ADDRESS: u8 = 0x19;
+
+        /// Only timestamp messages with PTP version `version`
+        pub fn version(version: u8) -> Self {
+            Self::from_bits_truncate((version as u16) << 1) & Self::RX_PTP_VER_MASK
+        }
+    }
+
+    impl MessageFilter {
+        /// Only match messages for which the bits selected by `mask` in the
+        /// first byte are equal to `data`
+        pub fn new(mask: u8, data: u8) -> Self {
+            Self::from_bits_truncate(((mask as u16) << 8) | data as u16)
+        }
+    }
+
+    impl Default for MessageFilter {
+        fn default() -> Self {
+            Self::empty()
+        }
+    }
+
+    impl From<PtpMessages> for MessageFilter {
+        fn from(messages: PtpMessages) -> Self {
+            // The messageType is stored in the lower nibble of the first byte,
+            // and the event messages have types 0 through 3.
+            match messages {
+                PtpMessages::AllEvents => Self::new(0x0C, 0x00),
+                PtpMessages::Sync => Self::new(0x0F, 0x00),
+                PtpMessages::DelayReq => Self::new(0x0F, 0x01),
+                PtpMessages::PdelayReq => Self::new(0x0F, 0x02),
+                PtpMessages::PdelayResp => Self::new(0x0F, 0x03),
+            }
+        }
+    }
+
+    impl PtpCtl {
+        pub const ADDRESS: u8 = 0x14;
+
+        /// Select `trigger` for a trigger command
+        pub fn trigger(trigger: u8) -> Self {
+            Self::from_bits_truncate((trigger as u16) << 10) & Self::TRIG_SEL_MASK
+        }
+    }
+
+    impl PtpTsts {
+        pub const ADDRESS: u8 = 0x17;
+    }
+
+    impl PtpEsts {
+        pub const ADDRESS: u8 = 0x1E;
+
+        /// The amount of events that were missed
+        pub fn missed(&self) -> u8 {
+            ((*self & Self::EVNTS_MISSED_MASK).bits >> 8) as u8
+        }
+
+        /// The amount of timestamp words, minus one, that are provided in
+        /// the Event Data register
+        pub fn timestamp_len(&self) -> u8 {
+            ((*self & Self::EVNT_TS_LEN_MASK).bits >> 6) as u8
+        }
+
+        /// The event that was detected
+        pub fn event_num(&self) -> u8 {
+            ((*self & Self::EVNT_NUM_MASK).bits >> 2) as u8
+        }
+    }
+
+    impl PtpTrig {
+        pub const ADDRESS: u8 = 0x14;
+
+        /// Select the trigger to configure
+        pub fn select(trigger: u8) -> Self {
+            Self::from_bits_truncate((trigger as u16) << 1) & Self::TRIG_CSEL_MASK
+        }
+
+        /// Select the GPIO that the trigger drives
+        pub fn gpio(gpio: u8) -> Self {
+            Self::from_bits_truncate((gpio as u16) << 8) & Self::TRIG_GPIO_MASK
+        }
+    }
+
+    impl PtpEvnt {
+        pub const ADDRESS: u8 = 0x15;
+
+        /// Select the event to configure
+        pub fn select(event: u8) -> Self {
+            Self::from_bits_truncate((event as u16) << 1) & Self::EVNT_SEL_MASK
+        }
+
+        /// Select the GPIO that the event monitors
+        pub fn gpio(gpio: u8) -> Self {
+            Self::from_bits_truncate((gpio as u16) << 8) & Self::EVNT_GPIO_MASK
+        }
+    }
+}
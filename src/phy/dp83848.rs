@@ -0,0 +1,551 @@
+//! Phy implementation for the Texas Instruments DP83848 10/100 Ethernet PHY
+//!
+//! The DP83848 summarizes the state of the link in the PHY Status register
+//! (PHYSTS), which is decoded into a [`LinkStatus`] by [`DP83848::phy_sts_decoded`].
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{Edcr, Fcscr, LedCr, PhyCr, PhySts, Rbr, RECR};
+
+use super::{
+    AdvancedPhySpeed, LinkCounters, PhySpeed, PhyWithCableDetect, PhyWithCounters,
+    PhyWithEnergyDetect, PhyWithLeds, PhyWithSpeed,
+};
+
+/// The LEDs of the DP83848
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Led {
+    /// The LED_LINK output
+    Link,
+    /// The LED_SPEED output
+    Speed,
+    /// The LED_ACT/COL output
+    Activity,
+}
+
+impl Led {
+    const ALL: [Led; 3] = [Led::Link, Led::Speed, Led::Activity];
+
+    /// The drive enable bit for this LED in the [`LedCr`] register
+    pub fn drive_flag(&self) -> LedCr {
+        match self {
+            Led::Link => LedCr::DRV_LNKLED,
+            Led::Speed => LedCr::DRV_SPDLED,
+            Led::Activity => LedCr::DRV_ACTLED,
+        }
+    }
+
+    /// The value bit for this LED in the [`LedCr`] register
+    pub fn value_flag(&self) -> LedCr {
+        match self {
+            Led::Link => LedCr::LNKLED,
+            Led::Speed => LedCr::SPDLED,
+            Led::Activity => LedCr::ACTLED,
+        }
+    }
+}
+
+/// The function of the LEDs, when they are not driven directly
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedMode {
+    /// LED_LINK indicates link, LED_SPEED indicates 100 Mbit/s and
+    /// LED_ACT/COL indicates activity
+    Mode1,
+    /// LED_LINK indicates link and blinks on activity, LED_SPEED indicates
+    /// 100 Mbit/s and LED_ACT/COL indicates collisions
+    Mode2,
+    /// LED_LINK indicates link and blinks on activity, LED_SPEED indicates
+    /// 100 Mbit/s and LED_ACT/COL indicates full duplex
+    Mode3,
+}
+
+/// The revision of the RMII specification that the PHY adheres to
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RmiiRevision {
+    /// RMII revision 1.0, in which CRS_DV stays asserted until the end of the
+    /// received packet
+    Rev1_0,
+    /// RMII revision 1.2, in which CRS_DV toggles at the end of the received
+    /// packet to indicate deassertion of CRS
+    Rev1_2,
+}
+
+/// The tolerance of the RMII receive elasticity buffer, which determines the
+/// maximum packet length that can be received without errors for a given
+/// reference clock frequency difference
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElasticityBuffer {
+    /// 2 bit tolerance, up to 2400 byte packets
+    Bits2,
+    /// 6 bit tolerance, up to 7200 byte packets
+    Bits6,
+    /// 10 bit tolerance, up to 12000 byte packets
+    Bits10,
+    /// 14 bit tolerance, up to 16800 byte packets
+    Bits14,
+}
+
+/// The decoded contents of the PHY Status register
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LinkStatus {
+    /// Whether a valid link is established
+    pub link_up: bool,
+    /// The speed and duplex mode of the link, if it is up
+    pub speed: Option<PhySpeed>,
+    /// Whether the PHY is operating in MDI-X mode
+    pub mdix: bool,
+    /// Whether an inverted polarity was detected on the receive pair
+    pub polarity_inverted: bool,
+    /// Whether a receive error occurred since the last read of PHYSTS
+    pub receive_error: bool,
+    /// Whether a false carrier event occurred since the last read of PHYSTS
+    pub false_carrier: bool,
+    /// Whether a 100BASE-TX signal is detected
+    pub signal_detect: bool,
+    /// Whether the 100BASE-TX descrambler is locked
+    pub descrambler_locked: bool,
+    /// Whether the link partner signals a remote fault
+    pub remote_fault: bool,
+    /// Whether a jabber condition was detected (10 Mbit/s only)
+    pub jabber: bool,
+    /// Whether autonegotiation has completed
+    pub autoneg_complete: bool,
+    /// Whether loopback mode is enabled
+    pub loopback: bool,
+}
+
+/// A DP83848
+#[derive(Debug)]
+pub struct DP83848<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> DP83848<MIIM> {
+    /// Create a new DP83848 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.phy_sts().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the PHY Status register
+    ///
+    /// Note that the receive error and false carrier indicators in this
+    /// register are latched, and are cleared by reading it.
+    pub fn phy_sts(&mut self) -> PhySts {
+        PhySts::from_bits_truncate(self.read(PhySts::ADDRESS))
+    }
+
+    /// Read and decode the PHY Status register
+    pub fn phy_sts_decoded(&mut self) -> LinkStatus {
+        self.phy_sts().into()
+    }
+
+    /// Read the RMII and Bypass register
+    pub fn rbr(&mut self) -> Rbr {
+        Rbr::from_bits_truncate(self.read(Rbr::ADDRESS))
+    }
+
+    /// Select RMII mode (`true`) or MII mode (`false`)
+    pub fn set_rmii(&mut self, rmii: bool) {
+        let bit = Rbr::RMII_MODE.bits();
+        self.modify(Rbr::ADDRESS, bit, if rmii { bit } else { 0 });
+    }
+
+    /// Get the RMII revision
+    pub fn rmii_revision(&mut self) -> RmiiRevision {
+        if self.rbr().contains(Rbr::RMII_REV1_0) {
+            RmiiRevision::Rev1_0
+        } else {
+            RmiiRevision::Rev1_2
+        }
+    }
+
+    /// Set the RMII revision
+    pub fn set_rmii_revision(&mut self, revision: RmiiRevision) {
+        let bit = Rbr::RMII_REV1_0.bits();
+        let value = match revision {
+            RmiiRevision::Rev1_0 => bit,
+            RmiiRevision::Rev1_2 => 0,
+        };
+        self.modify(Rbr::ADDRESS, bit, value);
+    }
+
+    /// Get the tolerance of the RMII receive elasticity buffer
+    pub fn elasticity_buffer(&mut self) -> ElasticityBuffer {
+        self.rbr().into()
+    }
+
+    /// Set the tolerance of the RMII receive elasticity buffer
+    pub fn set_elasticity_buffer(&mut self, buffer: ElasticityBuffer) {
+        self.modify(
+            Rbr::ADDRESS,
+            Rbr::ELAST_BUF_MASK.bits(),
+            Rbr::from(buffer).bits(),
+        );
+    }
+
+    /// Get the function of the LEDs
+    pub fn led_mode(&mut self) -> LedMode {
+        PhyCr::from_bits_truncate(self.read(PhyCr::ADDRESS)).into()
+    }
+
+    /// Set the function of the LEDs
+    pub fn set_led_mode(&mut self, mode: LedMode) {
+        self.modify(
+            PhyCr::ADDRESS,
+            PhyCr::LED_CNFG_MASK.bits(),
+            PhyCr::from(mode).bits(),
+        );
+    }
+
+    /// Drive `led` on (`Some(true)`) or off (`Some(false)`), or return it to
+    /// the function selected by [`Self::set_led_mode`] (`None`).
+    pub fn drive_led(&mut self, led: Led, state: Option<bool>) {
+        let mask = led.drive_flag() | led.value_flag();
+        let value = match state {
+            Some(true) => mask,
+            Some(false) => led.drive_flag(),
+            None => LedCr::empty(),
+        };
+        self.modify(LedCr::ADDRESS, mask.bits(), value.bits());
+    }
+
+    /// Read the Energy Detect Control register
+    pub fn edcr(&mut self) -> Edcr {
+        Edcr::from_bits_truncate(self.read(Edcr::ADDRESS))
+    }
+
+    /// Enable or disable energy detect mode
+    ///
+    /// While enabled, the PHY automatically powers down when no energy is
+    /// detected on the cable, and powers up again once energy is detected.
+    pub fn set_energy_detect(&mut self, enabled: bool) {
+        let mask = Edcr::ED_EN | Edcr::ED_AUTO_UP | Edcr::ED_AUTO_DOWN;
+        let value = if enabled { mask } else { Edcr::empty() };
+        self.modify(Edcr::ADDRESS, mask.bits(), value.bits());
+    }
+
+    /// Configure the energy detect thresholds
+    ///
+    /// The PHY powers up once `data_count` data energy events (1 to 15) have
+    /// been detected, and powers down once `err_count` energy loss events
+    /// (1 to 15) have been detected. Values outside of that range are
+    /// clamped.
+    pub fn set_energy_detect_thresholds(&mut self, err_count: u8, data_count: u8) {
+        let value = Edcr::thresholds(err_count.clamp(1, 15), data_count.clamp(1, 15));
+        let mask = Edcr::ED_ERR_COUNT_MASK | Edcr::ED_DATA_COUNT_MASK;
+        self.modify(Edcr::ADDRESS, mask.bits(), value.bits());
+    }
+
+    /// Check whether the PHY is powered up by the energy detect logic
+    pub fn energy_detect_powered_up(&mut self) -> bool {
+        self.edcr().contains(Edcr::ED_PWR_STATE)
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for DP83848<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for DP83848<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithLeds<MIIM> for DP83848<MIIM> {
+    fn led_count(&self) -> u8 {
+        Led::ALL.len() as u8
+    }
+
+    fn set_led(&mut self, led: u8, state: Option<bool>) {
+        if let Some(led) = Led::ALL.get(led as usize) {
+            self.drive_led(*led, state);
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithCableDetect<MIIM> for DP83848<MIIM> {
+    fn energy_detected(&mut self) -> bool {
+        self.energy_detect_powered_up()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEnergyDetect<MIIM> for DP83848<MIIM> {
+    fn energy_detect_power_down(&mut self) -> bool {
+        self.edcr().contains(Edcr::ED_EN)
+    }
+
+    fn set_energy_detect_power_down(&mut self, enabled: bool) {
+        self.set_energy_detect(enabled)
+    }
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for DP83848<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        read_and_clear_counters(self)
+    }
+}
+
+/// Read and clear the receive error and false carrier counters.
+///
+/// This is shared with the DP83640, which has the same counter registers.
+pub(crate) fn read_and_clear_counters<M: Miim, P: Phy<M>>(phy: &mut P) -> LinkCounters {
+    // Both counters are cleared by reading them
+    let fcscr = Fcscr::from_bits_truncate(phy.read(Fcscr::ADDRESS));
+
+    LinkCounters {
+        receive_errors: Some(phy.read(RECR)),
+        false_carriers: Some((fcscr & Fcscr::FCSCNT_MASK).bits()),
+        ..Default::default()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! DP83848 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    use super::{ElasticityBuffer, LedMode, LinkStatus};
+
+    /// Register 0x15, the Receive Error Counter register (RECR)
+    pub const RECR: u8 = 0x15;
+
+    bitflags! {
+        /// Register 0x14, the False Carrier Sense Counter register (FCSCR)
+        pub struct Fcscr: u16 {
+            const FCSCNT_MASK = (0xFF);
+        }
+
+        /// Register 0x10, the PHY Status register (PHYSTS)
+        pub struct PhySts: u16 {
+            const MDIX_MODE = (1 << 14);
+            const RECEIVE_ERROR_LATCH = (1 << 13);
+            const POLARITY_STATUS = (1 << 12);
+            const FALSE_CARRIER_SENSE_LATCH = (1 << 11);
+            const SIGNAL_DETECT = (1 << 10);
+            const DESCRAMBLER_LOCK = (1 << 9);
+            const PAGE_RECEIVED = (1 << 8);
+            const MII_INTERRUPT = (1 << 7);
+            const REMOTE_FAULT = (1 << 6);
+            const JABBER_DETECT = (1 << 5);
+            const AUTONEG_COMPLETE = (1 << 4);
+            const LOOPBACK_STATUS = (1 << 3);
+            const DUPLEX_STATUS = (1 << 2);
+            /// The PHY operates at 10 Mbit/s
+            const SPEED_STATUS = (1 << 1);
+            const LINK_STATUS = (1 << 0);
+        }
+
+        /// Register 0x17, the RMII and Bypass register (RBR)
+        pub struct Rbr: u16 {
+            const RMII_MODE = (1 << 5);
+            const RMII_REV1_0 = (1 << 4);
+            const RX_OVF_STS = (1 << 3);
+            const RX_UNF_STS = (1 << 2);
+            const ELAST_BUF_MASK = (0b11);
+        }
+
+        /// Register 0x18, the LED Direct Control register (LEDCR)
+        pub struct LedCr: u16 {
+            const DRV_SPDLED = (1 << 5);
+            const DRV_LNKLED = (1 << 4);
+            const DRV_ACTLED = (1 << 3);
+            const SPDLED = (1 << 2);
+            const LNKLED = (1 << 1);
+            const ACTLED = (1 << 0);
+        }
+
+        /// Register 0x19, the PHY Control register (PHYCR)
+        pub struct PhyCr: u16 {
+            const MDIX_EN = (1 << 15);
+            const FORCE_MDIX = (1 << 14);
+            const PAUSE_RX = (1 << 13);
+            const PAUSE_TX = (1 << 12);
+            const BIST_FE = (1 << 11);
+            const PSR_15 = (1 << 10);
+            const BIST_STATUS = (1 << 9);
+            const BIST_START = (1 << 8);
+            const BP_STRETCH = (1 << 7);
+            const LED_CNFG_MASK = (0b11 << 5);
+            const PHYADDR_MASK = (0b11111);
+        }
+
+        /// Register 0x1D, the Energy Detect Control register (EDCR)
+        pub struct Edcr: u16 {
+            const ED_EN = (1 << 15);
+            const ED_AUTO_UP = (1 << 14);
+            const ED_AUTO_DOWN = (1 << 13);
+            const ED_MAN = (1 << 12);
+            const ED_BURST_DIS = (1 << 11);
+            /// The PHY is powered up
+            const ED_PWR_STATE = (1 << 10);
+            const ED_ERR_MET = (1 << 9);
+            const ED_DATA_MET = (1 << 8);
+            const ED_ERR_COUNT_MASK = (0xF << 4);
+            const ED_DATA_COUNT_MASK = (0xF);
+        }
+    }
+
+    impl Edcr {
+        pub const ADDRESS: u8 = 0x1D;
+
+        /// The energy detect thresholds for `err_count` and `data_count`
+        pub fn thresholds(err_count: u8, data_count: u8) -> Self {
+            let err = ((err_count as u16) << 4) & Self::ED_ERR_COUNT_MASK.bits;
+            let data = (data_count as u16) & Self::ED_DATA_COUNT_MASK.bits;
+            Self::from_bits_truncate(err | data)
+        }
+    }
+
+    impl Fcscr {
+        pub const ADDRESS: u8 = 0x14;
+    }
+
+    impl Rbr {
+        pub const ADDRESS: u8 = 0x17;
+    }
+
+    impl From<Rbr> for ElasticityBuffer {
+        fn from(rbr: Rbr) -> Self {
+            match (rbr & Rbr::ELAST_BUF_MASK).bits() {
+                0b00 => ElasticityBuffer::Bits14,
+                0b01 => ElasticityBuffer::Bits2,
+                0b10 => ElasticityBuffer::Bits6,
+                _ => ElasticityBuffer::Bits10,
+            }
+        }
+    }
+
+    impl From<ElasticityBuffer> for Rbr {
+        fn from(buffer: ElasticityBuffer) -> Self {
+            let elast_buf = match buffer {
+                ElasticityBuffer::Bits14 => 0b00,
+                ElasticityBuffer::Bits2 => 0b01,
+                ElasticityBuffer::Bits6 => 0b10,
+                ElasticityBuffer::Bits10 => 0b11,
+            };
+            Rbr::from_bits_truncate(elast_buf)
+        }
+    }
+
+    impl LedCr {
+        pub const ADDRESS: u8 = 0x18;
+    }
+
+    impl PhyCr {
+        pub const ADDRESS: u8 = 0x19;
+    }
+
+    impl From<PhyCr> for LedMode {
+        fn from(phycr: PhyCr) -> Self {
+            match (phycr & PhyCr::LED_CNFG_MASK).bits() >> 5 {
+                0b00 => LedMode::Mode2,
+                0b10 => LedMode::Mode3,
+                _ => LedMode::Mode1,
+            }
+        }
+    }
+
+    impl From<LedMode> for PhyCr {
+        fn from(mode: LedMode) -> Self {
+            let cnfg = match mode {
+                LedMode::Mode1 => 0b01,
+                LedMode::Mode2 => 0b00,
+                LedMode::Mode3 => 0b10,
+            };
+            PhyCr::from_bits_truncate(cnfg << 5)
+        }
+    }
+
+    impl PhySts {
+        pub const ADDRESS: u8 = 0x10;
+    }
+
+    impl From<PhySts> for Option<PhySpeed> {
+        fn from(sts: PhySts) -> Self {
+            if !sts.contains(PhySts::LINK_STATUS) {
+                return None;
+            }
+
+            let speed = match (
+                sts.contains(PhySts::SPEED_STATUS),
+                sts.contains(PhySts::DUPLEX_STATUS),
+            ) {
+                (true, false) => PhySpeed::HalfDuplexBase10T,
+                (true, true) => PhySpeed::FullDuplexBase10T,
+                (false, false) => PhySpeed::HalfDuplexBase100Tx,
+                (false, true) => PhySpeed::FullDuplexBase100Tx,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl From<PhySts> for LinkStatus {
+        fn from(sts: PhySts) -> Self {
+            LinkStatus {
+                link_up: sts.contains(PhySts::LINK_STATUS),
+                speed: sts.into(),
+                mdix: sts.contains(PhySts::MDIX_MODE),
+                polarity_inverted: sts.contains(PhySts::POLARITY_STATUS),
+                receive_error: sts.contains(PhySts::RECEIVE_ERROR_LATCH),
+                false_carrier: sts.contains(PhySts::FALSE_CARRIER_SENSE_LATCH),
+                signal_detect: sts.contains(PhySts::SIGNAL_DETECT),
+                descrambler_locked: sts.contains(PhySts::DESCRAMBLER_LOCK),
+                remote_fault: sts.contains(PhySts::REMOTE_FAULT),
+                jabber: sts.contains(PhySts::JABBER_DETECT),
+                autoneg_complete: sts.contains(PhySts::AUTONEG_COMPLETE),
+                loopback: sts.contains(PhySts::LOOPBACK_STATUS),
+            }
+        }
+    }
+}
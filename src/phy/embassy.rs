@@ -0,0 +1,57 @@
+//! Async helpers for waiting on a PHY, using [`embassy_time`].
+//!
+//! These helpers poll the PHY and yield to the executor between two polls,
+//! instead of spinning like [`Phy::blocking_reset`] and friends.
+
+use embassy_time::{with_timeout, Duration, TimeoutError, Timer};
+
+use crate::{Miim, Phy};
+
+/// The time between two polls of the PHY
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Reset `phy`, and wait until the reset has completed.
+///
+/// Returns an error if the reset did not complete within `timeout`.
+pub async fn reset_with_timeout<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    timeout: Duration,
+) -> Result<(), TimeoutError> {
+    phy.reset();
+    with_timeout(timeout, async {
+        while phy.is_resetting() {
+            Timer::after(POLL_INTERVAL).await;
+        }
+    })
+    .await
+}
+
+/// Wait until `phy` reports its link as being up.
+///
+/// Returns an error if the link did not come up within `timeout`.
+pub async fn wait_for_link<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    timeout: Duration,
+) -> Result<(), TimeoutError> {
+    with_timeout(timeout, async {
+        while !phy.phy_link_up() {
+            Timer::after(POLL_INTERVAL).await;
+        }
+    })
+    .await
+}
+
+/// Wait until `phy` reports that autonegotiation has completed.
+///
+/// Returns an error if autonegotiation did not complete within `timeout`.
+pub async fn wait_autoneg_complete<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    timeout: Duration,
+) -> Result<(), TimeoutError> {
+    with_timeout(timeout, async {
+        while !phy.autoneg_completed() {
+            Timer::after(POLL_INTERVAL).await;
+        }
+    })
+    .await
+}
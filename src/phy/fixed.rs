@@ -0,0 +1,161 @@
+//! An emulated PHY with a fixed link, for links that do not have a PHY,
+//! such as MAC-to-MAC links.
+
+use crate::{
+    registers::{Bcr, Bsr, Esr},
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use super::{AdvancedPhySpeed, PhyWithSpeed};
+
+/// A [`Miim`] that emulates the standard registers of a PHY with a fixed
+/// link.
+///
+/// All writes are ignored, and all registers other than the BCR, BSR and
+/// ESR read as 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedMiim {
+    speed: AdvancedPhySpeed,
+    link_up: bool,
+}
+
+impl FixedMiim {
+    fn full_duplex(&self) -> bool {
+        matches!(
+            self.speed,
+            AdvancedPhySpeed::FullDuplexBase10T
+                | AdvancedPhySpeed::FullDuplexBase100Tx
+                | AdvancedPhySpeed::FullDuplexBase1000T
+                | AdvancedPhySpeed::FullDuplexBase1000Tx
+        )
+    }
+
+    fn bcr(&self) -> Bcr {
+        let mut bcr = match self.speed {
+            AdvancedPhySpeed::HalfDuplexBase10T | AdvancedPhySpeed::FullDuplexBase10T => {
+                Bcr::empty()
+            }
+            AdvancedPhySpeed::HalfDuplexBase100Tx | AdvancedPhySpeed::FullDuplexBase100Tx => {
+                Bcr::SPEED_SEL_LSB
+            }
+            _ => Bcr::SPEED_SEL_MSB,
+        };
+        bcr.set_full_duplex(self.full_duplex());
+        bcr
+    }
+
+    fn bsr(&self) -> Bsr {
+        let mut bsr = match self.speed {
+            AdvancedPhySpeed::HalfDuplexBase10T => Bsr::_10MBPSHD,
+            AdvancedPhySpeed::FullDuplexBase10T => Bsr::_10MPBSFD,
+            AdvancedPhySpeed::HalfDuplexBase100Tx => Bsr::_100BASEXHD,
+            AdvancedPhySpeed::FullDuplexBase100Tx => Bsr::_100BASEXFD,
+            _ => Bsr::EXTENDED_STATUS,
+        };
+
+        if self.link_up {
+            bsr |= Bsr::LINK_STATUS | Bsr::AUTONEG_COMPLETE;
+        }
+
+        bsr
+    }
+
+    fn esr(&self) -> Esr {
+        match self.speed {
+            AdvancedPhySpeed::HalfDuplexBase1000T => Esr::_1000BASETHD,
+            AdvancedPhySpeed::FullDuplexBase1000T => Esr::_1000BASETFD,
+            AdvancedPhySpeed::HalfDuplexBase1000Tx => Esr::_1000BASEXHD,
+            AdvancedPhySpeed::FullDuplexBase1000Tx => Esr::_1000BASEXFD,
+            _ => Esr::empty(),
+        }
+    }
+}
+
+impl Miim for FixedMiim {
+    fn read(&mut self, _phy: u8, reg: u8) -> u16 {
+        match reg {
+            Bcr::ADDRESS => self.bcr().bits(),
+            Bsr::ADDRESS => self.bsr().bits(),
+            Esr::ADDRESS => self.esr().bits(),
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, _phy: u8, _reg: u8, _data: u16) {}
+}
+
+/// An emulated PHY that reports a fixed link speed and link state.
+///
+/// This can be used for links that do not have a PHY, such as MAC-to-MAC
+/// links, so that they can be handled in the same way as links that do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPhy {
+    miim: FixedMiim,
+}
+
+impl FixedPhy {
+    /// Create a new fixed PHY, reporting a link at `speed` that is up if
+    /// `link_up` is `true`.
+    pub fn new(speed: AdvancedPhySpeed, link_up: bool) -> Self {
+        Self {
+            miim: FixedMiim { speed, link_up },
+        }
+    }
+
+    /// The speed that this PHY reports
+    pub fn speed(&self) -> AdvancedPhySpeed {
+        self.miim.speed
+    }
+
+    /// Change the speed that this PHY reports
+    pub fn set_speed(&mut self, speed: AdvancedPhySpeed) {
+        self.miim.speed = speed;
+    }
+
+    /// Whether this PHY reports the link as up
+    pub fn link_up(&self) -> bool {
+        self.miim.link_up
+    }
+
+    /// Change whether this PHY reports the link as up
+    pub fn set_link_up(&mut self, link_up: bool) {
+        self.miim.link_up = link_up;
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.miim.link_up
+    }
+}
+
+impl Phy<FixedMiim> for FixedPhy {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        let mut ana = AutoNegotiationAdvertisement::default();
+        match self.miim.speed {
+            AdvancedPhySpeed::HalfDuplexBase10T => ana.hd_10base_t = true,
+            AdvancedPhySpeed::FullDuplexBase10T => ana.fd_10base_t = true,
+            AdvancedPhySpeed::HalfDuplexBase100Tx => ana.hd_100base_tx = true,
+            AdvancedPhySpeed::FullDuplexBase100Tx => ana.fd_100base_tx = true,
+            _ => {}
+        }
+        ana
+    }
+
+    fn get_miim(&mut self) -> &mut FixedMiim {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        PhyAddress::from_const(0)
+    }
+}
+
+impl PhyWithSpeed<FixedMiim> for FixedPhy {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        if self.miim.link_up {
+            Some(self.miim.speed)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Phy implementation for the ICS1894 10/100 Ethernet PHY
+//!
+//! The ICS1894 summarizes its link state in the QuickPoll Detailed Status
+//! register, which makes it possible to read the speed, duplex mode and
+//! link quality indicators with a single register access.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::QuickPollStatus;
+
+use super::{AdvancedPhySpeed, PhySpeed, PhyWithSpeed};
+
+/// An ICS1894
+#[derive(Debug)]
+pub struct ICS1894<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> ICS1894<MIIM> {
+    /// Create a new ICS1894 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.quick_poll_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the QuickPoll Detailed Status register
+    ///
+    /// Note that the error indicators in this register are latched, and are
+    /// cleared by reading it.
+    pub fn quick_poll_status(&mut self) -> QuickPollStatus {
+        QuickPollStatus::from_bits_truncate(self.read(QuickPollStatus::ADDRESS))
+    }
+
+    /// Read the link quality indicators
+    ///
+    /// The returned value only contains the error indicators of the
+    /// QuickPoll Detailed Status register, i.e. it is empty if no errors were
+    /// detected since the last read of that register.
+    pub fn link_quality_errors(&mut self) -> QuickPollStatus {
+        self.quick_poll_status() & QuickPollStatus::ERROR_MASK
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for ICS1894<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for ICS1894<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! ICS1894 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    bitflags! {
+        /// Register 17, the QuickPoll Detailed Status register
+        pub struct QuickPollStatus: u16 {
+            /// The PHY operates at 100 Mbit/s
+            const DATA_RATE_100 = (1 << 15);
+            const FULL_DUPLEX = (1 << 14);
+            const AUTONEG_PROGRESS_MASK = (0b11 << 12);
+            const SIGNAL_LOST_100BASE_TX = (1 << 11);
+            const PLL_LOCK_ERROR_100BASE_TX = (1 << 10);
+            const FALSE_CARRIER = (1 << 9);
+            const INVALID_SYMBOL = (1 << 8);
+            const HALT_SYMBOL = (1 << 7);
+            const PREMATURE_END = (1 << 6);
+            const AUTONEG_COMPLETE = (1 << 5);
+            const JABBER = (1 << 3);
+            const REMOTE_FAULT = (1 << 2);
+            const LINK_STATUS = (1 << 1);
+            const ERROR_MASK = Self::SIGNAL_LOST_100BASE_TX.bits
+                | Self::PLL_LOCK_ERROR_100BASE_TX.bits
+                | Self::FALSE_CARRIER.bits
+                | Self::INVALID_SYMBOL.bits
+                | Self::HALT_SYMBOL.bits
+                | Self::PREMATURE_END.bits
+                | Self::JABBER.bits
+                | Self::REMOTE_FAULT.bits;
+        }
+    }
+
+    impl QuickPollStatus {
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl From<QuickPollStatus> for Option<PhySpeed> {
+        fn from(status: QuickPollStatus) -> Self {
+            if !status.contains(QuickPollStatus::LINK_STATUS) {
+                return None;
+            }
+
+            let speed = match (
+                status.contains(QuickPollStatus::DATA_RATE_100),
+                status.contains(QuickPollStatus::FULL_DUPLEX),
+            ) {
+                (false, false) => PhySpeed::HalfDuplexBase10T,
+                (false, true) => PhySpeed::FullDuplexBase10T,
+                (true, false) => PhySpeed::HalfDuplexBase100Tx,
+                (true, true) => PhySpeed::FullDuplexBase100Tx,
+            };
+
+            Some(speed)
+        }
+    }
+}
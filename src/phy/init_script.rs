@@ -0,0 +1,181 @@
+//! Declarative initialization sequences.
+//!
+//! Many PHYs require a fixed sequence of register accesses to be brought up
+//! correctly. An [`InitScript`] describes such a sequence as a slice of
+//! [`InitOp`]s, so that it can be written down like it appears in the
+//! datasheet:
+//!
+//! ```
+//! use ieee802_3_miim::phy::{InitOp, InitScript};
+//!
+//! const INIT: InitScript = InitScript::new(&[
+//!     // Reset the PHY, and wait for the reset to complete
+//!     InitOp::Modify { reg: 0x00, mask: 0x8000, value: 0x8000 },
+//!     InitOp::PollUntil { reg: 0x00, mask: 0x8000, value: 0, timeout_us: 500_000, interval_us: 1_000 },
+//!     // Vendor specific tuning
+//!     InitOp::Write { reg: 0x1F, value: 0x0007 },
+//!     InitOp::Delay { us: 100 },
+//! ]);
+//! ```
+
+use crate::{Miim, Phy};
+
+/// A single step of an [`InitScript`]
+///
+/// The available steps depend on the enabled features, so this enum is
+/// non-exhaustive.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InitOp {
+    /// Write `value` to `reg`
+    Write {
+        /// The register address
+        reg: u8,
+        /// The value to write
+        value: u16,
+    },
+    /// Modify the bits selected by `mask` in `reg`, leaving all other bits
+    /// untouched
+    Modify {
+        /// The register address
+        reg: u8,
+        /// The bits to modify
+        mask: u16,
+        /// The new value of the bits selected by `mask`
+        value: u16,
+    },
+    /// Wait until the bits selected by `mask` in `reg` are equal to `value`
+    PollUntil {
+        /// The register address
+        reg: u8,
+        /// The bits to compare
+        mask: u16,
+        /// The expected value of the bits selected by `mask`
+        value: u16,
+        /// The time after which to give up, in microseconds
+        timeout_us: u32,
+        /// The time between two reads, in microseconds
+        interval_us: u32,
+    },
+    /// Wait for `us` microseconds
+    Delay {
+        /// The time to wait, in microseconds
+        us: u32,
+    },
+    /// Write `value` to an MMD register
+    #[cfg(feature = "mmd")]
+    MmdWrite {
+        /// The MMD device address
+        device: u8,
+        /// The register address
+        reg: u16,
+        /// The value to write
+        value: u16,
+    },
+    /// Modify the bits selected by `mask` in an MMD register, leaving all
+    /// other bits untouched
+    #[cfg(feature = "mmd")]
+    MmdModify {
+        /// The MMD device address
+        device: u8,
+        /// The register address
+        reg: u16,
+        /// The bits to modify
+        mask: u16,
+        /// The new value of the bits selected by `mask`
+        value: u16,
+    },
+}
+
+/// An error that occurs while running an [`InitScript`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitScriptError {
+    /// The [`InitOp::PollUntil`] at index `step` timed out
+    Timeout {
+        /// The index of the step in the script
+        step: usize,
+        /// The last value that was read
+        value: u16,
+    },
+}
+
+/// A sequence of [`InitOp`]s
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InitScript<'a> {
+    ops: &'a [InitOp],
+}
+
+impl<'a> InitScript<'a> {
+    /// Create a new script that performs `ops` in order
+    pub const fn new(ops: &'a [InitOp]) -> Self {
+        Self { ops }
+    }
+
+    /// The steps of this script
+    pub const fn ops(&self) -> &'a [InitOp] {
+        self.ops
+    }
+
+    /// Run this script on `phy`, using `delay_us` to wait for the given
+    /// amount of microseconds.
+    pub fn run_with<M, P, D>(&self, phy: &mut P, mut delay_us: D) -> Result<(), InitScriptError>
+    where
+        M: Miim,
+        P: Phy<M>,
+        D: FnMut(u32),
+    {
+        for (step, op) in self.ops.iter().enumerate() {
+            match *op {
+                InitOp::Write { reg, value } => phy.write(reg, value),
+                InitOp::Modify { reg, mask, value } => phy.modify(reg, mask, value),
+                InitOp::PollUntil {
+                    reg,
+                    mask,
+                    value,
+                    timeout_us,
+                    interval_us,
+                } => {
+                    let mut waited = 0;
+                    loop {
+                        let read = phy.read(reg);
+                        if read & mask == value & mask {
+                            break;
+                        }
+
+                        if waited >= timeout_us {
+                            return Err(InitScriptError::Timeout { step, value: read });
+                        }
+
+                        delay_us(interval_us);
+                        waited = waited.saturating_add(interval_us.max(1));
+                    }
+                }
+                InitOp::Delay { us } => delay_us(us),
+                #[cfg(feature = "mmd")]
+                InitOp::MmdWrite { device, reg, value } => phy.mmd_write(device, reg, value),
+                #[cfg(feature = "mmd")]
+                InitOp::MmdModify {
+                    device,
+                    reg,
+                    mask,
+                    value,
+                } => phy.mmd_modify(device, reg, mask, value),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run this script on `phy`, using `delay` to wait.
+    #[cfg(feature = "embedded-hal")]
+    pub fn run<M, P, D>(&self, phy: &mut P, delay: &mut D) -> Result<(), InitScriptError>
+    where
+        M: Miim,
+        P: Phy<M>,
+        D: embedded_hal::delay::DelayNs,
+    {
+        self.run_with(phy, |us| delay.delay_us(us))
+    }
+}
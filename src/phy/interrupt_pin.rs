@@ -0,0 +1,101 @@
+//! Helpers for handling the interrupt output of a PHY that is connected
+//! to an [`InputPin`].
+
+use embedded_hal::digital::InputPin;
+#[cfg(feature = "async")]
+use embedded_hal_async::digital::Wait;
+
+use crate::Miim;
+
+use super::PhyWithInterrupts;
+#[cfg(feature = "async")]
+use super::{AdvancedPhySpeed, PhyWithAutonegInterrupt, PhyWithSpeed};
+
+/// Block until `pin` signals an interrupt, and then read and clear the
+/// interrupts of `phy`.
+///
+/// The interrupt output is asserted while `pin` is high if `active_high` is
+/// `true`, and while it is low otherwise.
+pub fn wait_for_interrupt<M, P, I>(
+    phy: &mut P,
+    pin: &mut I,
+    active_high: bool,
+) -> Result<P::Interrupts, I::Error>
+where
+    M: Miim,
+    P: PhyWithInterrupts<M>,
+    I: InputPin,
+{
+    loop {
+        let asserted = if active_high {
+            pin.is_high()?
+        } else {
+            pin.is_low()?
+        };
+
+        if asserted {
+            break;
+        }
+    }
+
+    Ok(phy.read_and_clear_interrupts())
+}
+
+/// Wait until `pin` signals an interrupt, and then read and clear the
+/// interrupts of `phy`.
+///
+/// The interrupt output is asserted while `pin` is high if `active_high` is
+/// `true`, and while it is low otherwise.
+#[cfg(feature = "async")]
+pub async fn wait_for_interrupt_async<M, P, W>(
+    phy: &mut P,
+    pin: &mut W,
+    active_high: bool,
+) -> Result<P::Interrupts, W::Error>
+where
+    M: Miim,
+    P: PhyWithInterrupts<M>,
+    W: Wait,
+{
+    if active_high {
+        pin.wait_for_high().await?;
+    } else {
+        pin.wait_for_low().await?;
+    }
+
+    Ok(phy.read_and_clear_interrupts())
+}
+
+/// Wait until autonegotiation of `phy` has completed, using the
+/// autonegotiation complete interrupt that is signalled on `pin`, and return
+/// the resolved link speed.
+///
+/// The autonegotiation complete interrupt is enabled, and left enabled when
+/// this function returns. Every time `pin` signals an interrupt, all pending
+/// interrupts of `phy` are read and cleared, including interrupts other than
+/// the autonegotiation complete interrupt.
+///
+/// The interrupt output is asserted while `pin` is high if `active_high` is
+/// `true`, and while it is low otherwise.
+#[cfg(feature = "async")]
+pub async fn wait_autoneg_complete_irq<M, P, W>(
+    phy: &mut P,
+    pin: &mut W,
+    active_high: bool,
+) -> Result<Option<AdvancedPhySpeed>, W::Error>
+where
+    M: Miim,
+    P: PhyWithAutonegInterrupt<M> + PhyWithSpeed<M>,
+    W: Wait,
+{
+    phy.set_autoneg_complete_interrupt(true);
+    // Clear stale interrupts, so that the pin is deasserted
+    phy.read_and_clear_interrupts();
+
+    // Autonegotiation may have completed before the interrupt was enabled
+    while !phy.autoneg_completed() {
+        wait_for_interrupt_async(phy, pin, active_high).await?;
+    }
+
+    Ok(phy.get_link_speed())
+}
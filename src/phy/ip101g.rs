@@ -0,0 +1,308 @@
+//! Phy implementation for the IC Plus IP101G 10/100 Ethernet PHY
+//!
+//! The vendor specific registers of the IP101G are located on pages, which
+//! are selected through register 20. Page 16 is selected by default, and
+//! contains the most commonly used registers.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    DigitalIoControl, InterruptRegister, PhySpecificStatus, SpecialControlStatus, PAGE_DEFAULT,
+    PAGE_SELECT,
+};
+
+use super::{AdvancedPhySpeed, PhySpeed, PhyWithInterrupts, PhyWithPages, PhyWithSpeed};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The link status changed
+    LinkChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// The link speed changed
+    SpeedChange,
+}
+
+impl Interrupt {
+    /// The mask bit for this interrupt in the [`InterruptRegister`]
+    pub fn mask_flag(&self) -> InterruptRegister {
+        match self {
+            Interrupt::LinkChange => InterruptRegister::MASK_LINK_CHANGE,
+            Interrupt::DuplexChange => InterruptRegister::MASK_DUPLEX_CHANGE,
+            Interrupt::SpeedChange => InterruptRegister::MASK_SPEED_CHANGE,
+        }
+    }
+
+    /// The status bit for this interrupt in the [`InterruptRegister`]
+    pub fn status_flag(&self) -> InterruptRegister {
+        match self {
+            Interrupt::LinkChange => InterruptRegister::LINK_CHANGE,
+            Interrupt::DuplexChange => InterruptRegister::DUPLEX_CHANGE,
+            Interrupt::SpeedChange => InterruptRegister::SPEED_CHANGE,
+        }
+    }
+}
+
+/// The function of the RXER/INTR_32 pin, which is only available on the
+/// 32-pin package
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Intr32Function {
+    /// The pin is the RX error output
+    RxError,
+    /// The pin is the interrupt output
+    Interrupt,
+}
+
+/// An IP101G
+#[derive(Debug)]
+pub struct IP101G<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> IP101G<MIIM> {
+    /// Create a new IP101G at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.phy_specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn phy_specific_status(&mut self) -> PhySpecificStatus {
+        PhySpecificStatus::from_bits_truncate(
+            self.read_paged(PAGE_DEFAULT, PhySpecificStatus::ADDRESS),
+        )
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        self.page_scoped(page).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    /// Enable or disable auto power saving
+    ///
+    /// While enabled, the PHY enters a low power mode when no cable is
+    /// connected.
+    pub fn set_auto_power_saving(&mut self, enabled: bool) {
+        let bit = SpecialControlStatus::AUTO_POWER_SAVING.bits();
+        self.modify_paged(
+            PAGE_DEFAULT,
+            SpecialControlStatus::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Select the function of the RXER/INTR_32 pin
+    pub fn set_intr32_function(&mut self, function: Intr32Function) {
+        let bit = DigitalIoControl::SEL_INTR32.bits();
+        let value = match function {
+            Intr32Function::RxError => 0,
+            Intr32Function::Interrupt => bit,
+        };
+        self.modify_paged(PAGE_DEFAULT, DigitalIoControl::ADDRESS, bit, value);
+    }
+
+    /// Enable an interrupt
+    ///
+    /// This also enables the interrupt output. Note that this reads the
+    /// interrupt register, which clears all pending interrupts.
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let mask = (interrupt.mask_flag()
+            | InterruptRegister::MASK_ALL
+            | InterruptRegister::INTR_PIN_USED)
+            .bits();
+        self.modify_paged(
+            PAGE_DEFAULT,
+            InterruptRegister::ADDRESS,
+            mask,
+            InterruptRegister::INTR_PIN_USED.bits(),
+        );
+    }
+
+    /// Disable an interrupt
+    ///
+    /// Note that this reads the interrupt register, which clears all pending
+    /// interrupts.
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let mask = interrupt.mask_flag().bits();
+        self.modify_paged(PAGE_DEFAULT, InterruptRegister::ADDRESS, mask, mask);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptRegister::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptRegister {
+        InterruptRegister::from_bits_truncate(
+            self.read_paged(PAGE_DEFAULT, InterruptRegister::ADDRESS),
+        )
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for IP101G<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for IP101G<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for IP101G<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for IP101G<MIIM> {
+    type Interrupts = InterruptRegister;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptRegister {
+        self.read_and_clear_interrupts()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! IP101G registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    use super::Interrupt;
+
+    /// Register 20, the Page Select register
+    pub const PAGE_SELECT: u8 = 20;
+    /// The page that is selected after a reset
+    pub const PAGE_DEFAULT: u16 = 16;
+
+    bitflags! {
+        /// Register 16 on page 16, the Special Control and Status register
+        pub struct SpecialControlStatus: u16 {
+            const RMII_V10 = (1 << 5);
+            const RMII_V12 = (1 << 4);
+            const AUTO_POWER_SAVING = (1 << 1);
+        }
+
+        /// Register 17 on page 16, the Interrupt Control and Status register
+        pub struct InterruptRegister: u16 {
+            const INTR_PIN_USED = (1 << 15);
+            const MASK_ALL = (1 << 11);
+            const MASK_SPEED_CHANGE = (1 << 10);
+            const MASK_DUPLEX_CHANGE = (1 << 9);
+            const MASK_LINK_CHANGE = (1 << 8);
+            const INTERRUPT_STATUS = (1 << 3);
+            const SPEED_CHANGE = (1 << 2);
+            const DUPLEX_CHANGE = (1 << 1);
+            const LINK_CHANGE = (1 << 0);
+        }
+
+        /// Register 29 on page 16, the Digital IO Specific Control register
+        pub struct DigitalIoControl: u16 {
+            /// Use the RXER/INTR_32 pin as interrupt output
+            const SEL_INTR32 = (1 << 2);
+        }
+
+        /// Register 30 on page 16, the PHY Specific Status register
+        pub struct PhySpecificStatus: u16 {
+            const AUTO_MDIX_DISABLE = (1 << 11);
+            const MDIX = (1 << 8);
+            const OP_MODE_MASK = (0b111);
+        }
+    }
+
+    impl SpecialControlStatus {
+        pub const ADDRESS: u8 = 16;
+    }
+
+    impl InterruptRegister {
+        pub const ADDRESS: u8 = 17;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.status_flag())
+        }
+    }
+
+    impl DigitalIoControl {
+        pub const ADDRESS: u8 = 29;
+    }
+
+    impl PhySpecificStatus {
+        pub const ADDRESS: u8 = 30;
+    }
+
+    impl From<PhySpecificStatus> for Option<PhySpeed> {
+        fn from(status: PhySpecificStatus) -> Self {
+            let speed = match (status & PhySpecificStatus::OP_MODE_MASK).bits() {
+                0b001 => PhySpeed::HalfDuplexBase10T,
+                0b010 => PhySpeed::HalfDuplexBase100Tx,
+                0b101 => PhySpeed::FullDuplexBase10T,
+                0b110 => PhySpeed::FullDuplexBase100Tx,
+                // Autonegotiation is in progress, or the PHY is isolated
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
@@ -0,0 +1,502 @@
+//! Phy implementation for the Microchip KSZ8051 (KSZ8051MNL, KSZ8051RNL)
+
+use crate::{
+    registers::Esr, AutoNegotiationAdvertisement, ExtendedPhyStatus, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{
+    ExpandedControl, InterruptControlStatus, OperationModeStrapOverride, OperationModeStrapStatus,
+    PhyControl1, PhyControl2,
+};
+
+use super::{
+    AdvancedPhySpeed, PhySpeed, PhyWithCableDetect, PhyWithEnergyDetect, PhyWithInterrupts,
+    PhyWithSpeed,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The link came up
+    LinkUp,
+    /// A remote fault occurred
+    RemoteFault,
+    /// The link went down
+    LinkDown,
+    /// The link partner acknowledged a page
+    LinkPartnerAck,
+    /// A fault occurred during parallel detection
+    ParallelDetectFault,
+    /// A page was received during auto negotiation
+    PageReceived,
+    /// A receive error occurred
+    ReceiveError,
+    /// Jabber was detected
+    Jabber,
+}
+
+impl Interrupt {
+    /// The enable bit for this interrupt in the [`InterruptControlStatus`] register
+    pub fn enable_flag(&self) -> InterruptControlStatus {
+        match self {
+            Interrupt::LinkUp => InterruptControlStatus::LINK_UP_IE,
+            Interrupt::RemoteFault => InterruptControlStatus::REMOTE_FAULT_IE,
+            Interrupt::LinkDown => InterruptControlStatus::LINK_DOWN_IE,
+            Interrupt::LinkPartnerAck => InterruptControlStatus::LINK_PARTNER_ACK_IE,
+            Interrupt::ParallelDetectFault => InterruptControlStatus::PARALLEL_DETECT_FAULT_IE,
+            Interrupt::PageReceived => InterruptControlStatus::PAGE_RECEIVED_IE,
+            Interrupt::ReceiveError => InterruptControlStatus::RECEIVE_ERROR_IE,
+            Interrupt::Jabber => InterruptControlStatus::JABBER_IE,
+        }
+    }
+
+    /// The status bit for this interrupt in the [`InterruptControlStatus`] register
+    pub fn status_flag(&self) -> InterruptControlStatus {
+        match self {
+            Interrupt::LinkUp => InterruptControlStatus::LINK_UP,
+            Interrupt::RemoteFault => InterruptControlStatus::REMOTE_FAULT,
+            Interrupt::LinkDown => InterruptControlStatus::LINK_DOWN,
+            Interrupt::LinkPartnerAck => InterruptControlStatus::LINK_PARTNER_ACK,
+            Interrupt::ParallelDetectFault => InterruptControlStatus::PARALLEL_DETECT_FAULT,
+            Interrupt::PageReceived => InterruptControlStatus::PAGE_RECEIVED,
+            Interrupt::ReceiveError => InterruptControlStatus::RECEIVE_ERROR,
+            Interrupt::Jabber => InterruptControlStatus::JABBER,
+        }
+    }
+}
+
+/// A KSZ8051.
+///
+/// The MII (KSZ8051MNL) and RMII (KSZ8051RNL) variants share the same
+/// register set, and are both supported by this driver.
+#[derive(Debug)]
+pub struct KSZ8051<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> KSZ8051<MIIM> {
+    /// Create a new KSZ8051 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.phy_control1().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the Operation Mode Strap Status register
+    pub fn strap_status(&mut self) -> OperationModeStrapStatus {
+        strap_status(self)
+    }
+
+    /// Read the Operation Mode Strap Override register
+    pub fn strap_override(&mut self) -> OperationModeStrapOverride {
+        strap_override(self)
+    }
+
+    /// Read the PHY Control 1 register
+    pub fn phy_control1(&mut self) -> PhyControl1 {
+        phy_control1(self)
+    }
+
+    /// Read the PHY Control 2 register
+    pub fn phy_control2(&mut self) -> PhyControl2 {
+        phy_control2(self)
+    }
+
+    /// Modify the PHY Control 2 register
+    pub fn modify_phy_control2<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut PhyControl2),
+    {
+        modify_phy_control2(self, f)
+    }
+
+    /// Enable an interrupt
+    ///
+    /// Note that this reads the interrupt control/status register, which clears
+    /// all pending interrupts.
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        enable_interrupt(self, interrupt)
+    }
+
+    /// Disable an interrupt
+    ///
+    /// Note that this reads the interrupt control/status register, which clears
+    /// all pending interrupts.
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        disable_interrupt(self, interrupt)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptControlStatus::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptControlStatus {
+        read_and_clear_interrupts(self)
+    }
+
+    /// Configure the polarity of the interrupt pin.
+    ///
+    /// If `active_high` is false (the default), the pin is active low.
+    pub fn set_interrupt_active_high(&mut self, active_high: bool) {
+        self.modify_phy_control2(|ctrl2| ctrl2.set(PhyControl2::INTERRUPT_LEVEL, active_high));
+    }
+
+    /// Enable or disable Energy Detect Power-Down mode
+    pub fn set_edpd(&mut self, enabled: bool) {
+        set_edpd(self, enabled)
+    }
+
+    /// Check whether Energy Detect Power-Down mode is enabled
+    pub fn edpd_enabled(&mut self) -> bool {
+        edpd_enabled(self)
+    }
+
+    /// Enable or disable power saving mode
+    pub fn set_power_saving(&mut self, enabled: bool) {
+        set_power_saving(self, enabled)
+    }
+
+    /// Check whether energy is detected on the receive input
+    pub fn energy_detected(&mut self) -> bool {
+        energy_detected(self)
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for KSZ8051<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+
+    fn honors_broadcast(&mut self) -> bool {
+        honors_broadcast(self)
+    }
+
+    fn esr(&mut self) -> Option<Esr> {
+        None
+    }
+
+    fn extended_status(&mut self) -> Option<ExtendedPhyStatus> {
+        None
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for KSZ8051<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithCableDetect<MIIM> for KSZ8051<MIIM> {
+    fn energy_detected(&mut self) -> bool {
+        self.energy_detected()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEnergyDetect<MIIM> for KSZ8051<MIIM> {
+    fn energy_detect_power_down(&mut self) -> bool {
+        self.edpd_enabled()
+    }
+
+    fn set_energy_detect_power_down(&mut self, enabled: bool) {
+        self.set_edpd(enabled)
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for KSZ8051<MIIM> {
+    type Interrupts = InterruptControlStatus;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptControlStatus {
+        self.read_and_clear_interrupts()
+    }
+}
+
+// The following functions are shared by the KSZ8051 and the KSZ8081, which use the same
+// register map.
+
+/// Read the Operation Mode Strap Status register
+pub(crate) fn strap_status<M: Miim, P: Phy<M>>(phy: &mut P) -> OperationModeStrapStatus {
+    OperationModeStrapStatus::from_bits_truncate(phy.read(OperationModeStrapStatus::ADDRESS))
+}
+
+/// Read the Operation Mode Strap Override register
+pub(crate) fn strap_override<M: Miim, P: Phy<M>>(phy: &mut P) -> OperationModeStrapOverride {
+    OperationModeStrapOverride::from_bits_truncate(phy.read(OperationModeStrapOverride::ADDRESS))
+}
+
+/// Check whether address 0 is neither strapped nor overridden off as the
+/// broadcast address
+pub(crate) fn honors_broadcast<M: Miim, P: Phy<M>>(phy: &mut P) -> bool {
+    let strapped_off = strap_status(phy).contains(OperationModeStrapStatus::BROADCAST_OFF);
+    let overridden_off = strap_override(phy).contains(OperationModeStrapOverride::BROADCAST_OFF);
+    !strapped_off && !overridden_off
+}
+
+/// Read the PHY Control 1 register
+pub(crate) fn phy_control1<M: Miim, P: Phy<M>>(phy: &mut P) -> PhyControl1 {
+    PhyControl1::from_bits_truncate(phy.read(PhyControl1::ADDRESS))
+}
+
+/// Read the PHY Control 2 register
+pub(crate) fn phy_control2<M: Miim, P: Phy<M>>(phy: &mut P) -> PhyControl2 {
+    PhyControl2::from_bits_truncate(phy.read(PhyControl2::ADDRESS))
+}
+
+/// Modify the PHY Control 2 register
+pub(crate) fn modify_phy_control2<M: Miim, P: Phy<M>, F>(phy: &mut P, f: F)
+where
+    F: FnOnce(&mut PhyControl2),
+{
+    let raw = phy.read(PhyControl2::ADDRESS);
+    let ctrl2 = &mut PhyControl2::from_bits_truncate(raw);
+    f(ctrl2);
+    // Preserve the bits that are not described by `PhyControl2`
+    let reserved = raw & !PhyControl2::all().bits();
+    phy.write(PhyControl2::ADDRESS, reserved | ctrl2.bits());
+}
+
+/// Read and clear all interrupts
+pub(crate) fn read_and_clear_interrupts<M: Miim, P: Phy<M>>(phy: &mut P) -> InterruptControlStatus {
+    InterruptControlStatus::from_bits_truncate(phy.read(InterruptControlStatus::ADDRESS))
+}
+
+/// Enable an interrupt
+pub(crate) fn enable_interrupt<M: Miim, P: Phy<M>>(phy: &mut P, interrupt: Interrupt) {
+    let mut reg_val = read_and_clear_interrupts(phy) & InterruptControlStatus::ENABLE_MASK;
+    reg_val.insert(interrupt.enable_flag());
+    phy.write(InterruptControlStatus::ADDRESS, reg_val.bits());
+}
+
+/// Disable an interrupt
+pub(crate) fn disable_interrupt<M: Miim, P: Phy<M>>(phy: &mut P, interrupt: Interrupt) {
+    let mut reg_val = read_and_clear_interrupts(phy) & InterruptControlStatus::ENABLE_MASK;
+    reg_val.remove(interrupt.enable_flag());
+    phy.write(InterruptControlStatus::ADDRESS, reg_val.bits());
+}
+
+/// Enable or disable Energy Detect Power-Down mode
+pub(crate) fn set_edpd<M: Miim, P: Phy<M>>(phy: &mut P, enabled: bool) {
+    let disabled = ExpandedControl::EDPD_DISABLED.bits();
+    // The register contains a _disable_ bit
+    let value = if enabled { 0 } else { disabled };
+    phy.modify(ExpandedControl::ADDRESS, disabled, value);
+}
+
+/// Check whether Energy Detect Power-Down mode is enabled
+pub(crate) fn edpd_enabled<M: Miim, P: Phy<M>>(phy: &mut P) -> bool {
+    let expanded = ExpandedControl::from_bits_truncate(phy.read(ExpandedControl::ADDRESS));
+    !expanded.contains(ExpandedControl::EDPD_DISABLED)
+}
+
+/// Enable or disable power saving mode
+pub(crate) fn set_power_saving<M: Miim, P: Phy<M>>(phy: &mut P, enabled: bool) {
+    modify_phy_control2(phy, |ctrl2| ctrl2.set(PhyControl2::POWER_SAVING, enabled));
+}
+
+/// Check whether energy is detected on the receive input
+pub(crate) fn energy_detected<M: Miim, P: Phy<M>>(phy: &mut P) -> bool {
+    phy_control1(phy).contains(PhyControl1::ENERGY_DETECT)
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! KSZ8051 registers
+    //!
+    //! The KSZ8081 uses the same register map. Bits that are marked as
+    //! RMII-only are reserved on the KSZ8051MNL.
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    use super::Interrupt;
+
+    bitflags! {
+        /// Register 0x16, the Operation Mode Strap Override register
+        pub struct OperationModeStrapOverride: u16 {
+            /// Disable address 0 as the broadcast address
+            const BROADCAST_OFF = (1 << 9);
+            /// Override to RMII back-to-back mode (RMII-only)
+            const RMII_BACK_TO_BACK = (1 << 6);
+            const NAND_TREE = (1 << 5);
+            /// Override to RMII mode (RMII-only)
+            const RMII = (1 << 1);
+        }
+
+        /// Register 0x17, the Operation Mode Strap Status register
+        pub struct OperationModeStrapStatus: u16 {
+            const PHY_ADDR_MASK = (0b111 << 13);
+            /// Address 0 is disabled as the broadcast address by the
+            /// B-CAST_OFF strap
+            const BROADCAST_OFF = (1 << 9);
+            /// RMII back-to-back mode is strapped (RMII-only)
+            const RMII_BACK_TO_BACK = (1 << 6);
+            const NAND_TREE = (1 << 5);
+            /// RMII mode is strapped (RMII-only)
+            const RMII = (1 << 1);
+        }
+
+        /// Register 0x18, the Expanded Control register
+        pub struct ExpandedControl: u16 {
+            const EDPD_DISABLED = (1 << 11);
+            const RX_PHY_LATENCY = (1 << 10);
+            const PREAMBLE_10BT_RESTORE = (1 << 6);
+        }
+
+        /// Register 0x1B, the Interrupt Control/Status register
+        ///
+        /// The upper byte contains the interrupt enables, the lower byte
+        /// contains the interrupt status bits. The status bits are cleared
+        /// on read.
+        pub struct InterruptControlStatus: u16 {
+            const JABBER_IE = (1 << 15);
+            const RECEIVE_ERROR_IE = (1 << 14);
+            const PAGE_RECEIVED_IE = (1 << 13);
+            const PARALLEL_DETECT_FAULT_IE = (1 << 12);
+            const LINK_PARTNER_ACK_IE = (1 << 11);
+            const LINK_DOWN_IE = (1 << 10);
+            const REMOTE_FAULT_IE = (1 << 9);
+            const LINK_UP_IE = (1 << 8);
+            const JABBER = (1 << 7);
+            const RECEIVE_ERROR = (1 << 6);
+            const PAGE_RECEIVED = (1 << 5);
+            const PARALLEL_DETECT_FAULT = (1 << 4);
+            const LINK_PARTNER_ACK = (1 << 3);
+            const LINK_DOWN = (1 << 2);
+            const REMOTE_FAULT = (1 << 1);
+            const LINK_UP = (1 << 0);
+        }
+
+        /// Register 0x1E, the PHY Control 1 register
+        pub struct PhyControl1: u16 {
+            const ENABLE_PAUSE = (1 << 9);
+            const LINK_STATUS = (1 << 8);
+            const POLARITY_STATUS = (1 << 7);
+            const MDI_MDIX_STATE = (1 << 5);
+            const ENERGY_DETECT = (1 << 4);
+            const PHY_ISOLATE = (1 << 3);
+            const FULL_DUPLEX = (0b1 << 2);
+            const MBIT100 = (0b1 << 1);
+            const MBIT10 = (0b1 << 0);
+        }
+
+        /// Register 0x1F, the PHY Control 2 register
+        pub struct PhyControl2: u16 {
+            const HP_MDIX = (1 << 15);
+            const MDIX_SELECT = (1 << 14);
+            const PAIR_SWAP_DISABLE = (1 << 13);
+            const FORCE_LINK = (1 << 11);
+            const POWER_SAVING = (1 << 10);
+            const INTERRUPT_LEVEL = (1 << 9);
+            const ENABLE_JABBER = (1 << 8);
+            /// Select the RMII reference clock (RMII-only)
+            ///
+            /// The meaning of this bit differs between the variants of the
+            /// KSZ8081.
+            const RMII_REF_CLOCK_SELECT = (1 << 7);
+            const LED_MODE_1 = (1 << 5);
+            const LED_MODE_0 = (1 << 4);
+            const DISABLE_TRANSMITTER = (1 << 3);
+            const REMOTE_LOOPBACK = (1 << 2);
+            const DISABLE_SCRAMBLER = (1 << 0);
+        }
+    }
+
+    impl OperationModeStrapOverride {
+        pub const ADDRESS: u8 = 0x16;
+    }
+
+    impl OperationModeStrapStatus {
+        pub const ADDRESS: u8 = 0x17;
+
+        /// The strapped PHY address
+        pub fn phy_addr(&self) -> u8 {
+            ((*self & Self::PHY_ADDR_MASK).bits() >> 13) as u8
+        }
+    }
+
+    impl ExpandedControl {
+        pub const ADDRESS: u8 = 0x18;
+    }
+
+    impl InterruptControlStatus {
+        pub const ADDRESS: u8 = 0x1B;
+        /// The mask selecting the interrupt enable bits of this register
+        pub const ENABLE_MASK: Self = Self::from_bits_truncate(0xFF00);
+
+        /// Check if `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.status_flag())
+        }
+    }
+
+    impl PhyControl1 {
+        pub const ADDRESS: u8 = 0x1E;
+
+        #[deprecated(note = "use `MDI_MDIX_STATE` instead")]
+        pub const MID_MIDX_STATE: Self = Self::MDI_MDIX_STATE;
+    }
+
+    impl PhyControl2 {
+        pub const ADDRESS: u8 = 0x1F;
+    }
+
+    impl From<PhyControl1> for Option<PhySpeed> {
+        fn from(ctrl: PhyControl1) -> Self {
+            let full_duplex = ctrl.contains(PhyControl1::FULL_DUPLEX);
+            let mbit_10 = ctrl.contains(PhyControl1::MBIT10);
+            let mbit_100 = ctrl.contains(PhyControl1::MBIT100);
+
+            // allow collapsible else/if for clearer semantics
+            #[allow(clippy::collapsible_else_if)]
+            let speed = if full_duplex {
+                if mbit_10 {
+                    PhySpeed::FullDuplexBase10T
+                } else if mbit_100 {
+                    PhySpeed::FullDuplexBase100Tx
+                } else {
+                    return None;
+                }
+            } else {
+                if mbit_10 {
+                    PhySpeed::HalfDuplexBase10T
+                } else if mbit_100 {
+                    PhySpeed::HalfDuplexBase100Tx
+                } else {
+                    return None;
+                }
+            };
+
+            Some(speed)
+        }
+    }
+}
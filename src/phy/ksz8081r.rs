@@ -1,16 +1,77 @@
 //! Phy implementation for the Microchip KSZ8081R
 
-use crate::{registers::Esr, AutoNegotiationAdvertisement, ExtendedPhyStatus, Miim, Phy};
+use crate::{
+    registers::{Bcr, Esr},
+    AutoNegotiationAdvertisement, ExtendedPhyStatus, Miim, Phy, PhyAddress,
+};
 
-use self::registers::PhyControl1;
+use self::registers::{
+    InterruptControlStatus, OperationModeStrapOverride, OperationModeStrapStatus, PhyControl1,
+    PhyControl2,
+};
 
-use super::{AdvancedPhySpeed, PhySpeed, PhyWithSpeed};
+/// All interrupt sources supported by this chip
+///
+/// The KSZ8081 shares its interrupt sources with the KSZ8051.
+pub use crate::phy::ksz8051::Interrupt;
+
+use super::{
+    ksz8051, AdvancedPhySpeed, PhySpeed, PhyWithCableDetect, PhyWithEnergyDetect,
+    PhyWithInterrupts, PhyWithMdix, PhyWithSpeed,
+};
+
+/// The RMII variants of the KSZ8081.
+///
+/// The variants share a PHY identifier, but differ in the meaning of
+/// the RMII reference clock select bit in PHY Control 2.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// KSZ8081RNA, defaults to a 25 MHz reference clock
+    Rna,
+    /// KSZ8081RNB, defaults to a 50 MHz reference clock
+    Rnb,
+    /// KSZ8081RND, defaults to a 50 MHz reference clock
+    Rnd,
+}
+
+/// The RMII reference clock mode
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefClock {
+    /// A 25 MHz crystal or clock is connected to XI
+    Clock25MHz,
+    /// A 50 MHz clock is connected to XI
+    Clock50MHz,
+}
+
+impl Variant {
+    /// The reference clock selected when the RMII reference clock
+    /// select bit has the value `bit`.
+    pub fn ref_clock(&self, bit: bool) -> RefClock {
+        match (self, bit) {
+            (Variant::Rna, false) => RefClock::Clock25MHz,
+            (Variant::Rna, true) => RefClock::Clock50MHz,
+            (Variant::Rnb | Variant::Rnd, false) => RefClock::Clock50MHz,
+            (Variant::Rnb | Variant::Rnd, true) => RefClock::Clock25MHz,
+        }
+    }
+
+    /// The value of the RMII reference clock select bit that selects `clock`.
+    pub fn ref_clock_bit(&self, clock: RefClock) -> bool {
+        self.ref_clock(true) == clock
+    }
+}
+
+/// The pair assignment of the twisted pair interface
+pub use crate::phy::MdiMode;
 
 /// A KSZ8081R
 #[derive(Debug)]
 pub struct KSZ8081R<MIIM: Miim> {
-    phy_addr: u8,
+    phy_addr: PhyAddress,
     miim: MIIM,
+    variant: Option<Variant>,
 }
 
 impl<MIIM: Miim> KSZ8081R<MIIM> {
@@ -24,11 +85,288 @@ impl<MIIM: Miim> KSZ8081R<MIIM> {
     pub const INTERRUPT_REG_INT_LINK_DOWN: u16 = 1 << 2;
 
     /// Create a new Ksz8081r at `phy_addr`, backed by the given `miim`,
-    pub fn new(miim: MIIM, phy_addr: u8) -> Self {
-        Self { phy_addr, miim }
+    ///
+    /// The variant of the PHY is unknown, so the reference clock is left as-is.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self {
+            phy_addr,
+            miim,
+            variant: None,
+        }
+    }
+
+    /// Create a new KSZ8081R at the raw address `phy_addr`, backed by the given `miim`.
+    ///
+    /// # Panics
+    /// This function panics if `phy_addr` is not less than 32.
+    #[deprecated(note = "use `new` with a `PhyAddress` instead")]
+    pub fn from_raw_address(miim: MIIM, phy_addr: u8) -> Self {
+        Self::new(miim, PhyAddress::from_const(phy_addr))
+    }
+
+    /// Create a new KSZ8081 `variant` at `phy_addr`, backed by the given `miim`,
+    /// and configure it to use `ref_clock` as its reference clock.
+    pub fn new_with_variant(
+        miim: MIIM,
+        phy_addr: PhyAddress,
+        variant: Variant,
+        ref_clock: RefClock,
+    ) -> Self {
+        let mut me = Self {
+            phy_addr,
+            miim,
+            variant: Some(variant),
+        };
+        me.set_ref_clock(ref_clock);
+        me
+    }
+
+    /// The variant of this PHY, if it is known
+    pub fn variant(&self) -> Option<Variant> {
+        self.variant
+    }
+
+    /// Set the variant of this PHY.
+    ///
+    /// This does not change the configuration of the PHY.
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = Some(variant);
+    }
+
+    /// Read the PHY Control 2 register
+    pub fn phy_control2(&mut self) -> PhyControl2 {
+        ksz8051::phy_control2(self)
+    }
+
+    /// Modify the PHY Control 2 register
+    pub fn modify_phy_control2<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut PhyControl2),
+    {
+        ksz8051::modify_phy_control2(self, f)
+    }
+
+    /// Get the currently active reference clock mode.
+    ///
+    /// Returns `None` if the variant of this PHY is unknown.
+    pub fn ref_clock(&mut self) -> Option<RefClock> {
+        let variant = self.variant?;
+        let bit = self
+            .phy_control2()
+            .contains(PhyControl2::RMII_REF_CLOCK_SELECT);
+        Some(variant.ref_clock(bit))
+    }
+
+    /// Configure the reference clock mode.
+    ///
+    /// This is a no-op if the variant of this PHY is unknown.
+    pub fn set_ref_clock(&mut self, ref_clock: RefClock) {
+        if let Some(variant) = self.variant {
+            let bit = variant.ref_clock_bit(ref_clock);
+            self.modify_phy_control2(|ctrl2| ctrl2.set(PhyControl2::RMII_REF_CLOCK_SELECT, bit));
+        }
+    }
+
+    /// Check whether the link is forced up
+    pub fn force_link(&mut self) -> bool {
+        self.phy_control2().contains(PhyControl2::FORCE_LINK)
+    }
+
+    /// Force the link up, regardless of the link status of the receiver
+    pub fn set_force_link(&mut self, enabled: bool) {
+        self.modify_phy_control2(|ctrl2| ctrl2.set(PhyControl2::FORCE_LINK, enabled));
+    }
+
+    /// Get the forced pair assignment, or `None` if auto MDI/MDI-X is enabled
+    pub fn mdi_mode(&mut self) -> Option<MdiMode> {
+        let ctrl2 = self.phy_control2();
+        if !ctrl2.contains(PhyControl2::PAIR_SWAP_DISABLE) {
+            None
+        } else if ctrl2.contains(PhyControl2::MDIX_SELECT) {
+            Some(MdiMode::Mdix)
+        } else {
+            Some(MdiMode::Mdi)
+        }
+    }
+
+    /// Force the pair assignment to `mode`, or enable auto MDI/MDI-X if
+    /// `mode` is `None`
+    pub fn set_mdi_mode(&mut self, mode: Option<MdiMode>) {
+        self.modify_phy_control2(|ctrl2| {
+            ctrl2.set(PhyControl2::PAIR_SWAP_DISABLE, mode.is_some());
+            if let Some(mode) = mode {
+                ctrl2.set(PhyControl2::MDIX_SELECT, mode == MdiMode::Mdix);
+            }
+        });
+    }
+
+    /// Get the pair assignment that is currently in use
+    pub fn mdi_status(&mut self) -> MdiMode {
+        if self.phy_control1().contains(PhyControl1::MDI_MDIX_STATE) {
+            MdiMode::Mdix
+        } else {
+            MdiMode::Mdi
+        }
+    }
+
+    /// Enable or disable the 100BASE-TX transmitter test mode
+    ///
+    /// If `mode` is `Some`, autonegotiation and auto MDI/MDI-X are disabled,
+    /// the PHY is forced into 100BASE-TX full duplex on the pairs selected by
+    /// `mode`, and the link is forced up. The PHY then continuously transmits
+    /// the scrambled idle pattern without requiring a link partner, which is
+    /// what is needed to measure the transmit waveform for compliance testing.
+    ///
+    /// If `mode` is `None`, auto MDI/MDI-X is enabled, the link is no longer
+    /// forced up and autonegotiation is enabled and restarted.
+    pub fn set_tx_test_mode(&mut self, mode: Option<MdiMode>) {
+        if let Some(mode) = mode {
+            self.modify_phy_control2(|ctrl2| {
+                ctrl2.insert(PhyControl2::PAIR_SWAP_DISABLE | PhyControl2::FORCE_LINK);
+                ctrl2.set(PhyControl2::MDIX_SELECT, mode == MdiMode::Mdix);
+                ctrl2.remove(PhyControl2::DISABLE_TRANSMITTER | PhyControl2::DISABLE_SCRAMBLER);
+            });
+            self.modify_bcr(|bcr| {
+                bcr.set_autonegotiation(false).set_full_duplex(true);
+                bcr.remove(Bcr::SPEED_SEL_MSB);
+                bcr.insert(Bcr::SPEED_SEL_LSB);
+            });
+        } else {
+            self.modify_phy_control2(|ctrl2| {
+                ctrl2.remove(PhyControl2::PAIR_SWAP_DISABLE | PhyControl2::FORCE_LINK);
+            });
+            self.modify_bcr(|bcr| {
+                bcr.set_autonegotiation(true).restart_autonegotiation();
+            });
+        }
+    }
+
+    /// Read the PHY Control 1 register
+    pub fn phy_control1(&mut self) -> PhyControl1 {
+        ksz8051::phy_control1(self)
+    }
+
+    /// Enable or disable Energy Detect Power-Down mode
+    ///
+    /// This only has an effect while power saving mode is enabled, see
+    /// [`Self::set_power_saving`].
+    pub fn set_edpd(&mut self, enabled: bool) {
+        ksz8051::set_edpd(self, enabled)
+    }
+
+    /// Check whether Energy Detect Power-Down mode is enabled
+    pub fn edpd_enabled(&mut self) -> bool {
+        ksz8051::edpd_enabled(self)
+    }
+
+    /// Enable or disable power saving mode
+    pub fn set_power_saving(&mut self, enabled: bool) {
+        ksz8051::set_power_saving(self, enabled)
+    }
+
+    /// Check whether power saving mode is enabled
+    pub fn power_saving(&mut self) -> bool {
+        self.phy_control2().contains(PhyControl2::POWER_SAVING)
+    }
+
+    /// Check whether energy is detected on the receive input
+    pub fn energy_detected(&mut self) -> bool {
+        ksz8051::energy_detected(self)
+    }
+
+    /// Read the Operation Mode Strap Status register
+    ///
+    /// This contains the strap-in values that were latched at power-up or
+    /// reset, including the PHY address.
+    pub fn strap_status(&mut self) -> OperationModeStrapStatus {
+        ksz8051::strap_status(self)
+    }
+
+    /// The PHY address that was strapped at power-up or reset
+    ///
+    /// The strapped address can not be overridden in software, but
+    /// [`Self::set_broadcast_disabled`] can be used to stop the PHY from
+    /// responding to address 0.
+    pub fn strapped_phy_addr(&mut self) -> u8 {
+        self.strap_status().phy_addr()
+    }
+
+    /// Read the Operation Mode Strap Override register
+    pub fn strap_override(&mut self) -> OperationModeStrapOverride {
+        ksz8051::strap_override(self)
+    }
+
+    /// Modify the Operation Mode Strap Override register
+    ///
+    /// Overrides of the operation mode take effect after a software reset.
+    pub fn modify_strap_override<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut OperationModeStrapOverride),
+    {
+        let raw = self.read(OperationModeStrapOverride::ADDRESS);
+        let over = &mut OperationModeStrapOverride::from_bits_truncate(raw);
+        f(over);
+        // Preserve the bits that are not described by `OperationModeStrapOverride`
+        let reserved = raw & !OperationModeStrapOverride::all().bits();
+        self.write(OperationModeStrapOverride::ADDRESS, reserved | over.bits());
+    }
+
+    /// Force the PHY into normal RMII mode, regardless of the strapped
+    /// operation mode, and reset it so the new mode takes effect.
+    ///
+    /// Verify that the reset is completed by checking [`Phy::is_resetting`]
+    /// before continuing usage.
+    pub fn force_rmii(&mut self) {
+        self.modify_strap_override(|over| {
+            over.remove(
+                OperationModeStrapOverride::RMII_BACK_TO_BACK
+                    | OperationModeStrapOverride::NAND_TREE,
+            );
+            over.insert(OperationModeStrapOverride::RMII);
+        });
+        self.reset();
+    }
+
+    /// Enable or disable PHY address 0 as the broadcast address
+    ///
+    /// If `disabled` is `true`, the PHY only responds to its strapped
+    /// address, also when that address is 0. If `disabled` is `false`,
+    /// broadcast remains disabled if the PHY is strapped B-CAST_OFF (see
+    /// [`OperationModeStrapStatus::BROADCAST_OFF`]).
+    pub fn set_broadcast_disabled(&mut self, disabled: bool) {
+        self.modify_strap_override(|over| {
+            over.set(OperationModeStrapOverride::BROADCAST_OFF, disabled)
+        });
+    }
+
+    /// Enable an interrupt
+    ///
+    /// Note that this reads the interrupt control/status register, which clears
+    /// all pending interrupts.
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        ksz8051::enable_interrupt(self, interrupt)
+    }
+
+    /// Disable an interrupt
+    ///
+    /// Note that this reads the interrupt control/status register, which clears
+    /// all pending interrupts.
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        ksz8051::disable_interrupt(self, interrupt)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptControlStatus::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptControlStatus {
+        ksz8051::read_and_clear_interrupts(self)
     }
 
     /// Enable the link up and link down interrupts
+    ///
+    /// This disables all other interrupts. Use [`Self::enable_interrupt`] to
+    /// enable individual interrupts.
     pub fn interrupt_enable(&mut self) {
         self.write(
             Self::INTERRUPT_REG,
@@ -38,8 +376,7 @@ impl<MIIM: Miim> KSZ8081R<MIIM> {
 
     /// Get the link speed at which the PHY is currently operating
     pub fn link_speed(&mut self) -> Option<PhySpeed> {
-        let phy_ctrl1 = PhyControl1::from_bits_truncate(self.read(PhyControl1::ADDRESS));
-        phy_ctrl1.into()
+        self.phy_control1().into()
     }
 
     /// Get the value of the interrupt register.
@@ -77,10 +414,14 @@ impl<MIIM: Miim> Phy<MIIM> for KSZ8081R<MIIM> {
         &mut self.miim
     }
 
-    fn get_phy_addr(&self) -> u8 {
+    fn phy_address(&self) -> PhyAddress {
         self.phy_addr
     }
 
+    fn honors_broadcast(&mut self) -> bool {
+        ksz8051::honors_broadcast(self)
+    }
+
     fn esr(&mut self) -> Option<Esr> {
         None
     }
@@ -96,57 +437,52 @@ impl<MIIM: Miim> PhyWithSpeed<MIIM> for KSZ8081R<MIIM> {
     }
 }
 
-#[allow(missing_docs)]
-pub mod registers {
-    use bitflags::bitflags;
-
-    use crate::phy::PhySpeed;
-
-    bitflags! {
-        pub struct PhyControl1: u16 {
-            const ENABLE_PAUSE = (1 << 9);
-            const LINK_STATUS = (1 << 8);
-            const POLARITY_STATUS = (1 << 7);
-            const MID_MIDX_STATE = (1 << 5);
-            const ENERGY_DETECT = (1 << 4);
-            const PHY_ISOLATE = (1 << 3);
-            const FULL_DUPLEX = (0b1 << 2);
-            const MBIT100 = (0b1 << 1);
-            const MBIT10 = (0b1 << 0);
-        }
+impl<MIIM: Miim> PhyWithMdix<MIIM> for KSZ8081R<MIIM> {
+    fn mdi_mode(&mut self) -> Option<MdiMode> {
+        self.mdi_mode()
     }
 
-    impl PhyControl1 {
-        pub const ADDRESS: u8 = 0x1E;
-    }
-
-    impl From<PhyControl1> for Option<PhySpeed> {
-        fn from(ctrl: PhyControl1) -> Self {
-            let full_duplex = ctrl.contains(PhyControl1::FULL_DUPLEX);
-            let mbit_10 = ctrl.contains(PhyControl1::MBIT10);
-            let mbit_100 = ctrl.contains(PhyControl1::MBIT100);
-
-            // allow collapsible else/if for clearer semantics
-            #[allow(clippy::collapsible_else_if)]
-            let speed = if full_duplex {
-                if mbit_10 {
-                    PhySpeed::FullDuplexBase10T
-                } else if mbit_100 {
-                    PhySpeed::FullDuplexBase100Tx
-                } else {
-                    return None;
-                }
-            } else {
-                if mbit_10 {
-                    PhySpeed::HalfDuplexBase10T
-                } else if mbit_100 {
-                    PhySpeed::HalfDuplexBase100Tx
-                } else {
-                    return None;
-                }
-            };
-
-            Some(speed)
-        }
+    fn set_mdi_mode(&mut self, mode: Option<MdiMode>) {
+        self.set_mdi_mode(mode)
+    }
+
+    fn mdi_status(&mut self) -> Option<MdiMode> {
+        Some(self.mdi_status())
+    }
+}
+
+impl<MIIM: Miim> PhyWithCableDetect<MIIM> for KSZ8081R<MIIM> {
+    fn energy_detected(&mut self) -> bool {
+        self.energy_detected()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEnergyDetect<MIIM> for KSZ8081R<MIIM> {
+    fn energy_detect_power_down(&mut self) -> bool {
+        self.power_saving() && self.edpd_enabled()
+    }
+
+    fn set_energy_detect_power_down(&mut self, enabled: bool) {
+        self.set_edpd(enabled);
+        self.set_power_saving(enabled);
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for KSZ8081R<MIIM> {
+    type Interrupts = InterruptControlStatus;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptControlStatus {
+        self.read_and_clear_interrupts()
     }
 }
+
+pub mod registers {
+    //! KSZ8081R registers
+    //!
+    //! The KSZ8081 shares its register map with the KSZ8051.
+
+    pub use crate::phy::ksz8051::registers::{
+        ExpandedControl, InterruptControlStatus, OperationModeStrapOverride,
+        OperationModeStrapStatus, PhyControl1, PhyControl2,
+    };
+}
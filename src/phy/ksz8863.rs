@@ -0,0 +1,155 @@
+//! Support for the PHY ports of the Microchip KSZ8863 3-port switch
+//!
+//! The KSZ8863 exposes the PHYs of switch ports 1 and 2 as regular IEEE 802.3
+//! PHYs on its MIIM bus, at PHY addresses 1 and 2. The global switch registers
+//! are reachable over the same bus, using the Serial Management Interface
+//! (SMI) framing.
+//!
+//! [`KSZ8863`] owns the [`Miim`], provides access to the global switch
+//! registers, and hands out a [`KSZ8863Port`] for each of the PHY ports. Every
+//! [`KSZ8863Port`] implements [`Phy`].
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{SMI_ADDRESS_SHIFT, SMI_READ};
+
+use super::{AdvancedPhySpeed, PhySpeed, PhyWithSpeed};
+
+/// One of the PHY ports of the KSZ8863
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Port {
+    /// Switch port 1
+    Port1,
+    /// Switch port 2
+    Port2,
+}
+
+impl Port {
+    /// The PHY address of this port
+    pub fn phy_addr(&self) -> u8 {
+        match self {
+            Port::Port1 => 1,
+            Port::Port2 => 2,
+        }
+    }
+}
+
+/// A KSZ8863 switch
+#[derive(Debug)]
+pub struct KSZ8863<MIIM: Miim> {
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> KSZ8863<MIIM> {
+    /// Create a new KSZ8863, backed by the given `miim`.
+    pub fn new(miim: MIIM) -> Self {
+        Self { miim }
+    }
+
+    /// Access one of the PHY ports of this switch
+    pub fn port(&mut self, port: Port) -> KSZ8863Port<'_, MIIM> {
+        KSZ8863Port {
+            port,
+            miim: &mut self.miim,
+        }
+    }
+
+    /// Initialize the PHYs of both ports
+    pub fn phy_init(&mut self) {
+        for port in [Port::Port1, Port::Port2] {
+            let mut phy = self.port(port);
+            phy.set_autonegotiation_advertisement(phy.best_supported_advertisement());
+        }
+    }
+
+    /// Read a global switch register
+    pub fn switch_read(&mut self, address: u8) -> u8 {
+        let phy_addr = (address >> SMI_ADDRESS_SHIFT) | SMI_READ;
+        self.miim.read(phy_addr, address & 0x1F) as u8
+    }
+
+    /// Write a global switch register
+    pub fn switch_write(&mut self, address: u8, value: u8) {
+        let phy_addr = address >> SMI_ADDRESS_SHIFT;
+        self.miim.write(phy_addr, address & 0x1F, value as u16);
+    }
+
+    /// Modify the bits selected by `mask` in a global switch register, leaving
+    /// all other bits untouched
+    pub fn switch_modify(&mut self, address: u8, mask: u8, value: u8) {
+        let old = self.switch_read(address);
+        self.switch_write(address, (old & !mask) | (value & mask));
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+/// A single PHY port of a [`KSZ8863`]
+#[derive(Debug)]
+pub struct KSZ8863Port<'a, MIIM: Miim> {
+    port: Port,
+    miim: &'a mut MIIM,
+}
+
+impl<'a, MIIM: Miim> KSZ8863Port<'a, MIIM> {
+    /// The switch port of this PHY
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    /// Get the link speed, as resolved from the BCR and the autonegotiation
+    /// results
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        super::resolve_link_speed(self)
+    }
+
+    /// Check if the link is up
+    pub fn link_established(&mut self) -> bool {
+        let bsr = self.bsr();
+        bsr.autoneg_completed() && bsr.phy_link_up()
+    }
+}
+
+impl<'a, MIIM: Miim> Phy<MIIM> for KSZ8863Port<'a, MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        PhyAddress::from_const(self.port.phy_addr())
+    }
+}
+
+impl<'a, MIIM: Miim> PhyWithSpeed<MIIM> for KSZ8863Port<'a, MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+pub mod registers {
+    //! KSZ8863 SMI framing
+    //!
+    //! A global switch register address is 8 bits wide. Over SMI, the upper 3
+    //! bits are sent as the PHY address and the lower 5 bits as the register
+    //! address. Bit 4 of the PHY address is set for reads.
+
+    /// The shift applied to a switch register address to obtain the SMI PHY address
+    pub const SMI_ADDRESS_SHIFT: u8 = 5;
+    /// The PHY address bit that selects an SMI read
+    pub const SMI_READ: u8 = 1 << 4;
+}
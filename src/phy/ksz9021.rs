@@ -0,0 +1,301 @@
+//! Phy implementation for the Microchip KSZ9021 gigabit Ethernet PHY
+
+use crate::{registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    ClockPadSkew, DataPadSkew, ExtendedRegisterControl, PhyControl, EXT_REG_DATA_READ,
+    EXT_REG_DATA_WRITE,
+};
+
+use super::{AdvancedPhySpeed, PhyWithSpeed};
+
+/// A KSZ9021
+#[derive(Debug)]
+pub struct KSZ9021<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> KSZ9021<MIIM> {
+    /// Create a new KSZ9021 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read an extended register
+    ///
+    /// The extended registers of the KSZ9021 are accessed through
+    /// MII registers 0x0B, 0x0C and 0x0D, and not through MMD.
+    pub fn extended_read(&mut self, address: u16) -> u16 {
+        let ctrl = ExtendedRegisterControl::read(address);
+        self.write(ExtendedRegisterControl::ADDRESS, ctrl.bits());
+        self.read(EXT_REG_DATA_READ)
+    }
+
+    /// Write an extended register
+    pub fn extended_write(&mut self, address: u16, value: u16) {
+        let ctrl = ExtendedRegisterControl::write(address);
+        self.write(ExtendedRegisterControl::ADDRESS, ctrl.bits());
+        self.write(EXT_REG_DATA_WRITE, value);
+    }
+
+    /// Get the RGMII clock and control pad skew
+    pub fn rgmii_clock_skew(&mut self) -> ClockPadSkew {
+        self.extended_read(ClockPadSkew::ADDRESS).into()
+    }
+
+    /// Set the RGMII clock and control pad skew
+    pub fn set_rgmii_clock_skew(&mut self, skew: ClockPadSkew) {
+        self.extended_write(ClockPadSkew::ADDRESS, skew.into());
+    }
+
+    /// Get the RGMII RX data pad skew
+    pub fn rgmii_rx_data_skew(&mut self) -> DataPadSkew {
+        self.extended_read(DataPadSkew::RX_ADDRESS).into()
+    }
+
+    /// Set the RGMII RX data pad skew
+    pub fn set_rgmii_rx_data_skew(&mut self, skew: DataPadSkew) {
+        self.extended_write(DataPadSkew::RX_ADDRESS, skew.into());
+    }
+
+    /// Get the RGMII TX data pad skew
+    pub fn rgmii_tx_data_skew(&mut self) -> DataPadSkew {
+        self.extended_read(DataPadSkew::TX_ADDRESS).into()
+    }
+
+    /// Set the RGMII TX data pad skew
+    pub fn set_rgmii_tx_data_skew(&mut self, skew: DataPadSkew) {
+        self.extended_write(DataPadSkew::TX_ADDRESS, skew.into());
+    }
+
+    /// Read the PHY Control register
+    pub fn phy_control(&mut self) -> PhyControl {
+        PhyControl::from_bits_truncate(self.read(PhyControl::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_control().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for KSZ9021<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for KSZ9021<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! KSZ9021 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    /// Register 0x0C, the Extended Register Data Write register
+    pub const EXT_REG_DATA_WRITE: u8 = 0x0C;
+    /// Register 0x0D, the Extended Register Data Read register
+    pub const EXT_REG_DATA_READ: u8 = 0x0D;
+
+    bitflags! {
+        /// Register 0x0B, the Extended Register Control register
+        pub struct ExtendedRegisterControl: u16 {
+            const WRITE = (1 << 15);
+            const ADDRESS_MASK = (0xFF);
+        }
+
+        /// Register 0x1F, the PHY Control register
+        pub struct PhyControl: u16 {
+            const SPEED_1000 = (1 << 6);
+            const SPEED_100 = (1 << 5);
+            const SPEED_10 = (1 << 4);
+            const FULL_DUPLEX = (1 << 3);
+            const MASTER = (1 << 2);
+        }
+    }
+
+    impl ExtendedRegisterControl {
+        pub const ADDRESS: u8 = 0x0B;
+
+        /// A control value that selects `address` for reading
+        pub fn read(address: u16) -> Self {
+            Self::from_bits_truncate(address & Self::ADDRESS_MASK.bits())
+        }
+
+        /// A control value that selects `address` for writing
+        pub fn write(address: u16) -> Self {
+            Self::read(address) | Self::WRITE
+        }
+    }
+
+    impl PhyControl {
+        pub const ADDRESS: u8 = 0x1F;
+    }
+
+    impl From<PhyControl> for Option<AdvancedPhySpeed> {
+        fn from(ctrl: PhyControl) -> Self {
+            let full_duplex = ctrl.contains(PhyControl::FULL_DUPLEX);
+            let speed = (
+                ctrl.contains(PhyControl::SPEED_10),
+                ctrl.contains(PhyControl::SPEED_100),
+                ctrl.contains(PhyControl::SPEED_1000),
+            );
+
+            let speed = match (speed, full_duplex) {
+                ((true, false, false), false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                ((true, false, false), true) => AdvancedPhySpeed::FullDuplexBase10T,
+                ((false, true, false), false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                ((false, true, false), true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                ((false, false, true), false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                ((false, false, true), true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    /// Extended register 0x104, the RGMII Clock and Control Pad Skew register.
+    ///
+    /// All values are 4 bits wide, in steps of 0.12 ns. A value of `0x7`
+    /// applies no skew.
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ClockPadSkew {
+        pub rx_clk: u8,
+        pub rx_ctl: u8,
+        pub tx_clk: u8,
+        pub tx_ctl: u8,
+    }
+
+    impl ClockPadSkew {
+        pub const ADDRESS: u16 = 0x104;
+    }
+
+    impl Default for ClockPadSkew {
+        fn default() -> Self {
+            Self {
+                rx_clk: 0x7,
+                rx_ctl: 0x7,
+                tx_clk: 0x7,
+                tx_ctl: 0x7,
+            }
+        }
+    }
+
+    impl From<u16> for ClockPadSkew {
+        fn from(value: u16) -> Self {
+            Self {
+                rx_clk: (value >> 12) as u8 & 0xF,
+                rx_ctl: (value >> 8) as u8 & 0xF,
+                tx_clk: (value >> 4) as u8 & 0xF,
+                tx_ctl: value as u8 & 0xF,
+            }
+        }
+    }
+
+    impl From<ClockPadSkew> for u16 {
+        fn from(skew: ClockPadSkew) -> Self {
+            (skew.rx_clk as u16 & 0xF) << 12
+                | (skew.rx_ctl as u16 & 0xF) << 8
+                | (skew.tx_clk as u16 & 0xF) << 4
+                | (skew.tx_ctl as u16 & 0xF)
+        }
+    }
+
+    /// Extended registers 0x105 and 0x106, the RGMII RX and TX Data Pad Skew registers.
+    ///
+    /// All values are 4 bits wide, in steps of 0.12 ns. A value of `0x7`
+    /// applies no skew.
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DataPadSkew {
+        pub d3: u8,
+        pub d2: u8,
+        pub d1: u8,
+        pub d0: u8,
+    }
+
+    impl DataPadSkew {
+        pub const RX_ADDRESS: u16 = 0x105;
+        pub const TX_ADDRESS: u16 = 0x106;
+    }
+
+    impl Default for DataPadSkew {
+        fn default() -> Self {
+            Self {
+                d3: 0x7,
+                d2: 0x7,
+                d1: 0x7,
+                d0: 0x7,
+            }
+        }
+    }
+
+    impl From<u16> for DataPadSkew {
+        fn from(value: u16) -> Self {
+            Self {
+                d3: (value >> 12) as u8 & 0xF,
+                d2: (value >> 8) as u8 & 0xF,
+                d1: (value >> 4) as u8 & 0xF,
+                d0: value as u8 & 0xF,
+            }
+        }
+    }
+
+    impl From<DataPadSkew> for u16 {
+        fn from(skew: DataPadSkew) -> Self {
+            (skew.d3 as u16 & 0xF) << 12
+                | (skew.d2 as u16 & 0xF) << 8
+                | (skew.d1 as u16 & 0xF) << 4
+                | (skew.d0 as u16 & 0xF)
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Phy implementation for the Microchip KSZ9131 gigabit Ethernet PHY
+
+use crate::{
+    registers::{Bcr, MasterSlaveControl, MasterSlaveStatus},
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{CommonControl, DllControl, LinkMd, PhyControl, MMD_DEVICE};
+
+use super::{
+    AdvancedPhySpeed, CableDiagnostics, CablePairStatus, PhyWithCableDiagnostics, PhyWithEee,
+    PhyWithRgmiiDelays, PhyWithSpeed, RgmiiDelays,
+};
+
+/// The LED mode of the KSZ9131
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedMode {
+    /// Tri-color dual-LED mode
+    TriColorDual,
+    /// Single-LED mode
+    Single,
+}
+
+/// A KSZ9131
+#[derive(Debug)]
+pub struct KSZ9131<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> KSZ9131<MIIM> {
+    /// Create a new KSZ9131 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Check whether the RGMII RX clock delay is enabled
+    pub fn rx_delay(&mut self) -> bool {
+        let dll = DllControl::from_bits_truncate(self.mmd_read(MMD_DEVICE, DllControl::RXC));
+        !dll.contains(DllControl::BYPASS)
+    }
+
+    /// Enable or disable the internal RGMII RX clock delay
+    pub fn set_rx_delay(&mut self, enabled: bool) {
+        let bypass = DllControl::BYPASS.bits();
+        let value = if enabled { 0 } else { bypass };
+        self.mmd_modify(MMD_DEVICE, DllControl::RXC, bypass, value);
+    }
+
+    /// Check whether the RGMII TX clock delay is enabled
+    pub fn tx_delay(&mut self) -> bool {
+        let dll = DllControl::from_bits_truncate(self.mmd_read(MMD_DEVICE, DllControl::TXC));
+        !dll.contains(DllControl::BYPASS)
+    }
+
+    /// Enable or disable the internal RGMII TX clock delay
+    pub fn set_tx_delay(&mut self, enabled: bool) {
+        let bypass = DllControl::BYPASS.bits();
+        let value = if enabled { 0 } else { bypass };
+        self.mmd_modify(MMD_DEVICE, DllControl::TXC, bypass, value);
+    }
+
+    /// Get the currently configured LED mode
+    pub fn led_mode(&mut self) -> LedMode {
+        let common =
+            CommonControl::from_bits_truncate(self.mmd_read(MMD_DEVICE, CommonControl::ADDRESS));
+        if common.contains(CommonControl::SINGLE_LED_MODE) {
+            LedMode::Single
+        } else {
+            LedMode::TriColorDual
+        }
+    }
+
+    /// Set the LED mode
+    pub fn set_led_mode(&mut self, mode: LedMode) {
+        let single = CommonControl::SINGLE_LED_MODE.bits();
+        let value = if mode == LedMode::Single { single } else { 0 };
+        self.mmd_modify(MMD_DEVICE, CommonControl::ADDRESS, single, value);
+    }
+
+    /// Read the PHY Control register
+    pub fn phy_control(&mut self) -> PhyControl {
+        PhyControl::from_bits_truncate(self.read(PhyControl::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_control().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for KSZ9131<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for KSZ9131<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEee<MIIM> for KSZ9131<MIIM> {}
+
+impl<MIIM: Miim> PhyWithCableDiagnostics<MIIM> for KSZ9131<MIIM> {
+    fn cable_pairs(&self) -> u8 {
+        4
+    }
+
+    fn test_cable_pair(&mut self, pair: u8) -> Option<CableDiagnostics> {
+        if pair >= self.cable_pairs() {
+            return None;
+        }
+
+        Some(test_cable_pair(self, pair))
+    }
+}
+
+/// The maximum amount of times the LinkMD register is polled before a test
+/// is considered to have failed.
+const LINKMD_MAX_POLLS: u32 = 100_000;
+
+/// Run a LinkMD cable diagnostic test on `pair`.
+///
+/// If the test does not complete within [`LINKMD_MAX_POLLS`] reads of the
+/// LinkMD register, or the PHY does not respond, the result is
+/// [`CablePairStatus::TestFailed`].
+///
+/// This is shared with other PHYs that implement the KSZ9131 LinkMD registers.
+pub(crate) fn test_cable_pair<M: Miim, P: Phy<M>>(phy: &mut P, pair: u8) -> CableDiagnostics {
+    // The test must be run in forced 1000BASE-T full duplex master mode
+    let bcr = phy.bcr();
+    let msc = phy.read(MasterSlaveControl::ADDRESS);
+    let master =
+        MasterSlaveControl::MS_MANUAL_CONFIG_ENABLE | MasterSlaveControl::MS_MANUAL_CONFIG_VALUE;
+
+    phy.modify_bcr(|bcr| {
+        bcr.set_autonegotiation(false).set_full_duplex(true);
+        bcr.remove(Bcr::SPEED_SEL_LSB);
+        bcr.insert(Bcr::SPEED_SEL_MSB);
+    });
+
+    phy.write(MasterSlaveControl::ADDRESS, msc | master.bits());
+
+    phy.write(LinkMd::ADDRESS, LinkMd::start(pair).bits());
+    let mut result = CableDiagnostics {
+        status: CablePairStatus::TestFailed,
+        fault_distance_cm: None,
+    };
+    for _ in 0..LINKMD_MAX_POLLS {
+        let raw = phy.read(LinkMd::ADDRESS);
+        // An absent PHY reads as all ones, and never clears ENABLE
+        if raw == 0xFFFF {
+            break;
+        }
+
+        let lmd = LinkMd::from_bits_truncate(raw);
+        if !lmd.contains(LinkMd::ENABLE) {
+            result = lmd.into();
+            break;
+        }
+    }
+
+    phy.write(MasterSlaveControl::ADDRESS, msc);
+    phy.write(Bcr::ADDRESS, bcr.bits());
+    if bcr.autonegotiation() {
+        phy.modify_bcr(|bcr| {
+            bcr.restart_autonegotiation();
+        });
+    }
+
+    result
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for KSZ9131<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        RgmiiDelays {
+            tx: self.tx_delay(),
+            rx: self.rx_delay(),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_tx_delay(delays.tx);
+        self.set_rx_delay(delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! KSZ9131 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::{AdvancedPhySpeed, CableDiagnostics, CablePairStatus};
+
+    /// The MMD device containing the vendor specific registers
+    pub const MMD_DEVICE: u8 = 2;
+
+    bitflags! {
+        /// MMD register 2.0x0, the Common Control register
+        pub struct CommonControl: u16 {
+            const SINGLE_LED_MODE = (1 << 4);
+        }
+
+        /// MMD registers 2.0x4C and 2.0x4D, the RGMII RXC and TXC DLL Control registers
+        pub struct DllControl: u16 {
+            const BYPASS = (1 << 12);
+        }
+
+        /// Register 0x12, the LinkMD Cable Diagnostic register
+        pub struct LinkMd: u16 {
+            const ENABLE = (1 << 15);
+            const PAIR_MASK = (0b11 << 12);
+            const STATUS_MASK = (0b11 << 8);
+            const FAULT_DATA_MASK = (0xFF);
+        }
+
+        /// Register 0x1F, the PHY Control register
+        pub struct PhyControl: u16 {
+            const SPEED_1000 = (1 << 6);
+            const SPEED_100 = (1 << 5);
+            const SPEED_10 = (1 << 4);
+            const FULL_DUPLEX = (1 << 3);
+            const MASTER = (1 << 2);
+        }
+    }
+
+    impl CommonControl {
+        pub const ADDRESS: u16 = 0x00;
+    }
+
+    impl DllControl {
+        pub const RXC: u16 = 0x4C;
+        pub const TXC: u16 = 0x4D;
+    }
+
+    impl LinkMd {
+        pub const ADDRESS: u8 = 0x12;
+
+        /// Start a cable diagnostic test on `pair`
+        pub fn start(pair: u8) -> Self {
+            Self::ENABLE | Self::from_bits_truncate(((pair & 0b11) as u16) << 12)
+        }
+
+        /// The raw fault data of the last test
+        pub fn fault_data(&self) -> u8 {
+            (self.bits() & Self::FAULT_DATA_MASK.bits()) as u8
+        }
+    }
+
+    impl From<LinkMd> for CableDiagnostics {
+        fn from(lmd: LinkMd) -> Self {
+            let status = match (lmd & LinkMd::STATUS_MASK).bits() >> 8 {
+                0b00 => CablePairStatus::Normal,
+                0b01 => CablePairStatus::Open,
+                0b10 => CablePairStatus::Short,
+                _ => CablePairStatus::TestFailed,
+            };
+
+            let fault_distance_cm = match status {
+                CablePairStatus::Open | CablePairStatus::Short => {
+                    // The distance is (fault_data - 22) * 0.8 meters
+                    Some(lmd.fault_data().saturating_sub(22) as u16 * 80)
+                }
+                _ => None,
+            };
+
+            Self {
+                status,
+                fault_distance_cm,
+            }
+        }
+    }
+
+    impl PhyControl {
+        pub const ADDRESS: u8 = 0x1F;
+    }
+
+    impl From<PhyControl> for Option<AdvancedPhySpeed> {
+        fn from(ctrl: PhyControl) -> Self {
+            let full_duplex = ctrl.contains(PhyControl::FULL_DUPLEX);
+            let speed = (
+                ctrl.contains(PhyControl::SPEED_10),
+                ctrl.contains(PhyControl::SPEED_100),
+                ctrl.contains(PhyControl::SPEED_1000),
+            );
+
+            let speed = match (speed, full_duplex) {
+                ((true, false, false), false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                ((true, false, false), true) => AdvancedPhySpeed::FullDuplexBase10T,
+                ((false, true, false), false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                ((false, true, false), true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                ((false, false, true), false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                ((false, false, true), true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
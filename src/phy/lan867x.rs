@@ -0,0 +1,349 @@
+//! Phy implementation for the Microchip LAN8670/LAN8671/LAN8672 10BASE-T1S PHYs
+//!
+//! 10BASE-T1S is a 10 Mbit/s half duplex, multidrop standard, and does not
+//! support autonegotiation. Instead, access to the shared medium is either
+//! arbitrated through PLCA (Physical Layer Collision Avoidance), or through
+//! regular CSMA/CD.
+//!
+//! The vendor initialization sequence performed by [`LAN867X::phy_init`] is
+//! the one described in application note AN1699 for Rev.B1 silicon.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    CollisionDetectControl, PlcaBurst, PlcaControl0, PlcaControl1, PlcaStatus, PlcaTimer, Sts2,
+    FIXUPS, MMD_DEVICE,
+};
+
+/// The PLCA configuration of a node
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlcaConfig {
+    /// Whether PLCA is enabled
+    pub enabled: bool,
+    /// The ID of this node. Node 0 is the PLCA coordinator, `0xFF` means
+    /// that the node is not configured.
+    pub node_id: u8,
+    /// The maximum number of nodes on the segment. Only used by the coordinator.
+    pub node_count: u8,
+    /// The transmit opportunity timer, in bit times
+    pub to_timer: u8,
+    /// The maximum number of additional frames that may be sent in a single
+    /// transmit opportunity
+    pub burst_count: u8,
+    /// The time to wait for a new frame during a burst, in bit times
+    pub burst_timer: u8,
+}
+
+impl Default for PlcaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            node_id: 0xFF,
+            node_count: 8,
+            to_timer: 0x20,
+            burst_count: 0,
+            burst_timer: 0x80,
+        }
+    }
+}
+
+/// The maximum amount of times the Status 2 register is polled by
+/// [`LAN867X::phy_init`] before it gives up waiting for the reset to complete
+pub const RESET_MAX_POLLS: u32 = 100_000;
+
+/// The PHY did not report that its reset completed within
+/// [`RESET_MAX_POLLS`] reads
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetTimeout;
+
+/// A LAN8670, LAN8671 or LAN8672
+#[derive(Debug)]
+pub struct LAN867X<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> LAN867X<MIIM> {
+    /// Create a new LAN867X at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This waits until the PHY reports that its reset has completed, and
+    /// then applies the vendor initialization sequence. It must be performed
+    /// after every reset of the PHY.
+    ///
+    /// If the reset does not complete within [`RESET_MAX_POLLS`] reads of the
+    /// Status 2 register, [`ResetTimeout`] is returned and the initialization
+    /// sequence is not applied.
+    pub fn phy_init(&mut self) -> Result<(), ResetTimeout> {
+        if !(0..RESET_MAX_POLLS).any(|_| self.reset_complete()) {
+            return Err(ResetTimeout);
+        }
+
+        for (address, mask, value) in FIXUPS {
+            self.mmd_modify(MMD_DEVICE, address, mask, value);
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the PHY has completed its reset
+    pub fn reset_complete(&mut self) -> bool {
+        let raw = self.mmd_read(MMD_DEVICE, Sts2::ADDRESS);
+        // A PHY that does not respond reads as all ones
+        raw != 0xFFFF && Sts2::from_bits_truncate(raw).contains(Sts2::RESET_COMPLETE)
+    }
+
+    /// Read the current PLCA configuration
+    pub fn plca_config(&mut self) -> PlcaConfig {
+        let ctrl0 =
+            PlcaControl0::from_bits_truncate(self.mmd_read(MMD_DEVICE, PlcaControl0::ADDRESS));
+        let ctrl1 = PlcaControl1::from(self.mmd_read(MMD_DEVICE, PlcaControl1::ADDRESS));
+        let timer = PlcaTimer::from_bits_truncate(self.mmd_read(MMD_DEVICE, PlcaTimer::ADDRESS));
+        let burst = PlcaBurst::from(self.mmd_read(MMD_DEVICE, PlcaBurst::ADDRESS));
+
+        PlcaConfig {
+            enabled: ctrl0.contains(PlcaControl0::ENABLE),
+            node_id: ctrl1.node_id,
+            node_count: ctrl1.node_count,
+            to_timer: timer.to_timer(),
+            burst_count: burst.max_count,
+            burst_timer: burst.timer,
+        }
+    }
+
+    /// Apply a PLCA configuration
+    ///
+    /// Collision detection is disabled while PLCA is enabled, and enabled
+    /// otherwise, as recommended by the application note.
+    pub fn set_plca_config(&mut self, config: PlcaConfig) {
+        // Disable PLCA while reconfiguring it
+        self.mmd_modify(
+            MMD_DEVICE,
+            PlcaControl0::ADDRESS,
+            PlcaControl0::ENABLE.bits(),
+            0,
+        );
+
+        let ctrl1 = PlcaControl1 {
+            node_count: config.node_count,
+            node_id: config.node_id,
+        };
+        self.mmd_write(MMD_DEVICE, PlcaControl1::ADDRESS, ctrl1.into());
+
+        self.mmd_modify(
+            MMD_DEVICE,
+            PlcaTimer::ADDRESS,
+            PlcaTimer::TO_TIMER_MASK.bits(),
+            config.to_timer as u16,
+        );
+
+        let burst = PlcaBurst {
+            max_count: config.burst_count,
+            timer: config.burst_timer,
+        };
+        self.mmd_write(MMD_DEVICE, PlcaBurst::ADDRESS, burst.into());
+
+        self.set_collision_detection(!config.enabled);
+
+        if config.enabled {
+            self.mmd_modify(
+                MMD_DEVICE,
+                PlcaControl0::ADDRESS,
+                PlcaControl0::ENABLE.bits(),
+                PlcaControl0::ENABLE.bits(),
+            );
+        }
+    }
+
+    /// Check whether PLCA beacons are being received or transmitted
+    pub fn plca_status(&mut self) -> bool {
+        PlcaStatus::from_bits_truncate(self.mmd_read(MMD_DEVICE, PlcaStatus::ADDRESS))
+            .contains(PlcaStatus::PST)
+    }
+
+    /// Check whether collision detection is enabled
+    pub fn collision_detection(&mut self) -> bool {
+        CollisionDetectControl::from_bits_truncate(
+            self.mmd_read(MMD_DEVICE, CollisionDetectControl::ADDRESS),
+        )
+        .contains(CollisionDetectControl::ENABLE)
+    }
+
+    /// Enable or disable collision detection
+    ///
+    /// Collision detection should be disabled when PLCA is in use, as
+    /// the PHY may otherwise report false collisions.
+    pub fn set_collision_detection(&mut self, enabled: bool) {
+        let enable = CollisionDetectControl::ENABLE.bits();
+        let value = if enabled { enable } else { 0 };
+        self.mmd_modify(MMD_DEVICE, CollisionDetectControl::ADDRESS, enable, value);
+    }
+
+    /// Check whether a link is established or not
+    ///
+    /// 10BASE-T1S has no link negotiation, so this only reflects the link
+    /// status reported by the PHY.
+    pub fn link_established(&mut self) -> bool {
+        self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for LAN867X<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        // 10BASE-T1S does not support autonegotiation
+        AutoNegotiationAdvertisement::default()
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! LAN867X registers
+
+    use bitflags::bitflags;
+
+    /// The MMD device containing the vendor specific and PLCA registers
+    pub const MMD_DEVICE: u8 = 31;
+
+    /// The vendor initialization sequence from AN1699, as
+    /// `(address, mask, value)` for MMD device [`MMD_DEVICE`].
+    pub const FIXUPS: [(u16, u16, u16); 12] = [
+        (0x00D0, 0x0E03, 0x0002),
+        (0x00D1, 0x0300, 0x0000),
+        (0x0084, 0xFFC0, 0x3380),
+        (0x0085, 0x000F, 0x0006),
+        (0x008A, 0xF800, 0xC000),
+        (0x0087, 0x801C, 0x801C),
+        (0x0088, 0x1FFF, 0x033F),
+        (0x008B, 0xFFFF, 0x0404),
+        (0x0080, 0x0600, 0x0600),
+        (0x00F1, 0x7F00, 0x2400),
+        (0x0096, 0x2000, 0x2000),
+        (0x0099, 0xFFFF, 0x7F80),
+    ];
+
+    bitflags! {
+        /// MMD register 31.0x19, the Status 2 register
+        pub struct Sts2: u16 {
+            const RESET_COMPLETE = (1 << 11);
+        }
+
+        /// MMD register 31.0x87, the Collision Detector Control 0 register
+        pub struct CollisionDetectControl: u16 {
+            const ENABLE = (1 << 15);
+        }
+
+        /// MMD register 31.0xCA01, the PLCA Control 0 register
+        pub struct PlcaControl0: u16 {
+            const ENABLE = (1 << 15);
+            const RESET = (1 << 14);
+        }
+
+        /// MMD register 31.0xCA03, the PLCA Status register
+        pub struct PlcaStatus: u16 {
+            const PST = (1 << 15);
+        }
+
+        /// MMD register 31.0xCA04, the PLCA Transmit Opportunity Timer register
+        pub struct PlcaTimer: u16 {
+            const TO_TIMER_MASK = (0xFF);
+        }
+    }
+
+    impl Sts2 {
+        pub const ADDRESS: u16 = 0x19;
+    }
+
+    impl CollisionDetectControl {
+        pub const ADDRESS: u16 = 0x87;
+    }
+
+    impl PlcaControl0 {
+        pub const ADDRESS: u16 = 0xCA01;
+    }
+
+    impl PlcaStatus {
+        pub const ADDRESS: u16 = 0xCA03;
+    }
+
+    impl PlcaTimer {
+        pub const ADDRESS: u16 = 0xCA04;
+
+        /// The transmit opportunity timer, in bit times
+        pub fn to_timer(&self) -> u8 {
+            (self.bits() & Self::TO_TIMER_MASK.bits()) as u8
+        }
+    }
+
+    /// MMD register 31.0xCA02, the PLCA Control 1 register
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PlcaControl1 {
+        pub node_count: u8,
+        pub node_id: u8,
+    }
+
+    impl PlcaControl1 {
+        pub const ADDRESS: u16 = 0xCA02;
+    }
+
+    impl From<u16> for PlcaControl1 {
+        fn from(value: u16) -> Self {
+            Self {
+                node_count: (value >> 8) as u8,
+                node_id: value as u8,
+            }
+        }
+    }
+
+    impl From<PlcaControl1> for u16 {
+        fn from(ctrl: PlcaControl1) -> Self {
+            (ctrl.node_count as u16) << 8 | ctrl.node_id as u16
+        }
+    }
+
+    /// MMD register 31.0xCA05, the PLCA Burst Mode register
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PlcaBurst {
+        pub max_count: u8,
+        pub timer: u8,
+    }
+
+    impl PlcaBurst {
+        pub const ADDRESS: u16 = 0xCA05;
+    }
+
+    impl From<u16> for PlcaBurst {
+        fn from(value: u16) -> Self {
+            Self {
+                max_count: (value >> 8) as u8,
+                timer: value as u8,
+            }
+        }
+    }
+
+    impl From<PlcaBurst> for u16 {
+        fn from(burst: PlcaBurst) -> Self {
+            (burst.max_count as u16) << 8 | burst.timer as u16
+        }
+    }
+}
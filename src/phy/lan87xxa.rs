@@ -1,18 +1,53 @@
-//! SMSC LAN87xxA (LAN8742A, LAN8720A) Ethernet PHYs
+//! SMSC LAN87xxA (LAN8742A, LAN8741A, LAN8740A, LAN8720A, LAN8710A) Ethernet PHYs
 
 use crate::{
     phy::lan87xxa::registers::InterruptReg, registers::Esr, AutoNegotiationAdvertisement,
-    ExtendedPhyStatus, Miim, Phy, PhyStatus,
+    ExtendedPhyStatus, Miim, Phy, PhyAddress, PhyStatus,
 };
 
-use self::registers::{Ssr, PHY_REG_WUCSR};
+use self::registers::{
+    ModeControlStatus, SpecialControlStatus, Ssr, PHY_REG_WUCSR, SYMBOL_ERROR_COUNTER,
+};
+
+use super::{
+    AdvancedPhySpeed, LinkCounters, Loopback, MdiMode, PhySpeed, PhyWithAutonegInterrupt,
+    PhyWithCableDetect, PhyWithCounters, PhyWithEnergyDetect, PhyWithInterrupts, PhyWithLoopback,
+    PhyWithMdix, PhyWithSpeed,
+};
 
-use super::{AdvancedPhySpeed, PhySpeed, PhyWithSpeed};
+#[cfg(feature = "lan8742a")]
+use self::registers::EdpdConfig;
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+use self::registers::TdrControlStatus;
+#[cfg(feature = "lan8710a")]
+use self::registers::{PhyMode, SpecialModes};
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+use self::registers::{Wucsr, WufCfgA, RX_ADDRA, RX_ADDRB, RX_ADDRC, WUF_CFGB, WUF_MASK};
+#[cfg(any(feature = "lan8740a", feature = "lan8741a"))]
+use super::PhyWithEee;
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+use super::WakeOnLanEvents;
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+use super::{CableDiagnostics, CablePairStatus};
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+use super::{PhyWithCableDiagnostics, PhyWithWakeOnLan};
 
 /// SMSC LAN8720A Ethernet PHY
-pub type LAN8720A<MIIM> = LAN87xxA<MIIM, false>;
+pub type LAN8720A<MIIM> = LAN87xxA<MIIM, 8720>;
 /// SMSC LAN8742A Ethernet PHY
-pub type LAN8742A<MIIM> = LAN87xxA<MIIM, true>;
+pub type LAN8742A<MIIM> = LAN87xxA<MIIM, 8742>;
+/// SMSC LAN8740A Ethernet PHY
+pub type LAN8740A<MIIM> = LAN87xxA<MIIM, 8740>;
+/// SMSC LAN8741A Ethernet PHY
+///
+/// Unlike the LAN8740A and LAN8742A, the LAN8741A does not support
+/// Wake-on-LAN.
+pub type LAN8741A<MIIM> = LAN87xxA<MIIM, 8741>;
+/// SMSC LAN8710A Ethernet PHY
+///
+/// The LAN8710A shares its PHY identifier with the LAN8720A, so both
+/// conversions from [`BarePhy`](super::BarePhy) succeed for either PHY.
+pub type LAN8710A<MIIM> = LAN87xxA<MIIM, 8710>;
 
 /// All interrupt sources supported by this chip
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -32,8 +67,8 @@ pub enum Interrupt {
     AutoNegotiationComplete,
     /// PoE Energy was turned on
     EnergyOn,
-    /// A Wake on Lan packet was received (only supported on LAN8742A)
-    #[cfg(feature = "lan8742a")]
+    /// A Wake on Lan packet was received (only supported on LAN8740A and LAN8742A)
+    #[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
     WoL,
 }
 
@@ -47,33 +82,230 @@ impl From<Interrupt> for InterruptReg {
             Interrupt::RemoteFault => InterruptReg::INT5_REMOTE_FAULT,
             Interrupt::AutoNegotiationComplete => InterruptReg::INT6_AUTO_NEG_COMPLETE,
             Interrupt::EnergyOn => InterruptReg::INT7_ENERGYON,
-            #[cfg(feature = "lan8742a")]
+            #[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
             Interrupt::WoL => InterruptReg::INT8_WOL,
         }
     }
 }
 
+impl Interrupt {
+    /// All interrupts supported by the enabled LAN87xx PHYs
+    pub const ALL: &'static [Interrupt] = &[
+        Interrupt::AutoNegotiationPageRecvd,
+        Interrupt::ParallelDetectionFault,
+        Interrupt::AutoNegotiationLpAck,
+        Interrupt::LinkDown,
+        Interrupt::RemoteFault,
+        Interrupt::AutoNegotiationComplete,
+        Interrupt::EnergyOn,
+        #[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+        Interrupt::WoL,
+    ];
+}
+
+/// A set of [`Interrupt`]s
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterruptSet(InterruptReg);
+
+impl InterruptSet {
+    /// An empty set of interrupts
+    pub fn empty() -> Self {
+        Self(InterruptReg::empty())
+    }
+
+    /// Check whether `interrupt` is part of this set
+    pub fn contains(&self, interrupt: Interrupt) -> bool {
+        self.0.contains(interrupt.into())
+    }
+
+    /// Check whether this set is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add `interrupt` to this set
+    pub fn insert(&mut self, interrupt: Interrupt) {
+        self.0.insert(interrupt.into())
+    }
+
+    /// Remove `interrupt` from this set
+    pub fn remove(&mut self, interrupt: Interrupt) {
+        self.0.remove(interrupt.into())
+    }
+
+    /// Iterate over all interrupts in this set
+    pub fn iter(&self) -> impl Iterator<Item = Interrupt> + '_ {
+        Interrupt::ALL
+            .iter()
+            .copied()
+            .filter(move |&int| self.contains(int))
+    }
+
+    /// The raw register value of this set
+    pub fn bits(&self) -> u16 {
+        self.0.bits()
+    }
+}
+
+impl From<InterruptReg> for InterruptSet {
+    fn from(reg: InterruptReg) -> Self {
+        Self(reg)
+    }
+}
+
+impl From<InterruptSet> for InterruptReg {
+    fn from(set: InterruptSet) -> Self {
+        set.0
+    }
+}
+
+impl FromIterator<Interrupt> for InterruptSet {
+    fn from_iter<T: IntoIterator<Item = Interrupt>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        iter.into_iter().for_each(|int| set.insert(int));
+        set
+    }
+}
+
+/// A wakeup frame filter
+///
+/// A received frame matches the filter if the CRC-16 of the bytes selected by
+/// `byte_mask`, starting at `pattern_offset` bytes into the frame, equals
+/// `crc`, and its destination address matches one of the enabled address
+/// types.
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct WakeupFrameFilter {
+    /// The offset of the first byte of the pattern in the frame
+    pub pattern_offset: u8,
+    /// The expected CRC-16 of the selected bytes
+    pub crc: u16,
+    /// The bytes that are included in the CRC. Bit `n` selects byte
+    /// `pattern_offset + n`.
+    pub byte_mask: u128,
+    /// Match frames addressed to the configured MAC address
+    pub match_address: bool,
+    /// Match multicast frames
+    pub match_multicast: bool,
+    /// Match broadcast frames
+    pub match_broadcast: bool,
+}
+
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+impl WakeupFrameFilter {
+    /// Create a filter that matches the bytes of `pattern` that are selected
+    /// by `byte_mask`, with the pattern starting `pattern_offset` bytes into
+    /// the frame.
+    ///
+    /// Bit `n` of `byte_mask` selects `pattern[n]`. Bits that select bytes
+    /// beyond the end of `pattern` are cleared. No address matching is
+    /// enabled.
+    pub fn from_pattern(pattern_offset: u8, pattern: &[u8], byte_mask: u128) -> Self {
+        let byte_mask = match pattern.len() {
+            len if len >= 128 => byte_mask,
+            len => byte_mask & ((1 << len) - 1),
+        };
+
+        Self {
+            pattern_offset,
+            crc: Self::crc(pattern, byte_mask),
+            byte_mask,
+            ..Default::default()
+        }
+    }
+
+    /// Calculate the CRC-16 of the bytes of `pattern` that are selected by
+    /// `byte_mask`, as expected by the wakeup frame filter.
+    ///
+    /// This is the CRC-16 with polynomial `x^16 + x^15 + x^2 + 1` and an
+    /// initial value of `0xFFFF`, where the bits of each byte are shifted in
+    /// least significant bit first.
+    pub fn crc(pattern: &[u8], byte_mask: u128) -> u16 {
+        let mut crc = 0xFFFF_u16;
+
+        let selected = pattern
+            .iter()
+            .take(128)
+            .enumerate()
+            .filter(|(idx, _)| byte_mask & (1 << idx) != 0);
+
+        for (_, byte) in selected {
+            for bit in 0..8 {
+                let data = (byte >> bit) & 1 == 1;
+                let msb = crc & 0x8000 != 0;
+                crc <<= 1;
+                if data != msb {
+                    crc ^= 0x8005;
+                }
+            }
+        }
+
+        crc
+    }
+}
+
+/// The MAC interface used by the LAN8710A
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MiiMode {
+    /// Media Independent Interface
+    Mii,
+    /// Reduced Media Independent Interface
+    Rmii,
+}
+
+/// The interval at which normal link pulses are transmitted while in
+/// Energy Detect Power-Down mode
+#[cfg(feature = "lan8742a")]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NlpInterval {
+    /// 1 second
+    Ms1000,
+    /// 768 milliseconds
+    Ms768,
+    /// 512 milliseconds
+    Ms512,
+    /// 256 milliseconds
+    Ms256,
+}
+
 /// An SMSC LAN87XXA Ethernet PHY.
 ///
-/// EXT_WUCSR_CLEAR is used to determine if the "WU CSR" bit
-/// in extended registers should be cleared
+/// `MODEL` is the model number of the PHY, and determines which of the
+/// model specific features are available.
 ///
-/// This type should not be used directly. Use [`LAN8720A`] or [`LAN8742A`] instead.
+/// This type should not be used directly. Use [`LAN8720A`], [`LAN8742A`],
+/// [`LAN8741A`], [`LAN8740A`] or [`LAN8710A`] instead.
 #[derive(Debug)]
-pub struct LAN87xxA<M: Miim, const HAS_MMD: bool> {
-    phy_addr: u8,
+pub struct LAN87xxA<M: Miim, const MODEL: u16> {
+    phy_addr: PhyAddress,
     miim: M,
 }
 
-impl<M: Miim, const HAS_MMD: bool> LAN87xxA<M, HAS_MMD> {
+impl<M: Miim, const MODEL: u16> LAN87xxA<M, MODEL> {
+    /// Whether this model has the Wake-on-LAN registers, whose "WU CSR"
+    /// register is cleared by [`Self::phy_init`]
+    const HAS_WOL: bool = matches!(MODEL, 8740 | 8742);
+
     /// Create a new LAN87XXA based PHY
-    pub fn new(miim: M, phy_addr: u8) -> Self {
+    pub fn new(miim: M, phy_addr: PhyAddress) -> Self {
         LAN87xxA { miim, phy_addr }
     }
 
+    /// Create a new LAN87XXA based PHY at the raw address `phy_addr`
+    ///
+    /// # Panics
+    /// This function panics if `phy_addr` is not less than 32.
+    #[deprecated(note = "use `new` with a `PhyAddress` instead")]
+    pub fn from_raw_address(miim: M, phy_addr: u8) -> Self {
+        Self::new(miim, PhyAddress::from_const(phy_addr))
+    }
+
     /// Initialize the PHY
     pub fn phy_init(&mut self) {
-        if HAS_MMD {
+        if Self::HAS_WOL {
             // Clear WU CSR
             self.mmd_write(3, PHY_REG_WUCSR, 0);
         }
@@ -92,11 +324,7 @@ impl<M: Miim, const HAS_MMD: bool> LAN87xxA<M, HAS_MMD> {
 
     /// Check if the link is up
     pub fn link_established(&mut self) -> bool {
-        let bsr = self.bsr();
-        let ssr = Ssr::from_bits_truncate(self.read(Ssr::ADDRESS));
-
-        // Link established only if it's up, and autonegotiation is completed
-        bsr.phy_link_up() && bsr.autoneg_completed() && ssr.contains(Ssr::AUTONEG_DONE)
+        link_established(self)
     }
 
     /// Block until a link is established
@@ -106,10 +334,22 @@ impl<M: Miim, const HAS_MMD: bool> LAN87xxA<M, HAS_MMD> {
 
     /// Enable an interrupt
     pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
-        let mut reg_val =
-            unsafe { InterruptReg::from_bits_unchecked(self.read(InterruptReg::MASK_ADDR)) };
-        reg_val.insert(interrupt.into());
-        self.write(InterruptReg::MASK_ADDR, reg_val.bits());
+        enable_interrupt(self, interrupt)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        disable_interrupt(self, interrupt)
+    }
+
+    /// Enable exactly the interrupts in `interrupts`, and disable all others
+    pub fn set_interrupt_mask(&mut self, interrupts: &[Interrupt]) {
+        set_interrupt_mask(self, interrupts)
+    }
+
+    /// Get the interrupts that are currently enabled
+    pub fn get_interrupt_mask(&mut self) -> InterruptSet {
+        get_interrupt_mask(self)
     }
 
     /// Read and clear all interrupts
@@ -117,43 +357,71 @@ impl<M: Miim, const HAS_MMD: bool> LAN87xxA<M, HAS_MMD> {
         &mut self,
         interrupt_storage: &mut [Option<Interrupt>; 8],
     ) {
-        let reg_val =
-            unsafe { InterruptReg::from_bits_unchecked(self.read(InterruptReg::SOURCE_ADDR)) };
-
-        let mut int_idx = 0;
-        macro_rules! int {
-            ($flag:expr, $int:expr) => {
-                #[allow(unused_assignments)]
-                if reg_val.contains($flag) {
-                    interrupt_storage[int_idx] = Some($int);
-                    int_idx += 1;
-                }
-            };
-        }
+        read_and_clear_active_interrupts(self, interrupt_storage)
+    }
 
-        int!(
-            InterruptReg::INT1_AUTO_NEG_PAGE_RECVD,
-            Interrupt::AutoNegotiationPageRecvd
-        );
-        int!(
-            InterruptReg::INT2_PARALLELL_DETECTION_FAULT,
-            Interrupt::ParallelDetectionFault
-        );
-        int!(
-            InterruptReg::INT3_AUTO_NEG_LP_ACK,
-            Interrupt::AutoNegotiationLpAck
-        );
-        int!(InterruptReg::INT4_LINK_DOWN, Interrupt::LinkDown);
-        int!(InterruptReg::INT5_REMOTE_FAULT, Interrupt::RemoteFault);
-        int!(
-            InterruptReg::INT6_AUTO_NEG_COMPLETE,
-            Interrupt::AutoNegotiationComplete
+    /// Read and clear all interrupts, returning the interrupts that were
+    /// active
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptSet {
+        read_and_clear_interrupts(self)
+    }
+
+    /// Read the Mode Control/Status register
+    pub fn mode_control_status(&mut self) -> ModeControlStatus {
+        ModeControlStatus::from_bits_truncate(self.read(ModeControlStatus::ADDRESS))
+    }
+
+    /// Check whether Energy Detect Power-Down is enabled
+    pub fn energy_detect_power_down(&mut self) -> bool {
+        self.mode_control_status()
+            .contains(ModeControlStatus::EDPWRDOWN)
+    }
+
+    /// Enable or disable Energy Detect Power-Down
+    ///
+    /// While enabled, the PHY powers down its analog circuitry when no energy
+    /// is detected on the cable, and powers up again once energy is detected.
+    pub fn set_energy_detect_power_down(&mut self, enabled: bool) {
+        let bit = ModeControlStatus::EDPWRDOWN.bits();
+        self.modify(
+            ModeControlStatus::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
         );
+    }
 
-        int!(InterruptReg::INT7_ENERGYON, Interrupt::EnergyOn);
+    /// Check whether the PHY is currently powered down because no energy is
+    /// detected on the cable
+    pub fn in_energy_detect_power_down(&mut self) -> bool {
+        let mcs = self.mode_control_status();
+        mcs.contains(ModeControlStatus::EDPWRDOWN) && !mcs.contains(ModeControlStatus::ENERGYON)
+    }
 
-        #[cfg(feature = "lan8742a")]
-        int!(InterruptReg::INT8_WOL, Interrupt::WoL);
+    /// Get the forced pair assignment, or `None` if auto MDI/MDI-X is enabled
+    pub fn mdi_mode(&mut self) -> Option<MdiMode> {
+        let special =
+            SpecialControlStatus::from_bits_truncate(self.read(SpecialControlStatus::ADDRESS));
+        if !special.contains(SpecialControlStatus::AMDIXCTRL) {
+            None
+        } else if special.contains(SpecialControlStatus::CH_SELECT) {
+            Some(MdiMode::Mdix)
+        } else {
+            Some(MdiMode::Mdi)
+        }
+    }
+
+    /// Force the pair assignment to `mode`, or enable auto MDI/MDI-X if
+    /// `mode` is `None`
+    pub fn set_mdi_mode(&mut self, mode: Option<MdiMode>) {
+        let value = match mode {
+            None => SpecialControlStatus::empty(),
+            Some(MdiMode::Mdi) => SpecialControlStatus::AMDIXCTRL,
+            Some(MdiMode::Mdix) => {
+                SpecialControlStatus::AMDIXCTRL | SpecialControlStatus::CH_SELECT
+            }
+        };
+        let mask = SpecialControlStatus::AMDIXCTRL | SpecialControlStatus::CH_SELECT;
+        self.modify(SpecialControlStatus::ADDRESS, mask.bits(), value.bits());
     }
 
     /// Release the underlying [`Miim`]
@@ -162,7 +430,7 @@ impl<M: Miim, const HAS_MMD: bool> LAN87xxA<M, HAS_MMD> {
     }
 }
 
-impl<M: Miim, const E: bool> Phy<M> for LAN87xxA<M, E> {
+impl<M: Miim, const MODEL: u16> Phy<M> for LAN87xxA<M, MODEL> {
     fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
         AutoNegotiationAdvertisement {
             hd_10base_t: true,
@@ -178,7 +446,7 @@ impl<M: Miim, const E: bool> Phy<M> for LAN87xxA<M, E> {
         &mut self.miim
     }
 
-    fn get_phy_addr(&self) -> u8 {
+    fn phy_address(&self) -> PhyAddress {
         self.phy_addr
     }
 
@@ -206,21 +474,504 @@ impl<M: Miim, const E: bool> Phy<M> for LAN87xxA<M, E> {
     }
 }
 
-impl<M: Miim, const E: bool> PhyWithSpeed<M> for LAN87xxA<M, E> {
+impl<M: Miim, const MODEL: u16> PhyWithSpeed<M> for LAN87xxA<M, MODEL> {
     fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
         self.link_speed().map(Into::into)
     }
 }
 
+impl<M: Miim, const MODEL: u16> PhyWithLoopback<M> for LAN87xxA<M, MODEL> {
+    fn loopback(&mut self) -> Option<Loopback> {
+        if self.bcr().loopback() {
+            Some(Loopback::Near)
+        } else if self
+            .mode_control_status()
+            .contains(ModeControlStatus::FARLOOPBACK)
+        {
+            Some(Loopback::Far)
+        } else {
+            None
+        }
+    }
+
+    fn set_loopback(&mut self, loopback: Option<Loopback>) {
+        self.modify_bcr(|bcr| {
+            bcr.set_loopback(loopback == Some(Loopback::Near));
+        });
+
+        let far = ModeControlStatus::FARLOOPBACK.bits();
+        self.modify(
+            ModeControlStatus::ADDRESS,
+            far,
+            if loopback == Some(Loopback::Far) {
+                far
+            } else {
+                0
+            },
+        );
+    }
+}
+
+impl<M: Miim, const MODEL: u16> PhyWithCounters<M> for LAN87xxA<M, MODEL> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        LinkCounters {
+            // The counter is cleared by reading it
+            symbol_errors: Some(self.read(SYMBOL_ERROR_COUNTER)),
+            ..Default::default()
+        }
+    }
+}
+
+impl<M: Miim, const MODEL: u16> PhyWithMdix<M> for LAN87xxA<M, MODEL> {
+    fn mdi_mode(&mut self) -> Option<MdiMode> {
+        self.mdi_mode()
+    }
+
+    fn set_mdi_mode(&mut self, mode: Option<MdiMode>) {
+        self.set_mdi_mode(mode)
+    }
+
+    /// The LAN87xxA does not report the pair assignment chosen by auto
+    /// MDI/MDI-X, so this always returns `None`.
+    fn mdi_status(&mut self) -> Option<MdiMode> {
+        None
+    }
+}
+
+impl<M: Miim, const MODEL: u16> PhyWithCableDetect<M> for LAN87xxA<M, MODEL> {
+    fn energy_detected(&mut self) -> bool {
+        self.mode_control_status()
+            .contains(ModeControlStatus::ENERGYON)
+    }
+}
+
+impl<M: Miim, const MODEL: u16> PhyWithEnergyDetect<M> for LAN87xxA<M, MODEL> {
+    fn energy_detect_power_down(&mut self) -> bool {
+        self.energy_detect_power_down()
+    }
+
+    fn set_energy_detect_power_down(&mut self, enabled: bool) {
+        self.set_energy_detect_power_down(enabled)
+    }
+}
+
+#[cfg(feature = "lan8710a")]
+impl<M: Miim> LAN87xxA<M, 8710> {
+    /// Read the Special Modes register
+    pub fn special_modes(&mut self) -> SpecialModes {
+        SpecialModes::from_bits_truncate(self.read(SpecialModes::ADDRESS))
+    }
+
+    /// Write the Special Modes register.
+    ///
+    /// The new configuration only takes effect after a reset.
+    pub fn set_special_modes(&mut self, special_modes: SpecialModes) {
+        self.write(SpecialModes::ADDRESS, special_modes.bits());
+    }
+
+    /// Get the MAC interface mode
+    pub fn mii_mode(&mut self) -> MiiMode {
+        self.special_modes().mii_mode()
+    }
+
+    /// Set the MAC interface mode.
+    ///
+    /// The new mode only takes effect after a reset.
+    pub fn set_mii_mode(&mut self, mode: MiiMode) {
+        let mut special_modes = SpecialModes::empty();
+        special_modes.set_mii_mode(mode);
+        self.modify(
+            SpecialModes::ADDRESS,
+            SpecialModes::MII_MODE.bits(),
+            special_modes.bits(),
+        );
+    }
+
+    /// Get the PHY mode configured in the Special Modes register
+    pub fn phy_mode(&mut self) -> PhyMode {
+        self.special_modes().phy_mode()
+    }
+}
+
+#[cfg(feature = "lan8742a")]
+impl<M: Miim> LAN87xxA<M, 8742> {
+    /// Configure the transmission of normal link pulses while in Energy
+    /// Detect Power-Down mode, or disable it if `interval` is `None`
+    ///
+    /// Transmitting link pulses allows two PHYs that both have Energy Detect
+    /// Power-Down enabled to detect each other.
+    pub fn set_edpd_tx_nlp(&mut self, interval: Option<NlpInterval>) {
+        let mask = (EdpdConfig::TX_NLP_ENABLE | EdpdConfig::TX_NLP_INTERVAL_MASK).bits();
+        let value = interval
+            .map(EdpdConfig::from)
+            .unwrap_or(EdpdConfig::empty());
+        self.modify(EdpdConfig::ADDRESS, mask, value.bits());
+    }
+}
+
+#[cfg(feature = "lan8740a")]
+impl<M: Miim> PhyWithEee<M> for LAN87xxA<M, 8740> {}
+
+#[cfg(feature = "lan8741a")]
+impl<M: Miim> PhyWithEee<M> for LAN87xxA<M, 8741> {}
+
+/// Implement Wake-on-LAN and TDR cable diagnostics for the models that
+/// support them
+macro_rules! wol_and_tdr {
+    ($([$feat:literal, $model:literal],)*) => {
+        $(
+            #[cfg(feature = $feat)]
+            impl<M: Miim> LAN87xxA<M, $model> {
+                /// Configure the wakeup frame filter, or disable it if `filter` is `None`
+                ///
+                /// Wakeup frames must also be enabled with
+                /// [`PhyWithWakeOnLan::set_wol_events`].
+                pub fn set_wakeup_frame_filter(&mut self, filter: Option<WakeupFrameFilter>) {
+                    set_wakeup_frame_filter(self, filter)
+                }
+
+                /// Check whether the wakeup frame filter was triggered, and clear the
+                /// indication
+                pub fn read_and_clear_wakeup_frame_filter_triggered(&mut self) -> bool {
+                    read_and_clear_wakeup_frame_filter_triggered(self)
+                }
+            }
+
+            #[cfg(feature = $feat)]
+            impl<M: Miim> PhyWithWakeOnLan<M> for LAN87xxA<M, $model> {
+                fn set_wol_mac_address(&mut self, mac_address: [u8; 6]) {
+                    set_wol_mac_address(self, mac_address)
+                }
+
+                fn wol_events(&mut self) -> WakeOnLanEvents {
+                    wol_events(self)
+                }
+
+                fn set_wol_events(&mut self, events: WakeOnLanEvents) {
+                    set_wol_events(self, events)
+                }
+
+                fn read_and_clear_wol_status(&mut self) -> WakeOnLanEvents {
+                    read_and_clear_wol_status(self)
+                }
+            }
+
+            #[cfg(feature = $feat)]
+            impl<M: Miim> PhyWithCableDiagnostics<M> for LAN87xxA<M, $model> {
+                fn cable_pairs(&self) -> u8 {
+                    2
+                }
+
+                fn test_cable_pair(&mut self, pair: u8) -> Option<CableDiagnostics> {
+                    test_cable_pair(self, pair)
+                }
+            }
+        )*
+    };
+}
+
+wol_and_tdr!(["lan8740a", 8740], ["lan8742a", 8742],);
+
+// The following functions are shared by all PHYs that use the LAN87xx register map.
+
+/// Check if the link is up, and autonegotiation is completed
+pub(crate) fn link_established<M: Miim, P: Phy<M>>(phy: &mut P) -> bool {
+    let bsr = phy.bsr();
+    let ssr = Ssr::from_bits_truncate(phy.read(Ssr::ADDRESS));
+
+    // Link established only if it's up, and autonegotiation is completed
+    bsr.phy_link_up() && bsr.autoneg_completed() && ssr.contains(Ssr::AUTONEG_DONE)
+}
+
+/// Enable an interrupt
+pub(crate) fn enable_interrupt<M: Miim, P: Phy<M>>(phy: &mut P, interrupt: Interrupt) {
+    let mut reg_val =
+        unsafe { InterruptReg::from_bits_unchecked(phy.read(InterruptReg::MASK_ADDR)) };
+    reg_val.insert(interrupt.into());
+    phy.write(InterruptReg::MASK_ADDR, reg_val.bits());
+}
+
+/// Disable an interrupt
+pub(crate) fn disable_interrupt<M: Miim, P: Phy<M>>(phy: &mut P, interrupt: Interrupt) {
+    let flag = InterruptReg::from(interrupt).bits();
+    phy.modify(InterruptReg::MASK_ADDR, flag, 0);
+}
+
+/// Enable exactly the interrupts in `interrupts`, and disable all others
+pub(crate) fn set_interrupt_mask<M: Miim, P: Phy<M>>(phy: &mut P, interrupts: &[Interrupt]) {
+    let mask: InterruptSet = interrupts.iter().copied().collect();
+    phy.write(InterruptReg::MASK_ADDR, mask.bits());
+}
+
+/// Get the interrupts that are currently enabled
+pub(crate) fn get_interrupt_mask<M: Miim, P: Phy<M>>(phy: &mut P) -> InterruptSet {
+    InterruptReg::from_bits_truncate(phy.read(InterruptReg::MASK_ADDR)).into()
+}
+
+/// Read and clear all interrupts
+pub(crate) fn read_and_clear_active_interrupts<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    interrupt_storage: &mut [Option<Interrupt>; 8],
+) {
+    let active = read_and_clear_interrupts(phy);
+
+    for (slot, interrupt) in interrupt_storage.iter_mut().zip(active.iter()) {
+        *slot = Some(interrupt);
+    }
+}
+
+/// Read and clear all interrupts
+pub(crate) fn read_and_clear_interrupts<M: Miim, P: Phy<M>>(phy: &mut P) -> InterruptSet {
+    InterruptReg::from_bits_truncate(phy.read(InterruptReg::SOURCE_ADDR)).into()
+}
+
+/// The maximum amount of times the TDR Control/Status register is polled
+/// before a test is considered to have failed.
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+const TDR_MAX_POLLS: u32 = 100_000;
+
+/// Run a TDR cable diagnostics test on `pair` (0 for the TX pair, 1 for the RX pair)
+///
+/// If the test does not complete within [`TDR_MAX_POLLS`] reads of the TDR
+/// Control/Status register, or the PHY does not respond, the result is
+/// [`CablePairStatus::TestFailed`]. The Basic Control and Special
+/// Control/Status Indication registers are restored in either case.
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn test_cable_pair<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    pair: u8,
+) -> Option<CableDiagnostics> {
+    if pair >= 2 {
+        return None;
+    }
+
+    let bcr = phy.bcr();
+    let special_control = phy.read(SpecialControlStatus::ADDRESS);
+
+    // Auto-MDIX must be disabled, and the PHY must be forced into 100BASE-TX
+    // full duplex mode for the duration of the test
+    let mdix = SpecialControlStatus::AMDIXCTRL.bits() | SpecialControlStatus::CH_SELECT.bits();
+    phy.modify(
+        SpecialControlStatus::ADDRESS,
+        mdix,
+        SpecialControlStatus::AMDIXCTRL.bits(),
+    );
+    phy.modify_bcr(|bcr| {
+        bcr.set_autonegotiation(false).set_full_duplex(true);
+        bcr.remove(crate::Bcr::SPEED_SEL_MSB);
+        bcr.insert(crate::Bcr::SPEED_SEL_LSB);
+    });
+
+    phy.write(
+        TdrControlStatus::ADDRESS,
+        TdrControlStatus::start(pair).bits(),
+    );
+
+    let mut result = CableDiagnostics {
+        status: CablePairStatus::TestFailed,
+        fault_distance_cm: None,
+    };
+    for _ in 0..TDR_MAX_POLLS {
+        let raw = phy.read(TdrControlStatus::ADDRESS);
+        // An absent PHY reads as all ones, which would look like a completed test
+        if raw == 0xFFFF {
+            break;
+        }
+
+        let tdr = TdrControlStatus::from_bits_truncate(raw);
+        if tdr.contains(TdrControlStatus::CHANNEL_STATUS) {
+            result = tdr.into();
+            break;
+        }
+    }
+
+    phy.write(TdrControlStatus::ADDRESS, 0);
+    phy.write(SpecialControlStatus::ADDRESS, special_control);
+    phy.write(crate::Bcr::ADDRESS, bcr.bits());
+    if bcr.autonegotiation() {
+        phy.modify_bcr(|bcr| {
+            bcr.restart_autonegotiation();
+        });
+    }
+
+    Some(result)
+}
+
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+impl From<WakeOnLanEvents> for Wucsr {
+    fn from(events: WakeOnLanEvents) -> Self {
+        let mut wucsr = Wucsr::empty();
+        wucsr.set(
+            Wucsr::MAGIC_PACKET_EN,
+            events.contains(WakeOnLanEvents::MAGIC_PACKET),
+        );
+        wucsr.set(
+            Wucsr::BROADCAST_EN,
+            events.contains(WakeOnLanEvents::BROADCAST),
+        );
+        wucsr.set(
+            Wucsr::PERFECT_DA_EN,
+            events.contains(WakeOnLanEvents::PERFECT_DA),
+        );
+        wucsr.set(
+            Wucsr::WAKEUP_FRAME_EN,
+            events.contains(WakeOnLanEvents::WAKEUP_FRAME),
+        );
+        wucsr
+    }
+}
+
+/// Set the MAC address used for Wake-on-LAN
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn set_wol_mac_address<M: Miim, P: Phy<M> + Sized>(phy: &mut P, mac: [u8; 6]) {
+    phy.mmd_write(3, RX_ADDRA, u16::from_le_bytes([mac[0], mac[1]]));
+    phy.mmd_write(3, RX_ADDRB, u16::from_le_bytes([mac[2], mac[3]]));
+    phy.mmd_write(3, RX_ADDRC, u16::from_le_bytes([mac[4], mac[5]]));
+}
+
+/// Get the enabled Wake-on-LAN events
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn wol_events<M: Miim, P: Phy<M> + Sized>(phy: &mut P) -> WakeOnLanEvents {
+    let wucsr = Wucsr::from_bits_truncate(phy.mmd_read(3, PHY_REG_WUCSR));
+
+    let mut events = WakeOnLanEvents::empty();
+    events.set(
+        WakeOnLanEvents::MAGIC_PACKET,
+        wucsr.contains(Wucsr::MAGIC_PACKET_EN),
+    );
+    events.set(
+        WakeOnLanEvents::BROADCAST,
+        wucsr.contains(Wucsr::BROADCAST_EN),
+    );
+    events.set(
+        WakeOnLanEvents::PERFECT_DA,
+        wucsr.contains(Wucsr::PERFECT_DA_EN),
+    );
+    events.set(
+        WakeOnLanEvents::WAKEUP_FRAME,
+        wucsr.contains(Wucsr::WAKEUP_FRAME_EN),
+    );
+    events
+}
+
+/// Enable the given Wake-on-LAN events
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn set_wol_events<M: Miim, P: Phy<M> + Sized>(phy: &mut P, events: WakeOnLanEvents) {
+    let enables = Wucsr::ENABLE_MASK.bits() | Wucsr::WOL_CONFIGURED.bits();
+    let mut wucsr = Wucsr::from(events);
+    wucsr.set(Wucsr::WOL_CONFIGURED, !events.is_empty());
+    // Do not write the status bits, as writing a 1 clears them
+    let old = phy.mmd_read(3, PHY_REG_WUCSR) & !Wucsr::STATUS_MASK.bits();
+    phy.mmd_write(3, PHY_REG_WUCSR, (old & !enables) | wucsr.bits());
+}
+
+/// Read and clear the received Wake-on-LAN events
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn read_and_clear_wol_status<M: Miim, P: Phy<M> + Sized>(
+    phy: &mut P,
+) -> WakeOnLanEvents {
+    let raw = phy.mmd_read(3, PHY_REG_WUCSR);
+    // The status bits are cleared by writing a 1 to them
+    phy.mmd_write(3, PHY_REG_WUCSR, raw);
+
+    let wucsr = Wucsr::from_bits_truncate(raw);
+    let mut events = WakeOnLanEvents::empty();
+    events.set(
+        WakeOnLanEvents::MAGIC_PACKET,
+        wucsr.contains(Wucsr::MAGIC_PACKET_RECEIVED),
+    );
+    events.set(
+        WakeOnLanEvents::BROADCAST,
+        wucsr.contains(Wucsr::BROADCAST_RECEIVED),
+    );
+    events.set(
+        WakeOnLanEvents::PERFECT_DA,
+        wucsr.contains(Wucsr::PERFECT_DA_RECEIVED),
+    );
+    events.set(
+        WakeOnLanEvents::WAKEUP_FRAME,
+        wucsr.contains(Wucsr::WAKEUP_FRAME_RECEIVED),
+    );
+    events
+}
+
+/// Configure the wakeup frame filter, or disable it if `filter` is `None`
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn set_wakeup_frame_filter<M: Miim, P: Phy<M> + Sized>(
+    phy: &mut P,
+    filter: Option<WakeupFrameFilter>,
+) {
+    let filter = match filter {
+        Some(filter) => filter,
+        None => {
+            phy.mmd_write(3, WufCfgA::ADDRESS, 0);
+            return;
+        }
+    };
+
+    phy.mmd_write(3, WUF_CFGB, filter.crc);
+
+    // The mask registers are ordered from the most significant to the
+    // least significant word
+    for (idx, word) in filter.byte_mask.to_be_bytes().chunks(2).enumerate() {
+        phy.mmd_write(
+            3,
+            WUF_MASK + idx as u16,
+            u16::from_be_bytes([word[0], word[1]]),
+        );
+    }
+
+    let mut cfga = WufCfgA::FILTER_ENABLE;
+    cfga.set(WufCfgA::ADDRESS_MATCH_ENABLE, filter.match_address);
+    cfga.set(WufCfgA::MULTICAST_ENABLE, filter.match_multicast);
+    cfga.set(WufCfgA::BROADCAST_ENABLE, filter.match_broadcast);
+    phy.mmd_write(
+        3,
+        WufCfgA::ADDRESS,
+        cfga.bits() | filter.pattern_offset as u16,
+    );
+}
+
+/// Check whether the wakeup frame filter was triggered, and clear the
+/// indication
+#[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+pub(crate) fn read_and_clear_wakeup_frame_filter_triggered<M: Miim, P: Phy<M> + Sized>(
+    phy: &mut P,
+) -> bool {
+    let cfga = phy.mmd_read(3, WufCfgA::ADDRESS);
+    // The triggered bit is cleared by writing a 1 to it
+    phy.mmd_write(3, WufCfgA::ADDRESS, cfga);
+    WufCfgA::from_bits_truncate(cfga).contains(WufCfgA::FILTER_TRIGGERED)
+}
+
 pub mod registers {
     #![allow(missing_docs)]
     //! LAN87xxA registers
 
     use bitflags::bitflags;
 
-    use crate::phy::PhySpeed;
+    use crate::phy::{CableDiagnostics, CablePairStatus, PhySpeed};
+
+    use super::MiiMode;
+    #[cfg(feature = "lan8742a")]
+    use super::NlpInterval;
 
     pub const PHY_REG_WUCSR: u16 = 0x8010;
+    pub const RX_ADDRA: u16 = 0x8061;
+    pub const RX_ADDRB: u16 = 0x8062;
+    pub const RX_ADDRC: u16 = 0x8063;
+    /// MMD register 3.32786, the Wakeup Filter Configuration B register,
+    /// which contains the expected CRC-16 of the filter pattern
+    pub const WUF_CFGB: u16 = 0x8012;
+    /// MMD registers 3.32801 through 3.32808, the Wakeup Filter Byte Mask
+    /// registers, starting with the most significant word
+    pub const WUF_MASK: u16 = 0x8021;
+
+    /// Register 26, the Symbol Error Counter register
+    pub const SYMBOL_ERROR_COUNTER: u8 = 26;
+
+    /// The TDR channel length is reported in steps of approximately 80 cm
+    pub const TDR_CM_PER_STEP: u16 = 80;
 
     bitflags! {
         pub struct InterruptReg: u16 {
@@ -231,10 +982,75 @@ pub mod registers {
             const INT5_REMOTE_FAULT = (1 << 5);
             const INT6_AUTO_NEG_COMPLETE = (1 << 6);
             const INT7_ENERGYON = (1 << 7);
-            #[cfg(feature = "lan8742a")]
+            #[cfg(any(feature = "lan8740a", feature = "lan8742a"))]
             const INT8_WOL = (1 << 8);
         }
 
+        /// MMD register 3.32784, the Wakeup Control and Status register
+        pub struct Wucsr: u16 {
+            const INTERFACE_DISABLE = (1 << 15);
+            const NPME_SELF_CLEAR = (1 << 9);
+            const WOL_CONFIGURED = (1 << 8);
+            const PERFECT_DA_RECEIVED = (1 << 7);
+            const WAKEUP_FRAME_RECEIVED = (1 << 6);
+            const MAGIC_PACKET_RECEIVED = (1 << 5);
+            const BROADCAST_RECEIVED = (1 << 4);
+            const PERFECT_DA_EN = (1 << 3);
+            const WAKEUP_FRAME_EN = (1 << 2);
+            const MAGIC_PACKET_EN = (1 << 1);
+            const BROADCAST_EN = (1 << 0);
+
+            const STATUS_MASK = (0b1111 << 4);
+            const ENABLE_MASK = (0b1111);
+        }
+
+        /// Register 16, the EDPD NLP/Crossover Time Configuration register
+        pub struct EdpdConfig: u16 {
+            const TX_NLP_ENABLE = (1 << 15);
+            const TX_NLP_INTERVAL_MASK = (0b11 << 13);
+            const RX_SINGLE_NLP_WAKE_ENABLE = (1 << 12);
+            const RX_NLP_MAX_INTERVAL_MASK = (0b11 << 10);
+            const EXTEND_CROSSOVER = (1 << 1);
+        }
+
+        /// Register 17, the Mode Control/Status register
+        pub struct ModeControlStatus: u16 {
+            /// Enable Energy Detect Power-Down
+            const EDPWRDOWN = (1 << 13);
+            const FARLOOPBACK = (1 << 9);
+            const ALTINT = (1 << 6);
+            /// Energy is detected on the cable
+            const ENERGYON = (1 << 1);
+        }
+
+        /// MMD register 3.32785, the Wakeup Filter Configuration A register
+        pub struct WufCfgA: u16 {
+            const FILTER_ENABLE = (1 << 15);
+            const FILTER_TRIGGERED = (1 << 14);
+            const ADDRESS_MATCH_ENABLE = (1 << 10);
+            const MULTICAST_ENABLE = (1 << 9);
+            const BROADCAST_ENABLE = (1 << 8);
+            const PATTERN_OFFSET_MASK = (0xFF);
+        }
+
+        /// Register 25, the TDR Control/Status register
+        pub struct TdrControlStatus: u16 {
+            const ENABLE = (1 << 15);
+            const ADC_FILTER_ENABLE = (1 << 14);
+            const CABLE_TYPE_MASK = (0b11 << 9);
+            const CHANNEL_STATUS = (1 << 8);
+            const CHANNEL_SELECT = (1 << 7);
+            const CHANNEL_LENGTH_MASK = (0x7F);
+        }
+
+        /// Register 27, the Special Control/Status Indications register
+        pub struct SpecialControlStatus: u16 {
+            const AMDIXCTRL = (1 << 15);
+            const CH_SELECT = (1 << 13);
+            const SQEOFF = (1 << 11);
+            const XPOL = (1 << 4);
+        }
+
         pub struct Ssr: u16 {
             const AUTONEG_DONE = (1 << 12);
             const FULL_DUPLEX = (0b1 << 4);
@@ -243,6 +1059,101 @@ pub mod registers {
         }
     }
 
+    /// The PHY mode, as configured in the Special Modes register
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum PhyMode {
+        /// 10BASE-T Half Duplex, autonegotiation disabled
+        HalfDuplexBase10T,
+        /// 10BASE-T Full Duplex, autonegotiation disabled
+        FullDuplexBase10T,
+        /// 100BASE-TX Half Duplex, autonegotiation disabled
+        HalfDuplexBase100Tx,
+        /// 100BASE-TX Full Duplex, autonegotiation disabled
+        FullDuplexBase100Tx,
+        /// 100BASE-TX Half Duplex is advertised, autonegotiation enabled
+        HalfDuplexBase100TxAutoneg,
+        /// Repeater mode, 100BASE-TX Half Duplex is advertised, autonegotiation enabled
+        Repeater,
+        /// Power down mode
+        PowerDown,
+        /// All capable, autonegotiation enabled
+        AllCapable,
+    }
+
+    bitflags! {
+        /// Register 18, the Special Modes register
+        pub struct SpecialModes: u16 {
+            const MII_MODE = (1 << 14);
+            const MODE_MASK = (0b111 << 5);
+            const PHYAD_MASK = (0b11111);
+        }
+    }
+
+    impl SpecialModes {
+        pub const ADDRESS: u8 = 18;
+
+        /// Get the configured MAC interface mode
+        pub fn mii_mode(&self) -> MiiMode {
+            if self.contains(Self::MII_MODE) {
+                MiiMode::Rmii
+            } else {
+                MiiMode::Mii
+            }
+        }
+
+        /// Set the MAC interface mode
+        pub fn set_mii_mode(&mut self, mode: MiiMode) -> &mut Self {
+            self.set(Self::MII_MODE, mode == MiiMode::Rmii);
+            self
+        }
+
+        /// Get the configured PHY mode
+        pub fn phy_mode(&self) -> PhyMode {
+            match (self.bits() & Self::MODE_MASK.bits()) >> 5 {
+                0b000 => PhyMode::HalfDuplexBase10T,
+                0b001 => PhyMode::FullDuplexBase10T,
+                0b010 => PhyMode::HalfDuplexBase100Tx,
+                0b011 => PhyMode::FullDuplexBase100Tx,
+                0b100 => PhyMode::HalfDuplexBase100TxAutoneg,
+                0b101 => PhyMode::Repeater,
+                0b110 => PhyMode::PowerDown,
+                _ => PhyMode::AllCapable,
+            }
+        }
+
+        /// Set the PHY mode
+        pub fn set_phy_mode(&mut self, mode: PhyMode) -> &mut Self {
+            let mode = match mode {
+                PhyMode::HalfDuplexBase10T => 0b000,
+                PhyMode::FullDuplexBase10T => 0b001,
+                PhyMode::HalfDuplexBase100Tx => 0b010,
+                PhyMode::FullDuplexBase100Tx => 0b011,
+                PhyMode::HalfDuplexBase100TxAutoneg => 0b100,
+                PhyMode::Repeater => 0b101,
+                PhyMode::PowerDown => 0b110,
+                PhyMode::AllCapable => 0b111,
+            };
+            self.remove(Self::MODE_MASK);
+            self.insert(Self::from_bits_truncate(mode << 5));
+            self
+        }
+
+        /// Get the configured PHY address
+        pub fn phy_address(&self) -> u8 {
+            (self.bits() & Self::PHYAD_MASK.bits()) as u8
+        }
+
+        /// Set the PHY address
+        pub fn set_phy_address(&mut self, address: u8) -> &mut Self {
+            self.remove(Self::PHYAD_MASK);
+            self.insert(Self::from_bits_truncate(
+                address as u16 & Self::PHYAD_MASK.bits(),
+            ));
+            self
+        }
+    }
+
     impl InterruptReg {
         pub const SOURCE_ADDR: u8 = 29;
         pub const MASK_ADDR: u8 = 30;
@@ -252,6 +1163,74 @@ pub mod registers {
         pub const ADDRESS: u8 = 31;
     }
 
+    impl EdpdConfig {
+        pub const ADDRESS: u8 = 16;
+    }
+
+    #[cfg(feature = "lan8742a")]
+    impl From<NlpInterval> for EdpdConfig {
+        fn from(interval: NlpInterval) -> Self {
+            let select = match interval {
+                NlpInterval::Ms1000 => 0b00,
+                NlpInterval::Ms768 => 0b01,
+                NlpInterval::Ms512 => 0b10,
+                NlpInterval::Ms256 => 0b11,
+            };
+            Self::TX_NLP_ENABLE | Self::from_bits_truncate(select << 13)
+        }
+    }
+
+    impl ModeControlStatus {
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl WufCfgA {
+        pub const ADDRESS: u16 = 0x8011;
+    }
+
+    impl TdrControlStatus {
+        pub const ADDRESS: u8 = 25;
+
+        /// Start a TDR test on `pair` (0 for the TX pair, 1 for the RX pair)
+        pub fn start(pair: u8) -> Self {
+            let mut me = Self::ENABLE | Self::ADC_FILTER_ENABLE;
+            me.set(Self::CHANNEL_SELECT, pair == 1);
+            me
+        }
+
+        /// The length reported by the last TDR test, in TDR steps
+        pub fn channel_length(&self) -> u8 {
+            (self.bits() & Self::CHANNEL_LENGTH_MASK.bits()) as u8
+        }
+    }
+
+    impl From<TdrControlStatus> for CableDiagnostics {
+        fn from(tdr: TdrControlStatus) -> Self {
+            let status = match (tdr & TdrControlStatus::CABLE_TYPE_MASK).bits() >> 9 {
+                0b01 => CablePairStatus::Short,
+                0b10 => CablePairStatus::Open,
+                0b11 => CablePairStatus::Normal,
+                _ => CablePairStatus::TestFailed,
+            };
+
+            let fault_distance_cm = match status {
+                CablePairStatus::Open | CablePairStatus::Short => {
+                    Some(tdr.channel_length() as u16 * TDR_CM_PER_STEP)
+                }
+                _ => None,
+            };
+
+            Self {
+                status,
+                fault_distance_cm,
+            }
+        }
+    }
+
+    impl SpecialControlStatus {
+        pub const ADDRESS: u8 = 27;
+    }
+
     impl From<Ssr> for Option<PhySpeed> {
         fn from(ssr: Ssr) -> Self {
             let full_duplex = ssr.contains(Ssr::FULL_DUPLEX);
@@ -282,3 +1261,21 @@ pub mod registers {
         }
     }
 }
+
+impl<M: Miim, const MODEL: u16> PhyWithInterrupts<M> for LAN87xxA<M, MODEL> {
+    type Interrupts = InterruptSet;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptSet {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<M: Miim, const MODEL: u16> PhyWithAutonegInterrupt<M> for LAN87xxA<M, MODEL> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
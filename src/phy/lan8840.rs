@@ -0,0 +1,335 @@
+//! Phy implementation for the Microchip LAN8840 gigabit Ethernet PHY
+//!
+//! The LAN8840 is derived from the KSZ9131, and shares its speed, RGMII delay
+//! and LinkMD registers (see [`super::ksz9131`]).
+
+use crate::{
+    phy::ksz9131::{
+        self,
+        registers::{DllControl, PhyControl, MMD_DEVICE},
+    },
+    registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{ClockPadSkew, ControlPadSkew, DataPadSkew};
+
+use super::{
+    AdvancedPhySpeed, CableDiagnostics, PhyWithCableDiagnostics, PhyWithEee, PhyWithRgmiiDelays,
+    PhyWithSpeed, RgmiiDelays,
+};
+
+/// A LAN8840
+#[derive(Debug)]
+pub struct LAN8840<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> LAN8840<MIIM> {
+    /// Create a new LAN8840 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Check whether the RGMII RX clock delay is enabled
+    pub fn rx_delay(&mut self) -> bool {
+        let dll = DllControl::from_bits_truncate(self.mmd_read(MMD_DEVICE, DllControl::RXC));
+        !dll.contains(DllControl::BYPASS)
+    }
+
+    /// Enable or disable the internal RGMII RX clock delay
+    pub fn set_rx_delay(&mut self, enabled: bool) {
+        let bypass = DllControl::BYPASS.bits();
+        let value = if enabled { 0 } else { bypass };
+        self.mmd_modify(MMD_DEVICE, DllControl::RXC, bypass, value);
+    }
+
+    /// Check whether the RGMII TX clock delay is enabled
+    pub fn tx_delay(&mut self) -> bool {
+        let dll = DllControl::from_bits_truncate(self.mmd_read(MMD_DEVICE, DllControl::TXC));
+        !dll.contains(DllControl::BYPASS)
+    }
+
+    /// Enable or disable the internal RGMII TX clock delay
+    pub fn set_tx_delay(&mut self, enabled: bool) {
+        let bypass = DllControl::BYPASS.bits();
+        let value = if enabled { 0 } else { bypass };
+        self.mmd_modify(MMD_DEVICE, DllControl::TXC, bypass, value);
+    }
+
+    /// Get the RGMII clock pad skew
+    pub fn rgmii_clock_skew(&mut self) -> ClockPadSkew {
+        self.mmd_read(MMD_DEVICE, ClockPadSkew::ADDRESS).into()
+    }
+
+    /// Set the RGMII clock pad skew
+    pub fn set_rgmii_clock_skew(&mut self, skew: ClockPadSkew) {
+        self.mmd_modify(
+            MMD_DEVICE,
+            ClockPadSkew::ADDRESS,
+            ClockPadSkew::MASK,
+            skew.into(),
+        );
+    }
+
+    /// Get the RGMII control pad skew
+    pub fn rgmii_control_skew(&mut self) -> ControlPadSkew {
+        self.mmd_read(MMD_DEVICE, ControlPadSkew::ADDRESS).into()
+    }
+
+    /// Set the RGMII control pad skew
+    pub fn set_rgmii_control_skew(&mut self, skew: ControlPadSkew) {
+        self.mmd_modify(
+            MMD_DEVICE,
+            ControlPadSkew::ADDRESS,
+            ControlPadSkew::MASK,
+            skew.into(),
+        );
+    }
+
+    /// Get the RGMII RX data pad skew
+    pub fn rgmii_rx_data_skew(&mut self) -> DataPadSkew {
+        self.mmd_read(MMD_DEVICE, DataPadSkew::RX_ADDRESS).into()
+    }
+
+    /// Set the RGMII RX data pad skew
+    pub fn set_rgmii_rx_data_skew(&mut self, skew: DataPadSkew) {
+        self.mmd_write(MMD_DEVICE, DataPadSkew::RX_ADDRESS, skew.into());
+    }
+
+    /// Get the RGMII TX data pad skew
+    pub fn rgmii_tx_data_skew(&mut self) -> DataPadSkew {
+        self.mmd_read(MMD_DEVICE, DataPadSkew::TX_ADDRESS).into()
+    }
+
+    /// Set the RGMII TX data pad skew
+    pub fn set_rgmii_tx_data_skew(&mut self, skew: DataPadSkew) {
+        self.mmd_write(MMD_DEVICE, DataPadSkew::TX_ADDRESS, skew.into());
+    }
+
+    /// Read the PHY Control register
+    pub fn phy_control(&mut self) -> PhyControl {
+        PhyControl::from_bits_truncate(self.read(PhyControl::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_control().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for LAN8840<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for LAN8840<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithEee<MIIM> for LAN8840<MIIM> {}
+
+impl<MIIM: Miim> PhyWithCableDiagnostics<MIIM> for LAN8840<MIIM> {
+    fn cable_pairs(&self) -> u8 {
+        4
+    }
+
+    fn test_cable_pair(&mut self, pair: u8) -> Option<CableDiagnostics> {
+        if pair >= self.cable_pairs() {
+            return None;
+        }
+
+        Some(ksz9131::test_cable_pair(self, pair))
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for LAN8840<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        RgmiiDelays {
+            tx: self.tx_delay(),
+            rx: self.rx_delay(),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_tx_delay(delays.tx);
+        self.set_rx_delay(delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! LAN8840 registers
+    //!
+    //! All registers in this module are located in MMD device
+    //! [`MMD_DEVICE`](crate::phy::ksz9131::registers::MMD_DEVICE).
+
+    /// MMD register 2.0x8, the RGMII Clock Pad Skew register.
+    ///
+    /// Both values are 5 bits wide. A value of `0xF` applies no skew.
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ClockPadSkew {
+        pub gtx_clk: u8,
+        pub rx_clk: u8,
+    }
+
+    impl ClockPadSkew {
+        pub const ADDRESS: u16 = 0x08;
+        pub const MASK: u16 = 0x3FF;
+    }
+
+    impl Default for ClockPadSkew {
+        fn default() -> Self {
+            Self {
+                gtx_clk: 0xF,
+                rx_clk: 0xF,
+            }
+        }
+    }
+
+    impl From<u16> for ClockPadSkew {
+        fn from(value: u16) -> Self {
+            Self {
+                gtx_clk: (value >> 5) as u8 & 0x1F,
+                rx_clk: value as u8 & 0x1F,
+            }
+        }
+    }
+
+    impl From<ClockPadSkew> for u16 {
+        fn from(skew: ClockPadSkew) -> Self {
+            (skew.gtx_clk as u16 & 0x1F) << 5 | (skew.rx_clk as u16 & 0x1F)
+        }
+    }
+
+    /// MMD register 2.0x4, the RGMII Control Signal Pad Skew register.
+    ///
+    /// Both values are 4 bits wide. A value of `0x7` applies no skew.
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ControlPadSkew {
+        pub tx_en: u8,
+        pub rx_dv: u8,
+    }
+
+    impl ControlPadSkew {
+        pub const ADDRESS: u16 = 0x04;
+        pub const MASK: u16 = 0xFF;
+    }
+
+    impl Default for ControlPadSkew {
+        fn default() -> Self {
+            Self {
+                tx_en: 0x7,
+                rx_dv: 0x7,
+            }
+        }
+    }
+
+    impl From<u16> for ControlPadSkew {
+        fn from(value: u16) -> Self {
+            Self {
+                tx_en: (value >> 4) as u8 & 0xF,
+                rx_dv: value as u8 & 0xF,
+            }
+        }
+    }
+
+    impl From<ControlPadSkew> for u16 {
+        fn from(skew: ControlPadSkew) -> Self {
+            (skew.tx_en as u16 & 0xF) << 4 | (skew.rx_dv as u16 & 0xF)
+        }
+    }
+
+    /// MMD registers 2.0x5 and 2.0x6, the RGMII RX and TX Data Pad Skew registers.
+    ///
+    /// All values are 4 bits wide. A value of `0x7` applies no skew.
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DataPadSkew {
+        pub d3: u8,
+        pub d2: u8,
+        pub d1: u8,
+        pub d0: u8,
+    }
+
+    impl DataPadSkew {
+        pub const RX_ADDRESS: u16 = 0x05;
+        pub const TX_ADDRESS: u16 = 0x06;
+    }
+
+    impl Default for DataPadSkew {
+        fn default() -> Self {
+            Self {
+                d3: 0x7,
+                d2: 0x7,
+                d1: 0x7,
+                d0: 0x7,
+            }
+        }
+    }
+
+    impl From<u16> for DataPadSkew {
+        fn from(value: u16) -> Self {
+            Self {
+                d3: (value >> 12) as u8 & 0xF,
+                d2: (value >> 8) as u8 & 0xF,
+                d1: (value >> 4) as u8 & 0xF,
+                d0: value as u8 & 0xF,
+            }
+        }
+    }
+
+    impl From<DataPadSkew> for u16 {
+        fn from(skew: DataPadSkew) -> Self {
+            (skew.d3 as u16 & 0xF) << 12
+                | (skew.d2 as u16 & 0xF) << 8
+                | (skew.d1 as u16 & 0xF) << 4
+                | (skew.d0 as u16 & 0xF)
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Support for the PHYs of the Microchip LAN9303 3-port switch
+//!
+//! The LAN9303 exposes three PHYs on its MIIM bus: a Virtual PHY, which
+//! emulates a PHY for the MAC connected to switch port 0, and the two physical
+//! PHYs of switch ports 1 and 2. They occupy three consecutive PHY addresses,
+//! starting at either 0 or 1 depending on the `phy_addr_sel_strap` pin.
+//!
+//! [`LAN9303`] owns the [`Miim`], and hands out a [`LAN9303Phy`] for each
+//! of the PHYs. Every [`LAN9303Phy`] implements [`Phy`], so the per-port
+//! link state can be managed like that of any other PHY.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::SpecialControlStatus;
+
+use super::{AdvancedPhySpeed, PhySpeed, PhyWithSpeed};
+
+/// One of the PHYs of the LAN9303
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Port {
+    /// The Virtual PHY, connected to switch port 0
+    VirtualPhy,
+    /// The PHY of switch port 1
+    Port1,
+    /// The PHY of switch port 2
+    Port2,
+}
+
+impl Port {
+    fn offset(&self) -> u8 {
+        match self {
+            Port::VirtualPhy => 0,
+            Port::Port1 => 1,
+            Port::Port2 => 2,
+        }
+    }
+}
+
+/// A LAN9303 switch
+#[derive(Debug)]
+pub struct LAN9303<MIIM: Miim> {
+    base_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> LAN9303<MIIM> {
+    /// Create a new LAN9303, backed by the given `miim`.
+    ///
+    /// `base_addr` is the PHY address of the Virtual PHY, which is 0 if
+    /// `phy_addr_sel_strap` is low, and 1 if it is high.
+    ///
+    /// # Panics
+    /// This function panics if the PHY address of [`Port::Port2`] is not less
+    /// than 32.
+    pub fn new(miim: MIIM, base_addr: PhyAddress) -> Self {
+        assert!(
+            PhyAddress::new(base_addr.get() + Port::Port2.offset()).is_some(),
+            "the PHY address of port 2 must be less than 32"
+        );
+
+        Self { base_addr, miim }
+    }
+
+    /// Access one of the PHYs of this switch
+    pub fn phy(&mut self, port: Port) -> LAN9303Phy<'_, MIIM> {
+        LAN9303Phy {
+            phy_addr: PhyAddress::from_const(self.base_addr.get() + port.offset()),
+            port,
+            miim: &mut self.miim,
+        }
+    }
+
+    /// Access the Virtual PHY
+    pub fn virtual_phy(&mut self) -> LAN9303Phy<'_, MIIM> {
+        self.phy(Port::VirtualPhy)
+    }
+
+    /// Initialize the PHYs of switch ports 1 and 2
+    pub fn phy_init(&mut self) {
+        for port in [Port::Port1, Port::Port2] {
+            let mut phy = self.phy(port);
+            phy.set_autonegotiation_advertisement(phy.best_supported_advertisement());
+        }
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+/// A single PHY of a [`LAN9303`]
+#[derive(Debug)]
+pub struct LAN9303Phy<'a, MIIM: Miim> {
+    phy_addr: PhyAddress,
+    port: Port,
+    miim: &'a mut MIIM,
+}
+
+impl<'a, MIIM: Miim> LAN9303Phy<'a, MIIM> {
+    /// The port this PHY belongs to
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    /// Read the Special Control/Status register
+    pub fn special_control_status(&mut self) -> SpecialControlStatus {
+        SpecialControlStatus::from_bits_truncate(self.read(SpecialControlStatus::ADDRESS))
+    }
+
+    /// Get the link speed
+    ///
+    /// For the Virtual PHY, this is the speed that it emulates towards
+    /// the MAC.
+    ///
+    /// If this returns `None`, some sort of corruption occured, or the PHY is
+    /// in an illegal state
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        self.special_control_status().into()
+    }
+
+    /// Check if the link is up
+    pub fn link_established(&mut self) -> bool {
+        let bsr = self.bsr();
+        bsr.autoneg_completed() && bsr.phy_link_up()
+    }
+}
+
+impl<'a, MIIM: Miim> Phy<MIIM> for LAN9303Phy<'a, MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<'a, MIIM: Miim> PhyWithSpeed<MIIM> for LAN9303Phy<'a, MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! LAN9303 PHY registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::PhySpeed;
+
+    bitflags! {
+        /// Register 31, the (Virtual) PHY Special Control/Status register
+        pub struct SpecialControlStatus: u16 {
+            const FULL_DUPLEX = (1 << 4);
+            const MBIT100 = (1 << 3);
+            const MBIT10 = (1 << 2);
+        }
+    }
+
+    impl SpecialControlStatus {
+        pub const ADDRESS: u8 = 31;
+    }
+
+    impl From<SpecialControlStatus> for Option<PhySpeed> {
+        fn from(scs: SpecialControlStatus) -> Self {
+            let full_duplex = scs.contains(SpecialControlStatus::FULL_DUPLEX);
+            let speed = (
+                scs.contains(SpecialControlStatus::MBIT10),
+                scs.contains(SpecialControlStatus::MBIT100),
+            );
+
+            let speed = match (speed, full_duplex) {
+                ((true, false), false) => PhySpeed::HalfDuplexBase10T,
+                ((true, false), true) => PhySpeed::FullDuplexBase10T,
+                ((false, true), false) => PhySpeed::HalfDuplexBase100Tx,
+                ((false, true), true) => PhySpeed::FullDuplexBase100Tx,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
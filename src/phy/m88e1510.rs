@@ -0,0 +1,481 @@
+//! Phy implementation for the Marvell 88E1510 gigabit Ethernet PHY
+//!
+//! The registers of the 88E1510 are divided into pages, which are selected
+//! through register 22. Page 0 contains the copper registers, including the
+//! IEEE 802.3 registers.
+
+use crate::{registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    CopperSpecificStatus, InterruptFlags, LedFunctionControl, COPPER_PAGE, PAGE_SELECT,
+    RECEIVE_ERROR_COUNTER,
+};
+
+use super::{
+    AdvancedPhySpeed, LinkCounters, PhyWithAutonegInterrupt, PhyWithCableDetect, PhyWithCounters,
+    PhyWithInterrupts, PhyWithPages, PhyWithSpeed,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+    /// The link speed changed
+    SpeedChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// A page was received during autonegotiation
+    PageReceived,
+    /// Autonegotiation completed
+    AutoNegotiationComplete,
+    /// The link status changed
+    LinkStatusChange,
+    /// A symbol error occurred
+    SymbolError,
+    /// A false carrier was detected
+    FalseCarrier,
+    /// The MDI crossover status changed
+    MdiCrossoverChange,
+    /// The link speed was downshifted
+    Downshift,
+    /// The energy detect state changed
+    EnergyDetectChange,
+    /// FLP exchange completed, but no link was established
+    FlpExchangeNoLink,
+    /// A Wake-on-LAN event occurred (88E1518 only)
+    WakeOnLan,
+    /// A DTE power device was detected
+    DtePowerDetect,
+    /// The polarity of the receive pair changed
+    PolarityChange,
+    /// Jabber was detected
+    Jabber,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::AutoNegotiationError => InterruptFlags::AUTONEG_ERROR,
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+            Interrupt::DuplexChange => InterruptFlags::DUPLEX_CHANGE,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::AutoNegotiationComplete => InterruptFlags::AUTONEG_COMPLETE,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::SymbolError => InterruptFlags::SYMBOL_ERROR,
+            Interrupt::FalseCarrier => InterruptFlags::FALSE_CARRIER,
+            Interrupt::MdiCrossoverChange => InterruptFlags::MDI_CROSSOVER_CHANGE,
+            Interrupt::Downshift => InterruptFlags::DOWNSHIFT,
+            Interrupt::EnergyDetectChange => InterruptFlags::ENERGY_DETECT_CHANGE,
+            Interrupt::FlpExchangeNoLink => InterruptFlags::FLP_EXCHANGE_NO_LINK,
+            Interrupt::WakeOnLan => InterruptFlags::WAKE_ON_LAN,
+            Interrupt::DtePowerDetect => InterruptFlags::DTE_POWER_DETECT,
+            Interrupt::PolarityChange => InterruptFlags::POLARITY_CHANGE,
+            Interrupt::Jabber => InterruptFlags::JABBER,
+        }
+    }
+}
+
+/// The function of one of the LEDs of the 88E1510
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LedFunction {
+    /// One of the link and activity indication modes. The meaning of
+    /// these modes (`0x0` through `0x7`) differs per LED.
+    Mode(u8),
+    /// Force the LED off
+    ForceOff,
+    /// Force the LED on
+    ForceOn,
+    /// Force the LED pin to high impedance
+    ForceHiZ,
+    /// Force the LED to blink
+    ForceBlink,
+}
+
+impl From<u8> for LedFunction {
+    fn from(value: u8) -> Self {
+        match value & 0xF {
+            0x8 => LedFunction::ForceOff,
+            0x9 => LedFunction::ForceOn,
+            0xA => LedFunction::ForceHiZ,
+            0xB => LedFunction::ForceBlink,
+            mode => LedFunction::Mode(mode),
+        }
+    }
+}
+
+impl From<LedFunction> for u8 {
+    fn from(function: LedFunction) -> Self {
+        match function {
+            LedFunction::Mode(mode) => mode & 0x7,
+            LedFunction::ForceOff => 0x8,
+            LedFunction::ForceOn => 0x9,
+            LedFunction::ForceHiZ => 0xA,
+            LedFunction::ForceBlink => 0xB,
+        }
+    }
+}
+
+/// A Marvell 88E1510
+#[derive(Debug)]
+pub struct M88E1510<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> M88E1510<MIIM> {
+    /// Create a new 88E1510 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This selects the copper page, advertises all supported 10/100/1000BASE-T
+    /// modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.write(PAGE_SELECT, COPPER_PAGE);
+
+        super::advertise_gigabit(self);
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        self.page_scoped(page).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    /// Get the function of LED `led` (0 through 3)
+    pub fn led_function(&mut self, led: u8) -> LedFunction {
+        led_function(self, led)
+    }
+
+    /// Set the function of LED `led` (0 through 3)
+    pub fn set_led_function(&mut self, led: u8, function: LedFunction) {
+        set_led_function(self, led, function)
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        set_interrupt_enabled(self, interrupt, true)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        set_interrupt_enabled(self, interrupt, false)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        read_and_clear_interrupts(self)
+    }
+
+    /// Read the Copper Specific Status register
+    pub fn copper_specific_status(&mut self) -> CopperSpecificStatus {
+        copper_specific_status(self)
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.copper_specific_status().into()
+    }
+
+    /// Check whether energy is detected on the cable
+    pub fn energy_detected(&mut self) -> bool {
+        self.copper_specific_status().energy_detected()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for M88E1510<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for M88E1510<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for M88E1510<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+// The helpers below are shared with the other 88E15xx PHYs.
+
+pub(crate) fn led_function<M: Miim, P: PhyWithPages<M>>(phy: &mut P, led: u8) -> LedFunction {
+    let lfc = phy
+        .page_scoped(LedFunctionControl::PAGE)
+        .read(LedFunctionControl::ADDRESS);
+    LedFunctionControl::from_bits_truncate(lfc).led_function(led)
+}
+
+pub(crate) fn set_led_function<M: Miim, P: PhyWithPages<M>>(
+    phy: &mut P,
+    led: u8,
+    function: LedFunction,
+) {
+    let mut lfc = LedFunctionControl::empty();
+    lfc.set_led_function(led, function);
+    phy.page_scoped(LedFunctionControl::PAGE).modify(
+        LedFunctionControl::ADDRESS,
+        LedFunctionControl::led_mask(led),
+        lfc.bits(),
+    );
+}
+
+pub(crate) fn set_interrupt_enabled<M: Miim, P: PhyWithPages<M>>(
+    phy: &mut P,
+    interrupt: Interrupt,
+    enabled: bool,
+) {
+    let flag = interrupt.flag().bits();
+    let value = if enabled { flag } else { 0 };
+    phy.page_scoped(COPPER_PAGE)
+        .modify(InterruptFlags::ENABLE_ADDRESS, flag, value);
+}
+
+pub(crate) fn read_and_clear_interrupts<M: Miim, P: PhyWithPages<M>>(
+    phy: &mut P,
+) -> InterruptFlags {
+    let status = phy
+        .page_scoped(COPPER_PAGE)
+        .read(InterruptFlags::STATUS_ADDRESS);
+    InterruptFlags::from_bits_truncate(status)
+}
+
+pub(crate) fn read_and_clear_counters<M: Miim, P: PhyWithPages<M>>(phy: &mut P) -> LinkCounters {
+    // The counter is cleared by reading it
+    let receive_errors = phy.page_scoped(COPPER_PAGE).read(RECEIVE_ERROR_COUNTER);
+
+    LinkCounters {
+        receive_errors: Some(receive_errors),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn copper_specific_status<M: Miim, P: PhyWithPages<M>>(
+    phy: &mut P,
+) -> CopperSpecificStatus {
+    let status = phy
+        .page_scoped(COPPER_PAGE)
+        .read(CopperSpecificStatus::ADDRESS);
+    CopperSpecificStatus::from_bits_truncate(status)
+}
+
+impl<MIIM: Miim> PhyWithCableDetect<MIIM> for M88E1510<MIIM> {
+    fn energy_detected(&mut self) -> bool {
+        self.energy_detected()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for M88E1510<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithAutonegInterrupt<MIIM> for M88E1510<MIIM> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for M88E1510<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        read_and_clear_counters(self)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! 88E1510 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::{Interrupt, LedFunction};
+
+    /// Register 22, the Page Address register
+    pub const PAGE_SELECT: u8 = 22;
+    /// The page containing the copper registers
+    pub const COPPER_PAGE: u16 = 0;
+    /// Register 21 on page 0, the Receive Error Counter register
+    pub const RECEIVE_ERROR_COUNTER: u8 = 21;
+
+    bitflags! {
+        /// Register 17 on page 0, the Copper Specific Status register 1
+        pub struct CopperSpecificStatus: u16 {
+            const SPEED_MASK = (0b11 << 14);
+            const FULL_DUPLEX = (1 << 13);
+            const PAGE_RECEIVED = (1 << 12);
+            const RESOLVED = (1 << 11);
+            const LINK = (1 << 10);
+            const MDI_CROSSOVER = (1 << 6);
+            /// Set while no energy is detected, and the PHY is in the
+            /// energy detect sleep state
+            const ENERGY_DETECT = (1 << 4);
+            const POLARITY_REVERSED = (1 << 1);
+            const JABBER = (1 << 0);
+        }
+
+        /// Register 18 (Interrupt Enable) and register 19 (Interrupt Status)
+        /// on page 0
+        pub struct InterruptFlags: u16 {
+            const AUTONEG_ERROR = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const DUPLEX_CHANGE = (1 << 13);
+            const PAGE_RECEIVED = (1 << 12);
+            const AUTONEG_COMPLETE = (1 << 11);
+            const LINK_STATUS_CHANGE = (1 << 10);
+            const SYMBOL_ERROR = (1 << 9);
+            const FALSE_CARRIER = (1 << 8);
+            const WAKE_ON_LAN = (1 << 7);
+            const MDI_CROSSOVER_CHANGE = (1 << 6);
+            const DOWNSHIFT = (1 << 5);
+            const ENERGY_DETECT_CHANGE = (1 << 4);
+            const FLP_EXCHANGE_NO_LINK = (1 << 3);
+            const DTE_POWER_DETECT = (1 << 2);
+            const POLARITY_CHANGE = (1 << 1);
+            const JABBER = (1 << 0);
+        }
+
+        /// Register 16 on page 3, the LED Function Control register
+        pub struct LedFunctionControl: u16 {
+            const LED3_MASK = (0xF << 12);
+            const LED2_MASK = (0xF << 8);
+            const LED1_MASK = (0xF << 4);
+            const LED0_MASK = (0xF);
+        }
+    }
+
+    impl CopperSpecificStatus {
+        pub const ADDRESS: u8 = 17;
+
+        /// Check whether energy is detected on the cable
+        pub fn energy_detected(&self) -> bool {
+            !self.contains(Self::ENERGY_DETECT)
+        }
+    }
+
+    impl From<CopperSpecificStatus> for Option<AdvancedPhySpeed> {
+        fn from(status: CopperSpecificStatus) -> Self {
+            // Speed and duplex are only valid once they have been resolved
+            if !status.contains(CopperSpecificStatus::RESOLVED) {
+                return None;
+            }
+
+            let full_duplex = status.contains(CopperSpecificStatus::FULL_DUPLEX);
+            let speed = (status & CopperSpecificStatus::SPEED_MASK).bits() >> 14;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl InterruptFlags {
+        pub const ENABLE_ADDRESS: u8 = 18;
+        pub const STATUS_ADDRESS: u8 = 19;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl LedFunctionControl {
+        pub const PAGE: u16 = 3;
+        pub const ADDRESS: u8 = 16;
+
+        fn led_shift(led: u8) -> u16 {
+            4 * led.min(3) as u16
+        }
+
+        /// The bits of LED `led` in this register
+        pub fn led_mask(led: u8) -> u16 {
+            Self::LED0_MASK.bits() << Self::led_shift(led)
+        }
+
+        /// Get the function of LED `led`
+        pub fn led_function(&self, led: u8) -> LedFunction {
+            let bits = (self.bits() & Self::led_mask(led)) >> Self::led_shift(led);
+            LedFunction::from(bits as u8)
+        }
+
+        /// Set the function of LED `led`
+        pub fn set_led_function(&mut self, led: u8, function: LedFunction) -> &mut Self {
+            let bits = u8::from(function) as u16;
+            self.bits &= !Self::led_mask(led);
+            self.bits |= bits << Self::led_shift(led);
+            self
+        }
+    }
+}
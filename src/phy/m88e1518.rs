@@ -0,0 +1,378 @@
+//! Phy implementation for the Marvell 88E1518 gigabit Ethernet PHY
+//!
+//! The 88E1518 shares its register layout with the 88E1510 (see
+//! [`super::m88e1510`]), but adds Wake-on-LAN and a synchronous Ethernet
+//! clock output.
+
+use crate::{
+    phy::m88e1510::{
+        self,
+        registers::{CopperSpecificStatus, InterruptFlags, COPPER_PAGE, PAGE_SELECT},
+        Interrupt, LedFunction,
+    },
+    registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{
+    ClockOutputControl, LedTimerControl, WolControl, MAGIC_PACKET_WORD0, WOL_PAGE,
+};
+
+use super::{
+    AdvancedPhySpeed, LinkCounters, PhyWithAutonegInterrupt, PhyWithCableDetect,
+    PhyWithClockOutput, PhyWithCounters, PhyWithInterrupts, PhyWithPages, PhyWithSpeed,
+    PhyWithWakeOnLan, WakeOnLanEvents,
+};
+
+/// The clock that is output on the CLK125 pin
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockOutput {
+    /// No clock is output
+    Disabled,
+    /// A 125 MHz clock derived from the local reference
+    LocalReference,
+    /// The clock recovered from the copper link, for synchronous Ethernet
+    Recovered,
+}
+
+/// A Marvell 88E1518
+#[derive(Debug)]
+pub struct M88E1518<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> M88E1518<MIIM> {
+    /// Create a new 88E1518 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This selects the copper page, advertises all supported 10/100/1000BASE-T
+    /// modes, and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.write(PAGE_SELECT, COPPER_PAGE);
+
+        super::advertise_gigabit(self);
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        self.page_scoped(page).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    /// Get the function of LED `led` (0 through 3)
+    pub fn led_function(&mut self, led: u8) -> LedFunction {
+        m88e1510::led_function(self, led)
+    }
+
+    /// Set the function of LED `led` (0 through 3)
+    pub fn set_led_function(&mut self, led: u8, function: LedFunction) {
+        m88e1510::set_led_function(self, led, function)
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        m88e1510::set_interrupt_enabled(self, interrupt, true)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        m88e1510::set_interrupt_enabled(self, interrupt, false)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        m88e1510::read_and_clear_interrupts(self)
+    }
+
+    /// Read the Copper Specific Status register
+    pub fn copper_specific_status(&mut self) -> CopperSpecificStatus {
+        m88e1510::copper_specific_status(self)
+    }
+
+    /// Get the clock that is output on the CLK125 pin
+    pub fn clock_output(&mut self) -> ClockOutput {
+        let coc = ClockOutputControl::from_bits_truncate(
+            self.read_paged(ClockOutputControl::PAGE, ClockOutputControl::ADDRESS),
+        );
+        coc.into()
+    }
+
+    /// Select the clock that is output on the CLK125 pin
+    pub fn set_clock_output(&mut self, output: ClockOutput) {
+        self.modify_paged(
+            ClockOutputControl::PAGE,
+            ClockOutputControl::ADDRESS,
+            ClockOutputControl::all().bits(),
+            ClockOutputControl::from(output).bits(),
+        );
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.copper_specific_status().into()
+    }
+
+    /// Check whether energy is detected on the cable
+    pub fn energy_detected(&mut self) -> bool {
+        self.copper_specific_status().energy_detected()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for M88E1518<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for M88E1518<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for M88E1518<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+/// Only [`WakeOnLanEvents::MAGIC_PACKET`] is supported. A wake-up event
+/// is signalled through the LED\[2\]/INTn pin.
+impl<MIIM: Miim> PhyWithWakeOnLan<MIIM> for M88E1518<MIIM> {
+    fn set_wol_mac_address(&mut self, mac_address: [u8; 6]) {
+        for (i, bytes) in mac_address.chunks(2).enumerate() {
+            // Word 0 contains the first two bytes, and is located at the
+            // highest address
+            self.write_paged(
+                WOL_PAGE,
+                MAGIC_PACKET_WORD0 - i as u8,
+                u16::from_le_bytes([bytes[0], bytes[1]]),
+            );
+        }
+    }
+
+    fn wol_events(&mut self) -> WakeOnLanEvents {
+        let ctrl = WolControl::from_bits_truncate(self.read_paged(WOL_PAGE, WolControl::ADDRESS));
+
+        let mut events = WakeOnLanEvents::empty();
+        events.set(
+            WakeOnLanEvents::MAGIC_PACKET,
+            ctrl.contains(WolControl::MAGIC_PACKET_MATCH_ENABLE),
+        );
+        events
+    }
+
+    fn set_wol_events(&mut self, events: WakeOnLanEvents) {
+        let magic_packet = events.contains(WakeOnLanEvents::MAGIC_PACKET);
+
+        if magic_packet {
+            self.enable_interrupt(Interrupt::WakeOnLan);
+
+            // Use LED[2] as an active low interrupt pin
+            let ltc = LedTimerControl::INTN_ENABLE | LedTimerControl::INT_ACTIVE_LOW;
+            self.modify_paged(
+                LedTimerControl::PAGE,
+                LedTimerControl::ADDRESS,
+                LedTimerControl::all().bits(),
+                ltc.bits(),
+            );
+        }
+
+        let mut ctrl = WolControl::CLEAR_WOL_STATUS;
+        ctrl.set(WolControl::MAGIC_PACKET_MATCH_ENABLE, magic_packet);
+        self.modify_paged(
+            WOL_PAGE,
+            WolControl::ADDRESS,
+            WolControl::all().bits(),
+            ctrl.bits(),
+        );
+    }
+
+    /// Note that this reads the interrupt status register, which clears all
+    /// pending interrupts.
+    fn read_and_clear_wol_status(&mut self) -> WakeOnLanEvents {
+        let interrupts = self.read_and_clear_interrupts();
+        self.modify_paged(
+            WOL_PAGE,
+            WolControl::ADDRESS,
+            WolControl::CLEAR_WOL_STATUS.bits(),
+            WolControl::CLEAR_WOL_STATUS.bits(),
+        );
+
+        let mut events = WakeOnLanEvents::empty();
+        events.set(
+            WakeOnLanEvents::MAGIC_PACKET,
+            interrupts.occurred(Interrupt::WakeOnLan),
+        );
+        events
+    }
+}
+
+impl<MIIM: Miim> PhyWithCableDetect<MIIM> for M88E1518<MIIM> {
+    fn energy_detected(&mut self) -> bool {
+        self.energy_detected()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for M88E1518<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithAutonegInterrupt<MIIM> for M88E1518<MIIM> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for M88E1518<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        m88e1510::read_and_clear_counters(self)
+    }
+}
+
+impl<MIIM: Miim> PhyWithClockOutput<MIIM> for M88E1518<MIIM> {
+    type ClockOutput = ClockOutput;
+
+    fn clock_output(&mut self) -> ClockOutput {
+        self.clock_output()
+    }
+
+    fn set_clock_output(&mut self, output: ClockOutput) {
+        self.set_clock_output(output)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! 88E1518 specific registers
+    //!
+    //! All other registers are shared with the 88E1510, see
+    //! [`crate::phy::m88e1510::registers`].
+
+    use bitflags::bitflags;
+
+    use super::ClockOutput;
+
+    /// The page containing the Wake-on-LAN registers
+    pub const WOL_PAGE: u16 = 17;
+    /// Registers 23 through 25 on page 17, the magic packet MAC address.
+    /// Word 0 (the first two bytes of the address) is register 25.
+    pub const MAGIC_PACKET_WORD0: u8 = 25;
+
+    bitflags! {
+        /// Register 16 on page 17, the Wake-on-LAN Control register
+        pub struct WolControl: u16 {
+            const MAGIC_PACKET_MATCH_ENABLE = (1 << 14);
+            const CLEAR_WOL_STATUS = (1 << 12);
+        }
+
+        /// Register 18 on page 3, the LED Timer Control register
+        pub struct LedTimerControl: u16 {
+            const FORCE_INT = (1 << 15);
+            const INT_ACTIVE_LOW = (1 << 11);
+            const INTN_ENABLE = (1 << 7);
+        }
+
+        /// Register 16 on page 2, the clock output configuration
+        pub struct ClockOutputControl: u16 {
+            const RECOVERED_CLOCK = (1 << 12);
+            const CLOCK_OUTPUT_DISABLE = (1 << 7);
+        }
+    }
+
+    impl WolControl {
+        pub const ADDRESS: u8 = 16;
+    }
+
+    impl LedTimerControl {
+        pub const PAGE: u16 = 3;
+        pub const ADDRESS: u8 = 18;
+    }
+
+    impl ClockOutputControl {
+        pub const PAGE: u16 = 2;
+        pub const ADDRESS: u8 = 16;
+    }
+
+    impl From<ClockOutputControl> for ClockOutput {
+        fn from(coc: ClockOutputControl) -> Self {
+            if coc.contains(ClockOutputControl::CLOCK_OUTPUT_DISABLE) {
+                ClockOutput::Disabled
+            } else if coc.contains(ClockOutputControl::RECOVERED_CLOCK) {
+                ClockOutput::Recovered
+            } else {
+                ClockOutput::LocalReference
+            }
+        }
+    }
+
+    impl From<ClockOutput> for ClockOutputControl {
+        fn from(output: ClockOutput) -> Self {
+            match output {
+                ClockOutput::Disabled => ClockOutputControl::CLOCK_OUTPUT_DISABLE,
+                ClockOutput::LocalReference => ClockOutputControl::empty(),
+                ClockOutput::Recovered => ClockOutputControl::RECOVERED_CLOCK,
+            }
+        }
+    }
+}
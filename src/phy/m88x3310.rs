@@ -0,0 +1,401 @@
+//! Phy implementation for the Marvell 88X3310 and 88X3340 multi-gigabit
+//! PHYs
+//!
+//! These PHYs only implement Clause 45: they do not respond to Clause 22
+//! frames, and do not have the Clause 22 MMD access registers. All register
+//! accesses of this driver go through [`Miim::read_c45`] and
+//! [`Miim::write_c45`], so the [`Miim`] must be able to generate Clause 45
+//! frames. The methods of [`Phy`] that access Clause 22 registers, such as
+//! [`Phy::bcr`] and [`Phy::status`], are not supported.
+//!
+//! The 88X3340 is a quad-port version of the 88X3310. Both share their PMA/PMD
+//! device identifier, [`M88X3310::PHY_IDENT`].
+
+use crate::registers::{AutoNegCap, MmdAnStatus, MmdStatus};
+use crate::{AutoNegotiationAdvertisement, Duplex, Miim, MmdDevice, Phy, PhyAddress, PhyIdent};
+
+use super::{c45, AdvancedPhySpeed, MultiGigSpeed, PhyWithSpeed};
+
+use self::registers::{
+    BootStatus, CopperStatus, FirmwareVersion0, FirmwareVersion1, PortControl, PCS_BASER_STATUS,
+    PCS_BASEX_STATUS, PCS_MMD, PMA_MMD, VEND2_MMD,
+};
+
+/// The interface between the PHY and the MAC, selected by the MACTYPE field
+/// of the port control register.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostInterface {
+    /// RXAUI for 10 Gbps, SGMII for lower speeds
+    Rxaui,
+    /// XAUI, rate matched to all speeds.
+    ///
+    /// On the 88X3340, this selects RXAUI for 10 Gbps and SGMII without
+    /// autonegotiation for lower speeds instead.
+    XauiRateMatch,
+    /// RXAUI, rate matched to all speeds
+    RxauiRateMatch,
+    /// XAUI for 10 Gbps, SGMII for lower speeds
+    Xaui,
+    /// 10GBASE-R for 10 Gbps, 5GBASE-R, 2500BASE-X and SGMII for lower
+    /// speeds
+    BaseR,
+    /// Like [`HostInterface::BaseR`], but without SGMII autonegotiation
+    BaseRNoSgmiiAn,
+    /// 10GBASE-R, rate matched to all speeds
+    BaseRRateMatch,
+    /// USXGMII
+    Usxgmii,
+}
+
+impl HostInterface {
+    fn from_mactype(mactype: u16) -> Self {
+        match mactype & 0b111 {
+            0 => Self::Rxaui,
+            1 => Self::XauiRateMatch,
+            2 => Self::RxauiRateMatch,
+            3 => Self::Xaui,
+            4 => Self::BaseR,
+            5 => Self::BaseRNoSgmiiAn,
+            6 => Self::BaseRRateMatch,
+            _ => Self::Usxgmii,
+        }
+    }
+
+    fn mactype(&self) -> u16 {
+        match self {
+            Self::Rxaui => 0,
+            Self::XauiRateMatch => 1,
+            Self::RxauiRateMatch => 2,
+            Self::Xaui => 3,
+            Self::BaseR => 4,
+            Self::BaseRNoSgmiiAn => 5,
+            Self::BaseRRateMatch => 6,
+            Self::Usxgmii => 7,
+        }
+    }
+}
+
+/// The version of the firmware running on the PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FirmwareVersion {
+    /// The major version
+    pub major: u8,
+    /// The minor version
+    pub minor: u8,
+    /// The incremental version
+    pub inc: u8,
+    /// The test version
+    pub test: u8,
+}
+
+/// A 88X3310 or 88X3340
+#[derive(Debug)]
+pub struct M88X3310<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> M88X3310<MIIM> {
+    /// The PMA/PMD device identifier of the 88X3310 and 88X3340, with the
+    /// revision number masked out.
+    pub const PHY_IDENT: u32 = 0x002B09A0;
+
+    /// Create a new 88X3310 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Check whether the PMA/PMD device identifier of this PHY is that of an
+    /// 88X3310 or 88X3340
+    pub fn identify(&mut self) -> bool {
+        self.mmd_device_ident(PMA_MMD)
+            .is_some_and(|ident| ident.raw_u32() & 0xFFFF_FFF0 == Self::PHY_IDENT)
+    }
+
+    /// Get the version of the firmware that is running on the PHY.
+    ///
+    /// Returns `None` if the firmware failed to boot.
+    pub fn firmware_version(&mut self) -> Option<FirmwareVersion> {
+        let boot = BootStatus::from_bits_truncate(self.mmd_read(PMA_MMD, BootStatus::ADDRESS));
+        if boot.contains(BootStatus::FATAL) {
+            return None;
+        }
+
+        let ver0 = self.mmd_read(PMA_MMD, FirmwareVersion0::ADDRESS);
+        let ver1 = self.mmd_read(PMA_MMD, FirmwareVersion1::ADDRESS);
+
+        Some(FirmwareVersion {
+            major: (ver0 >> 8) as u8,
+            minor: ver0 as u8,
+            inc: (ver1 >> 8) as u8,
+            test: ver1 as u8,
+        })
+    }
+
+    /// Get the interface that is used between the PHY and the MAC
+    pub fn host_interface(&mut self) -> HostInterface {
+        let port_control =
+            PortControl::from_bits_truncate(self.mmd_read(VEND2_MMD, PortControl::ADDRESS));
+        HostInterface::from_mactype((port_control & PortControl::MACTYPE_MASK).bits())
+    }
+
+    /// Select the interface that is used between the PHY and the MAC.
+    ///
+    /// This performs a software reset of the port, which is required for the
+    /// new interface to take effect.
+    pub fn set_host_interface(&mut self, interface: HostInterface) {
+        let mask = (PortControl::MACTYPE_MASK | PortControl::SOFTWARE_RESET).bits();
+        let value = interface.mactype() | PortControl::SOFTWARE_RESET.bits();
+        self.mmd_modify(VEND2_MMD, PortControl::ADDRESS, mask, value);
+    }
+
+    /// Power the port down, or power it back up
+    pub fn set_power_down(&mut self, power_down: bool) {
+        let bit = PortControl::POWER_DOWN.bits();
+        self.mmd_modify(
+            VEND2_MMD,
+            PortControl::ADDRESS,
+            bit,
+            if power_down { bit } else { 0 },
+        );
+    }
+
+    /// Get the copper specific status register
+    pub fn copper_status(&mut self) -> CopperStatus {
+        CopperStatus::from_bits_truncate(self.mmd_read(PCS_MMD, CopperStatus::ADDRESS))
+    }
+
+    /// Check whether the copper (10GBASE-T, 5GBASE-T, 2.5GBASE-T, 1000BASE-T,
+    /// 100BASE-TX or 10BASE-T) link is up
+    pub fn copper_link_up(&mut self) -> bool {
+        self.mmd_status(MmdDevice::Pcs)
+            .is_some_and(|status| status.contains(MmdStatus::RECEIVE_LINK_STATUS))
+    }
+
+    /// Check whether the 10GBASE-R fiber link is up
+    pub fn baser_link_up(&mut self) -> bool {
+        MmdStatus::from_bits_truncate(self.mmd_read(PCS_MMD, PCS_BASER_STATUS))
+            .contains(MmdStatus::RECEIVE_LINK_STATUS)
+    }
+
+    /// Check whether the 1000BASE-X fiber link is up
+    pub fn basex_link_up(&mut self) -> bool {
+        MmdStatus::from_bits_truncate(self.mmd_read(PCS_MMD, PCS_BASEX_STATUS))
+            .contains(MmdStatus::RECEIVE_LINK_STATUS)
+    }
+
+    /// Get the speed and duplex mode of the copper link.
+    ///
+    /// Returns `None` if the speed and duplex mode have not been resolved
+    /// yet.
+    pub fn copper_speed(&mut self) -> Option<(MultiGigSpeed, Duplex)> {
+        let status = self.copper_status();
+        if !status.contains(CopperStatus::RESOLVED) {
+            return None;
+        }
+
+        Some((status.speed(), status.duplex()))
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for M88X3310<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+
+    fn mmd_read(&mut self, mmd_address: u8, reg_address: u16) -> u16 {
+        self.miim
+            .read_c45(self.phy_addr.get(), mmd_address, reg_address)
+    }
+
+    fn mmd_write(&mut self, device_address: u8, reg_address: u16, reg_value: u16) {
+        self.miim
+            .write_c45(self.phy_addr.get(), device_address, reg_address, reg_value)
+    }
+
+    fn is_resetting(&mut self) -> bool {
+        self.mmd_is_resetting(MmdDevice::Pcs)
+    }
+
+    /// Reset the copper PCS
+    fn reset(&mut self) {
+        self.mmd_reset(MmdDevice::Pcs)
+    }
+
+    fn phy_link_up(&mut self) -> bool {
+        self.copper_link_up()
+    }
+
+    fn autoneg_completed(&mut self) -> bool {
+        self.mmd_an_status().contains(MmdAnStatus::AUTONEG_COMPLETE)
+    }
+
+    fn phy_ident(&mut self) -> Option<PhyIdent> {
+        self.mmd_device_ident(PMA_MMD)
+    }
+
+    /// Set the 10BASE-T and 100BASE-TX autonegotiation advertisement, and
+    /// restart autonegotiation
+    fn set_autonegotiation_advertisement(&mut self, ad: AutoNegotiationAdvertisement) {
+        c45::set_autonegotiation_advertisement(self, ad)
+    }
+
+    fn get_autonegotiation_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(c45::autonegotiation_advertisement(
+            self,
+            AutoNegCap::LOCAL_CAP_ADDRESS,
+        ))
+    }
+
+    fn get_autonegotiation_partner_caps(&mut self) -> Option<AutoNegotiationAdvertisement> {
+        Some(c45::autonegotiation_advertisement(
+            self,
+            AutoNegCap::PARTNER_CAP_ADDRESS,
+        ))
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for M88X3310<MIIM> {
+    /// Get the speed of the copper link.
+    ///
+    /// Returns `None` for 2.5, 5 and 10 Gbps links, as they can not be
+    /// represented by [`AdvancedPhySpeed`]. Use [`M88X3310::copper_speed`]
+    /// to get those.
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        let (speed, duplex) = self.copper_speed()?;
+        speed.advanced(duplex)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! 88X3310 registers
+
+    use bitflags::bitflags;
+
+    use crate::{phy::MultiGigSpeed, Duplex};
+
+    /// The PMA/PMD MMD
+    pub const PMA_MMD: u8 = 1;
+    /// The PCS MMD, containing the copper and fiber PCS registers
+    pub const PCS_MMD: u8 = 3;
+    /// The Auto-Negotiation MMD
+    pub const AN_MMD: u8 = 7;
+    /// The vendor specific MMD containing the port control registers
+    pub const VEND2_MMD: u8 = 31;
+
+    /// Register 0x1001 in MMD 3, the 10GBASE-R PCS status 1 register, laid
+    /// out like [`MmdStatus`](crate::registers::MmdStatus)
+    pub const PCS_BASER_STATUS: u16 = 0x1001;
+    /// Register 0x2001 in MMD 3, the 1000BASE-X status register, laid out
+    /// like [`MmdStatus`](crate::registers::MmdStatus)
+    pub const PCS_BASEX_STATUS: u16 = 0x2001;
+
+    bitflags! {
+        /// Register 0xC011 in MMD 1, the first firmware version register
+        pub struct FirmwareVersion0: u16 {
+            const MAJOR_MASK = (0xFF << 8);
+            const MINOR_MASK = (0xFF);
+        }
+
+        /// Register 0xC012 in MMD 1, the second firmware version register
+        pub struct FirmwareVersion1: u16 {
+            const INC_MASK = (0xFF << 8);
+            const TEST_MASK = (0xFF);
+        }
+
+        /// Register 0xC050 in MMD 1, the boot status register
+        pub struct BootStatus: u16 {
+            /// The firmware failed to boot
+            const FATAL = (1 << 0);
+        }
+
+        /// Register 0x8008 in MMD 3, the copper specific status register 1
+        pub struct CopperStatus: u16 {
+            const SPEED1_MASK = (0b11 << 14);
+            const FULL_DUPLEX = (1 << 13);
+            const RESOLVED = (1 << 11);
+            const MDIX = (1 << 6);
+            const SPEED2_MASK = (0b11 << 2);
+        }
+
+        /// Register 0xF001 in MMD 31, the port control register
+        pub struct PortControl: u16 {
+            const SOFTWARE_RESET = (1 << 15);
+            const POWER_DOWN = (1 << 11);
+            const MACTYPE_MASK = (0b111);
+        }
+    }
+
+    impl FirmwareVersion0 {
+        pub const ADDRESS: u16 = 0xC011;
+    }
+
+    impl FirmwareVersion1 {
+        pub const ADDRESS: u16 = 0xC012;
+    }
+
+    impl BootStatus {
+        pub const ADDRESS: u16 = 0xC050;
+    }
+
+    impl CopperStatus {
+        pub const ADDRESS: u16 = 0x8008;
+
+        /// The resolved speed of the copper link.
+        ///
+        /// Only valid if [`Self::RESOLVED`] is set.
+        pub fn speed(&self) -> MultiGigSpeed {
+            match (
+                (*self & Self::SPEED1_MASK).bits() >> 14,
+                (*self & Self::SPEED2_MASK).bits() >> 2,
+            ) {
+                (0b00, _) => MultiGigSpeed::Mbps10,
+                (0b01, _) => MultiGigSpeed::Mbps100,
+                (0b10, _) => MultiGigSpeed::Mbps1000,
+                (_, 0b01) => MultiGigSpeed::Mbps2500,
+                (_, 0b10) => MultiGigSpeed::Mbps5000,
+                (_, _) => MultiGigSpeed::Mbps10000,
+            }
+        }
+
+        /// The resolved duplex mode of the copper link.
+        ///
+        /// Only valid if [`Self::RESOLVED`] is set.
+        pub fn duplex(&self) -> Duplex {
+            if self.contains(Self::FULL_DUPLEX) {
+                Duplex::Full
+            } else {
+                Duplex::Half
+            }
+        }
+    }
+
+    impl PortControl {
+        pub const ADDRESS: u16 = 0xF001;
+    }
+}
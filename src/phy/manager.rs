@@ -0,0 +1,149 @@
+//! Managing several PHYs on one management bus.
+//!
+//! A [`PhyManager`] owns the drivers of several PHYs, polls them round-robin
+//! and keeps track of the link state of every port. The drivers must share
+//! the underlying bus themselves, for example by using a shared reference to
+//! a [`Miim`] that can be shared, such as `cs::CsMiim`.
+//!
+//! Time is measured in ticks of a monotonic, wrapping `u32` counter that is
+//! provided by the caller.
+
+use core::marker::PhantomData;
+
+use crate::Miim;
+
+use super::{AdvancedPhySpeed, PhyWithSpeed};
+
+/// A change of the link state of a port
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkEvent {
+    /// The link came up at the given speed, if it is known
+    Up(Option<AdvancedPhySpeed>),
+    /// The link went down
+    Down,
+}
+
+/// A change of the link state of the port with index `port`
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortEvent {
+    /// The index of the port
+    pub port: usize,
+    /// The change of the link state
+    pub event: LinkEvent,
+}
+
+#[derive(Debug)]
+struct Port<P> {
+    phy: P,
+    link_up: bool,
+    speed: Option<AdvancedPhySpeed>,
+    interval: u32,
+    last_poll: Option<u32>,
+}
+
+/// Polls `N` PHYs round-robin, and tracks the link state of each of them.
+#[derive(Debug)]
+pub struct PhyManager<M, P, const N: usize> {
+    ports: [Port<P>; N],
+    next: usize,
+    _miim: PhantomData<M>,
+}
+
+impl<M, P, const N: usize> PhyManager<M, P, N>
+where
+    M: Miim,
+    P: PhyWithSpeed<M>,
+{
+    /// Create a new manager for `phys`, which polls every PHY once every
+    /// `interval` ticks.
+    ///
+    /// All links are considered down until they are polled.
+    pub fn new(phys: [P; N], interval: u32) -> Self {
+        Self {
+            ports: phys.map(|phy| Port {
+                phy,
+                link_up: false,
+                speed: None,
+                interval,
+                last_poll: None,
+            }),
+            next: 0,
+            _miim: PhantomData,
+        }
+    }
+
+    /// Change the polling interval of `port`
+    pub fn set_interval(&mut self, port: usize, interval: u32) {
+        if let Some(port) = self.ports.get_mut(port) {
+            port.interval = interval;
+        }
+    }
+
+    /// Get the PHY of `port`
+    pub fn phy(&mut self, port: usize) -> Option<&mut P> {
+        self.ports.get_mut(port).map(|port| &mut port.phy)
+    }
+
+    /// Check whether the link of `port` was up when it was last polled
+    pub fn link_up(&self, port: usize) -> bool {
+        self.ports.get(port).is_some_and(|port| port.link_up)
+    }
+
+    /// Get the speed of the link of `port` when it was last polled, if the
+    /// link was up
+    pub fn speed(&self, port: usize) -> Option<AdvancedPhySpeed> {
+        self.ports.get(port).and_then(|port| port.speed)
+    }
+
+    /// Poll the next port whose polling interval has expired, and return the
+    /// change of its link state, if any.
+    ///
+    /// At most one PHY is polled per call, so that the time spent on the bus
+    /// per call is bounded.
+    pub fn poll(&mut self, now: u32) -> Option<PortEvent> {
+        for offset in 0..N {
+            let index = (self.next + offset) % N;
+            let port = &mut self.ports[index];
+
+            let due = match port.last_poll {
+                Some(last) => now.wrapping_sub(last) >= port.interval,
+                None => true,
+            };
+            if !due {
+                continue;
+            }
+
+            self.next = (index + 1) % N;
+            port.last_poll = Some(now);
+
+            let link_up = port.phy.phy_link_up();
+            let speed = if link_up {
+                port.phy.get_link_speed()
+            } else {
+                None
+            };
+
+            let event = match (port.link_up, link_up) {
+                (false, true) => Some(LinkEvent::Up(speed)),
+                (true, false) => Some(LinkEvent::Down),
+                _ => None,
+            };
+
+            port.link_up = link_up;
+            port.speed = speed;
+
+            return event.map(|event| PortEvent { port: index, event });
+        }
+
+        None
+    }
+
+    /// Release the PHYs
+    pub fn release(self) -> [P; N] {
+        self.ports.map(|port| port.phy)
+    }
+}
@@ -1,22 +1,273 @@
 //! Implementations of MIIM for existing PHYs
 
-use crate::{Miim, Phy};
+use crate::{Duplex, IllegalSpeed, LoopbackGuard, Miim, PageGuard, Phy, Speed};
 
-#[cfg(any(feature = "lan8720a", feature = "lan8742a"))]
+#[cfg(any(
+    feature = "ksz9021",
+    feature = "ksz9131",
+    feature = "lan8840",
+    feature = "rtl8211e",
+    feature = "rtl8211f",
+    feature = "m88e1510",
+    feature = "m88e1518",
+    feature = "vsc8211",
+    feature = "vsc8501",
+    feature = "vsc8541",
+    feature = "ar8031",
+    feature = "ar8035",
+    feature = "adin1300",
+    feature = "bcm54210e",
+    feature = "yt8511",
+    feature = "yt8521"
+))]
+use crate::registers::MasterSlaveControl;
+#[cfg(any(
+    feature = "ksz9021",
+    feature = "ksz9131",
+    feature = "lan8840",
+    feature = "rtl8211e",
+    feature = "rtl8211f",
+    feature = "m88e1510",
+    feature = "m88e1518",
+    feature = "vsc8211",
+    feature = "vsc8501",
+    feature = "vsc8541",
+    feature = "ar8031",
+    feature = "ar8035",
+    feature = "adin1300"
+))]
+use crate::registers::MasterSlaveStatus;
+#[cfg(feature = "mmd")]
+use crate::registers::{AutoNegCap, Eee};
+
+#[cfg(any(
+    feature = "lan8710a",
+    feature = "lan8720a",
+    feature = "lan8740a",
+    feature = "lan8741a",
+    feature = "lan8742a"
+))]
 pub mod lan87xxa;
+#[cfg(feature = "lan8710a")]
+pub use lan87xxa::LAN8710A;
+#[cfg(feature = "lan8740a")]
+pub use lan87xxa::LAN8740A;
+#[cfg(feature = "lan8741a")]
+pub use lan87xxa::LAN8741A;
 #[cfg(any(feature = "lan8720a", feature = "lan8742a"))]
 pub use lan87xxa::{LAN8720A, LAN8742A};
 
+#[cfg(any(feature = "ksz8051", feature = "ksz8081r"))]
+pub mod ksz8051;
+#[cfg(feature = "ksz8051")]
+pub use ksz8051::KSZ8051;
+
 #[cfg(feature = "ksz8081r")]
-mod ksz8081r;
+pub mod ksz8081r;
 #[cfg(feature = "ksz8081r")]
 pub use ksz8081r::KSZ8081R;
 
+#[cfg(feature = "ksz9021")]
+pub mod ksz9021;
+#[cfg(feature = "ksz9021")]
+pub use ksz9021::KSZ9021;
+
+#[cfg(any(feature = "ksz9131", feature = "lan8840"))]
+pub mod ksz9131;
+#[cfg(feature = "ksz9131")]
+pub use ksz9131::KSZ9131;
+
+#[cfg(feature = "lan867x")]
+pub mod lan867x;
+#[cfg(feature = "lan867x")]
+pub use lan867x::LAN867X;
+
+#[cfg(feature = "lan8840")]
+pub mod lan8840;
+#[cfg(feature = "lan8840")]
+pub use lan8840::LAN8840;
+
+#[cfg(feature = "lan9303")]
+pub mod lan9303;
+#[cfg(feature = "lan9303")]
+pub use lan9303::LAN9303;
+
+#[cfg(feature = "ksz8863")]
+pub mod ksz8863;
+#[cfg(feature = "ksz8863")]
+pub use ksz8863::KSZ8863;
+
+#[cfg(any(feature = "rtl8201f", feature = "rtl8211e", feature = "rtl8211f"))]
+pub mod realtek;
+
+#[cfg(feature = "rtl8201f")]
+pub mod rtl8201f;
+#[cfg(feature = "rtl8201f")]
+pub use rtl8201f::RTL8201F;
+
+#[cfg(any(feature = "rtl8211e", feature = "rtl8211f"))]
+pub mod rtl8211f;
+#[cfg(feature = "rtl8211f")]
+pub use rtl8211f::RTL8211F;
+
+#[cfg(feature = "rtl8211e")]
+pub mod rtl8211e;
+#[cfg(feature = "rtl8211e")]
+pub use rtl8211e::RTL8211E;
+
+#[cfg(any(feature = "m88e1510", feature = "m88e1518"))]
+pub mod m88e1510;
+#[cfg(feature = "m88e1510")]
+pub use m88e1510::M88E1510;
+
+#[cfg(feature = "m88e1518")]
+pub mod m88e1518;
+#[cfg(feature = "m88e1518")]
+pub use m88e1518::M88E1518;
+
+#[cfg(any(feature = "vsc8211", feature = "vsc8501", feature = "vsc8541"))]
+pub mod vsc8541;
+#[cfg(feature = "vsc8541")]
+pub use vsc8541::VSC8541;
+
+#[cfg(feature = "vsc8211")]
+pub mod vsc8211;
+#[cfg(feature = "vsc8211")]
+pub use vsc8211::VSC8211;
+
+#[cfg(feature = "vsc8501")]
+pub mod vsc8501;
+#[cfg(feature = "vsc8501")]
+pub use vsc8501::VSC8501;
+
+#[cfg(any(feature = "ar8031", feature = "ar8035"))]
+pub mod ar8031;
+#[cfg(feature = "ar8031")]
+pub use ar8031::AR8031;
+
+#[cfg(feature = "ar8035")]
+pub mod ar8035;
+#[cfg(feature = "ar8035")]
+pub use ar8035::AR8035;
+
+#[cfg(any(feature = "adin1200", feature = "adin1300"))]
+pub mod adin1200;
+#[cfg(feature = "adin1200")]
+pub use adin1200::ADIN1200;
+
+#[cfg(feature = "adin1300")]
+pub mod adin1300;
+#[cfg(feature = "adin1300")]
+pub use adin1300::ADIN1300;
+
+#[cfg(any(feature = "tja1100", feature = "tja1101", feature = "tja1102"))]
+pub mod tja1100;
+#[cfg(feature = "tja1100")]
+pub use tja1100::TJA1100;
+
+#[cfg(any(feature = "tja1101", feature = "tja1102"))]
+pub mod tja1101;
+#[cfg(feature = "tja1101")]
+pub use tja1101::TJA1101;
+
+#[cfg(feature = "tja1102")]
+pub mod tja1102;
+#[cfg(feature = "tja1102")]
+pub use tja1102::TJA1102;
+
+#[cfg(feature = "tja1103")]
+pub mod tja1103;
+#[cfg(feature = "tja1103")]
+pub use tja1103::TJA1103;
+
+#[cfg(feature = "m88x3310")]
+pub mod m88x3310;
+#[cfg(feature = "m88x3310")]
+pub use m88x3310::M88X3310;
+
+#[cfg(feature = "aqr107")]
+pub mod aqr107;
+#[cfg(feature = "aqr107")]
+pub use aqr107::AQR107;
+
+#[cfg(feature = "bcm5241")]
+pub mod bcm5241;
+#[cfg(feature = "bcm5241")]
+pub use bcm5241::BCM5241;
+
+#[cfg(feature = "bcm54210e")]
+pub mod bcm54210e;
+#[cfg(feature = "bcm54210e")]
+pub use bcm54210e::BCM54210E;
+
+#[cfg(feature = "ip101g")]
+pub mod ip101g;
+#[cfg(feature = "ip101g")]
+pub use ip101g::IP101G;
+
+#[cfg(any(feature = "yt8511", feature = "yt8521"))]
+pub mod yt8511;
+#[cfg(feature = "yt8511")]
+pub use yt8511::YT8511;
+
+#[cfg(feature = "yt8521")]
+pub mod yt8521;
+#[cfg(feature = "yt8521")]
+pub use yt8521::YT8521;
+
+#[cfg(feature = "ics1894")]
+pub mod ics1894;
+#[cfg(feature = "ics1894")]
+pub use ics1894::ICS1894;
+
+#[cfg(any(feature = "dp83848", feature = "dp83640"))]
+pub mod dp83848;
+#[cfg(feature = "dp83848")]
+pub use dp83848::DP83848;
+
+#[cfg(feature = "dp83640")]
+pub mod dp83640;
+#[cfg(feature = "dp83640")]
+pub use dp83640::DP83640;
+
 mod bare;
-pub use bare::BarePhy;
+pub use bare::{
+    find_phy, find_phy_including_broadcast, BarePhy, BarePhyBuilder, IdentPhyError, IdentifiedPhy,
+};
+
+#[cfg(feature = "mmd")]
+mod c45;
+#[cfg(feature = "mmd")]
+pub use c45::C45BarePhy;
+
+mod fixed;
+pub use fixed::{FixedMiim, FixedPhy};
+
+pub mod autoneg_monitor;
+pub use autoneg_monitor::{AutonegMonitor, AutonegOutcome};
+
+pub mod init_script;
+pub use init_script::{InitOp, InitScript, InitScriptError};
+
+pub mod manager;
+pub use manager::{LinkEvent, PhyManager, PortEvent};
+
+pub mod presence;
+pub use presence::{PresenceEvent, PresenceMonitor};
+
+#[cfg(feature = "embedded-hal")]
+pub mod interrupt_pin;
+#[cfg(feature = "embedded-hal")]
+pub use interrupt_pin::wait_for_interrupt;
+#[cfg(feature = "async")]
+pub use interrupt_pin::{wait_autoneg_complete_irq, wait_for_interrupt_async};
+
+#[cfg(feature = "embassy-time")]
+pub mod embassy;
 
 /// Basic link speeds, supported by (almost all) PHYs
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PhySpeed {
     /// 10BaseT - Half duplex
@@ -31,8 +282,8 @@ pub enum PhySpeed {
 
 /// An "advanced link speed" enum that covers more than just the
 /// basic ones described by the standard.
-
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub enum AdvancedPhySpeed {
@@ -46,6 +297,40 @@ pub enum AdvancedPhySpeed {
     FullDuplexBase1000Tx,
 }
 
+impl PhySpeed {
+    /// The speed of this link speed
+    pub fn speed(&self) -> Speed {
+        AdvancedPhySpeed::from(*self).speed()
+    }
+
+    /// The duplex mode of this link speed
+    pub fn duplex(&self) -> Duplex {
+        AdvancedPhySpeed::from(*self).duplex()
+    }
+}
+
+impl AdvancedPhySpeed {
+    /// The speed of this link speed
+    pub fn speed(&self) -> Speed {
+        match self {
+            Self::HalfDuplexBase10T | Self::FullDuplexBase10T => Speed::Mbps10,
+            Self::HalfDuplexBase100Tx | Self::FullDuplexBase100Tx => Speed::Mbps100,
+            _ => Speed::Mbps1000,
+        }
+    }
+
+    /// The duplex mode of this link speed
+    pub fn duplex(&self) -> Duplex {
+        match self {
+            Self::FullDuplexBase10T
+            | Self::FullDuplexBase100Tx
+            | Self::FullDuplexBase1000T
+            | Self::FullDuplexBase1000Tx => Duplex::Full,
+            _ => Duplex::Half,
+        }
+    }
+}
+
 impl From<PhySpeed> for AdvancedPhySpeed {
     fn from(s: PhySpeed) -> Self {
         match s {
@@ -57,6 +342,65 @@ impl From<PhySpeed> for AdvancedPhySpeed {
     }
 }
 
+/// The speed of a link, including the multi-gigabit speeds of Clause 45 PHYs
+/// that can not be represented by [`Speed`].
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MultiGigSpeed {
+    /// 10 Mbps
+    Mbps10,
+    /// 100 Mbps
+    Mbps100,
+    /// 1000 Mbps
+    Mbps1000,
+    /// 2.5 Gbps
+    Mbps2500,
+    /// 5 Gbps
+    Mbps5000,
+    /// 10 Gbps
+    Mbps10000,
+}
+
+impl From<Speed> for MultiGigSpeed {
+    fn from(speed: Speed) -> Self {
+        match speed {
+            Speed::Mbps10 => Self::Mbps10,
+            Speed::Mbps100 => Self::Mbps100,
+            Speed::Mbps1000 => Self::Mbps1000,
+        }
+    }
+}
+
+impl MultiGigSpeed {
+    /// The [`AdvancedPhySpeed`] for a link with this speed and `duplex`, if
+    /// it can be represented by one
+    pub fn advanced(&self, duplex: Duplex) -> Option<AdvancedPhySpeed> {
+        Some(match (self, duplex) {
+            (Self::Mbps10, Duplex::Half) => AdvancedPhySpeed::HalfDuplexBase10T,
+            (Self::Mbps10, Duplex::Full) => AdvancedPhySpeed::FullDuplexBase10T,
+            (Self::Mbps100, Duplex::Half) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+            (Self::Mbps100, Duplex::Full) => AdvancedPhySpeed::FullDuplexBase100Tx,
+            (Self::Mbps1000, Duplex::Half) => AdvancedPhySpeed::HalfDuplexBase1000T,
+            (Self::Mbps1000, Duplex::Full) => AdvancedPhySpeed::FullDuplexBase1000T,
+            _ => return None,
+        })
+    }
+}
+
+impl TryFrom<MultiGigSpeed> for Speed {
+    type Error = IllegalSpeed;
+
+    fn try_from(speed: MultiGigSpeed) -> Result<Self, Self::Error> {
+        match speed {
+            MultiGigSpeed::Mbps10 => Ok(Speed::Mbps10),
+            MultiGigSpeed::Mbps100 => Ok(Speed::Mbps100),
+            MultiGigSpeed::Mbps1000 => Ok(Speed::Mbps1000),
+            _ => Err(IllegalSpeed),
+        }
+    }
+}
+
 /// A PHY that also supports determining the link speed and duplex mode
 /// it is currently operating at.
 pub trait PhyWithSpeed<MIIM: Miim>: Phy<MIIM> {
@@ -64,3 +408,533 @@ pub trait PhyWithSpeed<MIIM: Miim>: Phy<MIIM> {
     /// operating.
     fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed>;
 }
+
+/// Determine the link speed of a PHY that has no vendor specific speed
+/// indication.
+///
+/// This is resolved from the advertised and link partner capabilities if
+/// autonegotiation is enabled, and from the BCR otherwise.
+#[cfg(any(feature = "ksz8863", feature = "rtl8201f"))]
+pub(crate) fn resolve_link_speed<M: Miim, P: Phy<M>>(phy: &mut P) -> Option<PhySpeed> {
+    let bcr = phy.bcr();
+
+    if !bcr.autonegotiation() {
+        let speed = match (bcr.speed().ok()?, bcr.duplex()) {
+            (Speed::Mbps10, Duplex::Half) => PhySpeed::HalfDuplexBase10T,
+            (Speed::Mbps10, Duplex::Full) => PhySpeed::FullDuplexBase10T,
+            (Speed::Mbps100, Duplex::Half) => PhySpeed::HalfDuplexBase100Tx,
+            (Speed::Mbps100, Duplex::Full) => PhySpeed::FullDuplexBase100Tx,
+            _ => return None,
+        };
+        return Some(speed);
+    }
+
+    let local = phy.get_autonegotiation_caps()?;
+    let partner = phy.get_autonegotiation_partner_caps()?;
+
+    if local.fd_100base_tx && partner.fd_100base_tx {
+        Some(PhySpeed::FullDuplexBase100Tx)
+    } else if local.hd_100base_tx && partner.hd_100base_tx {
+        Some(PhySpeed::HalfDuplexBase100Tx)
+    } else if local.fd_10base_t && partner.fd_10base_t {
+        Some(PhySpeed::FullDuplexBase10T)
+    } else if local.hd_10base_t && partner.hd_10base_t {
+        Some(PhySpeed::HalfDuplexBase10T)
+    } else {
+        None
+    }
+}
+
+/// Advertise all supported 10/100/1000BASE-T modes of a gigabit PHY, and
+/// restart autonegotiation.
+///
+/// 1000BASE-T is advertised through the MASTER-SLAVE Control register, and
+/// the other modes through [`Phy::best_supported_advertisement`].
+#[cfg(any(
+    feature = "ksz9021",
+    feature = "ksz9131",
+    feature = "lan8840",
+    feature = "rtl8211e",
+    feature = "rtl8211f",
+    feature = "m88e1510",
+    feature = "m88e1518",
+    feature = "vsc8211",
+    feature = "vsc8501",
+    feature = "vsc8541",
+    feature = "ar8031",
+    feature = "ar8035",
+    feature = "adin1300",
+    feature = "bcm54210e",
+    feature = "yt8511",
+    feature = "yt8521"
+))]
+pub(crate) fn advertise_gigabit<M: Miim, P: Phy<M>>(phy: &mut P) {
+    let mut msc = MasterSlaveControl::from_bits_truncate(phy.read(MasterSlaveControl::ADDRESS));
+    msc.set_1000base_t_fd(true).set_1000base_t_hd(true);
+    phy.write(MasterSlaveControl::ADDRESS, msc.bits());
+
+    phy.set_autonegotiation_advertisement(phy.best_supported_advertisement());
+}
+
+/// Read the MASTER-SLAVE Status register of a gigabit PHY, containing the
+/// 1000BASE-T abilities of the link partner
+#[cfg(any(
+    feature = "ksz9021",
+    feature = "ksz9131",
+    feature = "lan8840",
+    feature = "rtl8211e",
+    feature = "rtl8211f",
+    feature = "m88e1510",
+    feature = "m88e1518",
+    feature = "vsc8211",
+    feature = "vsc8501",
+    feature = "vsc8541",
+    feature = "ar8031",
+    feature = "ar8035",
+    feature = "adin1300"
+))]
+pub(crate) fn master_slave_status<M: Miim, P: Phy<M>>(phy: &mut P) -> MasterSlaveStatus {
+    MasterSlaveStatus::from_bits_truncate(phy.read(MasterSlaveStatus::ADDRESS))
+}
+
+/// The result of a cable diagnostics test on a single pair.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CablePairStatus {
+    /// The pair is terminated correctly
+    Normal,
+    /// The pair is open
+    Open,
+    /// The pair is shorted
+    Short,
+    /// The test failed, or the result could not be determined
+    TestFailed,
+}
+
+/// The result of a cable diagnostics test.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CableDiagnostics {
+    /// The status of the tested pair
+    pub status: CablePairStatus,
+    /// The distance to the detected fault in centimeters, if
+    /// a fault was detected and the PHY reports its location.
+    pub fault_distance_cm: Option<u16>,
+}
+
+/// A PHY that supports cable diagnostics (e.g. TDR).
+pub trait PhyWithCableDiagnostics<MIIM: Miim>: Phy<MIIM> {
+    /// The amount of cable pairs that can be tested by this PHY
+    fn cable_pairs(&self) -> u8;
+
+    /// Run a cable diagnostics test on `pair`, blocking until the test
+    /// has completed.
+    ///
+    /// The PHY is returned to its previous configuration once the test has
+    /// completed, but the link will go down during the test.
+    ///
+    /// Returns `None` if `pair` is not less than [`Self::cable_pairs`].
+    fn test_cable_pair(&mut self, pair: u8) -> Option<CableDiagnostics>;
+}
+
+/// A Signal Quality Indicator (SQI) reading
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sqi {
+    /// The signal quality, ranging from 0 (worst) to 7 (best)
+    pub level: u8,
+    /// The signal quality as reported by the PHY, before normalizing it.
+    ///
+    /// Its meaning is PHY specific, e.g. an SQI class or a mean square
+    /// error.
+    pub raw: u16,
+}
+
+/// A PHY that reports the quality of the received signal, such as most
+/// single-pair (100BASE-T1, 10BASE-T1L) PHYs.
+pub trait PhyWithSqi<MIIM: Miim>: Phy<MIIM> {
+    /// Get the signal quality of the link
+    ///
+    /// Returns `None` if the signal quality is not valid, e.g. because no
+    /// link is established.
+    fn sqi(&mut self) -> Option<Sqi>;
+}
+
+bitflags::bitflags! {
+    /// Wake-on-LAN events
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct WakeOnLanEvents: u8 {
+        /// A magic packet addressed to the configured MAC address
+        const MAGIC_PACKET = (1 << 0);
+        /// A broadcast frame
+        const BROADCAST = (1 << 1);
+        /// A frame with a destination address matching the configured MAC address
+        const PERFECT_DA = (1 << 2);
+        /// A frame matching one of the configured wake-up frame filters
+        const WAKEUP_FRAME = (1 << 3);
+    }
+}
+
+/// A PHY that supports Wake-on-LAN.
+pub trait PhyWithWakeOnLan<MIIM: Miim>: Phy<MIIM> {
+    /// Set the MAC address used for matching magic packets and
+    /// perfect DA frames
+    fn set_wol_mac_address(&mut self, mac_address: [u8; 6]);
+
+    /// Get the wake-up events that are currently enabled
+    fn wol_events(&mut self) -> WakeOnLanEvents;
+
+    /// Enable waking up on `events`, and disable waking up on all
+    /// other events.
+    fn set_wol_events(&mut self, events: WakeOnLanEvents);
+
+    /// Read the wake-up events that have been received, and clear them
+    fn read_and_clear_wol_status(&mut self) -> WakeOnLanEvents;
+}
+
+/// A loopback mode of a PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loopback {
+    /// Near-end loopback: data transmitted by the MAC is returned to the
+    /// MAC, instead of being transmitted on the cable
+    Near,
+    /// Far-end loopback: data received from the link partner is transmitted
+    /// back to the link partner
+    Far,
+}
+
+/// A PHY that supports loopback modes.
+pub trait PhyWithLoopback<MIIM: Miim>: Phy<MIIM> {
+    /// Get the loopback mode that is currently enabled, if any
+    fn loopback(&mut self) -> Option<Loopback>;
+
+    /// Enable `loopback`, or disable loopback if it is `None`.
+    fn set_loopback(&mut self, loopback: Option<Loopback>);
+
+    /// Enable `loopback` until the returned guard is dropped, after which
+    /// the previous loopback mode is restored.
+    ///
+    /// The PHY can still be accessed through the guard, e.g. to run a self
+    /// test.
+    fn loopback_scoped(&mut self, loopback: Loopback) -> LoopbackGuard<'_, MIIM, Self>
+    where
+        Self: Sized,
+    {
+        LoopbackGuard::new(self, loopback)
+    }
+}
+
+/// The interface between a PHY and the MAC
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterfaceMode {
+    /// Media Independent Interface
+    Mii,
+    /// Reduced Media Independent Interface
+    Rmii,
+    /// Gigabit Media Independent Interface
+    Gmii,
+    /// Reduced Gigabit Media Independent Interface
+    Rgmii {
+        /// Whether the PHY delays the TX clock (GTX_CLK) internally
+        tx_delay: bool,
+        /// Whether the PHY delays the RX clock internally
+        rx_delay: bool,
+    },
+    /// Serial Gigabit Media Independent Interface
+    Sgmii,
+}
+
+/// An [`InterfaceMode`] that is not supported by a PHY, or that can not be
+/// selected through its registers.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedInterfaceMode(pub InterfaceMode);
+
+/// A PHY that reports, and where supported configures, the interface to the
+/// MAC.
+///
+/// This can be used by MAC drivers to check that the PHY is configured for
+/// the same interface as the MAC.
+pub trait PhyWithInterfaceMode<MIIM: Miim>: Phy<MIIM> {
+    /// Get the interface mode in which the PHY operates, or `None` if it
+    /// can not be determined.
+    fn interface_mode(&mut self) -> Option<InterfaceMode>;
+
+    /// Configure the interface mode
+    ///
+    /// Many PHYs select their interface through strapping pins, and only
+    /// allow configuring the internal delays through their registers. An
+    /// error is returned if `mode` is not supported, or would require
+    /// changing such strapping options.
+    fn set_interface_mode(&mut self, mode: InterfaceMode) -> Result<(), UnsupportedInterfaceMode>;
+}
+
+/// The internal RGMII clock delays of a PHY
+///
+/// Enabling both delays corresponds to RGMII-ID, enabling only one of them to
+/// RGMII-TXID or RGMII-RXID.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct RgmiiDelays {
+    /// Whether the PHY delays the TX clock (GTX_CLK) internally
+    pub tx: bool,
+    /// Whether the PHY delays the RX clock internally
+    pub rx: bool,
+}
+
+/// A PHY that can add the RGMII clock delays internally, so that they do not
+/// have to be added by the MAC or the board.
+pub trait PhyWithRgmiiDelays<MIIM: Miim>: Phy<MIIM> {
+    /// Get the internal RGMII clock delays that are enabled
+    fn internal_delays(&mut self) -> RgmiiDelays;
+
+    /// Enable or disable the internal RGMII clock delays
+    ///
+    /// Where a PHY supports configuring the length of a delay, it is left
+    /// untouched.
+    fn set_internal_delays(&mut self, delays: RgmiiDelays);
+}
+
+/// The pair assignment of a twisted pair interface
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MdiMode {
+    /// Transmit on TXP/TXM, receive on RXP/RXM
+    Mdi,
+    /// Transmit on RXP/RXM, receive on TXP/TXM
+    Mdix,
+}
+
+/// A PHY that can swap its transmit and receive pairs, either automatically
+/// (auto MDI/MDI-X) or manually.
+pub trait PhyWithMdix<MIIM: Miim>: Phy<MIIM> {
+    /// Get the forced pair assignment, or `None` if auto MDI/MDI-X is enabled
+    fn mdi_mode(&mut self) -> Option<MdiMode>;
+
+    /// Force the pair assignment to `mode`, or enable auto MDI/MDI-X if
+    /// `mode` is `None`
+    fn set_mdi_mode(&mut self, mode: Option<MdiMode>);
+
+    /// Get the pair assignment that is currently in use
+    ///
+    /// Returns `None` if the PHY does not report it.
+    fn mdi_status(&mut self) -> Option<MdiMode>;
+}
+
+/// A PHY that extends its register space with pages, which are selected
+/// through a page select register.
+pub trait PhyWithPages<MIIM: Miim>: Phy<MIIM> {
+    /// The address of the page select register
+    const PAGE_SELECT: u8;
+
+    /// Get the page that is currently selected
+    fn page(&mut self) -> u16 {
+        self.read(Self::PAGE_SELECT)
+    }
+
+    /// Select `page` until the returned guard is dropped, after which the
+    /// previously selected page is restored.
+    ///
+    /// The registers on `page` can be accessed through the guard. Using
+    /// the guard instead of selecting pages manually prevents leaving the PHY
+    /// on another page, after which accesses to the standard registers would
+    /// return unrelated values.
+    fn page_scoped(&mut self, page: u16) -> PageGuard<'_, MIIM, Self>
+    where
+        Self: Sized,
+    {
+        PageGuard::new(self, page)
+    }
+}
+
+/// The link health counters of a PHY
+///
+/// Counters that are not implemented by a PHY are `None`.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct LinkCounters {
+    /// The amount of received frames during which RX_ER was asserted
+    pub receive_errors: Option<u16>,
+    /// The amount of false carrier events
+    pub false_carriers: Option<u16>,
+    /// The amount of invalid symbols that were received
+    pub symbol_errors: Option<u16>,
+    /// The amount of times the link went down
+    pub disconnects: Option<u16>,
+}
+
+/// A PHY that counts link health events, which can be used to monitor the
+/// quality of a link over time.
+pub trait PhyWithCounters<MIIM: Miim>: Phy<MIIM> {
+    /// Read all counters that are implemented by this PHY, and clear them.
+    ///
+    /// The counters contain the amount of events since the last call.
+    /// Counters that are saturated are not extended beyond their maximum.
+    fn read_and_clear_counters(&mut self) -> LinkCounters;
+}
+
+/// A PHY that can signal events through an interrupt output.
+pub trait PhyWithInterrupts<MIIM: Miim>: Phy<MIIM> {
+    /// The set of interrupts that are reported by this PHY
+    type Interrupts;
+
+    /// Read and clear all pending interrupts
+    fn read_and_clear_interrupts(&mut self) -> Self::Interrupts;
+}
+
+/// A PHY that can signal the completion of autonegotiation through its
+/// interrupt output.
+pub trait PhyWithAutonegInterrupt<MIIM: Miim>: PhyWithInterrupts<MIIM> {
+    /// Enable or disable the autonegotiation complete interrupt
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool);
+}
+
+/// A PHY with LEDs that can be driven directly, overriding their normal
+/// function.
+pub trait PhyWithLeds<MIIM: Miim>: Phy<MIIM> {
+    /// The number of LEDs of this PHY
+    fn led_count(&self) -> u8;
+
+    /// Drive `led` on (`Some(true)`) or off (`Some(false)`), or return it to
+    /// its normal function (`None`).
+    ///
+    /// LEDs are numbered from 0 to [`Self::led_count`], exclusive. This does
+    /// nothing if `led` does not exist.
+    fn set_led(&mut self, led: u8, state: Option<bool>);
+}
+
+/// A PHY that supports fast link down detection, in which it reports a
+/// link failure well before the standard link status is updated.
+///
+/// This is useful for applications that need to fail over to another link
+/// quickly.
+pub trait PhyWithFastLinkDown<MIIM: Miim>: Phy<MIIM> {
+    /// The configuration of the fast link down detection, describing the
+    /// criteria that trigger it and, if supported, its latency
+    type Config;
+
+    /// Get the current fast link down configuration
+    fn fast_link_down(&mut self) -> Self::Config;
+
+    /// Configure fast link down detection
+    fn set_fast_link_down(&mut self, config: Self::Config);
+}
+
+/// A PHY that can detect whether energy is present on the cable, even if
+/// no link is established.
+///
+/// This can be used to distinguish an unplugged cable (or a link partner
+/// that is powered off) from a link that is still being negotiated.
+pub trait PhyWithCableDetect<MIIM: Miim>: Phy<MIIM> {
+    /// Check whether energy is currently detected on the cable
+    fn energy_detected(&mut self) -> bool;
+}
+
+/// A PHY that supports Energy Detect Power-Down, in which it powers down
+/// most of its circuitry while no energy is detected on the cable.
+pub trait PhyWithEnergyDetect<MIIM: Miim>: PhyWithCableDetect<MIIM> {
+    /// Check whether Energy Detect Power-Down is enabled
+    fn energy_detect_power_down(&mut self) -> bool;
+
+    /// Enable or disable Energy Detect Power-Down
+    fn set_energy_detect_power_down(&mut self, enabled: bool);
+}
+
+/// A PHY that can output a clock on one of its pins, such as a free-running
+/// reference clock for the MAC or a clock recovered from the link for
+/// synchronous Ethernet.
+pub trait PhyWithClockOutput<MIIM: Miim>: Phy<MIIM> {
+    /// The clocks that can be output, describing their source and frequency
+    /// and, if supported, whether the output is disabled
+    type ClockOutput;
+
+    /// Get the clock that is currently output
+    fn clock_output(&mut self) -> Self::ClockOutput;
+
+    /// Select the clock that is output
+    fn set_clock_output(&mut self, output: Self::ClockOutput);
+}
+
+/// A PHY that supports Energy Efficient Ethernet through the standard
+/// Clause 45 EEE registers.
+#[cfg(feature = "mmd")]
+pub trait PhyWithEee<MIIM: Miim>: Phy<MIIM> + Sized {
+    /// Get the EEE modes supported by this PHY
+    fn eee_capabilities(&mut self) -> Eee {
+        Eee::from_bits_truncate(self.mmd_read(Eee::CAPABILITY_MMD, Eee::CAPABILITY_ADDRESS))
+    }
+
+    /// Get the EEE modes that this PHY advertises
+    fn eee_advertisement(&mut self) -> Eee {
+        Eee::from_bits_truncate(self.mmd_read(Eee::ADVERTISEMENT_MMD, Eee::ADVERTISEMENT_ADDRESS))
+    }
+
+    /// Set the EEE modes that this PHY advertises.
+    ///
+    /// Modes that are not supported by the PHY are not advertised. The
+    /// new advertisement only takes effect once autonegotiation is restarted.
+    fn set_eee_advertisement(&mut self, eee: Eee) {
+        let eee = eee & self.eee_capabilities();
+        self.mmd_write(
+            Eee::ADVERTISEMENT_MMD,
+            Eee::ADVERTISEMENT_ADDRESS,
+            eee.bits(),
+        );
+    }
+
+    /// Get the EEE modes that the link partner advertises
+    fn eee_partner_advertisement(&mut self) -> Eee {
+        Eee::from_bits_truncate(self.mmd_read(Eee::ADVERTISEMENT_MMD, Eee::PARTNER_ADDRESS))
+    }
+
+    /// Advertise `eee`, and restart autonegotiation so that the new
+    /// advertisement is exchanged with the link partner.
+    ///
+    /// The EEE advertisement is exchanged through next pages, so the
+    /// NEXT_PAGE bit of the autonegotiation advertisement register is set as
+    /// well if any mode is advertised, and cleared if no mode is advertised.
+    /// Modes that are not supported by the PHY are not advertised.
+    ///
+    /// Use [`Self::resolved_eee`] to find out which modes are used once
+    /// autonegotiation has completed.
+    fn advertise_eee(&mut self, eee: Eee) {
+        self.set_eee_advertisement(eee);
+
+        let next_page = if self.eee_advertisement().is_empty() {
+            0
+        } else {
+            AutoNegCap::NEXT_PAGE.bits()
+        };
+        self.modify(
+            AutoNegCap::LOCAL_CAP_ADDRESS,
+            AutoNegCap::NEXT_PAGE.bits(),
+            next_page,
+        );
+
+        self.modify_bcr(|bcr| {
+            bcr.set_autonegotiation(true).restart_autonegotiation();
+        });
+    }
+
+    /// Get the EEE modes that are advertised by both this PHY and the link
+    /// partner.
+    ///
+    /// Returns `None` if autonegotiation has not completed yet.
+    fn resolved_eee(&mut self) -> Option<Eee> {
+        if !self.autoneg_completed() {
+            return None;
+        }
+
+        Some(self.eee_advertisement() & self.eee_partner_advertisement())
+    }
+}
@@ -0,0 +1,139 @@
+//! Monitoring the presence of a PHY.
+//!
+//! A [`PresenceMonitor`] periodically reads the PHY identifier of a PHY, and
+//! reports when the PHY disappears, (re)appears, or is replaced by a
+//! different PHY. This can be used to handle PHYs on pluggable daughterboards.
+//!
+//! A PHY that loses power during a brown-out keeps its identifier, so it can
+//! not be detected through the identifier alone. After configuring the PHY,
+//! call [`PresenceMonitor::configured`] so that the monitor also compares the
+//! autonegotiation advertisement register, which returns to its reset value
+//! if the PHY is reset.
+//!
+//! Time is measured in ticks of a monotonic, wrapping `u32` counter that is
+//! provided by the caller.
+
+use crate::{registers::AutoNegCap, Miim, Phy, PhyIdent};
+
+/// A change of the presence of a PHY
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresenceEvent {
+    /// The PHY no longer responds
+    Disappeared,
+    /// The PHY is still present, but its autonegotiation advertisement no
+    /// longer matches the value recorded by [`PresenceMonitor::configured`],
+    /// so it was most likely reset and must be configured again
+    Reset,
+    /// A PHY with the given identifier appeared where no PHY was present
+    Appeared(PhyIdent),
+    /// The PHY was replaced by a PHY with a different identifier
+    Replaced {
+        /// The identifier of the previous PHY
+        previous: PhyIdent,
+        /// The identifier of the current PHY
+        current: PhyIdent,
+    },
+}
+
+/// Periodically checks whether a PHY is present
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PresenceMonitor {
+    ident: Option<PhyIdent>,
+    advertisement: Option<u16>,
+    interval: u32,
+    last_check: Option<u32>,
+}
+
+impl PresenceMonitor {
+    /// Create a monitor that checks the presence of a PHY every `interval`
+    /// ticks.
+    ///
+    /// The PHY is assumed to be absent until it is checked for the first
+    /// time.
+    pub fn new(interval: u32) -> Self {
+        Self {
+            ident: None,
+            advertisement: None,
+            interval,
+            last_check: None,
+        }
+    }
+
+    /// The identifier of the PHY when it was last checked, or `None` if no
+    /// PHY was present
+    pub fn ident(&self) -> Option<PhyIdent> {
+        self.ident
+    }
+
+    /// Record the autonegotiation advertisement of `phy`, after it has been
+    /// configured.
+    ///
+    /// Until the PHY disappears, is replaced or is reset, subsequent checks
+    /// report [`PresenceEvent::Reset`] if the advertisement differs from the
+    /// recorded value. This should be called again after the PHY has been
+    /// reconfigured.
+    pub fn configured<M: Miim, P: Phy<M>>(&mut self, phy: &mut P) {
+        self.advertisement = Some(phy.read(AutoNegCap::LOCAL_CAP_ADDRESS));
+    }
+
+    /// Read the identifier of `phy`.
+    ///
+    /// Returns `None` if no PHY responds. An absent PHY reads as all ones,
+    /// but some MIIM implementations read all zeros instead, so both are
+    /// considered absent.
+    pub fn read_ident<M: Miim, P: Phy<M>>(phy: &mut P) -> Option<PhyIdent> {
+        let ident = PhyIdent::new(phy.read(2), phy.read(3));
+
+        match ident.raw_u32() {
+            0xFFFF_FFFF | 0 => None,
+            _ => Some(ident),
+        }
+    }
+
+    /// Check the presence of `phy` if the interval has expired since it was
+    /// last checked, and return the change of its presence, if any.
+    pub fn poll<M: Miim, P: Phy<M>>(&mut self, phy: &mut P, now: u32) -> Option<PresenceEvent> {
+        let due = match self.last_check {
+            Some(last) => now.wrapping_sub(last) >= self.interval,
+            None => true,
+        };
+        if !due {
+            return None;
+        }
+
+        self.last_check = Some(now);
+        self.check(phy)
+    }
+
+    /// Check the presence of `phy` now, and return the change of its
+    /// presence, if any.
+    pub fn check<M: Miim, P: Phy<M>>(&mut self, phy: &mut P) -> Option<PresenceEvent> {
+        let current = Self::read_ident(phy);
+        let previous = core::mem::replace(&mut self.ident, current);
+
+        let event = match (previous, current) {
+            (Some(_), None) => Some(PresenceEvent::Disappeared),
+            (None, Some(current)) => Some(PresenceEvent::Appeared(current)),
+            (Some(previous), Some(current)) if previous != current => {
+                Some(PresenceEvent::Replaced { previous, current })
+            }
+            (Some(_), Some(_)) => match self.advertisement {
+                Some(ad) if phy.read(AutoNegCap::LOCAL_CAP_ADDRESS) != ad => {
+                    Some(PresenceEvent::Reset)
+                }
+                _ => None,
+            },
+            (None, None) => None,
+        };
+
+        // The recorded advertisement only applies to the configured PHY
+        if event.is_some() {
+            self.advertisement = None;
+        }
+
+        event
+    }
+}
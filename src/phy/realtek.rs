@@ -0,0 +1,42 @@
+//! Access to the paged registers of Realtek PHYs
+//!
+//! Realtek PHYs, such as the [`RTL8201F`](super::rtl8201f::RTL8201F) and the
+//! RTL8211 family, map pages of vendor specific registers into the register
+//! space. The page is selected by writing its number to the Page Select
+//! Register ([`PAGSR`]), after which the registers of the page can be
+//! accessed.
+//!
+//! The functions in this module select a page, access a register on it, and
+//! select the previously selected page again through a [`PageGuard`]. They
+//! work with any Realtek PHY that implements [`PhyWithPages`], so they can
+//! also be used to reach pages that are not (yet) supported by the drivers in
+//! this crate.
+//!
+//! [`PageGuard`]: crate::PageGuard
+
+use crate::{phy::PhyWithPages, Miim};
+
+/// Register 31, the Page Select Register (PAGSR)
+pub const PAGSR: u8 = 31;
+
+/// Read the register at `address` on `page`
+pub fn read_paged<M: Miim, P: PhyWithPages<M>>(phy: &mut P, page: u16, address: u8) -> u16 {
+    phy.page_scoped(page).read(address)
+}
+
+/// Write the register at `address` on `page`
+pub fn write_paged<M: Miim, P: PhyWithPages<M>>(phy: &mut P, page: u16, address: u8, value: u16) {
+    phy.page_scoped(page).write(address, value)
+}
+
+/// Modify the bits selected by `mask` in the register at `address` on
+/// `page`, leaving all other bits untouched
+pub fn modify_paged<M: Miim, P: PhyWithPages<M>>(
+    phy: &mut P,
+    page: u16,
+    address: u8,
+    mask: u16,
+    value: u16,
+) {
+    phy.page_scoped(page).modify(address, mask, value)
+}
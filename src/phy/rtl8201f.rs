@@ -0,0 +1,322 @@
+//! Phy implementation for the Realtek RTL8201F
+//!
+//! Registers 16 through 30 of the RTL8201F are paged. The page is selected
+//! through register 31, and page 0 must be selected for normal operation.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    InterruptEnable, InterruptStatus, RmiiModeSetting, WolEvents, WolReset, PAGE_SELECT,
+    WOL_MAC_ADDRESS, WOL_MAC_ADDRESS_PAGE,
+};
+
+use super::{realtek, AdvancedPhySpeed, PhySpeed, PhyWithInterrupts, PhyWithPages, PhyWithSpeed};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The link status changed
+    LinkChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+}
+
+impl Interrupt {
+    /// The enable bit for this interrupt in the [`InterruptEnable`] register
+    pub fn enable_flag(&self) -> InterruptEnable {
+        match self {
+            Interrupt::LinkChange => InterruptEnable::LINK_CHANGE,
+            Interrupt::DuplexChange => InterruptEnable::DUPLEX_CHANGE,
+            Interrupt::AutoNegotiationError => InterruptEnable::AUTONEG_ERROR,
+        }
+    }
+
+    /// The status bit for this interrupt in the [`InterruptStatus`] register
+    pub fn status_flag(&self) -> InterruptStatus {
+        match self {
+            Interrupt::LinkChange => InterruptStatus::LINK_CHANGE,
+            Interrupt::DuplexChange => InterruptStatus::DUPLEX_CHANGE,
+            Interrupt::AutoNegotiationError => InterruptStatus::AUTONEG_ERROR,
+        }
+    }
+}
+
+/// The direction of the RMII reference clock
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RmiiClockDirection {
+    /// The PHY outputs the 50 MHz reference clock
+    Output,
+    /// The 50 MHz reference clock is supplied to the PHY
+    Input,
+}
+
+/// A RTL8201F
+#[derive(Debug)]
+pub struct RTL8201F<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> RTL8201F<MIIM> {
+    /// Create a new RTL8201F at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    pub fn phy_init(&mut self) {
+        self.write(PAGE_SELECT, 0);
+        self.set_autonegotiation_advertisement(self.best_supported_advertisement());
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u8, address: u8) -> u16 {
+        realtek::read_paged(self, page as u16, address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u8, address: u8, value: u16) {
+        realtek::write_paged(self, page as u16, address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    pub fn modify_paged(&mut self, page: u8, address: u8, mask: u16, value: u16) {
+        realtek::modify_paged(self, page as u16, address, mask, value)
+    }
+
+    /// Get the link speed, as resolved from the BCR and the autonegotiation
+    /// results
+    pub fn link_speed(&mut self) -> Option<PhySpeed> {
+        super::resolve_link_speed(self)
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the RMII Mode Setting register
+    pub fn rmii_mode_setting(&mut self) -> RmiiModeSetting {
+        RmiiModeSetting::from_bits_truncate(
+            self.read_paged(RmiiModeSetting::PAGE, RmiiModeSetting::ADDRESS),
+        )
+    }
+
+    /// Select between MII (`false`) and RMII (`true`) mode
+    pub fn set_rmii_mode(&mut self, rmii: bool) {
+        let bit = RmiiModeSetting::RMII_MODE.bits();
+        let value = if rmii { bit } else { 0 };
+        self.modify_paged(RmiiModeSetting::PAGE, RmiiModeSetting::ADDRESS, bit, value);
+    }
+
+    /// Get the direction of the RMII reference clock
+    pub fn rmii_clock_direction(&mut self) -> RmiiClockDirection {
+        if self
+            .rmii_mode_setting()
+            .contains(RmiiModeSetting::CLOCK_DIRECTION_INPUT)
+        {
+            RmiiClockDirection::Input
+        } else {
+            RmiiClockDirection::Output
+        }
+    }
+
+    /// Set the direction of the RMII reference clock
+    pub fn set_rmii_clock_direction(&mut self, direction: RmiiClockDirection) {
+        let bit = RmiiModeSetting::CLOCK_DIRECTION_INPUT.bits();
+        let value = if direction == RmiiClockDirection::Input {
+            bit
+        } else {
+            0
+        };
+        self.modify_paged(RmiiModeSetting::PAGE, RmiiModeSetting::ADDRESS, bit, value);
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.enable_flag().bits();
+        self.modify_paged(InterruptEnable::PAGE, InterruptEnable::ADDRESS, flag, flag);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.enable_flag().bits();
+        self.modify_paged(InterruptEnable::PAGE, InterruptEnable::ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptStatus::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptStatus {
+        InterruptStatus::from_bits_truncate(self.read(InterruptStatus::ADDRESS))
+    }
+
+    /// Set the MAC address used to detect Wake-on-LAN magic packets
+    pub fn set_wol_mac_address(&mut self, mac: [u8; 6]) {
+        for (i, bytes) in mac.chunks(2).enumerate() {
+            self.write_paged(
+                WOL_MAC_ADDRESS_PAGE,
+                WOL_MAC_ADDRESS + i as u8,
+                u16::from_le_bytes([bytes[0], bytes[1]]),
+            );
+        }
+    }
+
+    /// Get the enabled Wake-on-LAN events
+    pub fn wol_events(&mut self) -> WolEvents {
+        WolEvents::from_bits_truncate(self.read_paged(WolEvents::PAGE, WolEvents::ADDRESS))
+    }
+
+    /// Set the enabled Wake-on-LAN events
+    pub fn set_wol_events(&mut self, events: WolEvents) {
+        self.modify_paged(
+            WolEvents::PAGE,
+            WolEvents::ADDRESS,
+            WolEvents::all().bits(),
+            events.bits(),
+        );
+    }
+
+    /// Reset the Wake-on-LAN logic, releasing the PMEB pin after a wake-up
+    /// event
+    pub fn reset_wol(&mut self) {
+        let reset = WolReset::RESET.bits();
+        self.modify_paged(WolReset::PAGE, WolReset::ADDRESS, reset, reset);
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for RTL8201F<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for RTL8201F<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for RTL8201F<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed().map(Into::into)
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for RTL8201F<MIIM> {
+    type Interrupts = InterruptStatus;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptStatus {
+        self.read_and_clear_interrupts()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! RTL8201F registers
+
+    use bitflags::bitflags;
+
+    use super::Interrupt;
+
+    /// Register 31, the Page Select register
+    pub const PAGE_SELECT: u8 = 31;
+
+    /// The page containing the Wake-on-LAN MAC address registers
+    pub const WOL_MAC_ADDRESS_PAGE: u8 = 18;
+    /// Registers 16 through 18 on page 18, the Wake-on-LAN MAC address
+    pub const WOL_MAC_ADDRESS: u8 = 16;
+
+    bitflags! {
+        /// Register 30 on page 0, the Interrupt Indicators register
+        pub struct InterruptStatus: u16 {
+            const AUTONEG_ERROR = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const DUPLEX_CHANGE = (1 << 13);
+            const LINK_CHANGE = (1 << 11);
+        }
+
+        /// Register 16 on page 7, the RMII Mode Setting register
+        pub struct RmiiModeSetting: u16 {
+            const CLOCK_DIRECTION_INPUT = (1 << 12);
+            const TX_TIMING_MASK = (0xF << 8);
+            const RX_TIMING_MASK = (0xF << 4);
+            const RMII_MODE = (1 << 3);
+        }
+
+        /// Register 19 on page 7, the Interrupt, WoL Enable and LED Function register
+        pub struct InterruptEnable: u16 {
+            const LINK_CHANGE = (1 << 13);
+            const DUPLEX_CHANGE = (1 << 12);
+            const AUTONEG_ERROR = (1 << 11);
+        }
+
+        /// Register 16 on page 17, the WoL Event Select register
+        pub struct WolEvents: u16 {
+            const MAGIC_PACKET = (1 << 12);
+            const WAKEUP_FRAME_MASK = (0xFF);
+        }
+
+        /// Register 17 on page 17, the WoL Reset register
+        pub struct WolReset: u16 {
+            const RESET = (1 << 15);
+        }
+    }
+
+    impl InterruptStatus {
+        pub const ADDRESS: u8 = 30;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.status_flag())
+        }
+    }
+
+    impl RmiiModeSetting {
+        pub const PAGE: u8 = 7;
+        pub const ADDRESS: u8 = 16;
+    }
+
+    impl InterruptEnable {
+        pub const PAGE: u8 = 7;
+        pub const ADDRESS: u8 = 19;
+    }
+
+    impl WolEvents {
+        pub const PAGE: u8 = 17;
+        pub const ADDRESS: u8 = 16;
+    }
+
+    impl WolReset {
+        pub const PAGE: u8 = 17;
+        pub const ADDRESS: u8 = 17;
+    }
+}
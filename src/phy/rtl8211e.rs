@@ -0,0 +1,325 @@
+//! Phy implementation for the Realtek RTL8211E gigabit Ethernet PHY
+//!
+//! Unlike the [`RTL8211F`](super::rtl8211f::RTL8211F), the interrupt and
+//! status registers of the RTL8211E are located on page 0, and its vendor
+//! specific configuration is done through extension pages. An extension
+//! page is accessed by selecting page 7 in register 31, and then writing
+//! the extension page number to register 30.
+
+use crate::{
+    phy::realtek::PAGSR, registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy,
+    PhyAddress,
+};
+
+use self::registers::{
+    DelayConfig, InterruptFlags, PhySpecificStatus, EXTENSION_PAGE, EXT_PAGE_SELECT,
+};
+
+use super::{
+    AdvancedPhySpeed, PhyWithAutonegInterrupt, PhyWithInterrupts, PhyWithPages, PhyWithRgmiiDelays,
+    PhyWithSpeed, RgmiiDelays,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+    /// The link speed changed
+    SpeedChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// A page was received during autonegotiation
+    PageReceived,
+    /// Autonegotiation completed
+    AutoNegotiationComplete,
+    /// The link status changed
+    LinkStatusChange,
+    /// A symbol error occurred
+    SymbolError,
+    /// A false carrier was detected
+    FalseCarrier,
+    /// Jabber was detected
+    Jabber,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::AutoNegotiationError => InterruptFlags::AUTONEG_ERROR,
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+            Interrupt::DuplexChange => InterruptFlags::DUPLEX_CHANGE,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::AutoNegotiationComplete => InterruptFlags::AUTONEG_COMPLETE,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::SymbolError => InterruptFlags::SYMBOL_ERROR,
+            Interrupt::FalseCarrier => InterruptFlags::FALSE_CARRIER,
+            Interrupt::Jabber => InterruptFlags::JABBER,
+        }
+    }
+}
+
+/// A RTL8211E
+#[derive(Debug)]
+pub struct RTL8211E<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> RTL8211E<MIIM> {
+    /// Create a new RTL8211E at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read a register on extension page `ext_page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_extension(&mut self, ext_page: u16, address: u8) -> u16 {
+        let mut phy = self.page_scoped(EXTENSION_PAGE);
+        phy.write(EXT_PAGE_SELECT, ext_page);
+        phy.read(address)
+    }
+
+    /// Write a register on extension page `ext_page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_extension(&mut self, ext_page: u16, address: u8, value: u16) {
+        let mut phy = self.page_scoped(EXTENSION_PAGE);
+        phy.write(EXT_PAGE_SELECT, ext_page);
+        phy.write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on extension page
+    /// `ext_page`, leaving all other bits untouched
+    pub fn modify_extension(&mut self, ext_page: u16, address: u8, mask: u16, value: u16) {
+        let mut phy = self.page_scoped(EXTENSION_PAGE);
+        phy.write(EXT_PAGE_SELECT, ext_page);
+        phy.modify(address, mask, value)
+    }
+
+    /// Read the RGMII delay configuration
+    pub fn delay_config(&mut self) -> DelayConfig {
+        DelayConfig::from_bits_truncate(
+            self.read_extension(DelayConfig::EXT_PAGE, DelayConfig::ADDRESS),
+        )
+    }
+
+    /// Override the RGMII TX and RX clock delays that are otherwise configured
+    /// by the TXDLY and RXDLY strapping pins
+    pub fn set_delays(&mut self, tx_delay: bool, rx_delay: bool) {
+        let mut config = DelayConfig::FORCE;
+        config.set(DelayConfig::TX_DELAY, tx_delay);
+        config.set(DelayConfig::RX_DELAY, rx_delay);
+        self.modify_extension(
+            DelayConfig::EXT_PAGE,
+            DelayConfig::ADDRESS,
+            DelayConfig::all().bits(),
+            config.bits(),
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, flag);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::ENABLE_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn phy_specific_status(&mut self) -> PhySpecificStatus {
+        PhySpecificStatus::from_bits_truncate(self.read(PhySpecificStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for RTL8211E<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for RTL8211E<MIIM> {
+    const PAGE_SELECT: u8 = PAGSR;
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for RTL8211E<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for RTL8211E<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithAutonegInterrupt<MIIM> for RTL8211E<MIIM> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for RTL8211E<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        let config = self.delay_config();
+        RgmiiDelays {
+            tx: config.contains(DelayConfig::TX_DELAY),
+            rx: config.contains(DelayConfig::RX_DELAY),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_delays(delays.tx, delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! RTL8211E registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::Interrupt;
+
+    /// The page that gives access to the extension pages
+    pub const EXTENSION_PAGE: u16 = 7;
+    /// Register 30, the Extension Page Select register
+    pub const EXT_PAGE_SELECT: u8 = 30;
+
+    bitflags! {
+        /// Register 18 (INER) and register 19 (INSR), the Interrupt Enable
+        /// and Interrupt Status registers
+        pub struct InterruptFlags: u16 {
+            const AUTONEG_ERROR = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const DUPLEX_CHANGE = (1 << 13);
+            const PAGE_RECEIVED = (1 << 12);
+            const AUTONEG_COMPLETE = (1 << 11);
+            const LINK_STATUS_CHANGE = (1 << 10);
+            const SYMBOL_ERROR = (1 << 9);
+            const FALSE_CARRIER = (1 << 8);
+            const JABBER = (1 << 0);
+        }
+
+        /// Register 17, the PHY Specific Status register
+        pub struct PhySpecificStatus: u16 {
+            const SPEED_MASK = (0b11 << 14);
+            const FULL_DUPLEX = (1 << 13);
+            const LINK = (1 << 10);
+        }
+
+        /// Register 28 on extension page 0xA4, the RGMII delay configuration
+        pub struct DelayConfig: u16 {
+            /// Use `TX_DELAY` and `RX_DELAY` instead of the strapping pins
+            const FORCE = (1 << 13);
+            const TX_DELAY = (1 << 12);
+            const RX_DELAY = (1 << 11);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const ENABLE_ADDRESS: u8 = 18;
+        pub const STATUS_ADDRESS: u8 = 19;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl PhySpecificStatus {
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl From<PhySpecificStatus> for Option<AdvancedPhySpeed> {
+        fn from(physr: PhySpecificStatus) -> Self {
+            let full_duplex = physr.contains(PhySpecificStatus::FULL_DUPLEX);
+            let speed = (physr & PhySpecificStatus::SPEED_MASK).bits() >> 14;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl DelayConfig {
+        pub const EXT_PAGE: u16 = 0xA4;
+        pub const ADDRESS: u8 = 28;
+    }
+}
@@ -0,0 +1,518 @@
+//! Phy implementation for the Realtek RTL8211F gigabit Ethernet PHY
+//!
+//! Most vendor specific registers of the RTL8211F are located on extended
+//! pages, which are selected through register 31.
+
+use crate::{registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    InterruptFlags, LedControl, PhyControl2, PhySpecificStatus, RxDelay, TxDelay, PAGE_SELECT,
+};
+
+use super::{
+    realtek, AdvancedPhySpeed, InterfaceMode, PhyWithAutonegInterrupt, PhyWithClockOutput,
+    PhyWithInterfaceMode, PhyWithInterrupts, PhyWithPages, PhyWithRgmiiDelays, PhyWithSpeed,
+    RgmiiDelays, UnsupportedInterfaceMode,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// Jabber was detected
+    Jabber,
+    /// The ALDPS state changed
+    AldpsStateChange,
+    /// A Wake-on-LAN event occurred
+    Pme,
+    /// The link status changed
+    LinkStatusChange,
+    /// Autonegotiation completed
+    AutoNegotiationComplete,
+    /// A page was received during autonegotiation
+    PageReceived,
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::Jabber => InterruptFlags::JABBER,
+            Interrupt::AldpsStateChange => InterruptFlags::ALDPS_STATE_CHANGE,
+            Interrupt::Pme => InterruptFlags::PME,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::AutoNegotiationComplete => InterruptFlags::AUTONEG_COMPLETE,
+            Interrupt::PageReceived => InterruptFlags::PAGE_RECEIVED,
+            Interrupt::AutoNegotiationError => InterruptFlags::AUTONEG_ERROR,
+        }
+    }
+}
+
+/// The events that an LED of the RTL8211F can indicate
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct LedFunction {
+    /// Light up if a 10 Mbps link is established
+    pub link_10: bool,
+    /// Light up if a 100 Mbps link is established
+    pub link_100: bool,
+    /// Light up if a 1000 Mbps link is established
+    pub link_1000: bool,
+    /// Blink on transmit or receive activity
+    pub active: bool,
+}
+
+/// The clock that is output on the CLKOUT pin
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockOutput {
+    /// No clock is output
+    Disabled,
+    /// A 25 MHz clock derived from the local reference
+    Clk25MHz,
+    /// A 125 MHz clock derived from the local reference
+    Clk125MHz,
+}
+
+/// A RTL8211F
+#[derive(Debug)]
+pub struct RTL8211F<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> RTL8211F<MIIM> {
+    /// Create a new RTL8211F at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        realtek::read_paged(self, page, address)
+    }
+
+    /// Write a register on `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        realtek::write_paged(self, page, address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on `page`, leaving all
+    /// other bits untouched
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        realtek::modify_paged(self, page, address, mask, value)
+    }
+
+    /// Check whether the RGMII TX clock delay is enabled
+    pub fn tx_delay(&mut self) -> bool {
+        TxDelay::from_bits_truncate(self.read_paged(TxDelay::PAGE, TxDelay::ADDRESS))
+            .contains(TxDelay::ENABLE)
+    }
+
+    /// Enable or disable the internal RGMII TX clock delay
+    pub fn set_tx_delay(&mut self, enabled: bool) {
+        let enable = TxDelay::ENABLE.bits();
+        let value = if enabled { enable } else { 0 };
+        self.modify_paged(TxDelay::PAGE, TxDelay::ADDRESS, enable, value);
+    }
+
+    /// Check whether the RGMII RX clock delay is enabled
+    pub fn rx_delay(&mut self) -> bool {
+        RxDelay::from_bits_truncate(self.read_paged(RxDelay::PAGE, RxDelay::ADDRESS))
+            .contains(RxDelay::ENABLE)
+    }
+
+    /// Enable or disable the internal RGMII RX clock delay
+    pub fn set_rx_delay(&mut self, enabled: bool) {
+        let enable = RxDelay::ENABLE.bits();
+        let value = if enabled { enable } else { 0 };
+        self.modify_paged(RxDelay::PAGE, RxDelay::ADDRESS, enable, value);
+    }
+
+    /// Get the clock that is output on the CLKOUT pin
+    pub fn clock_output(&mut self) -> ClockOutput {
+        PhyControl2::from_bits_truncate(self.read_paged(PhyControl2::PAGE, PhyControl2::ADDRESS))
+            .into()
+    }
+
+    /// Select the clock that is output on the CLKOUT pin
+    pub fn set_clock_output(&mut self, output: ClockOutput) {
+        self.modify_paged(
+            PhyControl2::PAGE,
+            PhyControl2::ADDRESS,
+            (PhyControl2::CLKOUT_FREQ_125MHZ | PhyControl2::CLKOUT_ENABLE).bits(),
+            PhyControl2::from(output).bits(),
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify_paged(
+            InterruptFlags::PAGE_ENABLE,
+            InterruptFlags::ENABLE_ADDRESS,
+            flag,
+            flag,
+        );
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify_paged(
+            InterruptFlags::PAGE_ENABLE,
+            InterruptFlags::ENABLE_ADDRESS,
+            flag,
+            0,
+        );
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(
+            self.read_paged(InterruptFlags::PAGE_STATUS, InterruptFlags::STATUS_ADDRESS),
+        )
+    }
+
+    /// Get the function of LED `led` (0, 1 or 2)
+    pub fn led_function(&mut self, led: u8) -> LedFunction {
+        let lcr =
+            LedControl::from_bits_truncate(self.read_paged(LedControl::PAGE, LedControl::ADDRESS));
+        lcr.led_function(led)
+    }
+
+    /// Set the function of LED `led` (0, 1 or 2)
+    ///
+    /// This also switches the LEDs to the custom LED configuration mode.
+    pub fn set_led_function(&mut self, led: u8, function: LedFunction) {
+        let mut lcr = LedControl::MODE;
+        lcr.set_led_function(led, function);
+        let mask = LedControl::MODE.bits() | LedControl::led_mask(led);
+        self.modify_paged(LedControl::PAGE, LedControl::ADDRESS, mask, lcr.bits());
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn phy_specific_status(&mut self) -> PhySpecificStatus {
+        PhySpecificStatus::from_bits_truncate(
+            self.read_paged(PhySpecificStatus::PAGE, PhySpecificStatus::ADDRESS),
+        )
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.phy_specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for RTL8211F<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for RTL8211F<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for RTL8211F<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithInterfaceMode<MIIM> for RTL8211F<MIIM> {
+    fn interface_mode(&mut self) -> Option<InterfaceMode> {
+        Some(InterfaceMode::Rgmii {
+            tx_delay: self.tx_delay(),
+            rx_delay: self.rx_delay(),
+        })
+    }
+
+    fn set_interface_mode(&mut self, mode: InterfaceMode) -> Result<(), UnsupportedInterfaceMode> {
+        match mode {
+            InterfaceMode::Rgmii { tx_delay, rx_delay } => {
+                self.set_tx_delay(tx_delay);
+                self.set_rx_delay(rx_delay);
+                Ok(())
+            }
+            mode => Err(UnsupportedInterfaceMode(mode)),
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for RTL8211F<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithAutonegInterrupt<MIIM> for RTL8211F<MIIM> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
+
+impl<MIIM: Miim> PhyWithClockOutput<MIIM> for RTL8211F<MIIM> {
+    type ClockOutput = ClockOutput;
+
+    fn clock_output(&mut self) -> ClockOutput {
+        self.clock_output()
+    }
+
+    fn set_clock_output(&mut self, output: ClockOutput) {
+        self.set_clock_output(output)
+    }
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for RTL8211F<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        RgmiiDelays {
+            tx: self.tx_delay(),
+            rx: self.rx_delay(),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_tx_delay(delays.tx);
+        self.set_rx_delay(delays.rx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! RTL8211F registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::{ClockOutput, Interrupt, LedFunction};
+
+    /// Register 31, the Page Select register
+    pub const PAGE_SELECT: u8 = 31;
+
+    bitflags! {
+        /// Register 18 on page 0xA42 (INER) and register 29 on page 0xA43 (INSR),
+        /// the Interrupt Enable and Interrupt Status registers
+        pub struct InterruptFlags: u16 {
+            const JABBER = (1 << 10);
+            const ALDPS_STATE_CHANGE = (1 << 9);
+            const PME = (1 << 7);
+            const LINK_STATUS_CHANGE = (1 << 4);
+            const AUTONEG_COMPLETE = (1 << 3);
+            const PAGE_RECEIVED = (1 << 2);
+            const AUTONEG_ERROR = (1 << 1);
+        }
+
+        /// Register 16 on page 0xD04, the LED Control register
+        pub struct LedControl: u16 {
+            const MODE = (1 << 15);
+            const LED2_MASK = (0x1F << 10);
+            const LED1_MASK = (0x1F << 5);
+            const LED0_MASK = (0x1F);
+        }
+
+        /// Register 17 on page 0xD08, the TX delay configuration
+        pub struct TxDelay: u16 {
+            const ENABLE = (1 << 8);
+        }
+
+        /// Register 21 on page 0xD08, the RX delay configuration
+        pub struct RxDelay: u16 {
+            const ENABLE = (1 << 3);
+        }
+
+        /// Register 25 on page 0xA43, the PHY Specific Control register 2
+        pub struct PhyControl2: u16 {
+            const CLKOUT_FREQ_125MHZ = (1 << 11);
+            const CLKOUT_ENABLE = (1 << 0);
+        }
+
+        /// Register 26 on page 0xA43, the PHY Specific Status register
+        pub struct PhySpecificStatus: u16 {
+            const SPEED_MASK = (0b11 << 4);
+            const FULL_DUPLEX = (1 << 3);
+            const LINK = (1 << 2);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const PAGE_ENABLE: u16 = 0xA42;
+        pub const ENABLE_ADDRESS: u8 = 18;
+        pub const PAGE_STATUS: u16 = 0xA43;
+        pub const STATUS_ADDRESS: u8 = 29;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl LedControl {
+        pub const PAGE: u16 = 0xD04;
+        pub const ADDRESS: u8 = 16;
+
+        const LINK_10: u16 = 1 << 0;
+        const LINK_100: u16 = 1 << 1;
+        const LINK_1000: u16 = 1 << 3;
+        const ACTIVE: u16 = 1 << 4;
+
+        fn led_shift(led: u8) -> u16 {
+            5 * led.min(2) as u16
+        }
+
+        /// The bits of LED `led` in this register
+        pub fn led_mask(led: u8) -> u16 {
+            Self::LED0_MASK.bits() << Self::led_shift(led)
+        }
+
+        /// Get the function of LED `led`
+        pub fn led_function(&self, led: u8) -> LedFunction {
+            let bits = (self.bits() & Self::led_mask(led)) >> Self::led_shift(led);
+            LedFunction {
+                link_10: bits & Self::LINK_10 != 0,
+                link_100: bits & Self::LINK_100 != 0,
+                link_1000: bits & Self::LINK_1000 != 0,
+                active: bits & Self::ACTIVE != 0,
+            }
+        }
+
+        /// Set the function of LED `led`
+        pub fn set_led_function(&mut self, led: u8, function: LedFunction) -> &mut Self {
+            let mut bits = 0;
+            if function.link_10 {
+                bits |= Self::LINK_10;
+            }
+            if function.link_100 {
+                bits |= Self::LINK_100;
+            }
+            if function.link_1000 {
+                bits |= Self::LINK_1000;
+            }
+            if function.active {
+                bits |= Self::ACTIVE;
+            }
+
+            self.bits &= !Self::led_mask(led);
+            self.bits |= bits << Self::led_shift(led);
+            self
+        }
+    }
+
+    impl TxDelay {
+        pub const PAGE: u16 = 0xD08;
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl RxDelay {
+        pub const PAGE: u16 = 0xD08;
+        pub const ADDRESS: u8 = 21;
+    }
+
+    impl PhyControl2 {
+        pub const PAGE: u16 = 0xA43;
+        pub const ADDRESS: u8 = 25;
+    }
+
+    impl From<PhyControl2> for ClockOutput {
+        fn from(phycr2: PhyControl2) -> Self {
+            if !phycr2.contains(PhyControl2::CLKOUT_ENABLE) {
+                ClockOutput::Disabled
+            } else if phycr2.contains(PhyControl2::CLKOUT_FREQ_125MHZ) {
+                ClockOutput::Clk125MHz
+            } else {
+                ClockOutput::Clk25MHz
+            }
+        }
+    }
+
+    impl From<ClockOutput> for PhyControl2 {
+        fn from(output: ClockOutput) -> Self {
+            match output {
+                ClockOutput::Disabled => PhyControl2::empty(),
+                ClockOutput::Clk25MHz => PhyControl2::CLKOUT_ENABLE,
+                ClockOutput::Clk125MHz => {
+                    PhyControl2::CLKOUT_ENABLE | PhyControl2::CLKOUT_FREQ_125MHZ
+                }
+            }
+        }
+    }
+
+    impl PhySpecificStatus {
+        pub const PAGE: u16 = 0xA43;
+        pub const ADDRESS: u8 = 26;
+    }
+
+    impl From<PhySpecificStatus> for Option<AdvancedPhySpeed> {
+        fn from(physr: PhySpecificStatus) -> Self {
+            let full_duplex = physr.contains(PhySpecificStatus::FULL_DUPLEX);
+            let speed = (physr & PhySpecificStatus::SPEED_MASK).bits() >> 4;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
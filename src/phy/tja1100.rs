@@ -0,0 +1,417 @@
+//! Phy implementation for the NXP TJA1100 100BASE-T1 automotive Ethernet PHY
+//!
+//! The TJA1100 does not support autonegotiation: it always operates at
+//! 100 Mbit/s full duplex, and must be configured as either the master or
+//! the slave side of the link.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use super::{LinkCounters, PhyWithCounters, PhyWithInterrupts, PhyWithSqi, Sqi};
+
+use self::registers::{
+    CommunicationStatus, Configuration1, ExtendedControl, GeneralStatus, InterruptFlags,
+    SYMBOL_ERROR_COUNTER,
+};
+
+/// A power mode of the TJA1100
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerMode {
+    /// The transmitter and receiver are switched off
+    Standby,
+    /// Normal operation
+    Normal,
+    /// Request the link partner to go to sleep, after which the PHY
+    /// enters sleep mode
+    SleepRequest,
+}
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The PHY was powered on
+    PowerOn,
+    /// A local wake-up event was detected
+    Wakeup,
+    /// A wake-up request was received from the link partner
+    WakeupRequestReceived,
+    /// A low power (sleep) request was received from the link partner
+    LowPowerRequestReceived,
+    /// PHY initialization failed
+    PhyInitFailure,
+    /// The link went down
+    LinkFail,
+    /// The link came up
+    LinkUp,
+    /// A symbol error occurred
+    SymbolError,
+    /// Link training failed
+    TrainingFailed,
+    /// The signal quality dropped below the warning limit
+    SqiWarning,
+    /// An SMI control error occurred
+    ControlError,
+    /// An undervoltage was detected
+    Undervoltage,
+    /// The supply voltage recovered from an undervoltage condition
+    UndervoltageRecovery,
+    /// The temperature exceeded the warning limit
+    Temperature,
+    /// A transition to sleep mode was aborted
+    SleepAbort,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::PowerOn => InterruptFlags::POWER_ON,
+            Interrupt::Wakeup => InterruptFlags::WAKEUP,
+            Interrupt::WakeupRequestReceived => InterruptFlags::WAKEUP_REQUEST_RECEIVED,
+            Interrupt::LowPowerRequestReceived => InterruptFlags::LOW_POWER_REQUEST_RECEIVED,
+            Interrupt::PhyInitFailure => InterruptFlags::PHY_INIT_FAILURE,
+            Interrupt::LinkFail => InterruptFlags::LINK_FAIL,
+            Interrupt::LinkUp => InterruptFlags::LINK_UP,
+            Interrupt::SymbolError => InterruptFlags::SYMBOL_ERROR,
+            Interrupt::TrainingFailed => InterruptFlags::TRAINING_FAILED,
+            Interrupt::SqiWarning => InterruptFlags::SQI_WARNING,
+            Interrupt::ControlError => InterruptFlags::CONTROL_ERROR,
+            Interrupt::Undervoltage => InterruptFlags::UNDERVOLTAGE,
+            Interrupt::UndervoltageRecovery => InterruptFlags::UNDERVOLTAGE_RECOVERY,
+            Interrupt::Temperature => InterruptFlags::TEMPERATURE,
+            Interrupt::SleepAbort => InterruptFlags::SLEEP_ABORT,
+        }
+    }
+}
+
+/// A TJA1100
+#[derive(Debug)]
+pub struct TJA1100<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> TJA1100<MIIM> {
+    /// Create a new TJA1100 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This unlocks the configuration registers, switches the PHY to
+    /// [`PowerMode::Normal`], and enables the link.
+    pub fn phy_init(&mut self) {
+        init(self)
+    }
+
+    /// Get the power mode that was last requested
+    ///
+    /// Returns `None` if no power mode change was requested.
+    pub fn power_mode(&mut self) -> Option<PowerMode> {
+        power_mode(self)
+    }
+
+    /// Request a change to `power_mode`
+    pub fn set_power_mode(&mut self, power_mode: PowerMode) {
+        set_power_mode(self, power_mode)
+    }
+
+    /// Enable or disable the link
+    ///
+    /// While disabled, the PHY does not attempt to establish a link.
+    pub fn set_link_control(&mut self, enabled: bool) {
+        set_link_control(self, enabled)
+    }
+
+    /// Check whether the PHY is configured as the master side of the link
+    pub fn is_master(&mut self) -> bool {
+        Configuration1::from_bits_truncate(self.read(Configuration1::ADDRESS))
+            .contains(Configuration1::MASTER)
+    }
+
+    /// Configure the PHY as the master or the slave side of the link
+    ///
+    /// The configuration registers must be unlocked first, which is done by
+    /// [`Self::phy_init`].
+    pub fn set_master(&mut self, master: bool) {
+        let bit = Configuration1::MASTER.bits();
+        self.modify(Configuration1::ADDRESS, bit, if master { bit } else { 0 });
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        set_interrupt_enabled(self, interrupt, true)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        set_interrupt_enabled(self, interrupt, false)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::SOURCE_ADDRESS))
+    }
+
+    /// Read the Communication Status register
+    pub fn communication_status(&mut self) -> CommunicationStatus {
+        CommunicationStatus::from_bits_truncate(self.read(CommunicationStatus::ADDRESS))
+    }
+
+    /// Get the signal quality index, ranging from 0 (worst) to 7 (best)
+    ///
+    /// Returns `None` if no link is established.
+    pub fn sqi(&mut self) -> Option<u8> {
+        self.communication_status().sqi()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.communication_status()
+            .contains(CommunicationStatus::LINK_UP)
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for TJA1100<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        // 100BASE-T1 does not support autonegotiation
+        AutoNegotiationAdvertisement::default()
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+// The helpers below are shared with the other TJA11xx PHYs.
+
+/// Unlock the configuration registers, switch to [`PowerMode::Normal`], and
+/// enable the link.
+pub(crate) fn init<M: Miim, P: Phy<M>>(phy: &mut P) {
+    let config_enable = ExtendedControl::CONFIG_ENABLE.bits();
+    phy.modify(ExtendedControl::ADDRESS, config_enable, config_enable);
+
+    set_power_mode(phy, PowerMode::Normal);
+    set_link_control(phy, true);
+}
+
+pub(crate) fn power_mode<M: Miim, P: Phy<M>>(phy: &mut P) -> Option<PowerMode> {
+    ExtendedControl::from_bits_truncate(phy.read(ExtendedControl::ADDRESS)).into()
+}
+
+pub(crate) fn set_power_mode<M: Miim, P: Phy<M>>(phy: &mut P, power_mode: PowerMode) {
+    phy.modify(
+        ExtendedControl::ADDRESS,
+        ExtendedControl::POWER_MODE_MASK.bits(),
+        ExtendedControl::from(power_mode).bits(),
+    );
+}
+
+pub(crate) fn set_link_control<M: Miim, P: Phy<M>>(phy: &mut P, enabled: bool) {
+    let bit = ExtendedControl::LINK_CONTROL.bits();
+    phy.modify(ExtendedControl::ADDRESS, bit, if enabled { bit } else { 0 });
+}
+
+pub(crate) fn set_interrupt_enabled<M: Miim, P: Phy<M>>(
+    phy: &mut P,
+    interrupt: Interrupt,
+    enabled: bool,
+) {
+    let flag = interrupt.flag().bits();
+    phy.modify(
+        InterruptFlags::ENABLE_ADDRESS,
+        flag,
+        if enabled { flag } else { 0 },
+    );
+}
+
+/// Read and clear the symbol error and link failure counters
+pub(crate) fn read_and_clear_counters<M: Miim, P: Phy<M>>(phy: &mut P) -> LinkCounters {
+    // Both counters are cleared by reading them
+    let symbol_errors = phy.read(SYMBOL_ERROR_COUNTER);
+    let status = GeneralStatus::from_bits_truncate(phy.read(GeneralStatus::ADDRESS));
+
+    LinkCounters {
+        symbol_errors: Some(symbol_errors),
+        disconnects: Some(status.link_fail_count() as u16),
+        ..Default::default()
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for TJA1100<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for TJA1100<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        read_and_clear_counters(self)
+    }
+}
+
+impl<MIIM: Miim> PhyWithSqi<MIIM> for TJA1100<MIIM> {
+    fn sqi(&mut self) -> Option<Sqi> {
+        self.sqi().map(|sqi| Sqi {
+            level: sqi,
+            raw: sqi as u16,
+        })
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! TJA1100 registers
+
+    use bitflags::bitflags;
+
+    use super::{Interrupt, PowerMode};
+
+    /// Register 20, the Symbol Error Counter register
+    pub const SYMBOL_ERROR_COUNTER: u8 = 20;
+
+    bitflags! {
+        /// Register 17, the Extended Control register
+        pub struct ExtendedControl: u16 {
+            const LINK_CONTROL = (1 << 15);
+            const POWER_MODE_MASK = (0b1111 << 11);
+            const POWER_MODE_NORMAL = (0b0011 << 11);
+            const POWER_MODE_SLEEP_REQUEST = (0b1011 << 11);
+            const POWER_MODE_STANDBY = (0b1100 << 11);
+            const CABLE_TEST = (1 << 5);
+            /// Unlock the configuration registers for writing
+            const CONFIG_ENABLE = (1 << 2);
+            const WAKE_REQUEST = (1 << 0);
+        }
+
+        /// Register 18, the Configuration 1 register
+        pub struct Configuration1: u16 {
+            const MASTER = (1 << 15);
+            const AUTO_OPERATION = (1 << 14);
+            const SLEEP_CONFIRM = (1 << 6);
+            const LED_MODE_MASK = (0b11 << 4);
+            const LED_ENABLE = (1 << 3);
+        }
+
+        /// Register 21 and register 22, the Interrupt Source and Interrupt
+        /// Enable registers
+        pub struct InterruptFlags: u16 {
+            const POWER_ON = (1 << 15);
+            const WAKEUP = (1 << 14);
+            const WAKEUP_REQUEST_RECEIVED = (1 << 13);
+            const LOW_POWER_REQUEST_RECEIVED = (1 << 12);
+            const PHY_INIT_FAILURE = (1 << 11);
+            const LINK_FAIL = (1 << 10);
+            const LINK_UP = (1 << 9);
+            const SYMBOL_ERROR = (1 << 8);
+            const TRAINING_FAILED = (1 << 7);
+            const SQI_WARNING = (1 << 6);
+            const CONTROL_ERROR = (1 << 5);
+            const UNDERVOLTAGE = (1 << 3);
+            const UNDERVOLTAGE_RECOVERY = (1 << 2);
+            const TEMPERATURE = (1 << 1);
+            const SLEEP_ABORT = (1 << 0);
+        }
+
+        /// Register 23, the Communication Status register
+        pub struct CommunicationStatus: u16 {
+            const LINK_UP = (1 << 15);
+            const TX_MODE_MASK = (0b11 << 13);
+            const LOCAL_RECEIVER_OK = (1 << 12);
+            const REMOTE_RECEIVER_OK = (1 << 11);
+            const SCRAMBLER_LOCKED = (1 << 10);
+            const SSD_ERROR = (1 << 9);
+            const ESD_ERROR = (1 << 8);
+            const SQI_MASK = (0b111 << 5);
+        }
+
+        /// Register 24, the General Status register
+        pub struct GeneralStatus: u16 {
+            const INTERRUPT_STATUS = (1 << 15);
+            const PLL_LOCKED = (1 << 14);
+            const LINK_FAIL_COUNT_MASK = (0b11111 << 3);
+        }
+    }
+
+    impl ExtendedControl {
+        pub const ADDRESS: u8 = 17;
+    }
+
+    impl From<ExtendedControl> for Option<PowerMode> {
+        fn from(ectrl: ExtendedControl) -> Self {
+            let mode = ectrl & ExtendedControl::POWER_MODE_MASK;
+
+            if mode == ExtendedControl::POWER_MODE_NORMAL {
+                Some(PowerMode::Normal)
+            } else if mode == ExtendedControl::POWER_MODE_SLEEP_REQUEST {
+                Some(PowerMode::SleepRequest)
+            } else if mode == ExtendedControl::POWER_MODE_STANDBY {
+                Some(PowerMode::Standby)
+            } else {
+                None
+            }
+        }
+    }
+
+    impl From<PowerMode> for ExtendedControl {
+        fn from(power_mode: PowerMode) -> Self {
+            match power_mode {
+                PowerMode::Standby => ExtendedControl::POWER_MODE_STANDBY,
+                PowerMode::Normal => ExtendedControl::POWER_MODE_NORMAL,
+                PowerMode::SleepRequest => ExtendedControl::POWER_MODE_SLEEP_REQUEST,
+            }
+        }
+    }
+
+    impl Configuration1 {
+        pub const ADDRESS: u8 = 18;
+    }
+
+    impl InterruptFlags {
+        pub const SOURCE_ADDRESS: u8 = 21;
+        pub const ENABLE_ADDRESS: u8 = 22;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl CommunicationStatus {
+        pub const ADDRESS: u8 = 23;
+
+        /// The signal quality index, or `None` if no link is established
+        pub fn sqi(&self) -> Option<u8> {
+            if self.contains(Self::LINK_UP) {
+                Some(((*self & Self::SQI_MASK).bits() >> 5) as u8)
+            } else {
+                None
+            }
+        }
+    }
+
+    impl GeneralStatus {
+        pub const ADDRESS: u8 = 24;
+
+        /// The amount of link failures since the register was last read
+        pub fn link_fail_count(&self) -> u8 {
+            ((*self & Self::LINK_FAIL_COUNT_MASK).bits() >> 3) as u8
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Phy implementation for the NXP TJA1101 100BASE-T1 automotive Ethernet PHY
+//!
+//! The TJA1101 shares its power mode, interrupt and communication status
+//! registers with the [`TJA1100`](super::tja1100::TJA1100), but adds a
+//! configurable WAKE_IN_OUT pin and more detailed error status reporting.
+
+use crate::{
+    phy::tja1100::{
+        self,
+        registers::{CommunicationStatus, InterruptFlags},
+        Interrupt, PowerMode,
+    },
+    phy::{LinkCounters, PhyWithCounters, PhyWithInterrupts, PhyWithSqi, Sqi},
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{CommonConfiguration, Configuration1, ExternalStatus};
+
+/// The wake-up configuration of a TJA1101
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct WakeConfig {
+    /// Wake up the PHY when a local wake-up event is detected on the
+    /// WAKE_IN_OUT pin
+    pub local_wake: bool,
+    /// Wake up the PHY when a wake-up request is received from the link
+    /// partner
+    pub remote_wake: bool,
+    /// Forward wake-up requests received from the link partner to the
+    /// WAKE_IN_OUT pin
+    pub forward_to_pin: bool,
+    /// Use an absolute input threshold for the WAKE_IN_OUT pin, instead of
+    /// one that is ratiometric to the battery supply
+    pub absolute_threshold: bool,
+}
+
+/// A TJA1101
+#[derive(Debug)]
+pub struct TJA1101<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> TJA1101<MIIM> {
+    /// Create a new TJA1101 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This unlocks the configuration registers, switches the PHY to
+    /// [`PowerMode::Normal`], and enables the link.
+    pub fn phy_init(&mut self) {
+        tja1100::init(self)
+    }
+
+    /// Get the power mode that was last requested
+    ///
+    /// Returns `None` if no power mode change was requested.
+    pub fn power_mode(&mut self) -> Option<PowerMode> {
+        tja1100::power_mode(self)
+    }
+
+    /// Request a change to `power_mode`
+    pub fn set_power_mode(&mut self, power_mode: PowerMode) {
+        tja1100::set_power_mode(self, power_mode)
+    }
+
+    /// Enable or disable the link
+    ///
+    /// While disabled, the PHY does not attempt to establish a link.
+    pub fn set_link_control(&mut self, enabled: bool) {
+        tja1100::set_link_control(self, enabled)
+    }
+
+    /// Check whether the PHY is configured as the master side of the link
+    pub fn is_master(&mut self) -> bool {
+        Configuration1::from_bits_truncate(self.read(Configuration1::ADDRESS))
+            .contains(Configuration1::MASTER)
+    }
+
+    /// Configure the PHY as the master or the slave side of the link
+    ///
+    /// The configuration registers must be unlocked first, which is done by
+    /// [`Self::phy_init`].
+    pub fn set_master(&mut self, master: bool) {
+        let bit = Configuration1::MASTER.bits();
+        self.modify(Configuration1::ADDRESS, bit, if master { bit } else { 0 });
+    }
+
+    /// Get the wake-up configuration
+    pub fn wake_config(&mut self) -> WakeConfig {
+        let cfg1 = Configuration1::from_bits_truncate(self.read(Configuration1::ADDRESS));
+        let common =
+            CommonConfiguration::from_bits_truncate(self.read(CommonConfiguration::ADDRESS));
+
+        WakeConfig {
+            local_wake: cfg1.contains(Configuration1::LOCAL_WAKE),
+            remote_wake: cfg1.contains(Configuration1::REMOTE_WAKE),
+            forward_to_pin: cfg1.contains(Configuration1::FORWARD_TO_PIN),
+            absolute_threshold: common.contains(CommonConfiguration::WAKE_ABSOLUTE),
+        }
+    }
+
+    /// Set the wake-up configuration
+    ///
+    /// The configuration registers must be unlocked first, which is done by
+    /// [`Self::phy_init`].
+    pub fn set_wake_config(&mut self, config: WakeConfig) {
+        let mut cfg1 = Configuration1::empty();
+        cfg1.set(Configuration1::LOCAL_WAKE, config.local_wake);
+        cfg1.set(Configuration1::REMOTE_WAKE, config.remote_wake);
+        cfg1.set(Configuration1::FORWARD_TO_PIN, config.forward_to_pin);
+        self.modify(
+            Configuration1::ADDRESS,
+            (Configuration1::LOCAL_WAKE
+                | Configuration1::REMOTE_WAKE
+                | Configuration1::FORWARD_TO_PIN)
+                .bits(),
+            cfg1.bits(),
+        );
+
+        let mut common = CommonConfiguration::empty();
+        common.set(
+            CommonConfiguration::WAKE_ABSOLUTE,
+            config.absolute_threshold,
+        );
+        self.modify(
+            CommonConfiguration::ADDRESS,
+            CommonConfiguration::WAKE_ABSOLUTE.bits(),
+            common.bits(),
+        );
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        tja1100::set_interrupt_enabled(self, interrupt, true)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        tja1100::set_interrupt_enabled(self, interrupt, false)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::SOURCE_ADDRESS))
+    }
+
+    /// Read the External Status register, which contains the undervoltage,
+    /// temperature and cable error status
+    pub fn external_status(&mut self) -> ExternalStatus {
+        ExternalStatus::from_bits_truncate(self.read(ExternalStatus::ADDRESS))
+    }
+
+    /// Read the Communication Status register
+    pub fn communication_status(&mut self) -> CommunicationStatus {
+        CommunicationStatus::from_bits_truncate(self.read(CommunicationStatus::ADDRESS))
+    }
+
+    /// Get the signal quality index, ranging from 0 (worst) to 7 (best)
+    ///
+    /// Returns `None` if no link is established.
+    pub fn sqi(&mut self) -> Option<u8> {
+        self.communication_status().sqi()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.communication_status()
+            .contains(CommunicationStatus::LINK_UP)
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for TJA1101<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        // 100BASE-T1 does not support autonegotiation
+        AutoNegotiationAdvertisement::default()
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for TJA1101<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithCounters<MIIM> for TJA1101<MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        tja1100::read_and_clear_counters(self)
+    }
+}
+
+impl<MIIM: Miim> PhyWithSqi<MIIM> for TJA1101<MIIM> {
+    fn sqi(&mut self) -> Option<Sqi> {
+        self.sqi().map(|sqi| Sqi {
+            level: sqi,
+            raw: sqi as u16,
+        })
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! TJA1101 specific registers
+    //!
+    //! All other registers are shared with the TJA1100, see
+    //! [`crate::phy::tja1100::registers`].
+
+    use bitflags::bitflags;
+
+    bitflags! {
+        /// Register 18, the Configuration 1 register
+        pub struct Configuration1: u16 {
+            const MASTER = (1 << 15);
+            /// Forward remote wake-up requests to the WAKE_IN_OUT pin
+            const FORWARD_TO_PIN = (1 << 14);
+            const REMOTE_WAKE = (1 << 11);
+            const LOCAL_WAKE = (1 << 10);
+            const MII_MODE_MASK = (0b11 << 8);
+            const MII_DRIVER = (1 << 7);
+            const SLEEP_CONFIRM = (1 << 6);
+        }
+
+        /// Register 25, the External Status register
+        pub struct ExternalStatus: u16 {
+            const UNDERVOLTAGE_VDDD_3V3 = (1 << 14);
+            const UNDERVOLTAGE_VDDA_3V3 = (1 << 13);
+            const UNDERVOLTAGE_VDDD_1V8 = (1 << 12);
+            const UNDERVOLTAGE_VDDIO = (1 << 10);
+            const TEMPERATURE_HIGH = (1 << 9);
+            const TEMPERATURE_WARNING = (1 << 8);
+            const SHORT_DETECT = (1 << 7);
+            const OPEN_DETECT = (1 << 6);
+            const POLARITY_DETECT = (1 << 5);
+            const INTERLEAVE_DETECT = (1 << 4);
+        }
+
+        /// Register 27, the Common Configuration register
+        pub struct CommonConfiguration: u16 {
+            const AUTO_OPERATION = (1 << 15);
+            const CLOCK_MODE_MASK = (0b11 << 12);
+            const LOCAL_WAKE_TIME_MASK = (0b11 << 7);
+            /// Use an absolute input threshold for the WAKE_IN_OUT pin
+            const WAKE_ABSOLUTE = (1 << 6);
+            /// Switch off the INH pin in sleep mode
+            const INH_SLEEP = (1 << 5);
+        }
+    }
+
+    impl Configuration1 {
+        pub const ADDRESS: u8 = 18;
+    }
+
+    impl ExternalStatus {
+        pub const ADDRESS: u8 = 25;
+    }
+
+    impl CommonConfiguration {
+        pub const ADDRESS: u8 = 27;
+    }
+}
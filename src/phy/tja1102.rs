@@ -0,0 +1,247 @@
+//! Support for the NXP TJA1102 dual 100BASE-T1 automotive Ethernet PHY
+//!
+//! The TJA1102 contains two PHYs at consecutive PHY addresses. Each of them
+//! has the register layout of a [`TJA1101`](super::tja1101::TJA1101), but
+//! only the first PHY has a PHY identifier. The Common Configuration register,
+//! which is shared between both PHYs, is also only accessible through the
+//! first PHY.
+//!
+//! [`TJA1102`] owns the [`Miim`] and gives access to the shared
+//! configuration, and hands out a [`TJA1102Port`] for each of the PHYs.
+//! Every [`TJA1102Port`] implements [`Phy`], so each port can be managed like
+//! any other PHY.
+
+use crate::{
+    phy::{
+        tja1100::{
+            self,
+            registers::{CommunicationStatus, InterruptFlags},
+            Interrupt, PowerMode,
+        },
+        tja1101::registers::{CommonConfiguration, Configuration1, ExternalStatus},
+        LinkCounters, PhyWithCounters, PhyWithInterrupts, PhyWithSqi, Sqi,
+    },
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+/// One of the PHYs of the TJA1102
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Port {
+    /// The first PHY, at the base address
+    Port0,
+    /// The second PHY, at the base address + 1
+    Port1,
+}
+
+impl Port {
+    fn offset(&self) -> u8 {
+        match self {
+            Port::Port0 => 0,
+            Port::Port1 => 1,
+        }
+    }
+}
+
+/// A TJA1102
+#[derive(Debug)]
+pub struct TJA1102<MIIM: Miim> {
+    base_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> TJA1102<MIIM> {
+    /// Create a new TJA1102, backed by the given `miim`.
+    ///
+    /// `base_addr` is the PHY address of [`Port::Port0`].
+    ///
+    /// # Panics
+    /// This function panics if the PHY address of [`Port::Port1`] is not less
+    /// than 32.
+    pub fn new(miim: MIIM, base_addr: PhyAddress) -> Self {
+        assert!(
+            PhyAddress::new(base_addr.get() + Port::Port1.offset()).is_some(),
+            "the PHY address of port 1 must be less than 32"
+        );
+
+        Self { base_addr, miim }
+    }
+
+    /// Access one of the PHYs of the TJA1102
+    pub fn port(&mut self, port: Port) -> TJA1102Port<'_, MIIM> {
+        TJA1102Port {
+            phy_addr: PhyAddress::from_const(self.base_addr.get() + port.offset()),
+            port,
+            miim: &mut self.miim,
+        }
+    }
+
+    /// Initialize both PHYs
+    ///
+    /// This unlocks the configuration registers of both PHYs, switches them
+    /// to [`PowerMode::Normal`], and enables their links.
+    pub fn phy_init(&mut self) {
+        for port in [Port::Port0, Port::Port1] {
+            tja1100::init(&mut self.port(port));
+        }
+    }
+
+    /// Read the Common Configuration register, which is shared between
+    /// both PHYs
+    pub fn common_configuration(&mut self) -> CommonConfiguration {
+        CommonConfiguration::from_bits_truncate(
+            self.miim
+                .read(self.base_addr.get(), CommonConfiguration::ADDRESS),
+        )
+    }
+
+    /// Write the Common Configuration register, which is shared between
+    /// both PHYs
+    ///
+    /// The configuration registers of [`Port::Port0`] must be unlocked first,
+    /// which is done by [`Self::phy_init`].
+    pub fn set_common_configuration(&mut self, config: CommonConfiguration) {
+        self.miim.write(
+            self.base_addr.get(),
+            CommonConfiguration::ADDRESS,
+            config.bits(),
+        );
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+/// A single PHY of a [`TJA1102`]
+#[derive(Debug)]
+pub struct TJA1102Port<'a, MIIM: Miim> {
+    phy_addr: PhyAddress,
+    port: Port,
+    miim: &'a mut MIIM,
+}
+
+impl<'a, MIIM: Miim> TJA1102Port<'a, MIIM> {
+    /// The port this PHY belongs to
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    /// Get the power mode that was last requested
+    ///
+    /// Returns `None` if no power mode change was requested.
+    pub fn power_mode(&mut self) -> Option<PowerMode> {
+        tja1100::power_mode(self)
+    }
+
+    /// Request a change to `power_mode`
+    pub fn set_power_mode(&mut self, power_mode: PowerMode) {
+        tja1100::set_power_mode(self, power_mode)
+    }
+
+    /// Enable or disable the link
+    ///
+    /// While disabled, the PHY does not attempt to establish a link.
+    pub fn set_link_control(&mut self, enabled: bool) {
+        tja1100::set_link_control(self, enabled)
+    }
+
+    /// Check whether the PHY is configured as the master side of the link
+    pub fn is_master(&mut self) -> bool {
+        Configuration1::from_bits_truncate(self.read(Configuration1::ADDRESS))
+            .contains(Configuration1::MASTER)
+    }
+
+    /// Configure the PHY as the master or the slave side of the link
+    ///
+    /// The configuration registers must be unlocked first, which is done by
+    /// [`TJA1102::phy_init`].
+    pub fn set_master(&mut self, master: bool) {
+        let bit = Configuration1::MASTER.bits();
+        self.modify(Configuration1::ADDRESS, bit, if master { bit } else { 0 });
+    }
+
+    /// Enable an interrupt
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        tja1100::set_interrupt_enabled(self, interrupt, true)
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        tja1100::set_interrupt_enabled(self, interrupt, false)
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Both PHYs share a single INT_N pin, so the interrupts of both ports
+    /// need to be checked when it is asserted.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::SOURCE_ADDRESS))
+    }
+
+    /// Read the External Status register, which contains the undervoltage,
+    /// temperature and cable error status
+    pub fn external_status(&mut self) -> ExternalStatus {
+        ExternalStatus::from_bits_truncate(self.read(ExternalStatus::ADDRESS))
+    }
+
+    /// Read the Communication Status register
+    pub fn communication_status(&mut self) -> CommunicationStatus {
+        CommunicationStatus::from_bits_truncate(self.read(CommunicationStatus::ADDRESS))
+    }
+
+    /// Get the signal quality index, ranging from 0 (worst) to 7 (best)
+    ///
+    /// Returns `None` if no link is established.
+    pub fn sqi(&mut self) -> Option<u8> {
+        self.communication_status().sqi()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.communication_status()
+            .contains(CommunicationStatus::LINK_UP)
+    }
+}
+
+impl<'a, MIIM: Miim> Phy<MIIM> for TJA1102Port<'a, MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        // 100BASE-T1 does not support autonegotiation
+        AutoNegotiationAdvertisement::default()
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<'a, MIIM: Miim> PhyWithInterrupts<MIIM> for TJA1102Port<'a, MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<'a, MIIM: Miim> PhyWithCounters<MIIM> for TJA1102Port<'a, MIIM> {
+    fn read_and_clear_counters(&mut self) -> LinkCounters {
+        tja1100::read_and_clear_counters(self)
+    }
+}
+
+impl<'a, MIIM: Miim> PhyWithSqi<MIIM> for TJA1102Port<'a, MIIM> {
+    fn sqi(&mut self) -> Option<Sqi> {
+        self.sqi().map(|sqi| Sqi {
+            level: sqi,
+            raw: sqi as u16,
+        })
+    }
+}
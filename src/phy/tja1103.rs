@@ -0,0 +1,267 @@
+//! Phy implementation for the NXP TJA1103 100BASE-T1 automotive Ethernet PHY
+//!
+//! Unlike the [`TJA1100`](super::tja1100::TJA1100), the TJA1103 uses a
+//! Clause 45 register layout: all of its vendor specific registers are
+//! located in MMD 30, and are accessed through the Clause 22 MMD access
+//! registers.
+//!
+//! After a reset, the TJA1103 requires a startup sequence before it starts
+//! operating. This is split into [`TJA1103::enable_configuration`] and
+//! [`TJA1103::phy_init`], as the datasheet requires a delay of at least
+//! 400 µs between the two.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use super::{PhyWithInterrupts, PhyWithSqi, Sqi};
+
+use self::registers::{
+    DeviceControl, InterruptFlags, PhyConfig, PhyControl, PmaControl, PmaStatus, PortControl,
+    PortInfraControl, SignalQuality, PMA_MMD, VEND1_MMD,
+};
+
+/// A TJA1103
+#[derive(Debug)]
+pub struct TJA1103<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> TJA1103<MIIM> {
+    /// Create a new TJA1103 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Unlock the global configuration registers.
+    ///
+    /// This is the first step of the startup sequence. Wait at least 400 µs
+    /// before calling [`Self::phy_init`].
+    pub fn enable_configuration(&mut self) {
+        self.mmd_write(
+            VEND1_MMD,
+            DeviceControl::ADDRESS,
+            (DeviceControl::CONFIG_GLOBAL_ENABLE | DeviceControl::CONFIG_ALL_ENABLE).bits(),
+        );
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This completes the startup sequence that was started by
+    /// [`Self::enable_configuration`]: it enables the port and PHY
+    /// configuration, enables autonomous operation, and starts the PHY.
+    pub fn phy_init(&mut self) {
+        self.mmd_write(VEND1_MMD, PortControl::ADDRESS, PortControl::ENABLE.bits());
+        self.mmd_write(
+            VEND1_MMD,
+            PhyControl::ADDRESS,
+            PhyControl::CONFIG_ENABLE.bits(),
+        );
+        self.mmd_write(
+            VEND1_MMD,
+            PortInfraControl::ADDRESS,
+            PortInfraControl::ENABLE.bits(),
+        );
+
+        let auto = PhyConfig::AUTO.bits();
+        self.mmd_modify(VEND1_MMD, PhyConfig::ADDRESS, auto, auto);
+
+        let start = PhyControl::START_OPERATION.bits();
+        self.mmd_modify(VEND1_MMD, PhyControl::ADDRESS, start, start);
+    }
+
+    /// Check whether the PHY is configured as the master side of the link
+    pub fn is_master(&mut self) -> bool {
+        PmaControl::from_bits_truncate(self.mmd_read(PMA_MMD, PmaControl::ADDRESS))
+            .contains(PmaControl::MASTER)
+    }
+
+    /// Configure the PHY as the master or the slave side of the link
+    pub fn set_master(&mut self, master: bool) {
+        let bit = PmaControl::MASTER.bits();
+        self.mmd_modify(
+            PMA_MMD,
+            PmaControl::ADDRESS,
+            bit,
+            if master { bit } else { 0 },
+        );
+    }
+
+    /// Get the signal quality index, ranging from 0 (worst) to 7 (best)
+    ///
+    /// Returns `None` if the SQI is not valid, e.g. because no link is
+    /// established.
+    pub fn sqi(&mut self) -> Option<u8> {
+        let sq =
+            SignalQuality::from_bits_truncate(self.mmd_read(VEND1_MMD, SignalQuality::ADDRESS));
+
+        if sq.contains(SignalQuality::VALID) {
+            Some((sq & SignalQuality::SQI_MASK).bits() as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Enable or disable the link event interrupt
+    pub fn set_link_event_interrupt(&mut self, enabled: bool) {
+        let bit = InterruptFlags::LINK_EVENT.bits();
+        self.mmd_modify(
+            VEND1_MMD,
+            InterruptFlags::ENABLE_ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Read and clear all interrupts.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        let status = self.mmd_read(VEND1_MMD, InterruptFlags::STATUS_ADDRESS);
+        self.mmd_write(VEND1_MMD, InterruptFlags::ACK_ADDRESS, status);
+        InterruptFlags::from_bits_truncate(status)
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        PmaStatus::from_bits_truncate(self.mmd_read(PMA_MMD, PmaStatus::ADDRESS))
+            .contains(PmaStatus::LINK)
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for TJA1103<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        // 100BASE-T1 does not support autonegotiation
+        AutoNegotiationAdvertisement::default()
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for TJA1103<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithSqi<MIIM> for TJA1103<MIIM> {
+    fn sqi(&mut self) -> Option<Sqi> {
+        self.sqi().map(|sqi| Sqi {
+            level: sqi,
+            raw: sqi as u16,
+        })
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! TJA1103 registers
+
+    use bitflags::bitflags;
+
+    /// The PMA/PMD MMD
+    pub const PMA_MMD: u8 = 1;
+    /// The vendor specific MMD containing the TJA1103 registers
+    pub const VEND1_MMD: u8 = 30;
+
+    bitflags! {
+        /// Register 0x0001 in MMD 1, the PMA/PMD Status 1 register
+        pub struct PmaStatus: u16 {
+            const LINK = (1 << 2);
+        }
+
+        /// Register 0x0834 in MMD 1, the BASE-T1 PMA/PMD Control register
+        pub struct PmaControl: u16 {
+            const MASTER = (1 << 14);
+        }
+
+        /// Register 0x0040 in MMD 30, the Device Control register
+        pub struct DeviceControl: u16 {
+            const RESET = (1 << 15);
+            const CONFIG_GLOBAL_ENABLE = (1 << 14);
+            const CONFIG_ALL_ENABLE = (1 << 13);
+        }
+
+        /// Register 0x8040 in MMD 30, the Port Control register
+        pub struct PortControl: u16 {
+            const ENABLE = (1 << 14);
+        }
+
+        /// Register 0x80A0 (acknowledge), register 0x80A1 (enable) and
+        /// register 0x80A2 (status) in MMD 30, the PHY interrupt registers
+        pub struct InterruptFlags: u16 {
+            const LINK_EVENT = (1 << 1);
+        }
+
+        /// Register 0x8100 in MMD 30, the PHY Control register
+        pub struct PhyControl: u16 {
+            const CONFIG_ENABLE = (1 << 14);
+            const START_OPERATION = (1 << 0);
+        }
+
+        /// Register 0x8108 in MMD 30, the PHY Configuration register
+        pub struct PhyConfig: u16 {
+            /// Autonomous operation: establish a link without host intervention
+            const AUTO = (1 << 0);
+        }
+
+        /// Register 0x8320 in MMD 30, the Signal Quality register
+        pub struct SignalQuality: u16 {
+            const VALID = (1 << 14);
+            const SQI_MASK = (0b111);
+        }
+
+        /// Register 0xAC00 in MMD 30, the Port Infrastructure Control register
+        pub struct PortInfraControl: u16 {
+            const ENABLE = (1 << 14);
+        }
+    }
+
+    impl PmaStatus {
+        pub const ADDRESS: u16 = 0x0001;
+    }
+
+    impl PmaControl {
+        pub const ADDRESS: u16 = 0x0834;
+    }
+
+    impl DeviceControl {
+        pub const ADDRESS: u16 = 0x0040;
+    }
+
+    impl PortControl {
+        pub const ADDRESS: u16 = 0x8040;
+    }
+
+    impl InterruptFlags {
+        pub const ACK_ADDRESS: u16 = 0x80A0;
+        pub const ENABLE_ADDRESS: u16 = 0x80A1;
+        pub const STATUS_ADDRESS: u16 = 0x80A2;
+    }
+
+    impl PhyControl {
+        pub const ADDRESS: u16 = 0x8100;
+    }
+
+    impl PhyConfig {
+        pub const ADDRESS: u16 = 0x8108;
+    }
+
+    impl SignalQuality {
+        pub const ADDRESS: u16 = 0x8320;
+    }
+
+    impl PortInfraControl {
+        pub const ADDRESS: u16 = 0xAC00;
+    }
+}
@@ -0,0 +1,300 @@
+//! Phy implementation for the Vitesse VSC8211 gigabit Ethernet PHY
+//!
+//! The extended registers of the VSC8211 are located on extended page 1,
+//! which is selected through register 31.
+
+use crate::{
+    phy::vsc8541::registers::PAGE_SELECT, registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{AuxiliaryControlStatus, InterruptFlags, PAGE_EXTENDED};
+
+use super::{
+    AdvancedPhySpeed, PhyWithAutonegInterrupt, PhyWithInterrupts, PhyWithPages, PhyWithSpeed,
+};
+
+/// All interrupt sources supported by this chip
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interrupt {
+    /// The link speed changed
+    SpeedChange,
+    /// The link status changed
+    LinkStatusChange,
+    /// The duplex mode changed
+    DuplexChange,
+    /// An autonegotiation error occurred
+    AutoNegotiationError,
+    /// Autonegotiation completed
+    AutoNegotiationComplete,
+    /// An inline powered device was detected
+    InlinePoweredDevice,
+    /// A symbol error occurred
+    SymbolError,
+    /// A false carrier was detected
+    FalseCarrier,
+    /// The link speed was downshifted
+    SpeedDownshift,
+    /// A MASTER/SLAVE resolution error occurred
+    MasterSlaveError,
+    /// A receive error occurred
+    ReceiveError,
+}
+
+impl Interrupt {
+    /// The bit for this interrupt in the [`InterruptFlags`] registers
+    pub fn flag(&self) -> InterruptFlags {
+        match self {
+            Interrupt::SpeedChange => InterruptFlags::SPEED_CHANGE,
+            Interrupt::LinkStatusChange => InterruptFlags::LINK_STATUS_CHANGE,
+            Interrupt::DuplexChange => InterruptFlags::DUPLEX_CHANGE,
+            Interrupt::AutoNegotiationError => InterruptFlags::AUTONEG_ERROR,
+            Interrupt::AutoNegotiationComplete => InterruptFlags::AUTONEG_COMPLETE,
+            Interrupt::InlinePoweredDevice => InterruptFlags::INLINE_POWERED_DEVICE,
+            Interrupt::SymbolError => InterruptFlags::SYMBOL_ERROR,
+            Interrupt::FalseCarrier => InterruptFlags::FALSE_CARRIER,
+            Interrupt::SpeedDownshift => InterruptFlags::SPEED_DOWNSHIFT,
+            Interrupt::MasterSlaveError => InterruptFlags::MASTER_SLAVE_ERROR,
+            Interrupt::ReceiveError => InterruptFlags::RECEIVE_ERROR,
+        }
+    }
+}
+
+/// A VSC8211
+#[derive(Debug)]
+pub struct VSC8211<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> VSC8211<MIIM> {
+    /// Create a new VSC8211 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read an extended register
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_extended(&mut self, address: u8) -> u16 {
+        self.page_scoped(PAGE_EXTENDED).read(address)
+    }
+
+    /// Write an extended register
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_extended(&mut self, address: u8, value: u16) {
+        self.page_scoped(PAGE_EXTENDED).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in an extended register, leaving
+    /// all other bits untouched
+    pub fn modify_extended(&mut self, address: u8, mask: u16, value: u16) {
+        self.page_scoped(PAGE_EXTENDED).modify(address, mask, value)
+    }
+
+    /// Enable an interrupt
+    ///
+    /// This also enables the MDINT pin.
+    pub fn enable_interrupt(&mut self, interrupt: Interrupt) {
+        let flags = (interrupt.flag() | InterruptFlags::PIN_ENABLE).bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flags, flags);
+    }
+
+    /// Disable an interrupt
+    pub fn disable_interrupt(&mut self, interrupt: Interrupt) {
+        let flag = interrupt.flag().bits();
+        self.modify(InterruptFlags::MASK_ADDRESS, flag, 0);
+    }
+
+    /// Read and clear all interrupts.
+    ///
+    /// Use [`InterruptFlags::occurred`] to determine which interrupts
+    /// were active.
+    pub fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS))
+    }
+
+    /// Check whether ActiPHY power management is enabled
+    pub fn actiphy(&mut self) -> bool {
+        self.auxiliary_control_status()
+            .contains(AuxiliaryControlStatus::ACTIPHY_ENABLE)
+    }
+
+    /// Enable or disable ActiPHY power management
+    ///
+    /// While ActiPHY is enabled, the PHY powers down most of its circuitry
+    /// when no link partner is connected, and periodically wakes up to
+    /// check for one.
+    pub fn set_actiphy(&mut self, enabled: bool) {
+        let bit = AuxiliaryControlStatus::ACTIPHY_ENABLE.bits();
+        self.modify(
+            AuxiliaryControlStatus::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Read the Auxiliary Control and Status register
+    pub fn auxiliary_control_status(&mut self) -> AuxiliaryControlStatus {
+        AuxiliaryControlStatus::from_bits_truncate(self.read(AuxiliaryControlStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.auxiliary_control_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for VSC8211<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for VSC8211<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for VSC8211<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+impl<MIIM: Miim> PhyWithInterrupts<MIIM> for VSC8211<MIIM> {
+    type Interrupts = InterruptFlags;
+
+    fn read_and_clear_interrupts(&mut self) -> InterruptFlags {
+        self.read_and_clear_interrupts()
+    }
+}
+
+impl<MIIM: Miim> PhyWithAutonegInterrupt<MIIM> for VSC8211<MIIM> {
+    fn set_autoneg_complete_interrupt(&mut self, enabled: bool) {
+        if enabled {
+            self.enable_interrupt(Interrupt::AutoNegotiationComplete)
+        } else {
+            self.disable_interrupt(Interrupt::AutoNegotiationComplete)
+        }
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! VSC8211 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::Interrupt;
+
+    /// The extended register page
+    pub const PAGE_EXTENDED: u16 = 0x0001;
+
+    bitflags! {
+        /// Register 25 and register 26, the Interrupt Mask and Interrupt
+        /// Status registers
+        pub struct InterruptFlags: u16 {
+            /// In the mask register: enable the MDINT pin. In the status
+            /// register: an interrupt is pending.
+            const PIN_ENABLE = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const LINK_STATUS_CHANGE = (1 << 13);
+            const DUPLEX_CHANGE = (1 << 12);
+            const AUTONEG_ERROR = (1 << 11);
+            const AUTONEG_COMPLETE = (1 << 10);
+            const INLINE_POWERED_DEVICE = (1 << 9);
+            const SYMBOL_ERROR = (1 << 8);
+            const FALSE_CARRIER = (1 << 3);
+            const SPEED_DOWNSHIFT = (1 << 2);
+            const MASTER_SLAVE_ERROR = (1 << 1);
+            const RECEIVE_ERROR = (1 << 0);
+        }
+
+        /// Register 28, the Auxiliary Control and Status register
+        pub struct AuxiliaryControlStatus: u16 {
+            const AUTONEG_COMPLETE = (1 << 15);
+            const AUTONEG_DISABLED = (1 << 14);
+            const MDI_CROSSOVER = (1 << 13);
+            const CD_PAIR_SWAP = (1 << 12);
+            const ACTIPHY_ENABLE = (1 << 6);
+            const FULL_DUPLEX = (1 << 5);
+            const SPEED_MASK = (0b11 << 3);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const MASK_ADDRESS: u8 = 25;
+        pub const STATUS_ADDRESS: u8 = 26;
+
+        /// Check whether `interrupt` occurred
+        pub fn occurred(&self, interrupt: Interrupt) -> bool {
+            self.contains(interrupt.flag())
+        }
+    }
+
+    impl AuxiliaryControlStatus {
+        pub const ADDRESS: u8 = 28;
+    }
+
+    impl From<AuxiliaryControlStatus> for Option<AdvancedPhySpeed> {
+        fn from(aux: AuxiliaryControlStatus) -> Self {
+            let full_duplex = aux.contains(AuxiliaryControlStatus::FULL_DUPLEX);
+            let speed = (aux & AuxiliaryControlStatus::SPEED_MASK).bits() >> 3;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Phy implementation for the Microsemi VSC8501 gigabit Ethernet PHY
+//!
+//! The VSC8501 shares its extended page layout, RGMII skew configuration
+//! and Auxiliary Control and Status register with the
+//! [`VSC8541`](super::vsc8541::VSC8541).
+
+use crate::{
+    phy::vsc8541::{
+        registers::{AuxiliaryControlStatus, RgmiiControl, PAGE_SELECT},
+        RgmiiSkew,
+    },
+    registers::MasterSlaveStatus,
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{ExtendedModeControl, InterruptFlags, WOL_MAC_ADDRESS_LOWER, WOL_PAGE};
+
+use super::{
+    AdvancedPhySpeed, PhyWithFastLinkDown, PhyWithPages, PhyWithSpeed, PhyWithWakeOnLan,
+    WakeOnLanEvents,
+};
+
+/// A VSC8501
+#[derive(Debug)]
+pub struct VSC8501<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> VSC8501<MIIM> {
+    /// Create a new VSC8501 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported 10/100/1000BASE-T modes, and restarts
+    /// autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Read a register on extended page `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Write a register on extended page `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn write_paged(&mut self, page: u16, address: u8, value: u16) {
+        self.page_scoped(page).write(address, value)
+    }
+
+    /// Modify the bits selected by `mask` in a register on extended page
+    /// `page`, leaving all other bits untouched
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    /// Get the RGMII RX clock skew
+    pub fn rgmii_rx_skew(&mut self) -> RgmiiSkew {
+        let ctrl = self.read_paged(RgmiiControl::PAGE, RgmiiControl::ADDRESS);
+        RgmiiSkew::from(ctrl >> RgmiiControl::RX_CLK_DELAY_SHIFT)
+    }
+
+    /// Set the RGMII RX clock skew
+    pub fn set_rgmii_rx_skew(&mut self, skew: RgmiiSkew) {
+        self.modify_paged(
+            RgmiiControl::PAGE,
+            RgmiiControl::ADDRESS,
+            RgmiiControl::RX_CLK_DELAY_MASK.bits(),
+            u16::from(skew) << RgmiiControl::RX_CLK_DELAY_SHIFT,
+        );
+    }
+
+    /// Get the RGMII TX clock skew
+    pub fn rgmii_tx_skew(&mut self) -> RgmiiSkew {
+        let ctrl = self.read_paged(RgmiiControl::PAGE, RgmiiControl::ADDRESS);
+        RgmiiSkew::from(ctrl)
+    }
+
+    /// Set the RGMII TX clock skew
+    pub fn set_rgmii_tx_skew(&mut self, skew: RgmiiSkew) {
+        self.modify_paged(
+            RgmiiControl::PAGE,
+            RgmiiControl::ADDRESS,
+            RgmiiControl::TX_CLK_DELAY_MASK.bits(),
+            u16::from(skew),
+        );
+    }
+
+    /// Check whether the fast link failure indication is enabled
+    pub fn fast_link_failure(&mut self) -> bool {
+        let emc = ExtendedModeControl::from_bits_truncate(
+            self.read_paged(ExtendedModeControl::PAGE, ExtendedModeControl::ADDRESS),
+        );
+        emc.contains(ExtendedModeControl::FAST_LINK_FAILURE)
+    }
+
+    /// Enable or disable the fast link failure indication
+    ///
+    /// While enabled, a link failure is indicated on the FLF pin well before
+    /// the link status in the BSR is updated.
+    pub fn set_fast_link_failure(&mut self, enabled: bool) {
+        let bit = ExtendedModeControl::FAST_LINK_FAILURE.bits();
+        self.modify_paged(
+            ExtendedModeControl::PAGE,
+            ExtendedModeControl::ADDRESS,
+            bit,
+            if enabled { bit } else { 0 },
+        );
+    }
+
+    /// Read the Auxiliary Control and Status register
+    pub fn auxiliary_control_status(&mut self) -> AuxiliaryControlStatus {
+        AuxiliaryControlStatus::from_bits_truncate(self.read(AuxiliaryControlStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.auxiliary_control_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for VSC8501<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for VSC8501<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for VSC8501<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+/// Only [`WakeOnLanEvents::MAGIC_PACKET`] is supported. A wake-up event
+/// is signalled through the MDINT pin.
+impl<MIIM: Miim> PhyWithWakeOnLan<MIIM> for VSC8501<MIIM> {
+    fn set_wol_mac_address(&mut self, mac_address: [u8; 6]) {
+        for (i, bytes) in mac_address.rchunks(2).enumerate() {
+            // The lower register contains the last two bytes
+            self.write_paged(
+                WOL_PAGE,
+                WOL_MAC_ADDRESS_LOWER + i as u8,
+                u16::from_be_bytes([bytes[0], bytes[1]]),
+            );
+        }
+    }
+
+    fn wol_events(&mut self) -> WakeOnLanEvents {
+        let mask = InterruptFlags::from_bits_truncate(self.read(InterruptFlags::MASK_ADDRESS));
+
+        let mut events = WakeOnLanEvents::empty();
+        events.set(
+            WakeOnLanEvents::MAGIC_PACKET,
+            mask.contains(InterruptFlags::WAKE_ON_LAN),
+        );
+        events
+    }
+
+    fn set_wol_events(&mut self, events: WakeOnLanEvents) {
+        let flags = InterruptFlags::PIN_ENABLE | InterruptFlags::WAKE_ON_LAN;
+        let value = if events.contains(WakeOnLanEvents::MAGIC_PACKET) {
+            flags
+        } else {
+            InterruptFlags::empty()
+        };
+
+        self.modify(InterruptFlags::MASK_ADDRESS, flags.bits(), value.bits());
+    }
+
+    /// Note that this reads the interrupt status register, which clears all
+    /// pending interrupts.
+    fn read_and_clear_wol_status(&mut self) -> WakeOnLanEvents {
+        let status = InterruptFlags::from_bits_truncate(self.read(InterruptFlags::STATUS_ADDRESS));
+
+        let mut events = WakeOnLanEvents::empty();
+        events.set(
+            WakeOnLanEvents::MAGIC_PACKET,
+            status.contains(InterruptFlags::WAKE_ON_LAN),
+        );
+        events
+    }
+}
+
+impl<MIIM: Miim> PhyWithFastLinkDown<MIIM> for VSC8501<MIIM> {
+    /// Whether the fast link failure indication on the FLF pin is enabled
+    type Config = bool;
+
+    fn fast_link_down(&mut self) -> bool {
+        self.fast_link_failure()
+    }
+
+    fn set_fast_link_down(&mut self, enabled: bool) {
+        self.set_fast_link_failure(enabled)
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! VSC8501 specific registers
+    //!
+    //! The extended page registers shared with the VSC8541 are found in
+    //! [`crate::phy::vsc8541::registers`].
+
+    use bitflags::bitflags;
+
+    /// The page containing the Wake-on-LAN registers
+    pub const WOL_PAGE: u16 = 0x0002;
+    /// Registers 21 through 23 on extended page 2, the Wake-on-LAN MAC
+    /// address. The last two bytes of the address are in register 21.
+    pub const WOL_MAC_ADDRESS_LOWER: u8 = 21;
+
+    bitflags! {
+        /// Register 25 and register 26, the Interrupt Mask and Interrupt
+        /// Status registers
+        pub struct InterruptFlags: u16 {
+            /// In the mask register: enable the MDINT pin. In the status
+            /// register: an interrupt is pending.
+            const PIN_ENABLE = (1 << 15);
+            const SPEED_CHANGE = (1 << 14);
+            const LINK_STATUS_CHANGE = (1 << 13);
+            const DUPLEX_CHANGE = (1 << 12);
+            const AUTONEG_ERROR = (1 << 11);
+            const AUTONEG_COMPLETE = (1 << 10);
+            const WAKE_ON_LAN = (1 << 6);
+        }
+
+        /// Register 19 on extended page 1, the Extended Mode Control register
+        pub struct ExtendedModeControl: u16 {
+            const FAST_LINK_FAILURE = (1 << 4);
+            const FORCE_MDI_CROSSOVER_MASK = (0b11 << 2);
+        }
+    }
+
+    impl InterruptFlags {
+        pub const MASK_ADDRESS: u8 = 25;
+        pub const STATUS_ADDRESS: u8 = 26;
+    }
+
+    impl ExtendedModeControl {
+        pub const PAGE: u16 = 0x0001;
+        pub const ADDRESS: u8 = 19;
+    }
+}
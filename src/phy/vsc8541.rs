@@ -0,0 +1,329 @@
+//! Phy implementation for the Microchip (formerly Vitesse/Microsemi) VSC8541
+//! gigabit Ethernet PHY
+//!
+//! The vendor specific registers of the VSC8541 are located on extended
+//! pages, which are selected through register 31.
+
+use crate::{registers::MasterSlaveStatus, AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{
+    AuxiliaryControlStatus, RgmiiControl, TokenRingControl, INIT_SCRIPT, PAGE_SELECT,
+    PAGE_TOKEN_RING, TR_DATA_HIGH, TR_DATA_LOW,
+};
+
+use super::{AdvancedPhySpeed, PhyWithPages, PhyWithSpeed};
+
+/// An RGMII clock skew supported by the VSC8541
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RgmiiSkew {
+    /// 0.2 ns
+    Skew0_2ns,
+    /// 0.8 ns
+    Skew0_8ns,
+    /// 1.1 ns
+    Skew1_1ns,
+    /// 1.7 ns
+    Skew1_7ns,
+    /// 2.0 ns
+    Skew2_0ns,
+    /// 2.3 ns
+    Skew2_3ns,
+    /// 2.6 ns
+    Skew2_6ns,
+    /// 3.4 ns
+    Skew3_4ns,
+}
+
+impl From<u16> for RgmiiSkew {
+    fn from(value: u16) -> Self {
+        match value & 0b111 {
+            0 => RgmiiSkew::Skew0_2ns,
+            1 => RgmiiSkew::Skew0_8ns,
+            2 => RgmiiSkew::Skew1_1ns,
+            3 => RgmiiSkew::Skew1_7ns,
+            4 => RgmiiSkew::Skew2_0ns,
+            5 => RgmiiSkew::Skew2_3ns,
+            6 => RgmiiSkew::Skew2_6ns,
+            _ => RgmiiSkew::Skew3_4ns,
+        }
+    }
+}
+
+impl From<RgmiiSkew> for u16 {
+    fn from(skew: RgmiiSkew) -> Self {
+        match skew {
+            RgmiiSkew::Skew0_2ns => 0,
+            RgmiiSkew::Skew0_8ns => 1,
+            RgmiiSkew::Skew1_1ns => 2,
+            RgmiiSkew::Skew1_7ns => 3,
+            RgmiiSkew::Skew2_0ns => 4,
+            RgmiiSkew::Skew2_3ns => 5,
+            RgmiiSkew::Skew2_6ns => 6,
+            RgmiiSkew::Skew3_4ns => 7,
+        }
+    }
+}
+
+/// A VSC8541
+#[derive(Debug)]
+pub struct VSC8541<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> VSC8541<MIIM> {
+    /// Create a new VSC8541 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This runs the initialization script that the datasheet requires
+    /// after every reset, advertises all supported 10/100/1000BASE-T modes,
+    /// and restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        self.run_init_script();
+
+        super::advertise_gigabit(self);
+    }
+
+    /// Run the initialization script, which optimizes the link detection and
+    /// receiver gain settings.
+    ///
+    /// This must be done after every reset of the PHY, and is also performed
+    /// by [`Self::phy_init`].
+    pub fn run_init_script(&mut self) {
+        let mut phy = self.page_scoped(PAGE_TOKEN_RING);
+        for (address, mask, value) in INIT_SCRIPT {
+            let old = phy.token_ring_read(address);
+            phy.token_ring_write(address, (old & !mask) | (value & mask));
+        }
+    }
+
+    fn token_ring_read(&mut self, address: u16) -> u32 {
+        self.write(
+            TokenRingControl::ADDRESS,
+            TokenRingControl::read(address).bits(),
+        );
+        let low = self.read(TR_DATA_LOW) as u32;
+        let high = self.read(TR_DATA_HIGH) as u32;
+        (high << 16) | low
+    }
+
+    fn token_ring_write(&mut self, address: u16, value: u32) {
+        self.write(TR_DATA_HIGH, (value >> 16) as u16);
+        self.write(TR_DATA_LOW, value as u16);
+        self.write(
+            TokenRingControl::ADDRESS,
+            TokenRingControl::write(address).bits(),
+        );
+    }
+
+    /// Read a register on extended page `page`
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn read_paged(&mut self, page: u16, address: u8) -> u16 {
+        self.page_scoped(page).read(address)
+    }
+
+    /// Modify the bits selected by `mask` in a register on extended page
+    /// `page`, leaving all other bits untouched
+    ///
+    /// The previously selected page is restored afterwards.
+    pub fn modify_paged(&mut self, page: u16, address: u8, mask: u16, value: u16) {
+        self.page_scoped(page).modify(address, mask, value)
+    }
+
+    /// Get the RGMII RX clock skew
+    pub fn rgmii_rx_skew(&mut self) -> RgmiiSkew {
+        let ctrl = self.read_paged(RgmiiControl::PAGE, RgmiiControl::ADDRESS);
+        RgmiiSkew::from(ctrl >> RgmiiControl::RX_CLK_DELAY_SHIFT)
+    }
+
+    /// Set the RGMII RX clock skew
+    pub fn set_rgmii_rx_skew(&mut self, skew: RgmiiSkew) {
+        self.modify_paged(
+            RgmiiControl::PAGE,
+            RgmiiControl::ADDRESS,
+            RgmiiControl::RX_CLK_DELAY_MASK.bits(),
+            u16::from(skew) << RgmiiControl::RX_CLK_DELAY_SHIFT,
+        );
+    }
+
+    /// Get the RGMII TX clock skew
+    pub fn rgmii_tx_skew(&mut self) -> RgmiiSkew {
+        let ctrl = self.read_paged(RgmiiControl::PAGE, RgmiiControl::ADDRESS);
+        RgmiiSkew::from(ctrl)
+    }
+
+    /// Set the RGMII TX clock skew
+    pub fn set_rgmii_tx_skew(&mut self, skew: RgmiiSkew) {
+        self.modify_paged(
+            RgmiiControl::PAGE,
+            RgmiiControl::ADDRESS,
+            RgmiiControl::TX_CLK_DELAY_MASK.bits(),
+            u16::from(skew),
+        );
+    }
+
+    /// Read the Auxiliary Control and Status register
+    pub fn auxiliary_control_status(&mut self) -> AuxiliaryControlStatus {
+        AuxiliaryControlStatus::from_bits_truncate(self.read(AuxiliaryControlStatus::ADDRESS))
+    }
+
+    /// Read the MASTER-SLAVE Status register, containing the
+    /// 1000BASE-T abilities of the link partner
+    pub fn master_slave_status(&mut self) -> MasterSlaveStatus {
+        super::master_slave_status(self)
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.auxiliary_control_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for VSC8541<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for VSC8541<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+impl<MIIM: Miim> PhyWithPages<MIIM> for VSC8541<MIIM> {
+    const PAGE_SELECT: u8 = PAGE_SELECT;
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! VSC8541 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    /// Register 31, the Extended Page Access register
+    pub const PAGE_SELECT: u8 = 31;
+    /// The standard (IEEE 802.3) register page
+    pub const PAGE_STANDARD: u16 = 0x0000;
+    /// The extended register page 2
+    pub const PAGE_EXTENDED_2: u16 = 0x0002;
+    /// The token ring register page
+    pub const PAGE_TOKEN_RING: u16 = 0x52B5;
+
+    /// Register 17 on the token ring page, the lower 16 bits of the token ring data
+    pub const TR_DATA_LOW: u8 = 17;
+    /// Register 18 on the token ring page, the upper bits of the token ring data
+    pub const TR_DATA_HIGH: u8 = 18;
+
+    /// The initialization script, as `(address, mask, value)` for token ring
+    /// registers.
+    pub const INIT_SCRIPT: [(u16, u32, u32); 3] = [
+        // LinkDetectCtrl
+        (0x07F8, 0x0000_0018, 0x0000_0018),
+        // VgaThresh100
+        (0x0FA4, 0x0000_007F, 0x0000_0018),
+        // VgaGain10
+        (0x0F92, 0x0001_F000, 0x0000_1000),
+    ];
+
+    bitflags! {
+        /// Register 16 on the token ring page, the Token Ring Control register
+        pub struct TokenRingControl: u16 {
+            const START = (1 << 15);
+            const READ = (1 << 13);
+            const ADDRESS_MASK = (0x1FFF);
+        }
+
+        /// Register 20 on extended page 2, the RGMII Control register
+        pub struct RgmiiControl: u16 {
+            const RX_CLK_DELAY_MASK = (0b111 << 4);
+            const TX_CLK_DELAY_MASK = (0b111);
+        }
+
+        /// Register 28, the Auxiliary Control and Status register
+        pub struct AuxiliaryControlStatus: u16 {
+            const AUTONEG_COMPLETE = (1 << 15);
+            const AUTONEG_DISABLED = (1 << 14);
+            const MDI_CROSSOVER = (1 << 13);
+            const CD_PAIR_SWAP = (1 << 12);
+            const FULL_DUPLEX = (1 << 5);
+            const SPEED_MASK = (0b11 << 3);
+        }
+    }
+
+    impl TokenRingControl {
+        pub const ADDRESS: u8 = 16;
+
+        /// Start a read of token ring register `address`
+        pub fn read(address: u16) -> Self {
+            Self::START | Self::READ | Self::from_bits_truncate(address)
+        }
+
+        /// Start a write of token ring register `address`
+        pub fn write(address: u16) -> Self {
+            Self::START | Self::from_bits_truncate(address)
+        }
+    }
+
+    impl RgmiiControl {
+        pub const PAGE: u16 = PAGE_EXTENDED_2;
+        pub const ADDRESS: u8 = 20;
+        pub const RX_CLK_DELAY_SHIFT: u16 = 4;
+    }
+
+    impl AuxiliaryControlStatus {
+        pub const ADDRESS: u8 = 28;
+    }
+
+    impl From<AuxiliaryControlStatus> for Option<AdvancedPhySpeed> {
+        fn from(aux: AuxiliaryControlStatus) -> Self {
+            let full_duplex = aux.contains(AuxiliaryControlStatus::FULL_DUPLEX);
+            let speed = (aux & AuxiliaryControlStatus::SPEED_MASK).bits() >> 3;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Phy implementation for the Motorcomm YT8511 gigabit Ethernet PHY
+//!
+//! The vendor specific configuration of the YT8511 is done through extended
+//! registers, which are accessed indirectly: the address of the extended
+//! register is written to register 0x1E, after which the extended register
+//! can be accessed through register 0x1F.
+
+use crate::{AutoNegotiationAdvertisement, Miim, Phy, PhyAddress};
+
+use self::registers::{ClockGate, SleepControl, SpecificStatus, EXT_ADDRESS, EXT_DATA};
+
+use super::{AdvancedPhySpeed, PhyWithRgmiiDelays, PhyWithSpeed, RgmiiDelays};
+
+/// A clock that can be output on the CLK_OUT pin of the YT8511
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockOutput {
+    /// 25 MHz from the PLL
+    Pll25MHz,
+    /// 25 MHz from the crystal
+    Crystal25MHz,
+    /// 62.5 MHz from the PLL
+    Pll62_5MHz,
+    /// 125 MHz from the PLL
+    Pll125MHz,
+}
+
+/// A YT8511
+#[derive(Debug)]
+pub struct YT8511<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> YT8511<MIIM> {
+    /// Create a new YT8511 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, including 1000BASE-T, and
+    /// restarts autonegotiation.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the PHY Specific Status register
+    pub fn specific_status(&mut self) -> SpecificStatus {
+        SpecificStatus::from_bits_truncate(self.read(SpecificStatus::ADDRESS))
+    }
+
+    /// Read the extended register at `address`
+    pub fn ext_read(&mut self, address: u16) -> u16 {
+        ext_read(self, address)
+    }
+
+    /// Write `value` to the extended register at `address`
+    pub fn ext_write(&mut self, address: u16, value: u16) {
+        ext_write(self, address, value)
+    }
+
+    /// Modify the bits selected by `mask` in the extended register at
+    /// `address`, leaving all other bits untouched
+    pub fn ext_modify(&mut self, address: u16, mask: u16, value: u16) {
+        ext_modify(self, address, mask, value)
+    }
+
+    /// Check whether the internal RGMII RX clock delay is enabled
+    pub fn rx_delay(&mut self) -> bool {
+        ClockGate::from_bits_truncate(self.ext_read(ClockGate::ADDRESS))
+            .contains(ClockGate::RX_DELAY)
+    }
+
+    /// Check whether the internal RGMII TX clock delay is enabled at
+    /// 1000 Mbit/s
+    pub fn tx_delay(&mut self) -> bool {
+        let cg = ClockGate::from_bits_truncate(self.ext_read(ClockGate::ADDRESS));
+        (cg & ClockGate::GE_TX_DELAY_MASK) != ClockGate::GE_TX_DELAY_OFF
+    }
+
+    /// Enable or disable the internal RGMII RX and TX clock delays
+    ///
+    /// When enabled, the RX delay is 1.8 ns at 1000 Mbit/s, and the TX delay
+    /// is 2 ns.
+    pub fn set_rgmii_delays(&mut self, rx_delay: bool, tx_delay: bool) {
+        let mut value = ClockGate::empty();
+        value.set(ClockGate::RX_DELAY, rx_delay);
+
+        if tx_delay {
+            value |= ClockGate::GE_TX_DELAY_ON | ClockGate::FE_TX_DELAY_ON;
+        } else {
+            value |= ClockGate::GE_TX_DELAY_OFF | ClockGate::FE_TX_DELAY_OFF;
+        }
+
+        self.ext_modify(
+            ClockGate::ADDRESS,
+            (ClockGate::RX_DELAY | ClockGate::GE_TX_DELAY_MASK | ClockGate::FE_TX_DELAY_MASK)
+                .bits(),
+            value.bits(),
+        );
+    }
+
+    /// Get the clock that is output on the CLK_OUT pin
+    pub fn clock_output(&mut self) -> ClockOutput {
+        ClockGate::from_bits_truncate(self.ext_read(ClockGate::ADDRESS)).into()
+    }
+
+    /// Select the clock that is output on the CLK_OUT pin
+    ///
+    /// The PLL is kept running in sleep mode when a clock from the PLL is
+    /// selected, so the clock output stays active.
+    pub fn set_clock_output(&mut self, output: ClockOutput) {
+        self.ext_modify(
+            ClockGate::ADDRESS,
+            ClockGate::CLK_SELECT_MASK.bits(),
+            ClockGate::from(output).bits(),
+        );
+
+        let pll_on = SleepControl::PLL_ON_IN_SLEEP.bits();
+        self.ext_modify(
+            SleepControl::ADDRESS,
+            pll_on,
+            if output == ClockOutput::Crystal25MHz {
+                0
+            } else {
+                pll_on
+            },
+        );
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for YT8511<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for YT8511<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+/// Read the extended register at `address`
+///
+/// This is shared with the YT8521, which uses the same indirect access
+/// scheme.
+pub(crate) fn ext_read<M: Miim, P: Phy<M>>(phy: &mut P, address: u16) -> u16 {
+    phy.write(EXT_ADDRESS, address);
+    phy.read(EXT_DATA)
+}
+
+pub(crate) fn ext_write<M: Miim, P: Phy<M>>(phy: &mut P, address: u16, value: u16) {
+    phy.write(EXT_ADDRESS, address);
+    phy.write(EXT_DATA, value);
+}
+
+pub(crate) fn ext_modify<M: Miim, P: Phy<M>>(phy: &mut P, address: u16, mask: u16, value: u16) {
+    phy.write(EXT_ADDRESS, address);
+    phy.modify(EXT_DATA, mask, value);
+}
+
+impl<MIIM: Miim> PhyWithRgmiiDelays<MIIM> for YT8511<MIIM> {
+    fn internal_delays(&mut self) -> RgmiiDelays {
+        RgmiiDelays {
+            tx: self.tx_delay(),
+            rx: self.rx_delay(),
+        }
+    }
+
+    fn set_internal_delays(&mut self, delays: RgmiiDelays) {
+        self.set_rgmii_delays(delays.rx, delays.tx);
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! YT8511 registers
+
+    use bitflags::bitflags;
+
+    use crate::phy::AdvancedPhySpeed;
+
+    use super::ClockOutput;
+
+    /// Register 0x1E, the Extended Register Address register
+    pub const EXT_ADDRESS: u8 = 0x1E;
+    /// Register 0x1F, the Extended Register Data register
+    pub const EXT_DATA: u8 = 0x1F;
+
+    bitflags! {
+        /// Register 0x11, the PHY Specific Status register
+        pub struct SpecificStatus: u16 {
+            const SPEED_MASK = (0b11 << 14);
+            const FULL_DUPLEX = (1 << 13);
+            const PAGE_RECEIVED = (1 << 12);
+            const RESOLVED = (1 << 11);
+            const LINK = (1 << 10);
+            const MDIX = (1 << 6);
+        }
+
+        /// Extended register 0x0C, the Clock Gating and Delay register
+        ///
+        /// The TX delays are `150 ps * N - 250 ps`.
+        pub struct ClockGate: u16 {
+            const FE_TX_DELAY_MASK = (0xF << 12);
+            const FE_TX_DELAY_ON = (0xF << 12);
+            const FE_TX_DELAY_OFF = (0x2 << 12);
+            const GE_TX_DELAY_MASK = (0xF << 4);
+            const GE_TX_DELAY_ON = (0xF << 4);
+            const GE_TX_DELAY_OFF = (0x2 << 4);
+            const CLK_SELECT_MASK = (0b11 << 1);
+            const RX_DELAY = (1 << 0);
+        }
+
+        /// Extended register 0x27, the Sleep Control register
+        pub struct SleepControl: u16 {
+            const PLL_ON_IN_SLEEP = (1 << 14);
+        }
+    }
+
+    impl SpecificStatus {
+        pub const ADDRESS: u8 = 0x11;
+    }
+
+    impl From<SpecificStatus> for Option<AdvancedPhySpeed> {
+        fn from(status: SpecificStatus) -> Self {
+            if !status.contains(SpecificStatus::RESOLVED) {
+                return None;
+            }
+
+            let full_duplex = status.contains(SpecificStatus::FULL_DUPLEX);
+            let speed = (status & SpecificStatus::SPEED_MASK).bits() >> 14;
+
+            let speed = match (speed, full_duplex) {
+                (0b00, false) => AdvancedPhySpeed::HalfDuplexBase10T,
+                (0b00, true) => AdvancedPhySpeed::FullDuplexBase10T,
+                (0b01, false) => AdvancedPhySpeed::HalfDuplexBase100Tx,
+                (0b01, true) => AdvancedPhySpeed::FullDuplexBase100Tx,
+                (0b10, false) => AdvancedPhySpeed::HalfDuplexBase1000T,
+                (0b10, true) => AdvancedPhySpeed::FullDuplexBase1000T,
+                _ => return None,
+            };
+
+            Some(speed)
+        }
+    }
+
+    impl ClockGate {
+        pub const ADDRESS: u16 = 0x0C;
+    }
+
+    impl From<ClockGate> for ClockOutput {
+        fn from(gate: ClockGate) -> Self {
+            match (gate & ClockGate::CLK_SELECT_MASK).bits() >> 1 {
+                0 => ClockOutput::Pll25MHz,
+                1 => ClockOutput::Crystal25MHz,
+                2 => ClockOutput::Pll62_5MHz,
+                _ => ClockOutput::Pll125MHz,
+            }
+        }
+    }
+
+    impl From<ClockOutput> for ClockGate {
+        fn from(output: ClockOutput) -> Self {
+            let select = match output {
+                ClockOutput::Pll25MHz => 0,
+                ClockOutput::Crystal25MHz => 1,
+                ClockOutput::Pll62_5MHz => 2,
+                ClockOutput::Pll125MHz => 3,
+            };
+            ClockGate::from_bits_truncate(select << 1)
+        }
+    }
+
+    impl SleepControl {
+        pub const ADDRESS: u16 = 0x27;
+    }
+}
@@ -0,0 +1,240 @@
+//! Phy implementation for the Motorcomm YT8521 gigabit Ethernet PHY
+//!
+//! The YT8521 uses the same indirect extended register access as the
+//! [`YT8511`](super::yt8511::YT8511), but can also be used as a combined
+//! copper (UTP) and fiber PHY. Both media have their own set of standard
+//! registers, and [`YT8521::select_register_space`] selects which of them
+//! are accessed through the Clause 22 registers.
+
+use crate::{
+    phy::yt8511::{self, registers::SpecificStatus},
+    AutoNegotiationAdvertisement, Miim, Phy, PhyAddress,
+};
+
+use self::registers::{ChipConfig, RegisterSpaceSelect};
+
+use super::{AdvancedPhySpeed, PhyWithSpeed};
+
+/// The operating mode of the YT8521, which selects the media and MAC
+/// interfaces that are used
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChipMode {
+    /// Copper to RGMII
+    UtpToRgmii,
+    /// Fiber to RGMII
+    FiberToRgmii,
+    /// Copper or fiber to RGMII, using whichever medium has a link
+    UtpFiberToRgmii,
+    /// Copper to SGMII
+    UtpToSgmii,
+    /// SGMII PHY to RGMII MAC
+    SgmiiPhyToRgmiiMac,
+    /// SGMII MAC to RGMII PHY
+    SgmiiMacToRgmiiPhy,
+    /// Copper to fiber, with autonegotiation on the fiber side
+    UtpToFiberAuto,
+    /// Copper to fiber, with a forced speed on the fiber side
+    UtpToFiberForce,
+}
+
+/// A set of standard registers of the YT8521
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegisterSpace {
+    /// The copper (UTP) registers
+    Utp,
+    /// The fiber registers
+    Fiber,
+}
+
+/// A YT8521
+#[derive(Debug)]
+pub struct YT8521<MIIM: Miim> {
+    phy_addr: PhyAddress,
+    miim: MIIM,
+}
+
+impl<MIIM: Miim> YT8521<MIIM> {
+    /// Create a new YT8521 at `phy_addr`, backed by the given `miim`.
+    pub fn new(miim: MIIM, phy_addr: PhyAddress) -> Self {
+        Self { phy_addr, miim }
+    }
+
+    /// Initialize the PHY
+    ///
+    /// This advertises all supported modes, including 1000BASE-T, and
+    /// restarts autonegotiation in the currently selected register space.
+    pub fn phy_init(&mut self) {
+        super::advertise_gigabit(self);
+    }
+
+    /// Get the link speed at which the PHY is currently operating
+    pub fn link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.specific_status().into()
+    }
+
+    /// Check whether a link is established or not
+    pub fn link_established(&mut self) -> bool {
+        self.autoneg_completed() && self.phy_link_up()
+    }
+
+    /// Read the PHY Specific Status register of the currently selected
+    /// register space
+    pub fn specific_status(&mut self) -> SpecificStatus {
+        SpecificStatus::from_bits_truncate(self.read(SpecificStatus::ADDRESS))
+    }
+
+    /// Read the extended register at `address`
+    pub fn ext_read(&mut self, address: u16) -> u16 {
+        yt8511::ext_read(self, address)
+    }
+
+    /// Write `value` to the extended register at `address`
+    pub fn ext_write(&mut self, address: u16, value: u16) {
+        yt8511::ext_write(self, address, value)
+    }
+
+    /// Modify the bits selected by `mask` in the extended register at
+    /// `address`, leaving all other bits untouched
+    pub fn ext_modify(&mut self, address: u16, mask: u16, value: u16) {
+        yt8511::ext_modify(self, address, mask, value)
+    }
+
+    /// Get the operating mode
+    pub fn chip_mode(&mut self) -> ChipMode {
+        ChipConfig::from_bits_truncate(self.ext_read(ChipConfig::ADDRESS)).into()
+    }
+
+    /// Set the operating mode
+    ///
+    /// The new mode takes effect after a software reset.
+    pub fn set_chip_mode(&mut self, mode: ChipMode) {
+        self.ext_modify(
+            ChipConfig::ADDRESS,
+            ChipConfig::MODE_SELECT_MASK.bits(),
+            ChipConfig::from(mode).bits(),
+        );
+    }
+
+    /// Get the register space that is currently accessed through the
+    /// standard registers
+    pub fn register_space(&mut self) -> RegisterSpace {
+        let select =
+            RegisterSpaceSelect::from_bits_truncate(self.ext_read(RegisterSpaceSelect::ADDRESS));
+
+        if select.contains(RegisterSpaceSelect::FIBER) {
+            RegisterSpace::Fiber
+        } else {
+            RegisterSpace::Utp
+        }
+    }
+
+    /// Select the register space that is accessed through the standard
+    /// registers
+    pub fn select_register_space(&mut self, space: RegisterSpace) {
+        let bit = RegisterSpaceSelect::FIBER.bits();
+        let value = match space {
+            RegisterSpace::Utp => 0,
+            RegisterSpace::Fiber => bit,
+        };
+        self.ext_modify(RegisterSpaceSelect::ADDRESS, bit, value);
+    }
+
+    /// Release the underlying [`Miim`]
+    pub fn release(self) -> MIIM {
+        self.miim
+    }
+}
+
+impl<MIIM: Miim> Phy<MIIM> for YT8521<MIIM> {
+    fn best_supported_advertisement(&self) -> AutoNegotiationAdvertisement {
+        AutoNegotiationAdvertisement {
+            hd_10base_t: true,
+            fd_10base_t: true,
+            hd_100base_tx: true,
+            fd_100base_tx: true,
+            base100_t4: false,
+            ..Default::default()
+        }
+    }
+
+    fn get_miim(&mut self) -> &mut MIIM {
+        &mut self.miim
+    }
+
+    fn phy_address(&self) -> PhyAddress {
+        self.phy_addr
+    }
+}
+
+impl<MIIM: Miim> PhyWithSpeed<MIIM> for YT8521<MIIM> {
+    fn get_link_speed(&mut self) -> Option<AdvancedPhySpeed> {
+        self.link_speed()
+    }
+}
+
+#[allow(missing_docs)]
+pub mod registers {
+    //! YT8521 specific registers
+    //!
+    //! All other registers are shared with the YT8511, see
+    //! [`crate::phy::yt8511::registers`].
+
+    use bitflags::bitflags;
+
+    use super::ChipMode;
+
+    bitflags! {
+        /// Extended register 0xA000, the Register Space Select register
+        pub struct RegisterSpaceSelect: u16 {
+            const FIBER = (1 << 1);
+        }
+
+        /// Extended register 0xA001, the Chip Configuration register
+        pub struct ChipConfig: u16 {
+            const SOFT_RESET_N = (1 << 15);
+            const RXC_DELAY_ENABLE = (1 << 8);
+            const MODE_SELECT_MASK = (0b111);
+        }
+    }
+
+    impl RegisterSpaceSelect {
+        pub const ADDRESS: u16 = 0xA000;
+    }
+
+    impl ChipConfig {
+        pub const ADDRESS: u16 = 0xA001;
+    }
+
+    impl From<ChipConfig> for ChipMode {
+        fn from(config: ChipConfig) -> Self {
+            match (config & ChipConfig::MODE_SELECT_MASK).bits() {
+                0 => ChipMode::UtpToRgmii,
+                1 => ChipMode::FiberToRgmii,
+                2 => ChipMode::UtpFiberToRgmii,
+                3 => ChipMode::UtpToSgmii,
+                4 => ChipMode::SgmiiPhyToRgmiiMac,
+                5 => ChipMode::SgmiiMacToRgmiiPhy,
+                6 => ChipMode::UtpToFiberAuto,
+                _ => ChipMode::UtpToFiberForce,
+            }
+        }
+    }
+
+    impl From<ChipMode> for ChipConfig {
+        fn from(mode: ChipMode) -> Self {
+            let select = match mode {
+                ChipMode::UtpToRgmii => 0,
+                ChipMode::FiberToRgmii => 1,
+                ChipMode::UtpFiberToRgmii => 2,
+                ChipMode::UtpToSgmii => 3,
+                ChipMode::SgmiiPhyToRgmiiMac => 4,
+                ChipMode::SgmiiMacToRgmiiPhy => 5,
+                ChipMode::UtpToFiberAuto => 6,
+                ChipMode::UtpToFiberForce => 7,
+            };
+            ChipConfig::from_bits_truncate(select)
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! This module contains definitions of all MIIM registers
 
-use crate::{Miim, Phy};
+use crate::{Duplex, IllegalSpeed, Miim, Phy, Speed};
 
 pub use regs::*;
 #[allow(missing_docs)]
@@ -90,6 +90,184 @@ mod regs {
             const LINK_PARTNER_AUTONEG_ABLE = (1 << 0);
         }
 
+        /// Register 9, the MASTER-SLAVE Control register
+        ///
+        /// This register contains the 1000BASE-T abilities that the local
+        /// PHY will advertise.
+        pub struct MasterSlaveControl: u16 {
+            const TEST_MODE_MASK = (0b111 << 13);
+            const MS_MANUAL_CONFIG_ENABLE = (1 << 12);
+            const MS_MANUAL_CONFIG_VALUE = (1 << 11);
+            const PORT_TYPE = (1 << 10);
+            const _1000BASETFD = (1 << 9);
+            const _1000BASETHD = (1 << 8);
+        }
+
+        /// Register 10, the MASTER-SLAVE Status register
+        ///
+        /// This register contains the 1000BASE-T abilities advertised by the
+        /// link partner.
+        pub struct MasterSlaveStatus: u16 {
+            const MS_CONFIG_FAULT = (1 << 15);
+            const MS_CONFIG_RESOLUTION = (1 << 14);
+            const LOCAL_RECEIVER_STATUS = (1 << 13);
+            const REMOTE_RECEIVER_STATUS = (1 << 12);
+            const PARTNER_1000BASETFD = (1 << 11);
+            const PARTNER_1000BASETHD = (1 << 10);
+            const IDLE_ERROR_COUNT_MASK = (0xFF);
+        }
+
+        /// MMD registers 3.20, 7.60 and 7.61, the EEE capability, EEE advertisement
+        /// and link partner EEE ability registers.
+        pub struct Eee: u16 {
+            const _10GBASEKR = (1 << 6);
+            const _10GBASEKX4 = (1 << 5);
+            const _1000BASEKX = (1 << 4);
+            const _10GBASET = (1 << 3);
+            const _1000BASET = (1 << 2);
+            const _100BASETX = (1 << 1);
+        }
+
+        /// MMD registers 1.0 and 3.0, the PMA/PMD and PCS control registers,
+        /// and MMD register 7.0, the Auto-Negotiation control register.
+        ///
+        /// [`MmdControl::LOW_POWER`] is not present in register 7.0, and
+        /// [`MmdControl::AUTONEG_ENABLE`] and [`MmdControl::RESTART_AUTONEG`]
+        /// are only present in register 7.0.
+        pub struct MmdControl: u16 {
+            const RESET = (1 << 15);
+            const AUTONEG_ENABLE = (1 << 12);
+            const LOW_POWER = (1 << 11);
+            const RESTART_AUTONEG = (1 << 9);
+        }
+
+        /// MMD registers 1.1 and 3.1, the PMA/PMD and PCS status registers.
+        pub struct MmdStatus: u16 {
+            const FAULT = (1 << 7);
+            const RECEIVE_LINK_STATUS = (1 << 2);
+            const LOW_POWER_ABILITY = (1 << 1);
+        }
+
+        /// MMD register 1.8, the PMA/PMD status 2 register.
+        pub struct PmaStatus2: u16 {
+            const DEVICE_PRESENT_MASK = (0b11 << 14);
+            const TRANSMIT_FAULT_ABILITY = (1 << 13);
+            const RECEIVE_FAULT_ABILITY = (1 << 12);
+            const TRANSMIT_FAULT = (1 << 11);
+            const RECEIVE_FAULT = (1 << 10);
+        }
+
+        /// MMD register 1.10, the PMD receive signal detect register.
+        pub struct PmdSignalDetect: u16 {
+            const LANE_3 = (1 << 4);
+            const LANE_2 = (1 << 3);
+            const LANE_1 = (1 << 2);
+            const LANE_0 = (1 << 1);
+            const GLOBAL = (1 << 0);
+        }
+
+        /// MMD register 1.11, the PMA/PMD extended ability register.
+        pub struct PmaExtendedAbility: u16 {
+            const NBASET = (1 << 14);
+            const BASET1 = (1 << 11);
+            const _10BASET = (1 << 8);
+            const _100BASETX = (1 << 7);
+            const _1000BASEKX = (1 << 6);
+            const _1000BASET = (1 << 5);
+            const _10GBASEKR = (1 << 4);
+            const _10GBASEKX4 = (1 << 3);
+            const _10GBASET = (1 << 2);
+            const _10GBASELRM = (1 << 1);
+            const _10GBASECX4 = (1 << 0);
+        }
+
+        /// MMD register 3.32, the 10GBASE-R and 10GBASE-T PCS status 1
+        /// register.
+        pub struct BaseRPcsStatus1: u16 {
+            const RECEIVE_LINK_STATUS = (1 << 12);
+            const PRBS9_ABILITY = (1 << 3);
+            const PRBS31_ABILITY = (1 << 2);
+            const HIGH_BER = (1 << 1);
+            const BLOCK_LOCK = (1 << 0);
+        }
+
+        /// MMD register 3.33, the 10GBASE-R and 10GBASE-T PCS status 2
+        /// register.
+        ///
+        /// The latched bits and the counters are cleared when this register
+        /// is read.
+        pub struct BaseRPcsStatus2: u16 {
+            const LATCHED_BLOCK_LOCK = (1 << 15);
+            const LATCHED_HIGH_BER = (1 << 14);
+            const BER_COUNTER_MASK = (0b111111 << 8);
+            const ERRORED_BLOCKS_MASK = (0xFF);
+        }
+
+        /// MMD register 7.1, the Auto-Negotiation status register.
+        pub struct MmdAnStatus: u16 {
+            const PARALLEL_DETECTION_FAULT = (1 << 9);
+            const EXTENDED_NEXT_PAGE = (1 << 7);
+            const PAGE_RECEIVED = (1 << 6);
+            const AUTONEG_COMPLETE = (1 << 5);
+            const REMOTE_FAULT = (1 << 4);
+            const AUTONEG_ABILITY = (1 << 3);
+            const LINK_STATUS = (1 << 2);
+            const PARTNER_AUTONEG_ABILITY = (1 << 0);
+        }
+
+        /// The technology ability field (bits A0 to A24) of a Clause 73
+        /// base page, in MMD registers 7.17 and 7.18 (advertisement) or
+        /// 7.20 and 7.21 (link partner ability).
+        ///
+        /// Bit `n` of this value is bit `An` of the base page.
+        pub struct BackplaneAbility: u32 {
+            const _5GBASEKR = (1 << 12);
+            const _2_5GBASEKX = (1 << 11);
+            const _25GBASEKR = (1 << 10);
+            const _25GBASEKRS = (1 << 9);
+            const _100GBASECR4 = (1 << 8);
+            const _100GBASEKR4 = (1 << 7);
+            const _100GBASEKP4 = (1 << 6);
+            const _100GBASECR10 = (1 << 5);
+            const _40GBASECR4 = (1 << 4);
+            const _40GBASEKR4 = (1 << 3);
+            const _10GBASEKR = (1 << 2);
+            const _10GBASEKX4 = (1 << 1);
+            const _1000BASEKX = (1 << 0);
+        }
+
+        /// The FEC capability field (bits F0 to F3) of a Clause 73 base
+        /// page, in MMD register 7.18 (advertisement) or 7.21 (link partner
+        /// ability).
+        pub struct BackplaneFec: u16 {
+            const BASER_FEC_REQUESTED = (1 << 15);
+            const BASER_FEC_ABILITY = (1 << 14);
+            const _25G_BASER_FEC_REQUESTED = (1 << 13);
+            const _25G_RS_FEC_REQUESTED = (1 << 12);
+        }
+
+        /// MMD registers x.5 and x.6, the Devices In Package registers.
+        ///
+        /// Register x.5 contains the lower 16 bits, and register x.6 the upper
+        /// 16 bits. Bit `n` is set if MMD `n` is present.
+        pub struct DevicesInPackage: u32 {
+            const VENDOR_SPECIFIC_2 = (1 << 31);
+            const VENDOR_SPECIFIC_1 = (1 << 30);
+            const CLAUSE22_EXTENSION = (1 << 29);
+            const SEPARATED_PMA_4 = (1 << 11);
+            const SEPARATED_PMA_3 = (1 << 10);
+            const SEPARATED_PMA_2 = (1 << 9);
+            const SEPARATED_PMA_1 = (1 << 8);
+            const AUTONEG = (1 << 7);
+            const TC = (1 << 6);
+            const DTE_XS = (1 << 5);
+            const PHY_XS = (1 << 4);
+            const PCS = (1 << 3);
+            const WIS = (1 << 2);
+            const PMA_PMD = (1 << 1);
+            const CLAUSE22 = (1 << 0);
+        }
+
         /// Register 15, the Extended Status Register
         pub struct Esr: u16 {
             const _1000BASEXFD = (1 << 15);
@@ -199,6 +377,32 @@ impl Bcr {
         self.insert(Self::RESTART_AUTONEG);
         self
     }
+
+    /// Get the selected speed. This is ignored by the PHY if
+    /// `Self::autonegotiation` is set.
+    pub fn speed(&self) -> Result<Speed, IllegalSpeed> {
+        Speed::try_from(*self)
+    }
+
+    /// Select the speed. This is ignored by the PHY if `Self::autonegotiation`
+    /// is set.
+    pub fn set_speed(&mut self, speed: Speed) -> &mut Self {
+        self.remove(Self::SPEED_SEL_MSB | Self::SPEED_SEL_LSB);
+        self.insert(speed.into());
+        self
+    }
+
+    /// Get the selected duplex mode. This is ignored by the PHY if
+    /// `Self::autonegotiation` is set.
+    pub fn duplex(&self) -> Duplex {
+        Duplex::from(*self)
+    }
+
+    /// Select the duplex mode. This is ignored by the PHY if
+    /// `Self::autonegotiation` is set.
+    pub fn set_duplex(&mut self, duplex: Duplex) -> &mut Self {
+        self.set_full_duplex(duplex == Duplex::Full)
+    }
 }
 
 impl Bsr {
@@ -311,7 +515,463 @@ impl NextPage {
     }
 }
 
+impl MasterSlaveControl {
+    /// The address of the MASTER-SLAVE Control register.
+    pub const ADDRESS: u8 = 9;
+
+    impl_flag!(
+        "Advertise 1000BASE-T Full Duplex.",
+        set_1000base_t_fd,
+        "Check if 1000BASE-T Full Duplex is advertised.",
+        base1000_t_fd,
+        Self::_1000BASETFD
+    );
+    impl_flag!(
+        "Advertise 1000BASE-T Half Duplex.",
+        set_1000base_t_hd,
+        "Check if 1000BASE-T Half Duplex is advertised.",
+        base1000_t_hd,
+        Self::_1000BASETHD
+    );
+}
+
+impl MasterSlaveStatus {
+    /// The address of the MASTER-SLAVE Status register.
+    pub const ADDRESS: u8 = 10;
+
+    /// Check if the link partner advertises 1000BASE-T Full Duplex
+    pub fn partner_1000base_t_fd(&self) -> bool {
+        self.contains(Self::PARTNER_1000BASETFD)
+    }
+
+    /// Check if the link partner advertises 1000BASE-T Half Duplex
+    pub fn partner_1000base_t_hd(&self) -> bool {
+        self.contains(Self::PARTNER_1000BASETHD)
+    }
+
+    /// The number of idle errors since the last read of this register
+    pub fn idle_error_count(&self) -> u8 {
+        (self.bits() & Self::IDLE_ERROR_COUNT_MASK.bits()) as u8
+    }
+}
+
+impl Eee {
+    /// The MMD containing the EEE capability register.
+    pub const CAPABILITY_MMD: u8 = 3;
+    /// The address of the EEE capability register.
+    pub const CAPABILITY_ADDRESS: u16 = 20;
+    /// The MMD containing the EEE advertisement registers.
+    pub const ADVERTISEMENT_MMD: u8 = 7;
+    /// The address of the local EEE advertisement register.
+    pub const ADVERTISEMENT_ADDRESS: u16 = 60;
+    /// The address of the link partner EEE ability register.
+    pub const PARTNER_ADDRESS: u16 = 61;
+}
+
+impl MmdControl {
+    /// The address of the control register of an MMD.
+    pub const ADDRESS: u16 = 0;
+}
+
+impl MmdStatus {
+    /// The address of the status register of an MMD.
+    pub const ADDRESS: u16 = 1;
+}
+
+impl PmaStatus2 {
+    /// The MMD containing the PMA/PMD status 2 register.
+    pub const MMD: u8 = 1;
+    /// The address of the PMA/PMD status 2 register.
+    pub const ADDRESS: u16 = 8;
+
+    /// Whether the device present field indicates that a PMA/PMD responds
+    /// at this address
+    pub fn device_present(&self) -> bool {
+        (*self & Self::DEVICE_PRESENT_MASK).bits() >> 14 == 0b10
+    }
+}
+
+impl PmdSignalDetect {
+    /// The MMD containing the PMD receive signal detect register.
+    pub const MMD: u8 = 1;
+    /// The address of the PMD receive signal detect register.
+    pub const ADDRESS: u16 = 10;
+}
+
+impl PmaExtendedAbility {
+    /// The MMD containing the PMA/PMD extended ability register.
+    pub const MMD: u8 = 1;
+    /// The address of the PMA/PMD extended ability register.
+    pub const ADDRESS: u16 = 11;
+}
+
+impl BaseRPcsStatus1 {
+    /// The MMD containing the 10GBASE-R PCS status registers.
+    pub const MMD: u8 = 3;
+    /// The address of the 10GBASE-R PCS status 1 register.
+    pub const ADDRESS: u16 = 32;
+}
+
+impl BaseRPcsStatus2 {
+    /// The MMD containing the 10GBASE-R PCS status registers.
+    pub const MMD: u8 = 3;
+    /// The address of the 10GBASE-R PCS status 2 register.
+    pub const ADDRESS: u16 = 33;
+
+    /// The amount of times that the PCS entered the high BER state since
+    /// this register was last read, saturating at 63
+    pub fn ber_count(&self) -> u8 {
+        ((*self & Self::BER_COUNTER_MASK).bits() >> 8) as u8
+    }
+
+    /// The amount of errored blocks since this register was last read,
+    /// saturating at 255
+    pub fn errored_blocks(&self) -> u8 {
+        (*self & Self::ERRORED_BLOCKS_MASK).bits() as u8
+    }
+}
+
+impl MmdAnStatus {
+    /// The MMD containing the Auto-Negotiation status register.
+    pub const MMD: u8 = 7;
+    /// The address of the Auto-Negotiation status register.
+    pub const ADDRESS: u16 = 1;
+}
+
+impl BackplaneAbility {
+    /// The MMD containing the Clause 73 base page registers.
+    pub const MMD: u8 = 7;
+    /// The address of the first advertisement register (bits D15 to D0).
+    pub const ADVERTISEMENT_ADDRESS: u16 = 16;
+    /// The address of the first link partner ability register (bits D15 to
+    /// D0).
+    pub const PARTNER_ADDRESS: u16 = 19;
+
+    /// The position of A0 in the second base page register (bits D31 to
+    /// D16)
+    const MIDDLE_SHIFT: u32 = 5;
+    /// The amount of technology ability bits in the second base page
+    /// register
+    const MIDDLE_BITS: u32 = 11;
+    /// The technology ability bits in the third base page register (bits
+    /// D47 to D32)
+    const HIGH_MASK: u16 = 0x0FFF;
+
+    /// Decode the technology ability field from the second and third base
+    /// page registers.
+    pub fn from_registers(middle: u16, high: u16) -> Self {
+        let low = (middle >> Self::MIDDLE_SHIFT) as u32;
+        let high = (high & Self::HIGH_MASK) as u32;
+        Self::from_bits_truncate(low | high << Self::MIDDLE_BITS)
+    }
+
+    /// Encode this technology ability field into the values of the second
+    /// and third base page registers.
+    ///
+    /// All other bits of the returned values are zero.
+    pub fn to_registers(&self) -> (u16, u16) {
+        let middle = (self.bits() << Self::MIDDLE_SHIFT) as u16;
+        let high = (self.bits() >> Self::MIDDLE_BITS) as u16 & Self::HIGH_MASK;
+        (middle, high)
+    }
+}
+
+impl DevicesInPackage {
+    /// The address of the register containing the lower 16 bits.
+    pub const LOW_ADDRESS: u16 = 5;
+    /// The address of the register containing the upper 16 bits.
+    pub const HIGH_ADDRESS: u16 = 6;
+
+    /// Check whether MMD `device` is present
+    pub fn has_device(&self, device: u8) -> bool {
+        device < 32 && self.bits() & (1 << device) != 0
+    }
+}
+
 impl Esr {
     /// The address of the Extended Status Register.
     pub const ADDRESS: u8 = 15;
 }
+
+/// Implement `decode`, [`Display`](core::fmt::Display) and (if enabled)
+/// `defmt::Format` for a register, rendering all set `$flag`s by name, and
+/// the value of all multi-bit `$field`s.
+///
+/// Registers that are not 16 bits wide are passed as `$reg: $ty`.
+macro_rules! impl_decode {
+    ($reg:ident, $($rest:tt)*) => {
+        impl_decode!($reg: u16, $($rest)*);
+    };
+    ($reg:ident: $ty:ty, [$($flag:ident),* $(,)?] $(, $field:ident => $label:literal)* $(,)?) => {
+        impl $reg {
+            /// Decode a raw register value.
+            ///
+            /// Bits that do not correspond to a flag are discarded. The result
+            /// implements [`Display`](core::fmt::Display), which renders
+            /// the flags that are set.
+            pub fn decode(raw: $ty) -> Self {
+                Self::from_bits_truncate(raw)
+            }
+        }
+
+        impl core::fmt::Display for $reg {
+            #[allow(unused_assignments)]
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut first = true;
+                $(
+                    if self.contains(Self::$flag) {
+                        if !first {
+                            f.write_str(" | ")?;
+                        }
+                        first = false;
+                        f.write_str(stringify!($flag))?;
+                    }
+                )*
+                $(
+                    if !first {
+                        f.write_str(" | ")?;
+                    }
+                    first = false;
+                    let mask = Self::$field.bits();
+                    write!(f, "{}={}", $label, (self.bits() & mask) >> mask.trailing_zeros())?;
+                )*
+                if first {
+                    f.write_str("(empty)")?;
+                }
+                Ok(())
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        impl defmt::Format for $reg {
+            #[allow(unused_assignments)]
+            fn format(&self, f: defmt::Formatter) {
+                let mut first = true;
+                $(
+                    if self.contains(Self::$flag) {
+                        if !first {
+                            defmt::write!(f, " | ");
+                        }
+                        first = false;
+                        defmt::write!(f, "{=str}", stringify!($flag));
+                    }
+                )*
+                $(
+                    if !first {
+                        defmt::write!(f, " | ");
+                    }
+                    first = false;
+                    let mask = Self::$field.bits();
+                    defmt::write!(f, "{=str}={=u16}", $label, (self.bits() & mask) >> mask.trailing_zeros());
+                )*
+                if first {
+                    defmt::write!(f, "(empty)");
+                }
+            }
+        }
+    };
+}
+
+impl_decode!(
+    Bcr,
+    [
+        RESET,
+        LOOPBACK,
+        SPEED_SEL_LSB,
+        AUTONEG_ENABLE,
+        POWER_DOWN,
+        ISOLATE,
+        RESTART_AUTONEG,
+        DUPLEX_MODE,
+        COLLISION_TEST,
+        SPEED_SEL_MSB,
+        UNIDIRECTIONAL_ENABLE,
+    ]
+);
+
+impl_decode!(
+    Bsr,
+    [
+        _100BASET4,
+        _100BASEXFD,
+        _100BASEXHD,
+        _10MPBSFD,
+        _10MBPSHD,
+        _100BASET2FD,
+        _100BASET2HD,
+        EXTENDED_STATUS,
+        UNIDRECTIONAL,
+        MF_PREAMBLE_SUPPRESSION,
+        AUTONEG_COMPLETE,
+        REMOTE_FAULT,
+        AUTONEG_ABLE,
+        LINK_STATUS,
+        JABBER_DETECT,
+        EXTENDED_CAPABILITIES,
+    ]
+);
+
+impl_decode!(
+    AutoNegCap,
+    [
+        NEXT_PAGE,
+        REMOTE_FAULT,
+        EXTENDED_NEXT_PAGE,
+        ASSYMETRIC_PAUSE,
+        PAUSE,
+        _100BASET4,
+        _100BASETXFD,
+        _100BASETX,
+        _10BASETFD,
+        _10BASET,
+    ],
+    SEL_MASK => "SELECTOR",
+);
+
+impl_decode!(
+    Ane,
+    [
+        RX_NEXT_PAGE_LOC_ABLE,
+        RX_NEXT_PAGE_LOC,
+        PARALLEL_DECT_FAULT,
+        LINK_PARTNER_NEXT_PAGE_ABLE,
+        NEXT_PAGE_ABLE,
+        PAGE_RECEIVED,
+        LINK_PARTNER_AUTONEG_ABLE,
+    ]
+);
+
+impl_decode!(
+    MasterSlaveControl,
+    [
+        MS_MANUAL_CONFIG_ENABLE,
+        MS_MANUAL_CONFIG_VALUE,
+        PORT_TYPE,
+        _1000BASETFD,
+        _1000BASETHD,
+    ],
+    TEST_MODE_MASK => "TEST_MODE",
+);
+
+impl_decode!(
+    MasterSlaveStatus,
+    [
+        MS_CONFIG_FAULT,
+        MS_CONFIG_RESOLUTION,
+        LOCAL_RECEIVER_STATUS,
+        REMOTE_RECEIVER_STATUS,
+        PARTNER_1000BASETFD,
+        PARTNER_1000BASETHD,
+    ],
+    IDLE_ERROR_COUNT_MASK => "IDLE_ERROR_COUNT",
+);
+
+impl_decode!(
+    Esr,
+    [_1000BASEXFD, _1000BASEXHD, _1000BASETFD, _1000BASETHD]
+);
+
+impl_decode!(
+    Eee,
+    [
+        _10GBASEKR,
+        _10GBASEKX4,
+        _1000BASEKX,
+        _10GBASET,
+        _1000BASET,
+        _100BASETX
+    ]
+);
+
+impl_decode!(MmdControl, [RESET, LOW_POWER]);
+
+impl_decode!(MmdStatus, [FAULT, RECEIVE_LINK_STATUS, LOW_POWER_ABILITY]);
+
+impl_decode!(
+    PmaExtendedAbility,
+    [
+        NBASET,
+        BASET1,
+        _10BASET,
+        _100BASETX,
+        _1000BASEKX,
+        _1000BASET,
+        _10GBASEKR,
+        _10GBASEKX4,
+        _10GBASET,
+        _10GBASELRM,
+        _10GBASECX4,
+    ]
+);
+
+impl_decode!(
+    MmdAnStatus,
+    [
+        PARALLEL_DETECTION_FAULT,
+        EXTENDED_NEXT_PAGE,
+        PAGE_RECEIVED,
+        AUTONEG_COMPLETE,
+        REMOTE_FAULT,
+        AUTONEG_ABILITY,
+        LINK_STATUS,
+        PARTNER_AUTONEG_ABILITY,
+    ]
+);
+
+impl_decode!(
+    BackplaneAbility: u32,
+    [
+        _5GBASEKR,
+        _2_5GBASEKX,
+        _25GBASEKR,
+        _25GBASEKRS,
+        _100GBASECR4,
+        _100GBASEKR4,
+        _100GBASEKP4,
+        _100GBASECR10,
+        _40GBASECR4,
+        _40GBASEKR4,
+        _10GBASEKR,
+        _10GBASEKX4,
+        _1000BASEKX,
+    ]
+);
+
+impl_decode!(
+    BackplaneFec,
+    [
+        BASER_FEC_REQUESTED,
+        BASER_FEC_ABILITY,
+        _25G_BASER_FEC_REQUESTED,
+        _25G_RS_FEC_REQUESTED,
+    ]
+);
+
+impl_decode!(
+    PmaStatus2,
+    [
+        TRANSMIT_FAULT_ABILITY,
+        RECEIVE_FAULT_ABILITY,
+        TRANSMIT_FAULT,
+        RECEIVE_FAULT,
+    ],
+    DEVICE_PRESENT_MASK => "DEVICE_PRESENT",
+);
+
+impl_decode!(
+    BaseRPcsStatus1,
+    [
+        RECEIVE_LINK_STATUS,
+        PRBS9_ABILITY,
+        PRBS31_ABILITY,
+        HIGH_BER,
+        BLOCK_LOCK,
+    ]
+);
+
+impl_decode!(
+    BaseRPcsStatus2,
+    [LATCHED_BLOCK_LOCK, LATCHED_HIGH_BER],
+    BER_COUNTER_MASK => "BER_COUNTER",
+    ERRORED_BLOCKS_MASK => "ERRORED_BLOCKS",
+);
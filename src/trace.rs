@@ -0,0 +1,289 @@
+//! Capturing and replaying MIIM transactions.
+//!
+//! A [`Recorder`] wraps a [`Miim`] and records every transaction into a
+//! caller-provided buffer. A [`Replayer`] feeds such a trace back into a
+//! driver, and reports the first transaction that deviates from it.
+//!
+//! This can be used to reproduce issues that occur in the field without
+//! hardware, or to check that the initialization sequence of a driver
+//! does not change.
+//!
+//! This module is only available with the `trace` feature.
+
+use crate::Miim;
+
+/// A single MIIM transaction
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transaction {
+    /// A read of register `reg` of PHY `phy`, that returned `data`
+    Read {
+        /// The PHY address
+        phy: u8,
+        /// The register address
+        reg: u8,
+        /// The value that was read
+        data: u16,
+    },
+    /// A write of `data` to register `reg` of PHY `phy`
+    Write {
+        /// The PHY address
+        phy: u8,
+        /// The register address
+        reg: u8,
+        /// The value that was written
+        data: u16,
+    },
+    /// A read of register `reg` of MMD `mmd` of Clause 45 PHY `phy`, that
+    /// returned `data`
+    ReadC45 {
+        /// The PHY address
+        phy: u8,
+        /// The MMD address
+        mmd: u8,
+        /// The register address
+        reg: u16,
+        /// The value that was read
+        data: u16,
+    },
+    /// A write of `data` to register `reg` of MMD `mmd` of Clause 45 PHY
+    /// `phy`
+    WriteC45 {
+        /// The PHY address
+        phy: u8,
+        /// The MMD address
+        mmd: u8,
+        /// The register address
+        reg: u16,
+        /// The value that was written
+        data: u16,
+    },
+}
+
+/// A [`Miim`] that records all transactions that are performed on the
+/// wrapped [`Miim`].
+///
+/// Clause 45 accesses are forwarded to the wrapped [`Miim`], and recorded as
+/// a single [`Transaction::ReadC45`] or [`Transaction::WriteC45`].
+#[derive(Debug)]
+pub struct Recorder<'buf, M: Miim> {
+    miim: M,
+    buffer: &'buf mut [Transaction],
+    len: usize,
+    dropped: usize,
+}
+
+impl<'buf, M: Miim> Recorder<'buf, M> {
+    /// Create a new recorder that records the transactions performed on
+    /// `miim` into `buffer`.
+    pub fn new(miim: M, buffer: &'buf mut [Transaction]) -> Self {
+        Self {
+            miim,
+            buffer,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// The transactions that have been recorded
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.buffer[..self.len]
+    }
+
+    /// The amount of transactions that were not recorded because the buffer
+    /// was full
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Remove all recorded transactions
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.dropped = 0;
+    }
+
+    /// Release the wrapped [`Miim`]
+    pub fn release(self) -> M {
+        self.miim
+    }
+
+    fn record(&mut self, transaction: Transaction) {
+        if let Some(slot) = self.buffer.get_mut(self.len) {
+            *slot = transaction;
+            self.len += 1;
+        } else {
+            self.dropped += 1;
+        }
+    }
+}
+
+impl<M: Miim> Miim for Recorder<'_, M> {
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        let data = self.miim.read(phy, reg);
+        self.record(Transaction::Read { phy, reg, data });
+        data
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        self.miim.write(phy, reg, data);
+        self.record(Transaction::Write { phy, reg, data });
+    }
+
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        let data = self.miim.read_c45(phy, mmd, reg);
+        self.record(Transaction::ReadC45 {
+            phy,
+            mmd,
+            reg,
+            data,
+        });
+        data
+    }
+
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        self.miim.write_c45(phy, mmd, reg, data);
+        self.record(Transaction::WriteC45 {
+            phy,
+            mmd,
+            reg,
+            data,
+        });
+    }
+}
+
+/// A transaction that did not match the trace of a [`Replayer`]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The index of the transaction in the trace
+    pub index: usize,
+    /// The transaction in the trace, or `None` if the trace has ended
+    pub expected: Option<Transaction>,
+    /// The transaction that was performed
+    ///
+    /// For reads, `data` is the value that was returned (`0xFFFF`).
+    pub actual: Transaction,
+}
+
+/// A [`Miim`] that replays a trace recorded by a [`Recorder`].
+///
+/// Reads return the value from the trace, and writes are compared with the
+/// trace. If a transaction does not match the trace, reads return `0xFFFF`
+/// (as if no PHY is present), and the first mismatch is stored.
+#[derive(Debug, Clone)]
+pub struct Replayer<'trace> {
+    trace: &'trace [Transaction],
+    position: usize,
+    mismatch: Option<Mismatch>,
+}
+
+impl<'trace> Replayer<'trace> {
+    /// Create a new replayer for `trace`
+    pub fn new(trace: &'trace [Transaction]) -> Self {
+        Self {
+            trace,
+            position: 0,
+            mismatch: None,
+        }
+    }
+
+    /// The amount of transactions that have been replayed
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Whether all transactions in the trace have been replayed
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.trace.len()
+    }
+
+    /// The first transaction that did not match the trace, if any
+    pub fn mismatch(&self) -> Option<Mismatch> {
+        self.mismatch
+    }
+
+    /// Whether the whole trace was replayed without any mismatches
+    pub fn matches(&self) -> bool {
+        self.is_finished() && self.mismatch.is_none()
+    }
+
+    fn next(&mut self) -> Option<Transaction> {
+        let expected = self.trace.get(self.position).copied();
+        self.position += 1;
+        expected
+    }
+
+    fn mismatched(&mut self, expected: Option<Transaction>, actual: Transaction) {
+        if self.mismatch.is_none() {
+            self.mismatch = Some(Mismatch {
+                index: self.position - 1,
+                expected,
+                actual,
+            });
+        }
+    }
+}
+
+impl Miim for Replayer<'_> {
+    fn read(&mut self, phy: u8, reg: u8) -> u16 {
+        let expected = self.next();
+        match expected {
+            Some(Transaction::Read {
+                phy: p,
+                reg: r,
+                data,
+            }) if p == phy && r == reg => data,
+            _ => {
+                let data = 0xFFFF;
+                self.mismatched(expected, Transaction::Read { phy, reg, data });
+                data
+            }
+        }
+    }
+
+    fn write(&mut self, phy: u8, reg: u8, data: u16) {
+        let expected = self.next();
+        let actual = Transaction::Write { phy, reg, data };
+        if expected != Some(actual) {
+            self.mismatched(expected, actual);
+        }
+    }
+
+    fn read_c45(&mut self, phy: u8, mmd: u8, reg: u16) -> u16 {
+        let expected = self.next();
+        match expected {
+            Some(Transaction::ReadC45 {
+                phy: p,
+                mmd: m,
+                reg: r,
+                data,
+            }) if p == phy && m == mmd && r == reg => data,
+            _ => {
+                let data = 0xFFFF;
+                let actual = Transaction::ReadC45 {
+                    phy,
+                    mmd,
+                    reg,
+                    data,
+                };
+                self.mismatched(expected, actual);
+                data
+            }
+        }
+    }
+
+    fn write_c45(&mut self, phy: u8, mmd: u8, reg: u16, data: u16) {
+        let expected = self.next();
+        let actual = Transaction::WriteC45 {
+            phy,
+            mmd,
+            reg,
+            data,
+        };
+        if expected != Some(actual) {
+            self.mismatched(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use ieee802_3_miim::{registers::AutoNegCap, AutoNegotiationAdvertisement, Pause, SelectorField};
+
+const PAUSES: [Pause; 4] = [
+    Pause::NoPause,
+    Pause::AsymmetricPartner,
+    Pause::Symmetric,
+    Pause::SymmetricAndAsymmetricLocal,
+];
+
+const SELECTOR_FIELDS: [Option<SelectorField>; 5] = [
+    None,
+    Some(SelectorField::Std802_3),
+    Some(SelectorField::Std802_9Islan16t),
+    Some(SelectorField::Std802_5),
+    Some(SelectorField::Std1394),
+];
+
+/// All possible advertisements
+fn advertisements() -> impl Iterator<Item = AutoNegotiationAdvertisement> {
+    (0..32u8).flat_map(|abilities| {
+        PAUSES.into_iter().flat_map(move |pause| {
+            SELECTOR_FIELDS
+                .into_iter()
+                .map(move |selector_field| AutoNegotiationAdvertisement {
+                    selector_field,
+                    hd_10base_t: abilities & (1 << 0) != 0,
+                    fd_10base_t: abilities & (1 << 1) != 0,
+                    hd_100base_tx: abilities & (1 << 2) != 0,
+                    fd_100base_tx: abilities & (1 << 3) != 0,
+                    base100_t4: abilities & (1 << 4) != 0,
+                    pause,
+                })
+        })
+    })
+}
+
+#[test]
+fn advertisement_round_trip() {
+    for ad in advertisements() {
+        let encoded = ad.encode();
+        assert_eq!(
+            AutoNegotiationAdvertisement::decode(encoded),
+            ad,
+            "{encoded:?}"
+        );
+    }
+}
+
+#[test]
+fn register_round_trip() {
+    let represented = AutoNegCap::ASSYMETRIC_PAUSE
+        | AutoNegCap::PAUSE
+        | AutoNegCap::_100BASET4
+        | AutoNegCap::_100BASETXFD
+        | AutoNegCap::_100BASETX
+        | AutoNegCap::_10BASETFD
+        | AutoNegCap::_10BASET
+        | AutoNegCap::SEL_MASK;
+
+    for bits in 0..=u16::MAX {
+        let ana = AutoNegCap::from_bits_truncate(bits);
+        let decoded = AutoNegotiationAdvertisement::decode(ana);
+
+        let mut expected = ana & represented;
+        if decoded.selector_field.is_none() {
+            expected.remove(AutoNegCap::SEL_MASK);
+        }
+
+        assert_eq!(decoded.encode(), expected, "{bits:#06X}");
+    }
+}
+
+#[test]
+fn ability_bits() {
+    let single = |f: fn(&mut AutoNegotiationAdvertisement)| {
+        let mut ad = AutoNegotiationAdvertisement {
+            selector_field: None,
+            ..Default::default()
+        };
+        f(&mut ad);
+        ad.encode().bits()
+    };
+
+    assert_eq!(single(|ad| ad.hd_10base_t = true), 1 << 5);
+    assert_eq!(single(|ad| ad.fd_10base_t = true), 1 << 6);
+    assert_eq!(single(|ad| ad.hd_100base_tx = true), 1 << 7);
+    assert_eq!(single(|ad| ad.fd_100base_tx = true), 1 << 8);
+    assert_eq!(single(|ad| ad.base100_t4 = true), 1 << 9);
+    assert_eq!(single(|ad| ad.pause = Pause::Symmetric), 1 << 10);
+    assert_eq!(single(|ad| ad.pause = Pause::AsymmetricPartner), 1 << 11);
+}
+
+#[test]
+#[cfg(all(feature = "phy", feature = "mock"))]
+fn phy_advertisement_round_trip() {
+    use ieee802_3_miim::{mock::MockMiim, phy::BarePhy, registers::Bsr, Phy, PhyAddress};
+
+    let addr = PhyAddress::from_const(1);
+    let mut miim = MockMiim::new();
+    let bsr = Bsr::_10MBPSHD
+        | Bsr::_10MPBSFD
+        | Bsr::_100BASEXHD
+        | Bsr::_100BASEXFD
+        | Bsr::EXTENDED_CAPABILITIES;
+    miim.set(addr.get(), Bsr::ADDRESS, bsr.bits());
+
+    let mut phy = BarePhy::new(&mut miim, addr, Pause::NoPause);
+
+    for ad in advertisements() {
+        phy.set_autonegotiation_advertisement(ad);
+        assert_eq!(phy.get_autonegotiation_caps(), Some(ad));
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(all(feature = "mock", feature = "lan8742a"))]
+
+use ieee802_3_miim::{
+    mock::{Access, Fault, MockMiim},
+    phy::{CableDiagnostics, CablePairStatus, PhyWithCableDiagnostics, LAN8742A},
+    registers::Bcr,
+    PhyAddress,
+};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+const TDR_CONTROL_STATUS: u8 = 25;
+const SPECIAL_CONTROL_STATUS: u8 = 27;
+
+const FAILED: CableDiagnostics = CableDiagnostics {
+    status: CablePairStatus::TestFailed,
+    fault_distance_cm: None,
+};
+
+fn miim() -> MockMiim {
+    let mut miim = MockMiim::new();
+    miim.set(ADDRESS.get(), Bcr::ADDRESS, Bcr::AUTONEG_ENABLE.bits());
+    miim.set(ADDRESS.get(), SPECIAL_CONTROL_STATUS, 0x0050);
+    miim
+}
+
+fn assert_restored(miim: &MockMiim) {
+    let bcr = Bcr::from_bits_truncate(miim.get(ADDRESS.get(), Bcr::ADDRESS));
+    assert!(bcr.contains(Bcr::AUTONEG_ENABLE));
+    assert_eq!(miim.get(ADDRESS.get(), SPECIAL_CONTROL_STATUS), 0x0050);
+    assert_eq!(miim.get(ADDRESS.get(), TDR_CONTROL_STATUS), 0);
+}
+
+#[test]
+fn tdr_timeout() {
+    // The TDR Control/Status register keeps the value that started the test,
+    // so the test never completes
+    let mut miim = miim();
+    let mut phy = LAN8742A::new(&mut miim, ADDRESS);
+
+    assert_eq!(phy.test_cable_pair(0), Some(FAILED));
+    assert_restored(&miim);
+}
+
+#[test]
+fn tdr_without_phy() {
+    let mut miim = miim();
+    assert!(miim.inject(Fault::AllOnes, Access::Read, Some(TDR_CONTROL_STATUS), 0));
+    let mut phy = LAN8742A::new(&mut miim, ADDRESS);
+
+    assert_eq!(phy.test_cable_pair(1), Some(FAILED));
+    assert_eq!(miim.faults_injected(), 1);
+    assert_restored(&miim);
+}
+
+#[test]
+fn tdr_invalid_pair() {
+    let mut miim = miim();
+    let mut phy = LAN8742A::new(&mut miim, ADDRESS);
+
+    assert_eq!(phy.test_cable_pair(2), None);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "mock", feature = "dp83848"))]
+
+use ieee802_3_miim::{
+    mock::MockMiim,
+    phy::{dp83848::LedMode, DP83848},
+    PhyAddress,
+};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+const PHYCR: u8 = 0x19;
+
+#[test]
+fn led_mode_encoding() {
+    // LED_CNFG[1:0] is in bits 6:5 of PHYCR
+    for (mode, cnfg) in [
+        (LedMode::Mode1, 0b01),
+        (LedMode::Mode2, 0b00),
+        (LedMode::Mode3, 0b10),
+    ] {
+        let mut miim = MockMiim::new();
+        miim.set(ADDRESS.get(), PHYCR, 0x8001);
+        let mut phy = DP83848::new(&mut miim, ADDRESS);
+
+        phy.set_led_mode(mode);
+        assert_eq!(phy.led_mode(), mode);
+        assert_eq!(miim.get(ADDRESS.get(), PHYCR), 0x8001 | cnfg << 5);
+    }
+
+    // LED_CNFG = 11 also selects mode 1
+    let mut miim = MockMiim::new();
+    miim.set(ADDRESS.get(), PHYCR, 0b11 << 5);
+    assert_eq!(DP83848::new(&mut miim, ADDRESS).led_mode(), LedMode::Mode1);
+}
@@ -0,0 +1,147 @@
+use ieee802_3_miim::frame::{Clause22Op, Clause45Op, DecodeError, Frame};
+
+const CLAUSE22_OPS: [Clause22Op; 2] = [Clause22Op::Read, Clause22Op::Write];
+
+const CLAUSE45_OPS: [Clause45Op; 4] = [
+    Clause45Op::Address,
+    Clause45Op::Write,
+    Clause45Op::Read,
+    Clause45Op::ReadIncrement,
+];
+
+const DATA: [u16; 4] = [0x0000, 0xFFFF, 0x1234, 0xA5A5];
+
+/// Assemble the raw bits of a frame from its fields
+fn raw(st: u32, op: u32, addr1: u32, addr2: u32, ta: u32, data: u16) -> u32 {
+    (st << 30) | (op << 28) | (addr1 << 23) | (addr2 << 18) | (ta << 16) | data as u32
+}
+
+#[test]
+fn clause22_round_trip() {
+    for op in CLAUSE22_OPS {
+        for phy in 0..32 {
+            for reg in 0..32 {
+                for data in DATA {
+                    let frame = Frame::Clause22 { op, phy, reg, data };
+                    assert_eq!(Ok(frame), Frame::decode(frame.encode()));
+                    assert_eq!(Ok(frame), Frame::from_bits(frame.bits()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn clause45_round_trip() {
+    for op in CLAUSE45_OPS {
+        for port in 0..32 {
+            for device in 0..32 {
+                for data in DATA {
+                    let frame = Frame::Clause45 {
+                        op,
+                        port,
+                        device,
+                        data,
+                    };
+                    assert_eq!(Ok(frame), Frame::decode(frame.encode()));
+                    assert_eq!(Ok(frame), Frame::from_bits(frame.bits()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn clause22_encoding() {
+    let read = Frame::Clause22 {
+        op: Clause22Op::Read,
+        phy: 1,
+        reg: 2,
+        data: 0xBEEF,
+    };
+    assert_eq!(raw(0b01, 0b10, 1, 2, 0b10, 0xBEEF), read.encode());
+    assert!(read.is_read());
+
+    let write = Frame::Clause22 {
+        op: Clause22Op::Write,
+        phy: 31,
+        reg: 0,
+        data: 0x1234,
+    };
+    assert_eq!(raw(0b01, 0b01, 31, 0, 0b10, 0x1234), write.encode());
+    assert!(!write.is_read());
+}
+
+#[test]
+fn clause45_encoding() {
+    let address = Frame::Clause45 {
+        op: Clause45Op::Address,
+        port: 3,
+        device: 1,
+        data: 0xC000,
+    };
+    assert_eq!(raw(0b00, 0b00, 3, 1, 0b10, 0xC000), address.encode());
+    assert!(!address.is_read());
+
+    let read = Frame::Clause45 {
+        op: Clause45Op::Read,
+        port: 3,
+        device: 1,
+        data: 0,
+    };
+    assert_eq!(raw(0b00, 0b11, 3, 1, 0b10, 0), read.encode());
+    assert!(read.is_read());
+}
+
+#[test]
+fn read_turnaround_is_accepted() {
+    // During reads, the first bit of the turnaround is not driven by the PHY
+    let bits = raw(0b01, 0b10, 1, 2, 0b00, 0xBEEF);
+    assert_eq!(
+        Ok(Frame::Clause22 {
+            op: Clause22Op::Read,
+            phy: 1,
+            reg: 2,
+            data: 0xBEEF,
+        }),
+        Frame::decode(bits)
+    );
+}
+
+#[test]
+fn reject_bad_start() {
+    for st in [0b10, 0b11] {
+        let bits = raw(st, 0b10, 1, 2, 0b10, 0);
+        assert_eq!(Err(DecodeError::InvalidStart), Frame::decode(bits));
+    }
+}
+
+#[test]
+fn reject_bad_opcode() {
+    for op in [0b00, 0b11] {
+        let bits = raw(0b01, op, 1, 2, 0b10, 0);
+        assert_eq!(Err(DecodeError::InvalidOp), Frame::decode(bits));
+    }
+}
+
+#[test]
+fn reject_bad_turnaround() {
+    for ta in [0b01, 0b11] {
+        let c22 = raw(0b01, 0b10, 1, 2, ta, 0);
+        assert_eq!(Err(DecodeError::InvalidTurnaround), Frame::decode(c22));
+
+        let c45 = raw(0b00, 0b11, 1, 2, ta, 0);
+        assert_eq!(Err(DecodeError::InvalidTurnaround), Frame::decode(c45));
+    }
+}
+
+#[test]
+fn from_bits_pads_missing_bits() {
+    let frame = Frame::Clause22 {
+        op: Clause22Op::Write,
+        phy: 5,
+        reg: 6,
+        data: 0,
+    };
+    assert_eq!(Ok(frame), Frame::from_bits(frame.bits().take(18)));
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "ftdi")]
+
+use std::io::{self, Read, Write};
+
+use ieee802_3_miim::{
+    ftdi::{FtdiMiim, DIVISOR_2_5MHZ},
+    Miim,
+};
+
+/// A transport that records all commands, and responds with `response`
+#[derive(Debug, Default)]
+struct FakePort {
+    commands: Vec<u8>,
+    response: Vec<u8>,
+}
+
+impl Read for FakePort {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.response.len());
+        buf[..len].copy_from_slice(&self.response[..len]);
+        self.response.drain(..len);
+        Ok(len)
+    }
+}
+
+impl Write for FakePort {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.commands.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn miim() -> FtdiMiim<FakePort> {
+    FtdiMiim::new(FakePort::default(), DIVISOR_2_5MHZ).unwrap()
+}
+
+#[test]
+fn init() {
+    let port = miim().release();
+    assert_eq!(
+        port.commands,
+        [0x8A, 0x97, 0x8D, 0x85, 0x86, 11, 0, 0x80, 0x02, 0x03]
+    );
+}
+
+#[test]
+fn clause22_write() {
+    let mut miim = miim();
+    miim.write(1, 4, 0x01E1);
+
+    let port = miim.release();
+    let commands = &port.commands[10..];
+    // ST = 01, OP = 01, PHYAD = 1, REGAD = 4, TA = 10
+    let frame: u32 = (0b0101 << 28) | (1 << 23) | (4 << 18) | (0b10 << 16) | 0x01E1;
+    let mut expected = vec![0x11, 7, 0, 0xFF, 0xFF, 0xFF, 0xFF];
+    expected.extend_from_slice(&frame.to_be_bytes());
+    expected.extend_from_slice(&[0x80, 0x02, 0x03]);
+    assert_eq!(commands, expected);
+}
+
+#[test]
+fn clause22_read() {
+    let port = FakePort {
+        response: vec![0b10, 0x78, 0x09],
+        ..Default::default()
+    };
+    let mut miim = FtdiMiim::new(port, DIVISOR_2_5MHZ).unwrap();
+
+    assert_eq!(miim.read(1, 1), 0x7809);
+    assert!(miim.take_error().is_none());
+
+    let port = miim.release();
+    let commands = &port.commands[10..];
+    // ST = 01, OP = 10, PHYAD = 1, REGAD = 1
+    assert_eq!(
+        commands,
+        [
+            0x11,
+            4,
+            0,
+            0xFF,
+            0xFF,
+            0xFF,
+            0xFF,
+            0b0110_0000,
+            0x13,
+            5,
+            0b1000_0100,
+            0x80,
+            0x00,
+            0x01,
+            0x22,
+            1,
+            0x20,
+            1,
+            0,
+            0x80,
+            0x02,
+            0x03,
+            0x87
+        ]
+    );
+}
+
+#[test]
+fn read_without_phy() {
+    // Nothing drives the turnaround, so it is pulled high
+    let port = FakePort {
+        response: vec![0b11, 0xFF, 0xFF],
+        ..Default::default()
+    };
+    let mut miim = FtdiMiim::new(port, DIVISOR_2_5MHZ).unwrap();
+
+    assert_eq!(miim.read(1, 1), 0xFFFF);
+    assert!(miim.take_error().is_none());
+}
+
+#[test]
+fn read_error() {
+    // The response is missing
+    let mut miim = FtdiMiim::new(FakePort::default(), DIVISOR_2_5MHZ).unwrap();
+
+    assert_eq!(miim.read(1, 1), 0xFFFF);
+    assert_eq!(
+        miim.take_error().map(|e| e.kind()),
+        Some(io::ErrorKind::UnexpectedEof)
+    );
+    assert!(miim.take_error().is_none());
+}
+
+#[test]
+fn clause45_read() {
+    let port = FakePort {
+        response: vec![0b10, 0x12, 0x34],
+        ..Default::default()
+    };
+    let mut miim = FtdiMiim::new(port, DIVISOR_2_5MHZ).unwrap();
+
+    assert_eq!(miim.read_c45(3, 1, 0xC000), 0x1234);
+
+    let port = miim.release();
+    let commands = &port.commands[10..];
+    // Address frame: ST = 00, OP = 00, PRTAD = 3, DEVAD = 1, TA = 10
+    let address: u32 = (3 << 23) | (1 << 18) | (0b10 << 16) | 0xC000;
+    assert_eq!(&commands[7..11], address.to_be_bytes());
+    // Read frame: ST = 00, OP = 11, PRTAD = 3
+    assert_eq!(commands[14..21], [0x11, 4, 0, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(commands[21], 0b0011_0001);
+    assert_eq!(commands[22..25], [0x13, 5, 0b1000_0100]);
+}
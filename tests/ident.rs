@@ -0,0 +1,66 @@
+#![cfg(all(feature = "mock", feature = "phy"))]
+// The helpers are unused if none of the drivers below are enabled
+#![allow(dead_code)]
+
+use ieee802_3_miim::{
+    mock::MockMiim,
+    phy::{find_phy, BarePhy, IdentPhyError, IdentifiedPhy},
+    registers::Bsr,
+    Pause, PhyAddress, PhyIdent,
+};
+
+fn miim(idents: &[(u8, u32)]) -> MockMiim {
+    let mut miim = MockMiim::new();
+    for &(address, ident) in idents {
+        miim.set(address, 2, (ident >> 16) as u16);
+        miim.set(address, 3, ident as u16);
+        miim.set(address, Bsr::ADDRESS, Bsr::EXTENDED_CAPABILITIES.bits());
+    }
+    miim
+}
+
+fn bare(ident: u32) -> BarePhy<MockMiim> {
+    BarePhy::new(
+        miim(&[(1, ident)]),
+        PhyAddress::from_const(1),
+        Pause::NoPause,
+    )
+}
+
+fn incorrect(ident: u32) -> Option<IdentPhyError> {
+    let ident = PhyIdent::new((ident >> 16) as u16, ident as u16);
+    Some(IdentPhyError::IncorrectPhyIdent(ident))
+}
+
+/// Check that the drivers `A` and `B`, whose PHYs have the identifiers `a`
+/// and `b`, do not accept each other's PHY
+fn assert_told_apart<A, B>(a: u32, b: u32)
+where
+    A: IdentifiedPhy<MockMiim> + TryFrom<BarePhy<MockMiim>, Error = IdentPhyError>,
+    B: IdentifiedPhy<MockMiim> + TryFrom<BarePhy<MockMiim>, Error = IdentPhyError>,
+{
+    let mut bus = miim(&[(1, a), (2, b)]);
+    assert_eq!(find_phy::<A, _>(&mut bus), PhyAddress::new(1));
+    assert_eq!(find_phy::<B, _>(&mut bus), PhyAddress::new(2));
+
+    assert!(A::try_from(bare(a)).is_ok());
+    assert_eq!(B::try_from(bare(a)).err(), incorrect(a));
+    assert!(B::try_from(bare(b)).is_ok());
+    assert_eq!(A::try_from(bare(b)).err(), incorrect(b));
+}
+
+#[test]
+#[cfg(all(feature = "rtl8211e", feature = "rtl8211f"))]
+fn rtl8211e_rtl8211f() {
+    use ieee802_3_miim::phy::{RTL8211E, RTL8211F};
+
+    assert_told_apart::<RTL8211E<_>, RTL8211F<_>>(0x001C_C915, 0x001C_C916);
+}
+
+#[test]
+#[cfg(all(feature = "ar8031", feature = "ar8035"))]
+fn ar8031_ar8035() {
+    use ieee802_3_miim::phy::{AR8031, AR8035};
+
+    assert_told_apart::<AR8031<_>, AR8035<_>>(0x004D_D074, 0x004D_D072);
+}
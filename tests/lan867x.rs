@@ -0,0 +1,25 @@
+#![cfg(all(feature = "mock", feature = "lan867x"))]
+
+use ieee802_3_miim::{
+    mock::MockMiim,
+    phy::{
+        lan867x::{ResetTimeout, RESET_MAX_POLLS},
+        LAN867X,
+    },
+    PhyAddress,
+};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+
+#[test]
+fn reset_timeout() {
+    // The mock does not implement MMD access, so the Status 2 register reads
+    // as its own address, in which the reset complete bit is not set
+    let mut miim = MockMiim::new();
+    let mut phy = LAN867X::new(&mut miim, ADDRESS);
+
+    assert_eq!(phy.phy_init(), Err(ResetTimeout));
+    // Every poll is a single indirect MMD read, and the initialization
+    // sequence is not applied
+    assert_eq!(miim.accesses(), RESET_MAX_POLLS as usize * 4);
+}
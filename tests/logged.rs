@@ -0,0 +1,51 @@
+#![cfg(all(feature = "mock", feature = "ksz8081r"))]
+
+use ieee802_3_miim::{
+    logged::{LoggedPhy, PhyEvent},
+    mock::MockMiim,
+    phy::KSZ8081R,
+    registers::Bsr,
+    Phy, PhyAddress,
+};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+
+#[test]
+fn overrides_are_forwarded() {
+    let mut miim = MockMiim::new();
+    miim.set(ADDRESS.get(), Bsr::ADDRESS, Bsr::EXTENDED_STATUS.bits());
+    miim.set(ADDRESS.get(), 0x0F, 0xFFFF);
+
+    let mut phy = LoggedPhy::new(KSZ8081R::new(&mut miim, ADDRESS), |_, _| {});
+
+    // The KSZ8081R does not have an extended status register, even though
+    // the BSR reports it
+    assert_eq!(phy.esr(), None);
+    assert_eq!(phy.extended_status(), None);
+}
+
+#[test]
+fn events_are_logged() {
+    let mut miim = MockMiim::new();
+    let mut events = Vec::new();
+
+    let ad = {
+        let mut phy = LoggedPhy::new(KSZ8081R::new(&mut miim, ADDRESS), |address, event| {
+            events.push((address, event))
+        });
+        phy.reset();
+        let ad = phy.best_supported_advertisement();
+        phy.set_autonegotiation_advertisement(ad);
+        // Reads are not logged
+        phy.bsr();
+        ad
+    };
+
+    assert_eq!(
+        events,
+        [
+            (ADDRESS, PhyEvent::Reset),
+            (ADDRESS, PhyEvent::SetAdvertisement(ad)),
+        ]
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "mock")]
+
+use ieee802_3_miim::{
+    mock::{Access, Fault, MockMiim, MAX_FAULTS},
+    Miim,
+};
+
+#[test]
+fn read_returns_last_write() {
+    let mut miim = MockMiim::new();
+    miim.write(1, 4, 0x1234);
+    miim.write(2, 4, 0x5678);
+
+    assert_eq!(miim.read(1, 4), 0x1234);
+    assert_eq!(miim.read(2, 4), 0x5678);
+    assert_eq!(miim.read(1, 5), 0);
+    assert_eq!(miim.accesses(), 5);
+    assert_eq!(miim.get(1, 4), 0x1234);
+}
+
+#[test]
+fn fail() {
+    let mut miim = MockMiim::new();
+    miim.set(1, 4, 0x1234);
+
+    assert!(miim.inject(Fault::Fail, Access::Any, None, 0));
+    assert_eq!(miim.read(1, 4), 0);
+    assert_eq!(miim.read(1, 4), 0x1234);
+
+    assert!(miim.inject(Fault::Fail, Access::Write, None, 0));
+    miim.write(1, 4, 0x5678);
+    assert_eq!(miim.get(1, 4), 0x1234);
+    assert_eq!(miim.faults_injected(), 2);
+}
+
+#[test]
+fn all_ones() {
+    let mut miim = MockMiim::new();
+    miim.set(1, 4, 0x1234);
+
+    assert!(miim.inject(Fault::AllOnes, Access::Read, None, 0));
+    assert_eq!(miim.read(1, 4), 0xFFFF);
+    assert_eq!(miim.get(1, 4), 0x1234);
+
+    assert!(miim.inject(Fault::AllOnes, Access::Write, None, 0));
+    miim.write(1, 4, 0x5678);
+    assert_eq!(miim.get(1, 4), 0xFFFF);
+}
+
+#[test]
+fn stale() {
+    let mut miim = MockMiim::new();
+    miim.set(1, 4, 0x1234);
+    miim.write(1, 4, 0x5678);
+
+    assert!(miim.inject(Fault::Stale, Access::Read, None, 0));
+    assert_eq!(miim.read(1, 4), 0x1234);
+    assert_eq!(miim.read(1, 4), 0x5678);
+
+    assert!(miim.inject(Fault::Stale, Access::Write, None, 0));
+    miim.write(1, 4, 0x9ABC);
+    assert_eq!(miim.get(1, 4), 0x5678);
+}
+
+#[test]
+fn fault_after_accesses() {
+    let mut miim = MockMiim::new();
+    miim.set(1, 4, 0x1234);
+
+    assert!(miim.inject(Fault::AllOnes, Access::Read, None, 2));
+    // Writes are not counted for read faults
+    miim.write(1, 5, 0);
+    assert_eq!(miim.read(1, 4), 0x1234);
+    assert_eq!(miim.read(1, 4), 0x1234);
+    assert_eq!(miim.read(1, 4), 0xFFFF);
+    assert_eq!(miim.read(1, 4), 0x1234);
+}
+
+#[test]
+fn fault_on_register() {
+    let mut miim = MockMiim::new();
+    miim.set(1, 4, 0x1234);
+    miim.set(1, 5, 0x5678);
+
+    assert!(miim.inject(Fault::AllOnes, Access::Read, Some(5), 0));
+    assert_eq!(miim.read(1, 4), 0x1234);
+    assert_eq!(miim.read(1, 5), 0xFFFF);
+    assert_eq!(miim.read(1, 5), 0x5678);
+}
+
+#[test]
+fn max_faults() {
+    let mut miim = MockMiim::new();
+    for _ in 0..MAX_FAULTS {
+        assert!(miim.inject(Fault::Fail, Access::Any, None, 100));
+    }
+    assert!(!miim.inject(Fault::Fail, Access::Any, None, 100));
+
+    miim.clear_faults();
+    assert!(miim.inject(Fault::Fail, Access::Any, None, 0));
+}
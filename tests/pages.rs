@@ -0,0 +1,59 @@
+#![cfg(feature = "mock")]
+// The helpers are unused if none of the drivers below are enabled
+#![allow(dead_code)]
+
+use ieee802_3_miim::{mock::MockMiim, PhyAddress};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+
+/// Check that a paged write leaves the page that was selected before it
+/// selected, by pre-selecting `page` through `page_select`
+fn assert_page_restored(page_select: u8, page: u16, write: impl FnOnce(&mut MockMiim)) {
+    let mut miim = MockMiim::new();
+    miim.set(ADDRESS.get(), page_select, page);
+
+    write(&mut miim);
+
+    assert_eq!(miim.get(ADDRESS.get(), page_select), page);
+    assert_eq!(miim.get(ADDRESS.get(), 17), 0x1234);
+}
+
+#[test]
+#[cfg(feature = "ip101g")]
+fn ip101g() {
+    use ieee802_3_miim::phy::IP101G;
+
+    assert_page_restored(20, 3, |miim| {
+        IP101G::new(miim, ADDRESS).write_paged(16, 17, 0x1234)
+    });
+}
+
+#[test]
+#[cfg(feature = "dp83640")]
+fn dp83640() {
+    use ieee802_3_miim::phy::DP83640;
+
+    assert_page_restored(0x13, 2, |miim| {
+        DP83640::new(miim, ADDRESS).write_paged(4, 17, 0x1234)
+    });
+}
+
+#[test]
+#[cfg(feature = "vsc8501")]
+fn vsc8501() {
+    use ieee802_3_miim::phy::VSC8501;
+
+    assert_page_restored(31, 0x10, |miim| {
+        VSC8501::new(miim, ADDRESS).write_paged(2, 17, 0x1234)
+    });
+}
+
+#[test]
+#[cfg(feature = "vsc8211")]
+fn vsc8211() {
+    use ieee802_3_miim::phy::VSC8211;
+
+    assert_page_restored(31, 0x10, |miim| {
+        VSC8211::new(miim, ADDRESS).write_extended(17, 0x1234)
+    });
+}
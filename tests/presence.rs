@@ -0,0 +1,67 @@
+#![cfg(all(feature = "mock", feature = "ksz8081r"))]
+
+use ieee802_3_miim::{
+    mock::MockMiim,
+    phy::{PresenceEvent, PresenceMonitor, KSZ8081R},
+    registers::AutoNegCap,
+    PhyAddress, PhyIdent,
+};
+
+const ADDRESS: PhyAddress = PhyAddress::from_const(1);
+
+fn set_ident(miim: &mut MockMiim, ident: u32) {
+    miim.set(ADDRESS.get(), 2, (ident >> 16) as u16);
+    miim.set(ADDRESS.get(), 3, ident as u16);
+}
+
+#[test]
+fn presence_changes_are_reported() {
+    let mut monitor = PresenceMonitor::new(10);
+    let mut miim = MockMiim::new();
+    set_ident(&mut miim, 0x0022_1561);
+    let first = PhyIdent::new(0x0022, 0x1561);
+
+    let mut phy = KSZ8081R::new(&mut miim, ADDRESS);
+    assert_eq!(
+        monitor.poll(&mut phy, 0),
+        Some(PresenceEvent::Appeared(first))
+    );
+    assert_eq!(monitor.poll(&mut phy, 5), None);
+    assert_eq!(monitor.poll(&mut phy, 10), None);
+
+    set_ident(&mut miim, 0xFFFF_FFFF);
+    let mut phy = KSZ8081R::new(&mut miim, ADDRESS);
+    assert_eq!(monitor.check(&mut phy), Some(PresenceEvent::Disappeared));
+    assert_eq!(monitor.ident(), None);
+
+    set_ident(&mut miim, 0x0022_1622);
+    let mut phy = KSZ8081R::new(&mut miim, ADDRESS);
+    assert_eq!(
+        monitor.check(&mut phy),
+        Some(PresenceEvent::Appeared(PhyIdent::new(0x0022, 0x1622)))
+    );
+}
+
+#[test]
+fn reset_is_reported() {
+    let mut monitor = PresenceMonitor::new(10);
+    let mut miim = MockMiim::new();
+    set_ident(&mut miim, 0x0022_1561);
+    miim.set(ADDRESS.get(), AutoNegCap::LOCAL_CAP_ADDRESS, 0x0181);
+
+    let mut phy = KSZ8081R::new(&mut miim, ADDRESS);
+    assert!(matches!(
+        monitor.check(&mut phy),
+        Some(PresenceEvent::Appeared(_))
+    ));
+    monitor.configured(&mut phy);
+    assert_eq!(monitor.check(&mut phy), None);
+
+    // The advertisement returns to its reset value after a brown-out
+    miim.set(ADDRESS.get(), AutoNegCap::LOCAL_CAP_ADDRESS, 0x01E1);
+    let mut phy = KSZ8081R::new(&mut miim, ADDRESS);
+    assert_eq!(monitor.check(&mut phy), Some(PresenceEvent::Reset));
+
+    // The reset is only reported once, until the PHY is configured again
+    assert_eq!(monitor.check(&mut phy), None);
+}
@@ -0,0 +1,223 @@
+#![cfg(all(feature = "trace", feature = "mock"))]
+
+use ieee802_3_miim::{
+    mock::MockMiim,
+    trace::{Mismatch, Recorder, Replayer, Transaction},
+    Miim,
+};
+
+fn sequence<M: Miim>(miim: &mut M) -> u16 {
+    miim.write(1, 0, 0x8000);
+    let value = miim.read(1, 1);
+    miim.write(1, 4, value);
+    value
+}
+
+#[test]
+fn record() {
+    let mut mock = MockMiim::new();
+    mock.set(1, 1, 0x7809);
+
+    let mut buffer = [Transaction::Read {
+        phy: 0,
+        reg: 0,
+        data: 0,
+    }; 8];
+    let mut recorder = Recorder::new(&mut mock, &mut buffer);
+    assert_eq!(sequence(&mut recorder), 0x7809);
+
+    assert_eq!(
+        recorder.transactions(),
+        &[
+            Transaction::Write {
+                phy: 1,
+                reg: 0,
+                data: 0x8000
+            },
+            Transaction::Read {
+                phy: 1,
+                reg: 1,
+                data: 0x7809
+            },
+            Transaction::Write {
+                phy: 1,
+                reg: 4,
+                data: 0x7809
+            },
+        ]
+    );
+    assert_eq!(recorder.dropped(), 0);
+}
+
+#[test]
+fn record_full_buffer() {
+    let mut mock = MockMiim::new();
+    let mut buffer = [Transaction::Read {
+        phy: 0,
+        reg: 0,
+        data: 0,
+    }; 2];
+    let mut recorder = Recorder::new(&mut mock, &mut buffer);
+    sequence(&mut recorder);
+
+    assert_eq!(recorder.transactions().len(), 2);
+    assert_eq!(recorder.dropped(), 1);
+
+    recorder.clear();
+    assert!(recorder.transactions().is_empty());
+    assert_eq!(recorder.dropped(), 0);
+}
+
+#[test]
+fn replay() {
+    let mut mock = MockMiim::new();
+    mock.set(1, 1, 0x7809);
+
+    let mut buffer = [Transaction::Read {
+        phy: 0,
+        reg: 0,
+        data: 0,
+    }; 8];
+    let mut recorder = Recorder::new(&mut mock, &mut buffer);
+    sequence(&mut recorder);
+    let len = recorder.transactions().len();
+
+    let mut replayer = Replayer::new(&buffer[..len]);
+    assert_eq!(sequence(&mut replayer), 0x7809);
+    assert!(replayer.matches());
+    assert_eq!(replayer.mismatch(), None);
+}
+
+#[test]
+fn replay_mismatch() {
+    let trace = [
+        Transaction::Write {
+            phy: 1,
+            reg: 0,
+            data: 0x8000,
+        },
+        Transaction::Read {
+            phy: 1,
+            reg: 2,
+            data: 0x0022,
+        },
+    ];
+
+    let mut replayer = Replayer::new(&trace);
+    replayer.write(1, 0, 0x8000);
+    assert_eq!(replayer.read(1, 3), 0xFFFF);
+    replayer.write(1, 4, 0);
+
+    assert!(replayer.is_finished());
+    assert!(!replayer.matches());
+    assert_eq!(
+        replayer.mismatch(),
+        Some(Mismatch {
+            index: 1,
+            expected: Some(trace[1]),
+            actual: Transaction::Read {
+                phy: 1,
+                reg: 3,
+                data: 0xFFFF
+            },
+        })
+    );
+}
+
+#[test]
+fn replay_past_end() {
+    let mut replayer = Replayer::new(&[]);
+    replayer.write(1, 0, 0);
+
+    assert_eq!(
+        replayer.mismatch(),
+        Some(Mismatch {
+            index: 0,
+            expected: None,
+            actual: Transaction::Write {
+                phy: 1,
+                reg: 0,
+                data: 0
+            },
+        })
+    );
+}
+
+fn sequence_c45<M: Miim>(miim: &mut M) -> u16 {
+    miim.write_c45(1, 1, 0x0000, 0x8000);
+    let value = miim.read_c45(1, 7, 0x0010);
+    miim.write_c45(1, 7, 0x0010, value);
+    value
+}
+
+#[test]
+fn record_replay_c45() {
+    let mut mock = MockMiim::new();
+
+    let mut buffer = [Transaction::Read {
+        phy: 0,
+        reg: 0,
+        data: 0,
+    }; 8];
+    let mut recorder = Recorder::new(&mut mock, &mut buffer);
+    let value = sequence_c45(&mut recorder);
+
+    assert_eq!(
+        recorder.transactions(),
+        &[
+            Transaction::WriteC45 {
+                phy: 1,
+                mmd: 1,
+                reg: 0x0000,
+                data: 0x8000
+            },
+            Transaction::ReadC45 {
+                phy: 1,
+                mmd: 7,
+                reg: 0x0010,
+                data: value
+            },
+            Transaction::WriteC45 {
+                phy: 1,
+                mmd: 7,
+                reg: 0x0010,
+                data: value
+            },
+        ]
+    );
+    let len = recorder.transactions().len();
+
+    let mut replayer = Replayer::new(&buffer[..len]);
+    assert_eq!(sequence_c45(&mut replayer), value);
+    assert!(replayer.matches());
+}
+
+#[test]
+fn replay_c45_mismatch() {
+    let trace = [Transaction::ReadC45 {
+        phy: 1,
+        mmd: 7,
+        reg: 0x0010,
+        data: 0x1DE1,
+    }];
+
+    // A Clause 22 access does not match a Clause 45 transaction
+    let mut replayer = Replayer::new(&trace);
+    assert_eq!(replayer.read(1, 7), 0xFFFF);
+    assert_eq!(
+        replayer.mismatch(),
+        Some(Mismatch {
+            index: 0,
+            expected: Some(trace[0]),
+            actual: Transaction::Read {
+                phy: 1,
+                reg: 7,
+                data: 0xFFFF
+            },
+        })
+    );
+
+    let mut replayer = Replayer::new(&trace);
+    assert_eq!(replayer.read_c45(1, 3, 0x0010), 0xFFFF);
+    assert!(!replayer.matches());
+}
@@ -0,0 +1,53 @@
+#![cfg(any(feature = "lan8740a", feature = "lan8742a"))]
+
+use ieee802_3_miim::phy::lan87xxa::WakeupFrameFilter;
+
+/// The broadcast destination address, as used by the wakeup frame filters
+/// of the Linux smsc95xx driver
+const BROADCAST: [u8; 6] = [0xFF; 6];
+
+#[test]
+fn crc_check_value() {
+    // The filter CRC is the bit reversed CRC-16/MODBUS, as computed by
+    // `bitrev16(crc16(0xFFFF, ..))` in the Linux smsc95xx and lan78xx drivers.
+    // The CRC-16/MODBUS check value of "123456789" is 0x4B37.
+    assert_eq!(WakeupFrameFilter::crc(b"123456789", u128::MAX), 0xECD2);
+}
+
+#[test]
+fn crc_broadcast_pattern() {
+    assert_eq!(WakeupFrameFilter::crc(&BROADCAST, 0x3F), 0x8029);
+}
+
+#[test]
+fn crc_skips_unselected_bytes() {
+    // Match the ARP ethertype at offset 12 of a frame
+    let mut frame = [0u8; 14];
+    frame[..6].copy_from_slice(&BROADCAST);
+    frame[12..].copy_from_slice(&[0x08, 0x06]);
+
+    assert_eq!(WakeupFrameFilter::crc(&frame, 0x3000), 0x614E);
+    assert_eq!(
+        WakeupFrameFilter::crc(&frame, 0x3000),
+        WakeupFrameFilter::crc(&[0x08, 0x06], 0b11)
+    );
+}
+
+#[test]
+fn from_pattern_masks_to_pattern_length() {
+    let filter = WakeupFrameFilter::from_pattern(0, &BROADCAST, u128::MAX);
+
+    assert_eq!(filter.pattern_offset, 0);
+    assert_eq!(filter.byte_mask, 0x3F);
+    assert_eq!(filter.crc, 0x8029);
+    assert!(!filter.match_address && !filter.match_multicast && !filter.match_broadcast);
+
+    let filter = WakeupFrameFilter::from_pattern(12, &[0x08, 0x06, 0x00], 0b1011);
+    assert_eq!(filter.pattern_offset, 12);
+    assert_eq!(filter.byte_mask, 0b011);
+    assert_eq!(filter.crc, 0x614E);
+
+    // A pattern of 128 bytes or more leaves the mask as-is
+    let filter = WakeupFrameFilter::from_pattern(0, &[0xFF; 130], u128::MAX);
+    assert_eq!(filter.byte_mask, u128::MAX);
+}